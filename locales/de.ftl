@@ -0,0 +1,439 @@
+# Deutsche Meldungen von LoreGui. Jeder Schlüssel muss auch in den anderen Sprachen vorhanden sein.
+
+## Menü und Befehle
+
+command-new-database = Neue Lore-Datenbank
+command-open-database = Lore-Datenbank öffnen
+command-recent = Zuletzt verwendet
+command-undo = Rückgängig
+command-redo = Wiederholen
+command-settings = Einstellungen
+command-error-log = { $count ->
+    [0] Fehlerprotokoll
+   *[other] Fehlerprotokoll ({ $count })
+}
+command-notifications = { $count ->
+    [0] Benachrichtigungen
+   *[other] Benachrichtigungen ({ $count })
+}
+command-save-description = Beschreibung speichern
+command-save-history-content = Geschichtseintrag speichern
+command-recover-drafts = Entwürfe wiederherstellen
+command-show-entities = Entitäten anzeigen
+command-show-history-items = Geschichtseinträge anzeigen
+command-show-relationships = Beziehungen anzeigen
+command-show-dashboard = Übersicht anzeigen
+command-read-texts = Beschreibungen und Geschichtseinträge lesen
+command-edit-texts = Beschreibungen und Geschichtseinträge bearbeiten
+command-new-entity = Neue Entität
+command-relabel-entity = Entität umbenennen
+command-delete-entity = Entität löschen
+command-copy-entity = Entität in Datenbank kopieren
+command-duplicate-entity = Entität duplizieren
+command-merge-entities = Entitäten zusammenführen
+command-change-category = Kategorie ändern
+command-new-descriptor = Neuer Deskriptor
+command-rename-descriptor = Deskriptor umbenennen
+command-rename-descriptor-everywhere = Deskriptor überall umbenennen
+command-show-reserved-descriptors = Reservierte Deskriptoren anzeigen
+command-hide-reserved-descriptors = Reservierte Deskriptoren ausblenden
+command-filter-category = Kategorie anzeigen: { $category }
+command-group-by-category = Entitäten nach Kategorie gruppieren
+command-ungroup-by-category = Entitäten nicht mehr nach Kategorie gruppieren
+command-delete-descriptor = Deskriptor löschen
+command-new-history-item = Neuer Geschichtseintrag
+command-redate-history-item = Geschichtseintrag umdatieren
+command-delete-history-item = Geschichtseintrag löschen
+command-new-relationship = Neue Beziehung
+command-change-role = Rolle ändern
+command-delete-relationship = Beziehung löschen
+command-go-to = Gehe zu { $label }
+command-palette-search = Befehl oder Entität eingeben...
+command-palette-nothing-found = [Nichts gefunden]
+
+## Hauptfenster
+
+view-entities = Entitäten
+view-history-items = Geschichtseinträge
+view-relationships = Beziehungen
+view-dashboard = Übersicht
+tab-no-database = [Keine Datenbank]
+tab-close = ×
+tab-close-tooltip = Diese Datenbank schließen
+no-database-loaded = [Keine Datenbank geladen]
+column-label = Label
+column-descriptor = Deskriptor
+column-description = Beschreibung
+column-year = Jahr
+column-day = Tag
+column-timestamp = Zeitstempel
+column-content = Inhalt
+column-parent = Elternteil
+column-child = Kind
+column-role = Rolle
+column-selected = Ausgewählt: { $entry }
+column-loading = { $text } (wird geladen...)
+column-search = Tippen zum Suchen...
+category-all = Alle Kategorien
+entry-none = [keine]
+file-filter-database = Lore-Datenbank (.db)
+file-filter-any = Alle
+
+## Globale Suche
+
+search-placeholder = Alles durchsuchen...
+search-results-button = Ergebnisse
+search-result-count = { $count ->
+    [0] Keine Ergebnisse
+    [one] { $count } Ergebnis
+   *[other] { $count } Ergebnisse
+}
+search-in-progress = { $summary } (suche...)
+search-syntax-hint = "Anführungszeichen" für Phrasen und * für Präfixe verwenden.
+search-history-item = Jahr { $year }, Tag { $day } ({ $timestamp })
+
+## Schaltflächen
+
+button-add-palette = Palette hinzufügen
+button-back = Zurück
+button-cancel = Abbrechen
+button-change = Ändern
+button-clear = Leeren
+button-close = Schließen
+button-concatenate = Aneinanderhängen
+button-copy = Kopieren
+button-duplicate = Duplizieren
+button-copy-to-clipboard = In die Zwischenablage kopieren
+button-create = Erstellen
+button-delete = Löschen
+button-discard = Verwerfen
+button-discard-changes = Änderungen verwerfen
+button-edit = Bearbeiten
+button-hide-diff = Unterschiede ausblenden
+button-hide-details = Details ausblenden
+button-keep-mine = Meine behalten
+button-keep-source = Quelle behalten
+button-keep-target = Ziel behalten
+button-merge = Zusammenführen
+button-next = Weiter
+button-no = Nein
+button-ok = Ok
+button-open = Öffnen
+button-read = Lesen
+button-pin = Anheften
+button-redate = Umdatieren
+button-remove = Entfernen
+button-reset-to-defaults = Auf Standard zurücksetzen
+button-restore = Wiederherstellen
+button-save = Speichern
+button-save-changes = Änderungen speichern
+button-show-details = Details anzeigen
+button-take-theirs = Fremde übernehmen
+button-unpin = Lösen
+button-update = Aktualisieren
+button-view-diff = Unterschiede anzeigen
+button-yes = Ja
+
+## Dialoge
+
+dialog-change-role = Rolle { $role } der Beziehung zwischen { $parent } und { $child } ändern
+dialog-change-category = Kategorie von { $label } ändern
+dialog-commands = Befehle
+dialog-confirmation = Bestätigung
+dialog-conflict = Von jemand anderem geändert
+dialog-copy-entity = Entität kopieren: { $label }
+dialog-duplicate-entity = Entität duplizieren: { $label }
+dialog-draft-recovery = Ungespeicherte Änderungen wiederherstellen
+dialog-error = Fehler
+dialog-merge-entities = Zwei Entitäten zusammenführen
+dialog-new-descriptor = Neuer Deskriptor
+dialog-new-entity = Neue Entität erstellen
+dialog-new-history-item = Neuen Geschichtseintrag erstellen
+dialog-new-relationship = Neue Beziehung
+dialog-recent-databases = Zuletzt verwendete Datenbanken
+dialog-redate-history-item = Geschichtseintrag umdatieren: Jahr { $year }, Tag { $day } ({ $timestamp })
+dialog-relabel-entity = Entität umbenennen: { $label }
+dialog-rename-descriptor = Deskriptor { $descriptor } der Entität { $label } umbenennen
+dialog-rename-descriptor-everywhere = Deskriptor { $descriptor } bei allen Entitäten umbenennen
+dialog-settings = Einstellungen
+dialog-unsaved-changes = Ungespeicherte Änderungen
+
+field-category = Kategorie:
+field-group-by-category = Nach Kategorie gruppieren
+field-child = Kind:
+field-content = Inhalt:
+field-day = Tag:
+field-day-optional = Tag (optional):
+field-description = Beschreibung:
+field-descriptor = Deskriptor:
+field-label = Label:
+field-merge-source = Zusammenführen (wird gelöscht):
+field-merge-target = In:
+field-name = Name
+field-new-descriptor = Neuer Deskriptor
+field-new-label = Neues Label
+field-new-role = Neue Rolle
+field-new-name = Neuer Name
+field-keep-name = Namen des Originals beibehalten
+field-duplicate-relationships = Beziehungen duplizieren
+field-parent = Elternteil:
+field-role = Rolle:
+field-target-database = Zieldatenbank:
+field-target-label = Label in der Zieldatenbank:
+field-year = Jahr:
+
+confirm-delete-entity = Soll { $label } wirklich gelöscht werden?
+confirm-delete-descriptor = Soll der Deskriptor { $descriptor } von { $label } wirklich gelöscht werden?
+confirm-delete-history-item = Soll { $timestamp } wirklich gelöscht werden?
+confirm-delete-relationship = Soll die Beziehung { $role } zwischen { $parent } und { $child } wirklich gelöscht werden?
+
+unsaved-changes-description = Die bearbeitete Beschreibung hat ungespeicherte Änderungen. Sollen sie gespeichert werden?
+unsaved-changes-history-item = Der bearbeitete Geschichtseintrag hat ungespeicherte Änderungen. Sollen sie gespeichert werden?
+unsaved-changes-database = { $database } hat ungespeicherte Änderungen. Sollen sie gespeichert werden?
+unsaved-changes-all-databases = Einige der geöffneten Datenbanken haben ungespeicherte Änderungen. Sollen sie gespeichert werden?
+
+conflict-description = Die bearbeitete Beschreibung wurde außerhalb von LoreGui geändert. Welche Fassung soll behalten werden?
+conflict-history-item = Der bearbeitete Geschichtseintrag wurde außerhalb von LoreGui geändert. Welche Fassung soll behalten werden?
+conflict-diff-legend = Zeilen, die nur in der fremden Fassung stehen, sind mit - markiert, Zeilen nur in meiner mit +.
+
+recent-database-missing = { $path } (fehlt)
+recent-database-error = Die zuletzt verwendete Datenbank konnte nicht geöffnet werden:
+    { $error }
+recent-databases-none = [Keine zuletzt verwendeten Datenbanken]
+
+draft-description = Beschreibung von { $label }: { $descriptor }
+draft-history-item = Geschichtseintrag { $timestamp } aus dem Jahr { $year }
+draft-deleted = { $title } (existiert nicht mehr)
+draft-recovery-explanation = { $count ->
+    [one] Dieser Text hatte ungespeicherte Änderungen, als LoreGui zuletzt geschlossen wurde.
+   *[other] Diese { $count } Texte hatten ungespeicherte Änderungen, als LoreGui zuletzt geschlossen wurde.
+} Zeilen nur in der Datenbank sind mit - markiert, Zeilen nur im Entwurf mit +.
+
+## Einstellungen
+
+settings-appearance = Darstellung
+settings-language = Sprache
+settings-theme = Farbschema
+settings-notification-duration = Anzeigedauer von Benachrichtigungen
+settings-seconds = { $seconds } s
+settings-custom-palettes = Eigene Paletten
+settings-palette-name = Name
+settings-new-palette = Neue Palette
+settings-keyboard-shortcuts = Tastenkürzel
+settings-press-a-key = [Taste drücken]
+settings-unbound = [Nicht belegt]
+settings-shortcut-conflicts = Einige Aktionen teilen sich dieselben Tasten. Bitte vor dem Speichern auflösen.
+theme-light = Hell
+theme-dark = Dunkel
+palette-background = Hintergrund
+palette-text = Text
+palette-primary = Primär
+palette-success = Erfolg
+palette-danger = Gefahr
+size-font = Grundschriftgröße
+size-header = Überschriftengröße
+size-row-height = Zeilenhöhe in Listen
+size-spacing = Abstand
+
+shortcut-save = Aktiven Editor speichern
+shortcut-new = Neuer Eintrag in der aktuellen Ansicht
+shortcut-delete = Auswahl löschen
+shortcut-submit-dialog = Dialog bestätigen
+shortcut-close-dialog = Dialog schließen
+shortcut-undo = Rückgängig
+shortcut-redo = Wiederholen
+shortcut-entity-view = Entitäten anzeigen
+shortcut-history-view = Geschichtseinträge anzeigen
+shortcut-relationship-view = Beziehungen anzeigen
+shortcut-dashboard-view = Übersicht anzeigen
+shortcut-select-previous = Vorherigen Eintrag auswählen
+shortcut-select-next = Nächsten Eintrag auswählen
+shortcut-previous-column = Zur vorherigen Spalte
+shortcut-next-column = Zur nächsten Spalte
+shortcut-command-palette = Alle Befehle anzeigen
+shortcut-toggle-read-mode = Zwischen Lesen und Bearbeiten wechseln
+
+## Kommandozeile
+
+cli-usage = Aufruf: loregui <DATENBANK> <BEFEHL>
+cli-commands = Befehle:
+cli-gui-hint = Ohne Argumente wird die grafische Oberfläche gestartet.
+cli-no-database = Keine Datenbank angegeben.
+cli-unknown-command = Unbekannter Befehl: { $command }
+cli-invalid-timestamp = { $timestamp } ist kein gültiger Zeitstempel.
+cli-history-item-missing = Ereignis { $timestamp } existiert nicht.
+cli-entity-created = Entität { $label } erstellt.
+cli-entity-relabelled = Entität { $old_label } in { $new_label } umbenannt.
+cli-entity-deleted = Entität { $label } gelöscht.
+cli-descriptor-changed = Deskriptor { $descriptor } der Entität { $label } geändert.
+cli-descriptor-added = Deskriptor { $descriptor } zur Entität { $label } hinzugefügt.
+cli-history-item-created = Ereignis { $timestamp } erstellt.
+cli-history-item-redated = Ereignis { $timestamp } umdatiert.
+cli-relationship-created = Beziehung zwischen { $parent } und { $child } erstellt.
+cli-role-changed = Rolle der Beziehung zwischen { $parent } und { $child } in { $role } geändert.
+
+## Fehler
+
+error-file = Dateifehler: { $message }
+error-input = Eingabefehler: { $message }
+error-no-database = Keine Datenbank
+error-multiple-results = Mehrere Ergebnisse
+error-lore-core = Fehler in lorecore: { $error }
+error-in-context = { $operation } fehlgeschlagen: { $cause }
+error-operation-failed = { $operation } fehlgeschlagen.
+error-caused-by = Ursachen:
+error-database-missing = Die Datenbank { $path } existiert nicht.
+error-entity-missing = Die Entität { $label } existiert nicht.
+error-file-read = { $path } konnte nicht gelesen werden: { $error }
+error-file-parse = { $path } konnte nicht verarbeitet werden: { $error }
+error-file-remove = { $path } konnte nicht entfernt werden: { $error }
+error-file-write = { $path } konnte nicht geschrieben werden: { $error }
+error-drafts-serialise = Die Entwürfe konnten nicht serialisiert werden: { $error }
+error-search-index = Suchindex: { $error }
+error-store-recent-databases = Die Liste der zuletzt verwendeten Datenbanken konnte nicht in den Benutzereinstellungen gespeichert werden.
+error-store-key-bindings = Die Tastenbelegung konnte nicht in den Benutzereinstellungen gespeichert werden.
+error-store-database-sessions = Die Datenbanksitzungen konnten nicht in den Benutzereinstellungen gespeichert werden.
+error-store-appearance = Die Darstellung konnte nicht in den Benutzereinstellungen gespeichert werden.
+error-shortcut-conflicts = Mehrere Aktionen teilen sich dieselben Tasten.
+error-palette-name = Eigene Paletten brauchen einen Namen.
+error-palette-colour = Die Farbe { $colour } "{ $value }" der Palette "{ $palette }" ist kein Hex-Code wie #1e2430.
+error-copy-empty-label = Eine Entität kann nicht auf ein leeres Label kopiert werden.
+error-copy-label-exists = Die Entität { $label } existiert in der Zieldatenbank bereits.
+error-duplicate-label-exists = Die Entität { $label } existiert bereits.
+error-copy-target-missing = Bitte eine andere geöffnete Datenbank wählen, in die die Entität kopiert werden soll.
+error-empty-descriptor = Ein leerer Deskriptor kann nicht erstellt werden.
+error-empty-label = Eine Entität ohne Label kann nicht erstellt werden.
+error-empty-name = Eine Entität ohne Namen kann nicht erstellt werden.
+error-empty-category = Eine Entität ohne Kategorie kann nicht erstellt werden.
+error-empty-parent-or-child = Elternteil und Kind dürfen nicht leer sein.
+error-relabel-empty-label = Eine Entität mit leerem Label kann nicht umbenannt werden.
+error-relabel-empty-new-label = Eine Entität kann nicht in ein leeres Label umbenannt werden.
+error-rename-empty-descriptor = Ein leerer Deskriptor kann nicht umbenannt werden.
+error-rename-empty-new-descriptor = Ein Deskriptor kann nicht in einen leeren Deskriptor umbenannt werden.
+error-descriptor-exists = Die Entität { $label } hat bereits den Deskriptor { $descriptor }.
+
+## Fehlerprotokoll
+
+error-log-title = { $count ->
+    [0] Keine Fehler in dieser Sitzung.
+    [one] Ein Fehler in dieser Sitzung.
+   *[other] { $count } Fehler in dieser Sitzung.
+}
+error-subject-database = Datenbank
+error-subject-target-database = Zieldatenbank
+error-subject-category = Kategorie
+
+## Benachrichtigungen
+
+notification-history-title = { $count ->
+    [0] Keine Benachrichtigungen in dieser Sitzung.
+    [one] Eine Benachrichtigung in dieser Sitzung.
+   *[other] { $count } Benachrichtigungen in dieser Sitzung.
+}
+notify-entity-created = Entität '{ $label }' erstellt
+notify-entity-relabelled = Entität '{ $old }' in '{ $new }' umbenannt
+notify-entity-deleted = { $relationships ->
+    [0] Entität '{ $label }' gelöscht
+    [one] Entität '{ $label }' und eine Beziehung gelöscht
+   *[other] Entität '{ $label }' und { $relationships } Beziehungen gelöscht
+}
+notify-entity-copied = Entität '{ $label }' nach { $database } kopiert
+notify-entity-duplicated = { $relationships ->
+    [true] Entität '{ $label }' samt Beziehungen als '{ $new }' dupliziert
+   *[other] Entität '{ $label }' als '{ $new }' dupliziert
+}
+notify-entities-merged = Entität '{ $source }' in '{ $target }' zusammengeführt
+notify-category-changed = Kategorie von '{ $label }' in '{ $category }' geändert
+notify-descriptor-created = Deskriptor '{ $descriptor }' zu '{ $label }' hinzugefügt
+notify-descriptor-renamed = Deskriptor '{ $old }' von '{ $label }' in '{ $new }' umbenannt
+notify-descriptor-renamed-everywhere = { $count ->
+    [one] Deskriptor '{ $old }' bei einer Entität in '{ $new }' umbenannt
+   *[other] Deskriptor '{ $old }' bei { $count } Entitäten in '{ $new }' umbenannt
+}
+notify-collisions-skipped = { $summary }, { $skipped } übersprungen
+notify-descriptor-deleted = Deskriptor '{ $descriptor }' von '{ $label }' gelöscht
+notify-description-saved = Beschreibung gespeichert
+notify-history-item-created = Geschichtseintrag in Jahr { $year }, Tag { $day } erstellt
+notify-history-item-redated = Geschichtseintrag { $timestamp } nach Jahr { $year }, Tag { $day } verschoben
+notify-history-item-deleted = Geschichtseintrag { $timestamp } gelöscht
+notify-history-content-saved = Geschichtseintrag gespeichert
+notify-relationship-created = Beziehung zwischen '{ $parent }' und '{ $child }' erstellt
+notify-role-changed = Rolle von '{ $child }' für '{ $parent }' in '{ $role }' geändert
+notify-relationship-deleted = Beziehung zwischen '{ $parent }' und '{ $child }' gelöscht
+notify-undone = Letzte Änderung rückgängig gemacht
+notify-redone = Rückgängig gemachte Änderung wiederhergestellt
+notify-external-change = Die Datenbank wurde von einem anderen Programm geändert. Die Ansichten wurden aktualisiert.
+
+## Statusleiste
+
+status-entities = { $count ->
+    [one] Eine Entität
+   *[other] { $count } Entitäten
+}
+status-descriptors = { $count ->
+    [one] ein Deskriptor
+   *[other] { $count } Deskriptoren
+}
+status-history-items = { $count ->
+    [one] ein Geschichtseintrag
+   *[other] { $count } Geschichtseinträge
+}
+status-relationships = { $count ->
+    [one] eine Beziehung
+   *[other] { $count } Beziehungen
+}
+status-search-matches = { $count ->
+    [0] keine Suchtreffer
+    [one] ein Suchtreffer
+   *[other] { $count } Suchtreffer
+}
+status-unsaved-changes = Ungespeicherte Änderungen
+status-saved = Keine ungespeicherten Änderungen
+status-file = { $size }, geändert { $modified }
+status-unknown = unbekannt
+
+## Dashboard
+
+dashboard-loading = { $title } (wird geladen…)
+dashboard-empty = Noch nichts vorhanden
+dashboard-categories = Entitäten pro Kategorie
+dashboard-uncategorised = Ohne Kategorie
+dashboard-entity-count = { $count ->
+    [one] eine Entität
+   *[other] { $count } Entitäten
+}
+dashboard-descriptors = Meistverwendete Deskriptoren
+dashboard-years = Geschichtseinträge pro Jahr
+dashboard-most-connected = Am stärksten vernetzte Entitäten
+dashboard-relationship-count = { $count ->
+    [one] eine Beziehung
+   *[other] { $count } Beziehungen
+}
+dashboard-orphans = Entitäten ohne Beziehungen ({ $count })
+dashboard-recent-history-items = Zuletzt hinzugefügte Geschichtseinträge
+
+## Entitäten zusammenführen
+
+merge-same-entity = Eine Entität kann nicht mit sich selbst zusammengeführt werden.
+merge-target-description = Ziel
+merge-source-description = Quelle
+merge-missing = (fehlt)
+merge-conflict = Konflikt:
+merge-preview = '{ $target }' nach dem Zusammenführen mit '{ $source }':
+merge-moved-relationships = { $count ->
+    [0] Es werden keine Beziehungen verschoben.
+    [one] Eine Beziehung wird verschoben.
+   *[other] { $count } Beziehungen werden verschoben.
+}
+
+## Deskriptoren umbenennen
+
+rename-everywhere-affected = { $count ->
+    [0] Keine Entität wird umbenannt.
+    [one] Eine Entität wird umbenannt:
+   *[other] { $count } Entitäten werden umbenannt:
+}
+rename-everywhere-collisions = { $count ->
+    [one] Eine Entität hat bereits { $descriptor } und wird übersprungen:
+   *[other] { $count } Entitäten haben bereits { $descriptor } und werden übersprungen:
+}
@@ -0,0 +1,439 @@
+# English messages of LoreGui. Every key must also exist in the other locales.
+
+## Menu and commands
+
+command-new-database = New Lore Database
+command-open-database = Open Lore Database
+command-recent = Recent
+command-undo = Undo
+command-redo = Redo
+command-settings = Settings
+command-error-log = { $count ->
+    [0] Error Log
+   *[other] Error Log ({ $count })
+}
+command-notifications = { $count ->
+    [0] Notifications
+   *[other] Notifications ({ $count })
+}
+command-save-description = Save Description
+command-save-history-content = Save History Item
+command-recover-drafts = Recover drafts
+command-show-entities = Show Entities
+command-show-history-items = Show History Items
+command-show-relationships = Show Relationships
+command-show-dashboard = Show Dashboard
+command-read-texts = Read Descriptions and History Items
+command-edit-texts = Edit Descriptions and History Items
+command-new-entity = New Entity
+command-relabel-entity = Relabel Entity
+command-delete-entity = Delete Entity
+command-copy-entity = Copy Entity to Database
+command-duplicate-entity = Duplicate Entity
+command-merge-entities = Merge Entities
+command-change-category = Change Category
+command-new-descriptor = New Descriptor
+command-rename-descriptor = Rename Descriptor
+command-rename-descriptor-everywhere = Rename Descriptor Everywhere
+command-show-reserved-descriptors = Show Reserved Descriptors
+command-hide-reserved-descriptors = Hide Reserved Descriptors
+command-filter-category = Show Category: { $category }
+command-group-by-category = Group Entities by Category
+command-ungroup-by-category = Stop Grouping Entities by Category
+command-delete-descriptor = Delete Descriptor
+command-new-history-item = New History Item
+command-redate-history-item = Redate History Item
+command-delete-history-item = Delete History Item
+command-new-relationship = New Relationship
+command-change-role = Change Role
+command-delete-relationship = Delete Relationship
+command-go-to = Go to { $label }
+command-palette-search = Type a command or entity...
+command-palette-nothing-found = [Nothing found]
+
+## Main window
+
+view-entities = Entities
+view-history-items = History Items
+view-relationships = Relationships
+view-dashboard = Dashboard
+tab-no-database = [No database]
+tab-close = ×
+tab-close-tooltip = Close this database
+no-database-loaded = [No database loaded]
+column-label = Label
+column-descriptor = Descriptor
+column-description = Description
+column-year = Year
+column-day = Day
+column-timestamp = Timestamp
+column-content = Content
+column-parent = Parent
+column-child = Child
+column-role = Role
+column-selected = Selected: { $entry }
+column-loading = { $text } (loading...)
+column-search = Type to search...
+category-all = All categories
+entry-none = [none]
+file-filter-database = Lore Database (.db)
+file-filter-any = Any
+
+## Global search
+
+search-placeholder = Search everything...
+search-results-button = Results
+search-result-count = { $count ->
+    [0] No results
+    [one] { $count } result
+   *[other] { $count } results
+}
+search-in-progress = { $summary } (searching...)
+search-syntax-hint = Use "quotes" for phrases and * for prefixes.
+search-history-item = Year { $year }, day { $day } ({ $timestamp })
+
+## Buttons
+
+button-add-palette = Add Palette
+button-back = Back
+button-cancel = Cancel
+button-change = Change
+button-clear = Clear
+button-close = Close
+button-concatenate = Concatenate
+button-copy = Copy
+button-duplicate = Duplicate
+button-copy-to-clipboard = Copy to clipboard
+button-create = Create
+button-delete = Delete
+button-discard = Discard
+button-discard-changes = Discard Changes
+button-edit = Edit
+button-hide-diff = Hide diff
+button-hide-details = Hide details
+button-keep-mine = Keep mine
+button-keep-source = Keep source
+button-keep-target = Keep target
+button-merge = Merge
+button-next = Next
+button-no = No
+button-ok = Ok
+button-open = Open
+button-read = Read
+button-pin = Pin
+button-redate = Redate
+button-remove = Remove
+button-reset-to-defaults = Reset to Defaults
+button-restore = Restore
+button-save = Save
+button-save-changes = Save Changes
+button-show-details = Show details
+button-take-theirs = Take theirs
+button-unpin = Unpin
+button-update = Update
+button-view-diff = View diff
+button-yes = Yes
+
+## Dialogs
+
+dialog-change-role = Change role { $role } for relationship between { $parent } and { $child }
+dialog-change-category = Change category of { $label }
+dialog-commands = Commands
+dialog-confirmation = Confirmation
+dialog-conflict = Changed by someone else
+dialog-copy-entity = Copy entity: { $label }
+dialog-duplicate-entity = Duplicate entity: { $label }
+dialog-draft-recovery = Recover unsaved changes
+dialog-error = Error
+dialog-merge-entities = Merge two entities
+dialog-new-descriptor = New Descriptor
+dialog-new-entity = Create new entity
+dialog-new-history-item = Create new history item
+dialog-new-relationship = New Relationship
+dialog-recent-databases = Recent Databases
+dialog-redate-history-item = Redate history for entity: year { $year }, day { $day } ({ $timestamp })
+dialog-relabel-entity = Relabel entity: { $label }
+dialog-rename-descriptor = Rename descriptor { $descriptor } of entity { $label }
+dialog-rename-descriptor-everywhere = Rename descriptor { $descriptor } on all entities
+dialog-settings = Settings
+dialog-unsaved-changes = Unsaved changes
+
+field-category = Category:
+field-group-by-category = Group by category
+field-child = Child:
+field-content = Content:
+field-day = Day:
+field-day-optional = Day (optional):
+field-description = Description:
+field-descriptor = Descriptor:
+field-label = Label:
+field-merge-source = Merge (will be deleted):
+field-merge-target = Into:
+field-name = Name
+field-new-descriptor = New Descriptor
+field-new-label = New Label
+field-new-role = New Role
+field-new-name = New Name
+field-keep-name = Keep the name of the original
+field-duplicate-relationships = Duplicate relationships
+field-parent = Parent:
+field-role = Role:
+field-target-database = Target Database:
+field-target-label = Label in Target Database:
+field-year = Year:
+
+confirm-delete-entity = Do you really want to delete { $label }?
+confirm-delete-descriptor = Do you really want to delete { $label }'s descriptor { $descriptor }?
+confirm-delete-history-item = Do you really want to delete { $timestamp }?
+confirm-delete-relationship = Do you really want to delete the { $role } relationship between { $parent } and { $child }?
+
+unsaved-changes-description = The description you are editing has unsaved changes. Do you want to save them?
+unsaved-changes-history-item = The history item you are editing has unsaved changes. Do you want to save them?
+unsaved-changes-database = { $database } has unsaved changes. Do you want to save them?
+unsaved-changes-all-databases = Some of the open databases have unsaved changes. Do you want to save them?
+
+conflict-description = The description you are editing was changed outside of LoreGui. Which version do you want to keep?
+conflict-history-item = The history item you are editing was changed outside of LoreGui. Which version do you want to keep?
+conflict-diff-legend = Lines only in theirs are marked -, lines only in mine +.
+
+recent-database-missing = { $path } (missing)
+recent-database-error = The last used database could not be opened:
+    { $error }
+recent-databases-none = [No recent databases]
+
+draft-description = Description of { $label }: { $descriptor }
+draft-history-item = History item { $timestamp } of year { $year }
+draft-deleted = { $title } (no longer exists)
+draft-recovery-explanation = { $count ->
+    [one] This text had unsaved changes when LoreGui was last closed.
+   *[other] These { $count } texts had unsaved changes when LoreGui was last closed.
+} Lines only in the database are marked -, lines only in the draft +.
+
+## Settings
+
+settings-appearance = Appearance
+settings-language = Language
+settings-theme = Theme
+settings-notification-duration = Notification duration
+settings-seconds = { $seconds } s
+settings-custom-palettes = Custom palettes
+settings-palette-name = Name
+settings-new-palette = New palette
+settings-keyboard-shortcuts = Keyboard Shortcuts
+settings-press-a-key = [Press a key]
+settings-unbound = [Unbound]
+settings-shortcut-conflicts = Some actions share the same keys. Please resolve this before saving.
+theme-light = Light
+theme-dark = Dark
+palette-background = Background
+palette-text = Text
+palette-primary = Primary
+palette-success = Success
+palette-danger = Danger
+size-font = Base font size
+size-header = Header size
+size-row-height = List row height
+size-spacing = Spacing
+
+shortcut-save = Save the active editor
+shortcut-new = New item in the current view
+shortcut-delete = Delete the selection
+shortcut-submit-dialog = Submit dialog
+shortcut-close-dialog = Close dialog
+shortcut-undo = Undo
+shortcut-redo = Redo
+shortcut-entity-view = Show entities
+shortcut-history-view = Show history items
+shortcut-relationship-view = Show relationships
+shortcut-dashboard-view = Show dashboard
+shortcut-select-previous = Select previous entry
+shortcut-select-next = Select next entry
+shortcut-previous-column = Move to previous column
+shortcut-next-column = Move to next column
+shortcut-command-palette = Show all commands
+shortcut-toggle-read-mode = Switch between reading and editing
+
+## Command line
+
+cli-usage = Usage: loregui <DATABASE> <COMMAND>
+cli-commands = Commands:
+cli-gui-hint = Without any arguments, the graphical user interface is started.
+cli-no-database = No database given.
+cli-unknown-command = Unknown command: { $command }
+cli-invalid-timestamp = { $timestamp } is not a valid timestamp.
+cli-history-item-missing = History item { $timestamp } does not exist.
+cli-entity-created = Created entity { $label }.
+cli-entity-relabelled = Relabelled entity { $old_label } to { $new_label }.
+cli-entity-deleted = Deleted entity { $label }.
+cli-descriptor-changed = Changed descriptor { $descriptor } of entity { $label }.
+cli-descriptor-added = Added descriptor { $descriptor } to entity { $label }.
+cli-history-item-created = Created history item { $timestamp }.
+cli-history-item-redated = Redated history item { $timestamp }.
+cli-relationship-created = Created relationship between { $parent } and { $child }.
+cli-role-changed = Changed role of relationship between { $parent } and { $child } to { $role }.
+
+## Errors
+
+error-file = File error: { $message }
+error-input = Input error: { $message }
+error-no-database = No database
+error-multiple-results = Multiple results
+error-lore-core = Lore core error: { $error }
+error-in-context = { $operation } failed: { $cause }
+error-operation-failed = { $operation } failed.
+error-caused-by = Caused by:
+error-database-missing = Database { $path } does not exist.
+error-entity-missing = Entity { $label } does not exist.
+error-file-read = Could not read { $path }: { $error }
+error-file-parse = Could not parse { $path }: { $error }
+error-file-remove = Could not remove { $path }: { $error }
+error-file-write = Could not write { $path }: { $error }
+error-drafts-serialise = Could not serialise drafts: { $error }
+error-search-index = Search index: { $error }
+error-store-recent-databases = The list of recent databases could not be stored as user preference.
+error-store-key-bindings = The key bindings could not be stored as user preference.
+error-store-database-sessions = The database sessions could not be stored as user preference.
+error-store-appearance = The appearance could not be stored as user preference.
+error-shortcut-conflicts = Several actions share the same keys.
+error-palette-name = Custom palettes need a name.
+error-palette-colour = { $colour } colour "{ $value }" of palette "{ $palette }" is not a hex code like #1e2430.
+error-copy-empty-label = Cannot copy entity to an empty label.
+error-copy-label-exists = Entity { $label } already exists in the target database.
+error-duplicate-label-exists = Entity { $label } already exists.
+error-copy-target-missing = Please choose another open database to copy the entity to.
+error-empty-descriptor = Cannot create empty descriptor.
+error-empty-label = Cannot create entity with empty label.
+error-empty-name = Cannot create entity with empty name.
+error-empty-category = Cannot create entity with empty category.
+error-empty-parent-or-child = Parent and child cannot be empty.
+error-relabel-empty-label = Cannot relabel entity with empty label.
+error-relabel-empty-new-label = Cannot relabel entity with empty new label.
+error-rename-empty-descriptor = Cannot rename descriptor with empty descriptor.
+error-rename-empty-new-descriptor = Cannot rename descriptor with empty new descriptor.
+error-descriptor-exists = Entity { $label } already has the descriptor { $descriptor }.
+
+## Error log
+
+error-log-title = { $count ->
+    [0] No errors in this session.
+    [one] One error in this session.
+   *[other] { $count } errors in this session.
+}
+error-subject-database = Database
+error-subject-target-database = Target database
+error-subject-category = Category
+
+## Notifications
+
+notification-history-title = { $count ->
+    [0] No notifications in this session.
+    [one] One notification in this session.
+   *[other] { $count } notifications in this session.
+}
+notify-entity-created = Entity '{ $label }' created
+notify-entity-relabelled = Entity '{ $old }' relabelled to '{ $new }'
+notify-entity-deleted = { $relationships ->
+    [0] Entity '{ $label }' deleted
+    [one] Entity '{ $label }' and one relationship deleted
+   *[other] Entity '{ $label }' and { $relationships } relationships deleted
+}
+notify-entity-copied = Entity '{ $label }' copied to { $database }
+notify-entity-duplicated = { $relationships ->
+    [true] Entity '{ $label }' duplicated as '{ $new }' with its relationships
+   *[other] Entity '{ $label }' duplicated as '{ $new }'
+}
+notify-entities-merged = Entity '{ $source }' merged into '{ $target }'
+notify-category-changed = Category of '{ $label }' changed to '{ $category }'
+notify-descriptor-created = Descriptor '{ $descriptor }' added to '{ $label }'
+notify-descriptor-renamed = Descriptor '{ $old }' of '{ $label }' renamed to '{ $new }'
+notify-descriptor-renamed-everywhere = { $count ->
+    [one] Descriptor '{ $old }' renamed to '{ $new }' on one entity
+   *[other] Descriptor '{ $old }' renamed to '{ $new }' on { $count } entities
+}
+notify-collisions-skipped = { $summary }, { $skipped } skipped
+notify-descriptor-deleted = Descriptor '{ $descriptor }' of '{ $label }' deleted
+notify-description-saved = Description saved
+notify-history-item-created = History item created in year { $year }, day { $day }
+notify-history-item-redated = History item { $timestamp } moved to year { $year }, day { $day }
+notify-history-item-deleted = History item { $timestamp } deleted
+notify-history-content-saved = History item saved
+notify-relationship-created = Relationship between '{ $parent }' and '{ $child }' created
+notify-role-changed = Role of '{ $child }' for '{ $parent }' changed to '{ $role }'
+notify-relationship-deleted = Relationship between '{ $parent }' and '{ $child }' deleted
+notify-undone = Last change undone
+notify-redone = Last undone change redone
+notify-external-change = The database was changed by another program. The views were refreshed.
+
+## Status bar
+
+status-entities = { $count ->
+    [one] One entity
+   *[other] { $count } entities
+}
+status-descriptors = { $count ->
+    [one] one descriptor
+   *[other] { $count } descriptors
+}
+status-history-items = { $count ->
+    [one] one history item
+   *[other] { $count } history items
+}
+status-relationships = { $count ->
+    [one] one relationship
+   *[other] { $count } relationships
+}
+status-search-matches = { $count ->
+    [0] no search matches
+    [one] one search match
+   *[other] { $count } search matches
+}
+status-unsaved-changes = Unsaved changes
+status-saved = No unsaved changes
+status-file = { $size }, modified { $modified }
+status-unknown = unknown
+
+## Dashboard
+
+dashboard-loading = { $title } (loading…)
+dashboard-empty = Nothing yet
+dashboard-categories = Entities per category
+dashboard-uncategorised = Without category
+dashboard-entity-count = { $count ->
+    [one] one entity
+   *[other] { $count } entities
+}
+dashboard-descriptors = Most used descriptors
+dashboard-years = History items per year
+dashboard-most-connected = Most connected entities
+dashboard-relationship-count = { $count ->
+    [one] one relationship
+   *[other] { $count } relationships
+}
+dashboard-orphans = Entities without relationships ({ $count })
+dashboard-recent-history-items = Recently added history items
+
+## Merging entities
+
+merge-same-entity = An entity cannot be merged into itself.
+merge-target-description = Target
+merge-source-description = Source
+merge-missing = (missing)
+merge-conflict = Conflict:
+merge-preview = '{ $target }' after merging '{ $source }' into it:
+merge-moved-relationships = { $count ->
+    [0] No relationships will be moved.
+    [one] One relationship will be moved.
+   *[other] { $count } relationships will be moved.
+}
+
+## Renaming descriptors
+
+rename-everywhere-affected = { $count ->
+    [0] No entity will be renamed.
+    [one] One entity will be renamed:
+   *[other] { $count } entities will be renamed:
+}
+rename-everywhere-collisions = { $count ->
+    [one] One entity already has { $descriptor } and is skipped:
+   *[other] { $count } entities already have { $descriptor } and are skipped:
+}
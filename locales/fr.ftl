@@ -0,0 +1,439 @@
+# Messages français de LoreGui. Chaque clé doit aussi exister dans les autres langues.
+
+## Menu et commandes
+
+command-new-database = Nouvelle base Lore
+command-open-database = Ouvrir une base Lore
+command-recent = Récents
+command-undo = Annuler
+command-redo = Rétablir
+command-settings = Paramètres
+command-error-log = { $count ->
+    [0] Journal des erreurs
+   *[other] Journal des erreurs ({ $count })
+}
+command-notifications = { $count ->
+    [0] Notifications
+   *[other] Notifications ({ $count })
+}
+command-save-description = Enregistrer la description
+command-save-history-content = Enregistrer l'entrée d'historique
+command-recover-drafts = Récupérer les brouillons
+command-show-entities = Afficher les entités
+command-show-history-items = Afficher les entrées d'historique
+command-show-relationships = Afficher les relations
+command-show-dashboard = Afficher le tableau de bord
+command-read-texts = Lire les descriptions et les entrées d'historique
+command-edit-texts = Modifier les descriptions et les entrées d'historique
+command-new-entity = Nouvelle entité
+command-relabel-entity = Renommer l'entité
+command-delete-entity = Supprimer l'entité
+command-copy-entity = Copier l'entité vers une base
+command-duplicate-entity = Dupliquer l'entité
+command-merge-entities = Fusionner des entités
+command-change-category = Changer de catégorie
+command-new-descriptor = Nouveau descripteur
+command-rename-descriptor = Renommer le descripteur
+command-rename-descriptor-everywhere = Renommer le descripteur partout
+command-show-reserved-descriptors = Afficher les descripteurs réservés
+command-hide-reserved-descriptors = Masquer les descripteurs réservés
+command-filter-category = Afficher la catégorie : { $category }
+command-group-by-category = Grouper les entités par catégorie
+command-ungroup-by-category = Ne plus grouper les entités par catégorie
+command-delete-descriptor = Supprimer le descripteur
+command-new-history-item = Nouvelle entrée d'historique
+command-redate-history-item = Redater l'entrée d'historique
+command-delete-history-item = Supprimer l'entrée d'historique
+command-new-relationship = Nouvelle relation
+command-change-role = Changer le rôle
+command-delete-relationship = Supprimer la relation
+command-go-to = Aller à { $label }
+command-palette-search = Saisir une commande ou une entité...
+command-palette-nothing-found = [Aucun résultat]
+
+## Fenêtre principale
+
+view-entities = Entités
+view-history-items = Entrées d'historique
+view-relationships = Relations
+view-dashboard = Tableau de bord
+tab-no-database = [Aucune base]
+tab-close = ×
+tab-close-tooltip = Fermer cette base de données
+no-database-loaded = [Aucune base chargée]
+column-label = Libellé
+column-descriptor = Descripteur
+column-description = Description
+column-year = Année
+column-day = Jour
+column-timestamp = Horodatage
+column-content = Contenu
+column-parent = Parent
+column-child = Enfant
+column-role = Rôle
+column-selected = Sélection : { $entry }
+column-loading = { $text } (chargement...)
+column-search = Tapez pour rechercher...
+category-all = Toutes les catégories
+entry-none = [aucun]
+file-filter-database = Base Lore (.db)
+file-filter-any = Tous
+
+## Recherche globale
+
+search-placeholder = Tout rechercher...
+search-results-button = Résultats
+search-result-count = { $count ->
+    [0] Aucun résultat
+    [one] { $count } résultat
+   *[other] { $count } résultats
+}
+search-in-progress = { $summary } (recherche...)
+search-syntax-hint = Utilisez des "guillemets" pour les expressions et * pour les préfixes.
+search-history-item = Année { $year }, jour { $day } ({ $timestamp })
+
+## Boutons
+
+button-add-palette = Ajouter la palette
+button-back = Retour
+button-cancel = Annuler
+button-change = Modifier
+button-clear = Vider
+button-close = Fermer
+button-concatenate = Concaténer
+button-copy = Copier
+button-duplicate = Dupliquer
+button-copy-to-clipboard = Copier dans le presse-papiers
+button-create = Créer
+button-delete = Supprimer
+button-discard = Abandonner
+button-discard-changes = Abandonner les modifications
+button-edit = Modifier
+button-hide-diff = Masquer les différences
+button-hide-details = Masquer les détails
+button-keep-mine = Garder la mienne
+button-keep-source = Garder la source
+button-keep-target = Garder la cible
+button-merge = Fusionner
+button-next = Suivant
+button-no = Non
+button-ok = Ok
+button-open = Ouvrir
+button-read = Lire
+button-pin = Épingler
+button-redate = Redater
+button-remove = Retirer
+button-reset-to-defaults = Rétablir les valeurs par défaut
+button-restore = Restaurer
+button-save = Enregistrer
+button-save-changes = Enregistrer les modifications
+button-show-details = Afficher les détails
+button-take-theirs = Prendre la leur
+button-unpin = Désépingler
+button-update = Mettre à jour
+button-view-diff = Voir les différences
+button-yes = Oui
+
+## Dialogues
+
+dialog-change-role = Changer le rôle { $role } de la relation entre { $parent } et { $child }
+dialog-change-category = Changer la catégorie de { $label }
+dialog-commands = Commandes
+dialog-confirmation = Confirmation
+dialog-conflict = Modifié par quelqu'un d'autre
+dialog-copy-entity = Copier l'entité : { $label }
+dialog-duplicate-entity = Dupliquer l'entité : { $label }
+dialog-draft-recovery = Récupérer les modifications non enregistrées
+dialog-error = Erreur
+dialog-merge-entities = Fusionner deux entités
+dialog-new-descriptor = Nouveau descripteur
+dialog-new-entity = Créer une nouvelle entité
+dialog-new-history-item = Créer une nouvelle entrée d'historique
+dialog-new-relationship = Nouvelle relation
+dialog-recent-databases = Bases récentes
+dialog-redate-history-item = Redater l'entrée d'historique : année { $year }, jour { $day } ({ $timestamp })
+dialog-relabel-entity = Renommer l'entité : { $label }
+dialog-rename-descriptor = Renommer le descripteur { $descriptor } de l'entité { $label }
+dialog-rename-descriptor-everywhere = Renommer le descripteur { $descriptor } sur toutes les entités
+dialog-settings = Paramètres
+dialog-unsaved-changes = Modifications non enregistrées
+
+field-category = Catégorie :
+field-group-by-category = Grouper par catégorie
+field-child = Enfant :
+field-content = Contenu :
+field-day = Jour :
+field-day-optional = Jour (facultatif) :
+field-description = Description :
+field-descriptor = Descripteur :
+field-label = Libellé :
+field-merge-source = Fusionner (sera supprimée) :
+field-merge-target = Dans :
+field-name = Nom
+field-new-descriptor = Nouveau descripteur
+field-new-label = Nouveau libellé
+field-new-role = Nouveau rôle
+field-new-name = Nouveau nom
+field-keep-name = Conserver le nom de l'original
+field-duplicate-relationships = Dupliquer les relations
+field-parent = Parent :
+field-role = Rôle :
+field-target-database = Base cible :
+field-target-label = Libellé dans la base cible :
+field-year = Année :
+
+confirm-delete-entity = Voulez-vous vraiment supprimer { $label } ?
+confirm-delete-descriptor = Voulez-vous vraiment supprimer le descripteur { $descriptor } de { $label } ?
+confirm-delete-history-item = Voulez-vous vraiment supprimer { $timestamp } ?
+confirm-delete-relationship = Voulez-vous vraiment supprimer la relation { $role } entre { $parent } et { $child } ?
+
+unsaved-changes-description = La description en cours de modification contient des changements non enregistrés. Voulez-vous les enregistrer ?
+unsaved-changes-history-item = L'entrée d'historique en cours de modification contient des changements non enregistrés. Voulez-vous les enregistrer ?
+unsaved-changes-database = { $database } contient des changements non enregistrés. Voulez-vous les enregistrer ?
+unsaved-changes-all-databases = Certaines des bases ouvertes contiennent des changements non enregistrés. Voulez-vous les enregistrer ?
+
+conflict-description = La description en cours de modification a été changée en dehors de LoreGui. Quelle version voulez-vous garder ?
+conflict-history-item = L'entrée d'historique en cours de modification a été changée en dehors de LoreGui. Quelle version voulez-vous garder ?
+conflict-diff-legend = Les lignes présentes seulement dans la leur sont marquées -, celles présentes seulement dans la mienne +.
+
+recent-database-missing = { $path } (introuvable)
+recent-database-error = La dernière base utilisée n'a pas pu être ouverte :
+    { $error }
+recent-databases-none = [Aucune base récente]
+
+draft-description = Description de { $label } : { $descriptor }
+draft-history-item = Entrée d'historique { $timestamp } de l'année { $year }
+draft-deleted = { $title } (n'existe plus)
+draft-recovery-explanation = { $count ->
+    [one] Ce texte contenait des modifications non enregistrées à la dernière fermeture de LoreGui.
+   *[other] Ces { $count } textes contenaient des modifications non enregistrées à la dernière fermeture de LoreGui.
+} Les lignes présentes seulement dans la base sont marquées -, celles présentes seulement dans le brouillon +.
+
+## Paramètres
+
+settings-appearance = Apparence
+settings-language = Langue
+settings-theme = Thème
+settings-notification-duration = Durée des notifications
+settings-seconds = { $seconds } s
+settings-custom-palettes = Palettes personnalisées
+settings-palette-name = Nom
+settings-new-palette = Nouvelle palette
+settings-keyboard-shortcuts = Raccourcis clavier
+settings-press-a-key = [Appuyez sur une touche]
+settings-unbound = [Non attribué]
+settings-shortcut-conflicts = Certaines actions partagent les mêmes touches. Veuillez corriger cela avant d'enregistrer.
+theme-light = Clair
+theme-dark = Sombre
+palette-background = Arrière-plan
+palette-text = Texte
+palette-primary = Principale
+palette-success = Succès
+palette-danger = Danger
+size-font = Taille de police de base
+size-header = Taille des titres
+size-row-height = Hauteur des lignes de liste
+size-spacing = Espacement
+
+shortcut-save = Enregistrer l'éditeur actif
+shortcut-new = Nouvel élément dans la vue actuelle
+shortcut-delete = Supprimer la sélection
+shortcut-submit-dialog = Valider le dialogue
+shortcut-close-dialog = Fermer le dialogue
+shortcut-undo = Annuler
+shortcut-redo = Rétablir
+shortcut-entity-view = Afficher les entités
+shortcut-history-view = Afficher les entrées d'historique
+shortcut-relationship-view = Afficher les relations
+shortcut-dashboard-view = Afficher le tableau de bord
+shortcut-select-previous = Sélectionner l'entrée précédente
+shortcut-select-next = Sélectionner l'entrée suivante
+shortcut-previous-column = Aller à la colonne précédente
+shortcut-next-column = Aller à la colonne suivante
+shortcut-command-palette = Afficher toutes les commandes
+shortcut-toggle-read-mode = Basculer entre lecture et modification
+
+## Ligne de commande
+
+cli-usage = Utilisation : loregui <BASE_DE_DONNÉES> <COMMANDE>
+cli-commands = Commandes :
+cli-gui-hint = Sans arguments, l'interface graphique est lancée.
+cli-no-database = Aucune base de données indiquée.
+cli-unknown-command = Commande inconnue : { $command }
+cli-invalid-timestamp = { $timestamp } n'est pas un horodatage valide.
+cli-history-item-missing = L'événement { $timestamp } n'existe pas.
+cli-entity-created = Entité { $label } créée.
+cli-entity-relabelled = Entité { $old_label } renommée en { $new_label }.
+cli-entity-deleted = Entité { $label } supprimée.
+cli-descriptor-changed = Descripteur { $descriptor } de l'entité { $label } modifié.
+cli-descriptor-added = Descripteur { $descriptor } ajouté à l'entité { $label }.
+cli-history-item-created = Événement { $timestamp } créé.
+cli-history-item-redated = Événement { $timestamp } redaté.
+cli-relationship-created = Relation entre { $parent } et { $child } créée.
+cli-role-changed = Rôle de la relation entre { $parent } et { $child } changé en { $role }.
+
+## Erreurs
+
+error-file = Erreur de fichier : { $message }
+error-input = Erreur de saisie : { $message }
+error-no-database = Aucune base
+error-multiple-results = Plusieurs résultats
+error-lore-core = Erreur de lorecore : { $error }
+error-in-context = Échec de « { $operation } » : { $cause }
+error-operation-failed = Échec de « { $operation } ».
+error-caused-by = Causes :
+error-database-missing = La base { $path } n'existe pas.
+error-entity-missing = L'entité { $label } n'existe pas.
+error-file-read = Impossible de lire { $path } : { $error }
+error-file-parse = Impossible d'analyser { $path } : { $error }
+error-file-remove = Impossible de supprimer { $path } : { $error }
+error-file-write = Impossible d'écrire { $path } : { $error }
+error-drafts-serialise = Impossible de sérialiser les brouillons : { $error }
+error-search-index = Index de recherche : { $error }
+error-store-recent-databases = La liste des bases récentes n'a pas pu être enregistrée dans les préférences.
+error-store-key-bindings = Les raccourcis clavier n'ont pas pu être enregistrés dans les préférences.
+error-store-database-sessions = Les sessions des bases n'ont pas pu être enregistrées dans les préférences.
+error-store-appearance = L'apparence n'a pas pu être enregistrée dans les préférences.
+error-shortcut-conflicts = Plusieurs actions partagent les mêmes touches.
+error-palette-name = Les palettes personnalisées doivent avoir un nom.
+error-palette-colour = La couleur { $colour } "{ $value }" de la palette "{ $palette }" n'est pas un code hexadécimal comme #1e2430.
+error-copy-empty-label = Impossible de copier une entité vers un libellé vide.
+error-copy-label-exists = L'entité { $label } existe déjà dans la base cible.
+error-duplicate-label-exists = L'entité { $label } existe déjà.
+error-copy-target-missing = Veuillez choisir une autre base ouverte vers laquelle copier l'entité.
+error-empty-descriptor = Impossible de créer un descripteur vide.
+error-empty-label = Impossible de créer une entité sans libellé.
+error-empty-name = Impossible de créer une entité sans nom.
+error-empty-category = Impossible de créer une entité sans catégorie.
+error-empty-parent-or-child = Le parent et l'enfant ne peuvent pas être vides.
+error-relabel-empty-label = Impossible de renommer une entité au libellé vide.
+error-relabel-empty-new-label = Impossible de renommer une entité avec un nouveau libellé vide.
+error-rename-empty-descriptor = Impossible de renommer un descripteur vide.
+error-rename-empty-new-descriptor = Impossible de renommer un descripteur avec un nouveau descripteur vide.
+error-descriptor-exists = L'entité { $label } possède déjà le descripteur { $descriptor }.
+
+## Journal des erreurs
+
+error-log-title = { $count ->
+    [0] Aucune erreur dans cette session.
+    [one] Une erreur dans cette session.
+   *[other] { $count } erreurs dans cette session.
+}
+error-subject-database = Base de données
+error-subject-target-database = Base de données cible
+error-subject-category = Catégorie
+
+## Notifications
+
+notification-history-title = { $count ->
+    [0] Aucune notification dans cette session.
+    [one] Une notification dans cette session.
+   *[other] { $count } notifications dans cette session.
+}
+notify-entity-created = Entité « { $label } » créée
+notify-entity-relabelled = Entité « { $old } » renommée en « { $new } »
+notify-entity-deleted = { $relationships ->
+    [0] Entité « { $label } » supprimée
+    [one] Entité « { $label } » et une relation supprimées
+   *[other] Entité « { $label } » et { $relationships } relations supprimées
+}
+notify-entity-copied = Entité « { $label } » copiée vers { $database }
+notify-entity-duplicated = { $relationships ->
+    [true] Entité « { $label } » dupliquée en « { $new } » avec ses relations
+   *[other] Entité « { $label } » dupliquée en « { $new } »
+}
+notify-entities-merged = Entité « { $source } » fusionnée dans « { $target } »
+notify-category-changed = Catégorie de « { $label } » changée en « { $category } »
+notify-descriptor-created = Descripteur « { $descriptor } » ajouté à « { $label } »
+notify-descriptor-renamed = Descripteur « { $old } » de « { $label } » renommé en « { $new } »
+notify-descriptor-renamed-everywhere = { $count ->
+    [one] Descripteur « { $old } » renommé en « { $new } » sur une entité
+   *[other] Descripteur « { $old } » renommé en « { $new } » sur { $count } entités
+}
+notify-collisions-skipped = { $summary }, { $skipped } ignorées
+notify-descriptor-deleted = Descripteur « { $descriptor } » de « { $label } » supprimé
+notify-description-saved = Description enregistrée
+notify-history-item-created = Entrée d'historique créée en l'an { $year }, jour { $day }
+notify-history-item-redated = Entrée d'historique { $timestamp } déplacée en l'an { $year }, jour { $day }
+notify-history-item-deleted = Entrée d'historique { $timestamp } supprimée
+notify-history-content-saved = Entrée d'historique enregistrée
+notify-relationship-created = Relation entre « { $parent } » et « { $child } » créée
+notify-role-changed = Rôle de « { $child } » pour « { $parent } » changé en « { $role } »
+notify-relationship-deleted = Relation entre « { $parent } » et « { $child } » supprimée
+notify-undone = Dernière modification annulée
+notify-redone = Modification annulée rétablie
+notify-external-change = La base de données a été modifiée par un autre programme. Les vues ont été actualisées.
+
+## Barre d'état
+
+status-entities = { $count ->
+    [one] { $count } entité
+   *[other] { $count } entités
+}
+status-descriptors = { $count ->
+    [one] { $count } descripteur
+   *[other] { $count } descripteurs
+}
+status-history-items = { $count ->
+    [one] { $count } entrée d'historique
+   *[other] { $count } entrées d'historique
+}
+status-relationships = { $count ->
+    [one] { $count } relation
+   *[other] { $count } relations
+}
+status-search-matches = { $count ->
+    [0] aucun résultat de recherche
+    [one] un résultat de recherche
+   *[other] { $count } résultats de recherche
+}
+status-unsaved-changes = Modifications non enregistrées
+status-saved = Aucune modification non enregistrée
+status-file = { $size }, modifié le { $modified }
+status-unknown = inconnu
+
+## Dashboard
+
+dashboard-loading = { $title } (chargement…)
+dashboard-empty = Rien pour l'instant
+dashboard-categories = Entités par catégorie
+dashboard-uncategorised = Sans catégorie
+dashboard-entity-count = { $count ->
+    [one] { $count } entité
+   *[other] { $count } entités
+}
+dashboard-descriptors = Descripteurs les plus utilisés
+dashboard-years = Entrées d'historique par année
+dashboard-most-connected = Entités les plus connectées
+dashboard-relationship-count = { $count ->
+    [one] { $count } relation
+   *[other] { $count } relations
+}
+dashboard-orphans = Entités sans relation ({ $count })
+dashboard-recent-history-items = Entrées d'historique ajoutées récemment
+
+## Fusion d'entités
+
+merge-same-entity = Une entité ne peut pas être fusionnée avec elle-même.
+merge-target-description = Cible
+merge-source-description = Source
+merge-missing = (absent)
+merge-conflict = Conflit :
+merge-preview = « { $target } » après y avoir fusionné « { $source } » :
+merge-moved-relationships = { $count ->
+    [0] Aucune relation ne sera déplacée.
+    [one] Une relation sera déplacée.
+   *[other] { $count } relations seront déplacées.
+}
+
+## Renommage de descripteurs
+
+rename-everywhere-affected = { $count ->
+    [0] Aucune entité ne sera renommée.
+    [one] Une entité sera renommée :
+   *[other] { $count } entités seront renommées :
+}
+rename-everywhere-collisions = { $count ->
+    [one] Une entité possède déjà { $descriptor } et sera ignorée :
+   *[other] { $count } entités possèdent déjà { $descriptor } et seront ignorées :
+}
@@ -0,0 +1,364 @@
+use std::collections::{BTreeMap, BTreeSet};
+
+use lorecore::{
+    sql::search_params::{EntityColumnSearchParams, HistoryItemSearchParams, SqlSearchText},
+    types::*,
+};
+
+use super::{SqlGui, ViewType};
+use crate::{
+    dialog::draft_recovery::{DraftRecoveryDialog, RecoveredDraft},
+    drafts::{load_drafts, store_drafts, Draft, DraftKey},
+    editor::EditorState,
+    errors::LoreGuiError,
+};
+
+/// The drafts files of the databases, keyed by database path.
+#[derive(Debug, Default)]
+pub(super) struct DraftFiles {
+    /// What was last written to the files.
+    written: BTreeMap<String, Vec<Draft>>,
+    /// Drafts left behind by an earlier session, that have been neither restored nor discarded yet.
+    orphaned: BTreeMap<String, Vec<Draft>>,
+}
+
+impl SqlGui {
+    fn drafts_of_tab(&self, index: usize) -> Vec<Draft> {
+        if index == self.active_tab {
+            [
+                self.entity_view_state.draft(),
+                self.history_view_state.draft(),
+            ]
+            .into_iter()
+            .flatten()
+            .collect()
+        } else {
+            self.tabs
+                .get(index)
+                .map(|tab| tab.drafts())
+                .unwrap_or_default()
+        }
+    }
+
+    /// Drafts left behind stay in their file until they are restored or discarded, even once their database is closed.
+    fn current_drafts(&self) -> BTreeMap<String, Vec<Draft>> {
+        let mut drafts = self.draft_files.orphaned.clone();
+        for index in 0..self.tabs.len() {
+            let path = match self.database_of_tab(index) {
+                Some(db) => db.path_as_string(),
+                None => continue,
+            };
+            drafts
+                .entry(path)
+                .or_default()
+                .extend(self.drafts_of_tab(index));
+        }
+        drafts
+    }
+
+    /// Writes the unsaved changes of all open databases to their drafts files, if they changed since the last time.
+    pub(super) fn autosave_drafts(&mut self) -> Result<(), LoreGuiError> {
+        let mut current = self.current_drafts();
+        let paths: BTreeSet<String> = current
+            .keys()
+            .chain(self.draft_files.written.keys())
+            .cloned()
+            .collect();
+        for path in paths {
+            let drafts = current.remove(&path).unwrap_or_default();
+            let written = self.draft_files.written.get(&path);
+            if written.map(Vec::as_slice).unwrap_or_default() == drafts.as_slice() {
+                continue;
+            }
+            store_drafts(&path, &drafts)?;
+            if drafts.is_empty() {
+                self.draft_files.written.remove(&path);
+            } else {
+                self.draft_files.written.insert(path, drafts);
+            }
+        }
+        Ok(())
+    }
+
+    /// Shows the drafts that an earlier session left behind for the active database.
+    pub(super) fn offer_draft_recovery(&mut self) -> Result<(), LoreGuiError> {
+        let path = match self.lore_database.as_ref() {
+            Some(db) => db.path_as_string(),
+            None => return Ok(()),
+        };
+        let drafts = load_drafts(&path)?;
+        if drafts.is_empty() {
+            return Ok(());
+        }
+        self.draft_files
+            .written
+            .insert(path.clone(), drafts.clone());
+        let mut orphaned = vec![];
+        for draft in drafts {
+            let stored = self.stored_text(&draft.key)?;
+            if stored.is_some_and(|stored| EditorState::new(&stored).get_text() == draft.text) {
+                continue;
+            }
+            orphaned.push(draft);
+        }
+        self.draft_files.orphaned.insert(path, orphaned);
+        self.show_draft_recovery()
+    }
+
+    pub(super) fn orphaned_drafts(&self) -> &[Draft] {
+        self.lore_database
+            .as_ref()
+            .and_then(|db| self.draft_files.orphaned.get(&db.path_as_string()))
+            .map(Vec::as_slice)
+            .unwrap_or_default()
+    }
+
+    pub(super) fn show_draft_recovery(&mut self) -> Result<(), LoreGuiError> {
+        let mut recovered = vec![];
+        for draft in self.orphaned_drafts() {
+            recovered.push(RecoveredDraft {
+                draft: draft.clone(),
+                stored: self.stored_text(&draft.key)?,
+            });
+        }
+        self.dialog = if recovered.is_empty() {
+            None
+        } else {
+            Some(Box::new(DraftRecoveryDialog::new(recovered)))
+        };
+        Ok(())
+    }
+
+    fn forget_orphaned_draft(&mut self, draft: &Draft) {
+        if let Some(db) = self.lore_database.as_ref() {
+            if let Some(orphaned) = self.draft_files.orphaned.get_mut(&db.path_as_string()) {
+                orphaned.retain(|orphan| orphan != draft);
+            }
+        }
+    }
+
+    /// Selects what the draft belongs to and puts the draft into its editor, unsaved.
+    pub(super) fn restore_draft(&mut self, draft: Draft) -> Result<(), LoreGuiError> {
+        let stored = self.stored_text(&draft.key)?.ok_or_else(|| {
+            LoreGuiError::InputError(format!("{} no longer exists.", draft.title()))
+        })?;
+        self.forget_orphaned_draft(&draft);
+        let editor = EditorState::with_unsaved_changes(&stored, &draft.text);
+        match draft.key {
+            DraftKey::Description { label, descriptor } => {
+                self.selected_view = ViewType::Entity;
+                self.set_selected_label(Some(label.into()));
+                self.set_selected_descriptor(Some(descriptor.into()));
+                self.entity_view_state.set_description_editor(editor);
+            }
+            DraftKey::HistoryContent {
+                year,
+                day,
+                timestamp,
+            } => {
+                self.selected_view = ViewType::History;
+                self.set_selected_year(Year::try_from(year).ok());
+                self.set_selected_day(day.and_then(|day| Day::try_from(day).ok()));
+                self.history_view_state.restore_timestamp(Some(timestamp));
+                self.history_view_state.set_content_editor(editor);
+            }
+        }
+        self.dialog = None;
+        Ok(())
+    }
+
+    pub(super) fn discard_draft(&mut self, draft: Draft) -> Result<(), LoreGuiError> {
+        self.forget_orphaned_draft(&draft);
+        self.show_draft_recovery()
+    }
+
+    /// The text of the active database that the draft would replace, if it still exists.
+    fn stored_text(&self, key: &DraftKey) -> Result<Option<String>, LoreGuiError> {
+        let db = self
+            .lore_database
+            .as_ref()
+            .ok_or(LoreGuiError::NoDatabase)?;
+        match key {
+            DraftKey::Description { label, descriptor } => {
+                let search_params = EntityColumnSearchParams::new(
+                    Some(SqlSearchText::exact(label)),
+                    Some(SqlSearchText::exact(descriptor)),
+                );
+                let columns = db.read_entity_columns(search_params)?;
+                Ok(columns
+                    .first()
+                    .map(|col| col.description.to_str().to_string()))
+            }
+            DraftKey::HistoryContent {
+                year, timestamp, ..
+            } => {
+                let year = match Year::try_from(year.clone()) {
+                    Ok(year) => year,
+                    Err(_) => return Ok(None),
+                };
+                let search_params = HistoryItemSearchParams::new(Some(year), None, None, None);
+                let items = db.read_history_items(search_params)?;
+                Ok(items
+                    .into_iter()
+                    .find(|item| item.timestamp.to_string() == *timestamp)
+                    .map(|item| item.content.to_str().to_string()))
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use iced::widget::text_editor::{Action, Edit};
+    use lorecore::sql::lore_database::LoreDatabase;
+
+    use super::*;
+    use crate::{
+        app::message_handling::GuiMessage,
+        entity_view::EntityViewMessage,
+        tests::{example_database, example_descriptors, example_labels},
+    };
+
+    fn gui_editing_description() -> SqlGui {
+        let mut gui = SqlGui {
+            lore_database: Some(example_database()),
+            ..Default::default()
+        };
+        gui.set_selected_label(Some(example_labels()[0].clone()));
+        gui.set_selected_descriptor(Some(example_descriptors()[0].0.clone()));
+        gui.refresh_views_now().unwrap();
+        gui.entity_view_state
+            .current_description
+            .perform(Action::Edit(Edit::Insert('x')));
+        gui
+    }
+
+    /// The state after LoreGui crashed and the database was opened again.
+    fn relaunched(gui: &SqlGui) -> SqlGui {
+        let path = gui.lore_database.as_ref().unwrap().path_as_string();
+        let mut relaunched = SqlGui {
+            lore_database: Some(LoreDatabase::open(path.into()).unwrap()),
+            ..Default::default()
+        };
+        relaunched.offer_draft_recovery().unwrap();
+        relaunched
+    }
+
+    fn path(gui: &SqlGui) -> String {
+        gui.lore_database.as_ref().unwrap().path_as_string()
+    }
+
+    #[test]
+    fn unsaved_description_is_written_until_it_is_saved() {
+        let mut gui = gui_editing_description();
+        let draft = gui.entity_view_state.draft().unwrap();
+
+        gui.handle_message(GuiMessage::AutosaveDrafts).unwrap();
+        assert_eq!(load_drafts(&path(&gui)).unwrap(), vec![draft]);
+
+        gui.handle_message(GuiMessage::EntityViewUpd(
+            EntityViewMessage::DescriptionSave,
+        ))
+        .unwrap();
+        gui.handle_message(GuiMessage::AutosaveDrafts).unwrap();
+        assert_eq!(load_drafts(&path(&gui)).unwrap(), vec![]);
+    }
+
+    #[test]
+    fn drafts_left_behind_are_offered_for_recovery() {
+        let mut gui = gui_editing_description();
+        gui.autosave_drafts().unwrap();
+        let draft = gui.entity_view_state.draft().unwrap();
+
+        let relaunched = relaunched(&gui);
+
+        assert!(relaunched.dialog.is_some());
+        assert_eq!(relaunched.orphaned_drafts(), &[draft]);
+    }
+
+    #[test]
+    fn drafts_that_were_saved_after_all_are_not_offered() {
+        let mut gui = gui_editing_description();
+        gui.autosave_drafts().unwrap();
+        gui.handle_message(GuiMessage::EntityViewUpd(
+            EntityViewMessage::DescriptionSave,
+        ))
+        .unwrap();
+
+        let relaunched = relaunched(&gui);
+
+        assert!(relaunched.dialog.is_none());
+        assert!(relaunched.orphaned_drafts().is_empty());
+    }
+
+    #[test]
+    fn restoring_puts_draft_into_editor() {
+        let mut gui = gui_editing_description();
+        gui.autosave_drafts().unwrap();
+        let draft = gui.entity_view_state.draft().unwrap();
+
+        let mut relaunched = relaunched(&gui);
+        relaunched
+            .handle_message(GuiMessage::RestoreDraft(draft.clone()))
+            .unwrap();
+        relaunched.refresh_views_now().unwrap();
+
+        assert!(relaunched.dialog.is_none());
+        assert_eq!(
+            relaunched.get_selected_label(),
+            Some(example_labels()[0].clone())
+        );
+        assert_eq!(relaunched.get_description_text(), draft.text);
+        assert!(relaunched
+            .entity_view_state
+            .current_description
+            .is_changed());
+        assert!(relaunched.orphaned_drafts().is_empty());
+
+        relaunched.autosave_drafts().unwrap();
+        assert_eq!(load_drafts(&path(&gui)).unwrap(), vec![draft]);
+    }
+
+    #[test]
+    fn discarding_removes_draft_from_file() {
+        let mut gui = gui_editing_description();
+        gui.autosave_drafts().unwrap();
+        let draft = gui.entity_view_state.draft().unwrap();
+
+        let mut relaunched = relaunched(&gui);
+        relaunched
+            .handle_message(GuiMessage::DiscardDraft(draft))
+            .unwrap();
+        relaunched.autosave_drafts().unwrap();
+
+        assert!(relaunched.dialog.is_none());
+        assert_eq!(load_drafts(&path(&gui)).unwrap(), vec![]);
+    }
+
+    #[test]
+    fn undecided_drafts_are_kept_after_closing_their_database() {
+        let mut gui = gui_editing_description();
+        gui.autosave_drafts().unwrap();
+        let draft = gui.entity_view_state.draft().unwrap();
+
+        let mut relaunched = relaunched(&gui);
+        relaunched.handle_message(GuiMessage::DialogClosed).unwrap();
+        relaunched.handle_message(GuiMessage::TabClosed(0)).unwrap();
+        relaunched.autosave_drafts().unwrap();
+
+        assert_eq!(load_drafts(&path(&gui)).unwrap(), vec![draft]);
+    }
+
+    #[test]
+    fn undecided_drafts_are_kept() {
+        let mut gui = gui_editing_description();
+        gui.autosave_drafts().unwrap();
+        let draft = gui.entity_view_state.draft().unwrap();
+
+        let mut relaunched = relaunched(&gui);
+        relaunched.handle_message(GuiMessage::DialogClosed).unwrap();
+        relaunched.autosave_drafts().unwrap();
+
+        assert_eq!(load_drafts(&path(&gui)).unwrap(), vec![draft]);
+    }
+}
@@ -0,0 +1,286 @@
+use lorecore::types::*;
+
+use super::{message_handling::GuiMessage, SqlGui, ViewType};
+use crate::{
+    commands::Command,
+    dialog::command_palette::CommandPaletteDialog,
+    entity_view,
+    error_log::ErrorLogMessage,
+    errors::LoreGuiError,
+    history_view,
+    localisation::{tr, tr_args},
+    notifications::NotificationMessage,
+    query_cache::CachedReader,
+    relationship_view,
+};
+
+impl SqlGui {
+    pub(super) fn menu_commands(&self) -> Vec<Command> {
+        vec![
+            Command::enabled(&tr("command-new-database"), GuiMessage::NewDatabase),
+            Command::enabled(&tr("command-open-database"), GuiMessage::OpenDatabase),
+            Command::enabled(&tr("command-recent"), GuiMessage::ShowRecentDatabases),
+            Command::new(
+                &tr("command-undo"),
+                self.undo_stack.can_undo().then_some(GuiMessage::Undo),
+            ),
+            Command::new(
+                &tr("command-redo"),
+                self.undo_stack.can_redo().then_some(GuiMessage::Redo),
+            ),
+            Command::enabled(&tr("command-settings"), GuiMessage::ShowSettings),
+            Command::enabled(
+                &tr_args(
+                    "command-error-log",
+                    &[("count", &self.error_log.entries().len())],
+                ),
+                GuiMessage::ErrorLogUpd(ErrorLogMessage::Show),
+            ),
+            Command::enabled(
+                &tr_args(
+                    "command-notifications",
+                    &[("count", &self.notifications.history().len())],
+                ),
+                GuiMessage::NotificationUpd(NotificationMessage::ShowHistory),
+            ),
+        ]
+    }
+
+    /// Everything the command palette offers, disabled where the corresponding button is.
+    pub(super) fn all_commands(&self) -> Vec<Command> {
+        let mut commands = self.menu_commands();
+        commands.push(Command::new(
+            &tr("command-recover-drafts"),
+            (!self.orphaned_drafts().is_empty()).then_some(GuiMessage::ShowDraftRecovery),
+        ));
+
+        let mut view_commands = vec![
+            Command::enabled(
+                &tr("command-show-entities"),
+                GuiMessage::ViewSelected(ViewType::Entity),
+            ),
+            Command::enabled(
+                &tr("command-show-history-items"),
+                GuiMessage::ViewSelected(ViewType::History),
+            ),
+            Command::enabled(
+                &tr("command-show-relationships"),
+                GuiMessage::ViewSelected(ViewType::Relationship),
+            ),
+            Command::enabled(
+                &tr("command-show-dashboard"),
+                GuiMessage::ViewSelected(ViewType::Dashboard),
+            ),
+            Command::enabled(
+                &tr(if self.read_mode.is_active() {
+                    "command-edit-texts"
+                } else {
+                    "command-read-texts"
+                }),
+                GuiMessage::ToggleReadMode,
+            ),
+        ];
+        let in_view = |view: ViewType, commands: Vec<Command>| {
+            commands
+                .into_iter()
+                .map(move |command| command.in_view(view.clone()))
+        };
+        view_commands.extend(in_view(
+            ViewType::Entity,
+            entity_view::widget::label_commands(&self.entity_view_state, self.tabs.len() > 1),
+        ));
+        view_commands.extend(in_view(
+            ViewType::Entity,
+            entity_view::widget::descriptor_commands(&self.entity_view_state),
+        ));
+        view_commands.extend(in_view(
+            ViewType::Entity,
+            entity_view::widget::category_commands(&self.entity_view_state),
+        ));
+        view_commands.extend(in_view(
+            ViewType::History,
+            history_view::widget::commands(&self.history_view_state),
+        ));
+        view_commands.extend(in_view(
+            ViewType::Relationship,
+            relationship_view::widget::commands(&self.relationship_view_state),
+        ));
+        if self.lore_database.is_none() {
+            for command in view_commands.iter_mut() {
+                command.message = None;
+            }
+        }
+        commands.extend(view_commands);
+        commands
+    }
+
+    pub(super) fn show_command_palette(&mut self) -> Result<(), LoreGuiError> {
+        let mut labels: Vec<Label> = vec![];
+        if let Some(db) = self.lore_database.as_ref() {
+            let reader = CachedReader::new(db, &self.query_cache);
+            for col in reader.read_entity_columns(None, None)?.iter() {
+                if !labels.contains(&col.label) {
+                    labels.push(col.label.clone());
+                }
+            }
+        }
+        self.dialog = Some(Box::new(CommandPaletteDialog::new(
+            self.all_commands(),
+            labels,
+        )));
+        Ok(())
+    }
+
+    pub(super) fn run_command(&mut self, command: Command) -> Result<(), LoreGuiError> {
+        self.dialog = None;
+        let message = match command.message {
+            Some(message) => message,
+            None => return Ok(()),
+        };
+        // A message held back by the unsaved changes dialog leaves the current view in place.
+        if self.ask_about_unsaved_changes(&message) {
+            return Ok(());
+        }
+        self.handle_confirmed_message(message)?;
+        if let Some(view) = command.view {
+            self.selected_view = view;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use iced::widget::text_editor::{Action, Edit};
+
+    use super::*;
+    use crate::{
+        dialog::DialogUpdate,
+        tests::{example_database, example_descriptors, example_labels},
+    };
+
+    fn command<'a>(commands: &'a [Command], name: &str) -> &'a Command {
+        commands
+            .iter()
+            .find(|command| command.name == name)
+            .unwrap()
+    }
+
+    #[test]
+    fn commands_are_enabled_like_their_buttons() {
+        let mut gui = SqlGui {
+            lore_database: Some(example_database()),
+            ..Default::default()
+        };
+        let commands = gui.all_commands();
+        assert!(command(&commands, "New Entity").is_enabled());
+        assert!(!command(&commands, "Relabel Entity").is_enabled());
+        assert!(!command(&commands, "Undo").is_enabled());
+
+        gui.set_selected_label(Some(example_labels()[0].clone()));
+        let commands = gui.all_commands();
+        assert!(command(&commands, "Relabel Entity").is_enabled());
+        assert!(!command(&commands, "Rename Descriptor").is_enabled());
+    }
+
+    #[test]
+    fn palette_filters_and_groups_the_labels() {
+        let mut gui = SqlGui {
+            lore_database: Some(example_database()),
+            ..Default::default()
+        };
+        let commands = gui.all_commands();
+        assert!(command(&commands, "Show Category: Without category").is_enabled());
+
+        let group = command(&commands, "Group Entities by Category").clone();
+        gui.handle_message(GuiMessage::RunCommand(Box::new(group)))
+            .unwrap();
+        assert!(gui.entity_view_state.is_grouped_by_category());
+        let commands = gui.all_commands();
+        assert!(command(&commands, "Stop Grouping Entities by Category").is_enabled());
+    }
+
+    #[test]
+    fn palette_switches_to_read_mode() {
+        let mut gui = SqlGui {
+            lore_database: Some(example_database()),
+            ..Default::default()
+        };
+        let read = command(&gui.all_commands(), "Read Descriptions and History Items").clone();
+        gui.handle_message(GuiMessage::RunCommand(Box::new(read)))
+            .unwrap();
+        assert!(gui.read_mode.is_active());
+        assert!(command(&gui.all_commands(), "Edit Descriptions and History Items").is_enabled());
+    }
+
+    #[test]
+    fn without_database_only_database_commands_are_enabled() {
+        let gui = SqlGui::default();
+        let commands = gui.all_commands();
+        assert!(command(&commands, "Open Lore Database").is_enabled());
+        assert!(!command(&commands, "Show History Items").is_enabled());
+        assert!(!command(&commands, "New Entity").is_enabled());
+    }
+
+    #[test]
+    fn running_a_command_shows_its_view() {
+        let mut gui = SqlGui {
+            lore_database: Some(example_database()),
+            selected_view: ViewType::Relationship,
+            ..Default::default()
+        };
+        gui.handle_message(GuiMessage::ShowCommandPalette).unwrap();
+        gui.handle_message(GuiMessage::DialogUpdate(DialogUpdate::Search(
+            "new history".to_string(),
+        )))
+        .unwrap();
+        gui.handle_message(GuiMessage::DialogSubmit).unwrap();
+
+        assert_eq!(gui.selected_view, ViewType::History);
+        assert_eq!(
+            gui.dialog.as_ref().map(|dialog| dialog.header()),
+            Some("Create new history item".to_string())
+        );
+    }
+
+    #[test]
+    fn command_held_back_by_unsaved_changes_keeps_the_view() {
+        let mut gui = SqlGui {
+            lore_database: Some(example_database()),
+            ..Default::default()
+        };
+        gui.set_selected_label(Some(example_labels()[0].clone()));
+        gui.set_selected_descriptor(Some(example_descriptors()[0].0.clone()));
+        gui.refresh_views_now().unwrap();
+        gui.entity_view_state
+            .current_description
+            .perform(Action::Edit(Edit::Insert('x')));
+
+        let command = Command::enabled("Undo", GuiMessage::Undo).in_view(ViewType::History);
+        gui.handle_message(GuiMessage::RunCommand(Box::new(command)))
+            .unwrap();
+
+        assert!(gui.dialog.is_some());
+        assert_eq!(gui.selected_view, ViewType::Entity);
+    }
+
+    #[test]
+    fn palette_jumps_to_entities() {
+        let mut gui = SqlGui {
+            lore_database: Some(example_database()),
+            selected_view: ViewType::History,
+            ..Default::default()
+        };
+        let label = example_labels()[1].clone();
+        gui.handle_message(GuiMessage::ShowCommandPalette).unwrap();
+        gui.handle_message(GuiMessage::DialogUpdate(DialogUpdate::Search(
+            label.to_string(),
+        )))
+        .unwrap();
+        gui.handle_message(GuiMessage::DialogSubmit).unwrap();
+
+        assert!(gui.dialog.is_none());
+        assert_eq!(gui.selected_view, ViewType::Entity);
+        assert_eq!(gui.get_selected_label(), Some(label));
+    }
+}
@@ -0,0 +1,189 @@
+use super::{message_handling::GuiMessage, SqlGui};
+use crate::{
+    dialog::error::ErrorDialog,
+    entity_view::EntityViewMessage,
+    error_log::ErrorLogMessage,
+    errors::{ErrorContext, LoreGuiError},
+    history_view::HistoryViewMessage,
+    localisation::tr,
+    notifications::NotificationLevel,
+};
+
+impl SqlGui {
+    /// Logs the error and shows it in a dialog.
+    pub(super) fn report_error(&mut self, error: LoreGuiError) {
+        self.error_log.push(error.clone());
+        self.dialog = Some(Box::new(ErrorDialog::new(error)));
+    }
+
+    /// Logs the error and mentions it in a toast, for errors that do not need to interrupt the user.
+    pub(super) fn report_warning(&mut self, error: LoreGuiError) {
+        self.notify(NotificationLevel::Warning, error.to_string());
+        self.error_log.push(error);
+    }
+
+    pub(super) fn update_error_log(&mut self, event: ErrorLogMessage) {
+        if let ErrorLogMessage::Show = event {
+            self.notifications.set_history_shown(false);
+        }
+        self.error_log.update(event);
+    }
+
+    /// What handling `message` does and to what, for errors to tell what failed.
+    pub(super) fn error_context(&self, message: &GuiMessage) -> Option<ErrorContext> {
+        let operation = |key: &str| ErrorContext::new(tr(key));
+        let context = match message {
+            GuiMessage::NewEntity(data) => {
+                operation("command-new-entity").with(tr("column-label"), data.label())
+            }
+            GuiMessage::RelabelEntity(data) => operation("command-relabel-entity")
+                .with(tr("column-label"), data.old_label())
+                .with(tr("field-new-label"), data.new_label()),
+            GuiMessage::DeleteEntity(label) => {
+                operation("command-delete-entity").with(tr("column-label"), label)
+            }
+            GuiMessage::CopyEntity(data) => operation("command-copy-entity")
+                .with(tr("column-label"), data.label())
+                .with(tr("error-subject-target-database"), data.target_database()),
+            GuiMessage::DuplicateEntity(data) => operation("command-duplicate-entity")
+                .with(tr("column-label"), data.label())
+                .with(tr("field-new-label"), data.new_label()),
+            GuiMessage::MergeEntities(data) => operation("command-merge-entities")
+                .with(tr("field-merge-source"), data.source())
+                .with(tr("field-merge-target"), data.target()),
+            GuiMessage::ChangeCategory(data) => operation("command-change-category")
+                .with(tr("column-label"), data.label())
+                .with(tr("error-subject-category"), data.category()),
+            GuiMessage::NewDescriptor(data) => operation("command-new-descriptor")
+                .with(tr("column-label"), data.label())
+                .with(tr("column-descriptor"), data.descriptor()),
+            GuiMessage::RenameDescriptor(data) => operation("command-rename-descriptor")
+                .with(tr("column-label"), data.label())
+                .with(tr("column-descriptor"), data.old_descriptor())
+                .with(tr("field-new-descriptor"), data.new_descriptor()),
+            GuiMessage::RenameDescriptorEverywhere(data) => {
+                operation("command-rename-descriptor-everywhere")
+                    .with(tr("column-descriptor"), data.old_descriptor())
+                    .with(tr("field-new-descriptor"), data.new_descriptor())
+            }
+            GuiMessage::DeleteDescriptor(label, descriptor) => {
+                operation("command-delete-descriptor")
+                    .with(tr("column-label"), label)
+                    .with(tr("column-descriptor"), descriptor)
+            }
+            GuiMessage::EntityViewUpd(EntityViewMessage::DescriptionSave) => {
+                let mut context = operation("command-save-description");
+                if let Some(label) = self.get_selected_label() {
+                    context = context.with(tr("column-label"), label);
+                }
+                if let Some(descriptor) = self.get_selected_descriptor() {
+                    context = context.with(tr("column-descriptor"), descriptor);
+                }
+                context
+            }
+            GuiMessage::NewHistoryItem(data) => operation("command-new-history-item")
+                .with(tr("column-year"), data.year())
+                .with(tr("column-timestamp"), data.timestamp()),
+            GuiMessage::RedateHistoryItem(data) => operation("command-redate-history-item")
+                .with(tr("column-timestamp"), data.timestamp()),
+            GuiMessage::DeleteHistoryItem(timestamp) => {
+                operation("command-delete-history-item").with(tr("column-timestamp"), timestamp)
+            }
+            GuiMessage::HistoryViewUpd(HistoryViewMessage::ContentSave) => {
+                let mut context = operation("command-save-history-content");
+                if let Some(timestamp) = self.get_selected_timestamp() {
+                    context = context.with(tr("column-timestamp"), timestamp);
+                }
+                context
+            }
+            GuiMessage::NewRelationship(data) => operation("command-new-relationship")
+                .with(tr("column-parent"), data.parent())
+                .with(tr("column-child"), data.child()),
+            GuiMessage::ChangeRole(data) => operation("command-change-role")
+                .with(tr("column-parent"), data.parent())
+                .with(tr("column-child"), data.child())
+                .with(tr("field-new-role"), data.new_role()),
+            GuiMessage::DeleteRelationship(relationship) => {
+                operation("command-delete-relationship")
+                    .with(tr("column-parent"), &relationship.parent)
+                    .with(tr("column-child"), &relationship.child)
+                    .with(tr("column-role"), &relationship.role)
+            }
+            GuiMessage::Undo => operation("command-undo"),
+            GuiMessage::Redo => operation("command-redo"),
+            _ => return None,
+        };
+        Some(context)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use lorecore::types::*;
+
+    use super::*;
+    use crate::{
+        dialog::new_entity::tests::example_new_entity_data,
+        tests::{example_database, example_labels},
+    };
+
+    #[test]
+    fn failed_operations_name_what_they_did_it_to() {
+        let mut gui = SqlGui {
+            lore_database: Some(example_database()),
+            ..Default::default()
+        };
+        let data = example_new_entity_data();
+        gui.handle_message(GuiMessage::NewEntity(data.clone()))
+            .unwrap();
+        let error = gui
+            .handle_message(GuiMessage::NewEntity(data.clone()))
+            .unwrap_err();
+
+        let contexts = error.contexts();
+        assert_eq!(contexts.len(), 1);
+        assert_eq!(contexts[0].operation(), "New Entity");
+        assert_eq!(
+            contexts[0].subjects(),
+            &[("Label".to_string(), data.label().to_string())]
+        );
+        assert_eq!(error.causes().len(), 1);
+    }
+
+    #[test]
+    fn operations_without_database_fail_in_context() {
+        let mut gui = SqlGui {
+            lore_database: None,
+            ..Default::default()
+        };
+        let label: Label = example_labels()[0].clone();
+        let error = gui
+            .handle_message(GuiMessage::DeleteEntity(label.clone()))
+            .unwrap_err();
+        assert_eq!(
+            error.details(),
+            format!(
+                "Delete Entity failed.\nLabel: {}\nCaused by:\n  1. No database",
+                label
+            )
+        );
+    }
+
+    #[test]
+    fn reported_errors_are_logged_and_shown() {
+        let mut gui = SqlGui {
+            lore_database: None,
+            ..Default::default()
+        };
+        let _ = gui.update(GuiMessage::DeleteEntity(example_labels()[0].clone()));
+
+        assert_eq!(gui.error_log.entries().len(), 1);
+        assert!(gui.dialog.is_some());
+
+        gui.handle_message(GuiMessage::DialogClosed).unwrap();
+        gui.handle_message(GuiMessage::ErrorLogUpd(ErrorLogMessage::Show))
+            .unwrap();
+        assert!(gui.error_log.is_shown());
+        assert_eq!(gui.error_log.entries().len(), 1);
+    }
+}
@@ -0,0 +1,257 @@
+use std::path::Path;
+
+use lorecore::sql::search_params::{
+    EntityColumnSearchParams, HistoryItemSearchParams, SqlSearchText,
+};
+
+use super::SqlGui;
+use crate::{
+    dialog::conflict::{ConflictDialog, ConflictingEditor, EditConflict},
+    errors::LoreGuiError,
+    file_watcher::FileStamp,
+    localisation::tr,
+    notifications::NotificationLevel,
+    query_cache::Table,
+};
+
+impl SqlGui {
+    /// Called after LoreGui wrote to the active database, so that the file watcher does not mistake this for an external change.
+    pub(super) fn remember_database_stamp(&mut self) {
+        self.database_stamp = self
+            .lore_database
+            .as_ref()
+            .map(|db| FileStamp::read(Path::new(&db.path_as_string())));
+    }
+
+    pub(super) fn database_file_changed(
+        &mut self,
+        path: &str,
+        stamp: FileStamp,
+    ) -> Result<(), LoreGuiError> {
+        let index = match self.find_tab(path) {
+            Some(index) => index,
+            None => return Ok(()),
+        };
+        if index != self.active_tab {
+            if !self.writes.is_writing_to(path) {
+                self.tabs[index].database_file_changed(stamp);
+            }
+            return Ok(());
+        }
+        // The stamp is remembered once LoreGui's own writes have finished.
+        if self.database_stamp == Some(stamp) || self.writes.is_writing_to(path) {
+            return Ok(());
+        }
+        self.database_stamp = Some(stamp);
+        self.record_changes(&Table::ALL);
+        self.search_index.record_everything();
+        self.notify(NotificationLevel::Warning, tr("notify-external-change"));
+        if let Some(conflict) = self.find_edit_conflict()? {
+            self.dialog = Some(Box::new(ConflictDialog::new(conflict)));
+        }
+        Ok(())
+    }
+
+    fn find_edit_conflict(&self) -> Result<Option<EditConflict>, LoreGuiError> {
+        if let Some(stored) = self.stored_description()? {
+            let editor = &self.entity_view_state.current_description;
+            if editor.conflicts_with(&stored) {
+                return Ok(Some(EditConflict {
+                    editor: ConflictingEditor::Description,
+                    mine: editor.get_text(),
+                    theirs: stored,
+                }));
+            }
+        }
+        if let Some(stored) = self.stored_history_content()? {
+            let editor = &self.history_view_state.current_content;
+            if editor.conflicts_with(&stored) {
+                return Ok(Some(EditConflict {
+                    editor: ConflictingEditor::HistoryContent,
+                    mine: editor.get_text(),
+                    theirs: stored,
+                }));
+            }
+        }
+        Ok(None)
+    }
+
+    fn stored_description(&self) -> Result<Option<String>, LoreGuiError> {
+        let (db, (label, descriptor)) = match (
+            self.lore_database.as_ref(),
+            self.entity_view_state.description_source(),
+        ) {
+            (Some(db), Some(source)) => (db, source),
+            _ => return Ok(None),
+        };
+        let search_params = EntityColumnSearchParams::new(
+            Some(SqlSearchText::exact(label.to_str())),
+            Some(SqlSearchText::exact(descriptor.to_str())),
+        );
+        let columns = db.read_entity_columns(search_params)?;
+        Ok(Some(
+            columns
+                .first()
+                .map(|col| col.description.to_str().to_string())
+                .unwrap_or_default(),
+        ))
+    }
+
+    fn stored_history_content(&self) -> Result<Option<String>, LoreGuiError> {
+        let (db, timestamp) = match (
+            self.lore_database.as_ref(),
+            self.history_view_state.content_timestamp(),
+        ) {
+            (Some(db), Some(timestamp)) => (db, timestamp),
+            _ => return Ok(None),
+        };
+        let search_params = HistoryItemSearchParams::new(None, None, Some(timestamp), None);
+        let items = db.read_history_items(search_params)?;
+        Ok(Some(
+            items
+                .first()
+                .map(|item| item.content.to_str().to_string())
+                .unwrap_or_default(),
+        ))
+    }
+
+    pub(super) fn keep_mine(&mut self, conflict: EditConflict) {
+        match conflict.editor {
+            ConflictingEditor::Description => self
+                .entity_view_state
+                .current_description
+                .rebase_onto(&conflict.theirs),
+            ConflictingEditor::HistoryContent => self
+                .history_view_state
+                .current_content
+                .rebase_onto(&conflict.theirs),
+        }
+        self.dialog = None;
+    }
+
+    pub(super) fn take_theirs(&mut self, conflict: EditConflict) {
+        match conflict.editor {
+            ConflictingEditor::Description => self
+                .entity_view_state
+                .set_description_text(&conflict.theirs),
+            ConflictingEditor::HistoryContent => {
+                self.history_view_state.set_content_text(&conflict.theirs)
+            }
+        }
+        self.dialog = None;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use iced::widget::text_editor::{Action, Edit};
+    use lorecore::sql::lore_database::LoreDatabase;
+
+    use super::*;
+    use crate::{
+        app::message_handling::GuiMessage,
+        tests::{example_database, example_descriptors, example_labels},
+    };
+
+    fn gui_editing_description() -> SqlGui {
+        let mut gui = SqlGui {
+            lore_database: Some(example_database()),
+            ..Default::default()
+        };
+        gui.set_selected_label(Some(example_labels()[0].clone()));
+        gui.set_selected_descriptor(Some(example_descriptors()[0].0.clone()));
+        gui.refresh_views_now().unwrap();
+        gui.entity_view_state
+            .current_description
+            .perform(Action::Edit(Edit::Insert('x')));
+        gui
+    }
+
+    fn change_description_externally(gui: &SqlGui, description: &str) -> GuiMessage {
+        let path = gui.lore_database.as_ref().unwrap().path_as_string();
+        let other_connection = LoreDatabase::open(path.clone().into()).unwrap();
+        other_connection
+            .change_entity_description(
+                (&example_labels()[0], &example_descriptors()[0].0),
+                &description.into(),
+            )
+            .unwrap();
+        let stamp = FileStamp::read(Path::new(&path));
+        GuiMessage::DatabaseFileChanged(path, stamp)
+    }
+
+    #[test]
+    fn external_change_refreshes_views() {
+        let mut gui = SqlGui {
+            lore_database: Some(example_database()),
+            ..Default::default()
+        };
+        gui.set_selected_label(Some(example_labels()[0].clone()));
+        gui.set_selected_descriptor(Some(example_descriptors()[0].0.clone()));
+        gui.refresh_views_now().unwrap();
+
+        let message = change_description_externally(&gui, "Changed elsewhere\n");
+        gui.handle_message(message).unwrap();
+        gui.refresh_views_now().unwrap();
+
+        assert!(gui.dialog.is_none());
+        assert_eq!(gui.get_description_text(), "Changed elsewhere\n");
+    }
+
+    #[test]
+    fn own_writes_are_not_external_changes() {
+        let mut gui = SqlGui {
+            lore_database: Some(example_database()),
+            ..Default::default()
+        };
+        gui.remember_database_stamp();
+        let path = gui.lore_database.as_ref().unwrap().path_as_string();
+        let stamp = FileStamp::read(Path::new(&path));
+
+        gui.handle_message(GuiMessage::DatabaseFileChanged(path, stamp))
+            .unwrap();
+
+        assert!(gui.changed_tables.is_empty());
+    }
+
+    #[test]
+    fn external_change_to_edited_description_asks_for_resolution() {
+        let mut gui = gui_editing_description();
+        let message = change_description_externally(&gui, "Changed elsewhere\n");
+        gui.handle_message(message).unwrap();
+        assert!(gui.dialog.is_some());
+    }
+
+    #[test]
+    fn taking_theirs_replaces_unsaved_changes() {
+        let mut gui = gui_editing_description();
+        let message = change_description_externally(&gui, "Changed elsewhere\n");
+        gui.handle_message(message).unwrap();
+
+        let submitted = gui.dialog.as_ref().unwrap().submit();
+        let conflict = match submitted {
+            GuiMessage::KeepMine(conflict) => conflict,
+            _ => panic!("Conflict dialog should keep my version on submit."),
+        };
+        gui.handle_message(GuiMessage::TakeTheirs(conflict))
+            .unwrap();
+        gui.refresh_views_now().unwrap();
+
+        assert!(gui.dialog.is_none());
+        assert_eq!(gui.get_description_text(), "Changed elsewhere\n");
+    }
+
+    #[test]
+    fn keeping_mine_makes_saving_overwrite_theirs() {
+        let mut gui = gui_editing_description();
+        let mine = gui.get_description_text();
+        let message = change_description_externally(&gui, "Changed elsewhere\n");
+        gui.handle_message(message).unwrap();
+        gui.handle_message(GuiMessage::DialogSubmit).unwrap();
+        gui.refresh_views_now().unwrap();
+
+        assert!(gui.dialog.is_none());
+        assert_eq!(gui.get_description_text(), mine);
+        assert!(gui.entity_view_state.current_description.is_changed());
+    }
+}
@@ -1,25 +1,47 @@
+use std::path::PathBuf;
+
+use iced::keyboard::{Key, Modifiers};
 use lorecore::types::*;
 
 use crate::{
+    appearance::Appearance,
+    commands::Command,
+    dashboard::{DashboardData, DashboardMessage},
     dialog::{
-        change_role::ChangeRoleData, new_descriptor::NewDescriptorData, new_entity::NewEntityData,
-        new_history_item::NewHistoryData, new_relationship::NewRelationshipData,
-        redate_history::RedateHistoryData, relabel_entity::RelabelEntityData,
-        rename_descriptor::RenameDescriptorData, DialogUpdate,
+        change_category::ChangeCategoryData, change_role::ChangeRoleData, conflict::EditConflict,
+        copy_entity::CopyEntityData, duplicate_entity::DuplicateEntityData,
+        merge_entities::MergeEntitiesData, new_descriptor::NewDescriptorData,
+        new_entity::NewEntityData, new_history_item::NewHistoryData,
+        new_relationship::NewRelationshipData, redate_history::RedateHistoryData,
+        relabel_entity::RelabelEntityData, rename_descriptor::RenameDescriptorData,
+        rename_descriptor_everywhere::RenameDescriptorEverywhereData, DialogUpdate,
     },
-    entity_view::EntityViewMessage,
+    drafts::Draft,
+    entity_view::{EntityViewData, EntityViewMessage},
+    error_log::ErrorLogMessage,
     errors::LoreGuiError,
-    history_view::HistoryViewMessage,
-    relationship_view::RelationshipViewMessage,
+    file_watcher::FileStamp,
+    global_search::{GlobalSearchMessage, SearchResults, SearchTarget},
+    history_view::{HistoryViewData, HistoryViewMessage},
+    notifications::NotificationMessage,
+    query_cache::Table,
+    relationship_view::{RelationshipViewData, RelationshipViewMessage},
+    shortcuts::KeyBindings,
 };
 
-use super::{SqlGui, ViewType};
+use super::{refreshing::Loaded, writing::Written, SqlGui, ViewType};
 
 #[derive(Debug, Clone)]
 pub(crate) enum GuiMessage {
     ViewSelected(ViewType),
     NewDatabase,
     OpenDatabase,
+    ShowRecentDatabases,
+    OpenDatabaseAt(PathBuf),
+    PinRecentDatabase(PathBuf, bool),
+    ForgetRecentDatabase(PathBuf),
+    TabSelected(usize),
+    TabClosed(usize),
     EntityViewUpd(EntityViewMessage),
     HistoryViewUpd(HistoryViewMessage),
     RelationshipViewUpd(RelationshipViewMessage),
@@ -29,8 +51,13 @@ pub(crate) enum GuiMessage {
     NewEntity(NewEntityData),
     RelabelEntity(RelabelEntityData),
     DeleteEntity(Label),
+    CopyEntity(CopyEntityData),
+    DuplicateEntity(DuplicateEntityData),
+    MergeEntities(MergeEntitiesData),
+    ChangeCategory(ChangeCategoryData),
     NewDescriptor(NewDescriptorData),
     RenameDescriptor(RenameDescriptorData),
+    RenameDescriptorEverywhere(RenameDescriptorEverywhereData),
     DeleteDescriptor(Label, Descriptor),
     NewHistoryItem(NewHistoryData),
     RedateHistoryItem(RedateHistoryData),
@@ -38,40 +65,165 @@ pub(crate) enum GuiMessage {
     NewRelationship(NewRelationshipData),
     ChangeRole(ChangeRoleData),
     DeleteRelationship(EntityRelationship),
+    Undo,
+    Redo,
+    KeyPressed(Key, Modifiers),
+    ShowSettings,
+    SettingsChanged(KeyBindings, Appearance),
+    EntityViewLoaded(Loaded<EntityViewData>),
+    HistoryViewLoaded(Loaded<HistoryViewData>),
+    RelationshipViewLoaded(Loaded<RelationshipViewData>),
+    DashboardLoaded(Loaded<DashboardData>),
+    WriteFinished(Written),
+    DatabaseFileChanged(String, FileStamp),
+    KeepMine(EditConflict),
+    TakeTheirs(EditConflict),
+    SaveAndContinue(Box<GuiMessage>),
+    DiscardAndContinue(Box<GuiMessage>),
+    WindowCloseRequested,
+    AutosaveDrafts,
+    ShowDraftRecovery,
+    RestoreDraft(Draft),
+    DiscardDraft(Draft),
+    GlobalSearchUpd(GlobalSearchMessage),
+    GlobalSearchLoaded(Loaded<SearchResults>),
+    SearchIndexUpdated(String, Result<(), LoreGuiError>),
+    JumpTo(SearchTarget),
+    ShowCommandPalette,
+    ToggleReadMode,
+    RunCommand(Box<Command>),
+    ErrorLogUpd(ErrorLogMessage),
+    CopyToClipboard(String),
+    NotificationUpd(NotificationMessage),
+    DashboardUpd(DashboardMessage),
+}
+
+impl GuiMessage {
+    /// The tables of the active database that handling this message may change, other than by a database write.
+    fn changed_tables(&self) -> &'static [Table] {
+        match self {
+            GuiMessage::NewDatabase | GuiMessage::OpenDatabase | GuiMessage::OpenDatabaseAt(_) => {
+                &Table::ALL
+            }
+            _ => &[],
+        }
+    }
 }
 
 impl SqlGui {
     pub(super) fn handle_message(&mut self, message: GuiMessage) -> Result<(), LoreGuiError> {
+        if self.ask_about_unsaved_changes(&message) {
+            return Ok(());
+        }
+        self.handle_confirmed_message(message)
+    }
+
+    /// Handles a message without asking about unsaved changes it may throw away.
+    pub(super) fn handle_confirmed_message(
+        &mut self,
+        message: GuiMessage,
+    ) -> Result<(), LoreGuiError> {
+        let context = self.error_context(&message);
+        let write = self
+            .database_write(&message)
+            .map_err(|error| match context.clone() {
+                Some(context) => error.in_context(context),
+                None => error,
+            })?;
+        if let Some(write) = write {
+            let notification = self.success_notification(&message);
+            return self.start_write(write, context, notification);
+        }
+        self.perform_message(message)
+            .map_err(|error| match context {
+                Some(context) => error.in_context(context),
+                None => error,
+            })
+    }
+
+    fn perform_message(&mut self, message: GuiMessage) -> Result<(), LoreGuiError> {
+        if !message.changed_tables().is_empty() {
+            self.record_changes(message.changed_tables());
+            self.search_index.record_everything();
+        }
         match message {
             GuiMessage::ViewSelected(view) => self.selected_view = view,
             GuiMessage::NewDatabase => self.new_database_from_dialog()?,
             GuiMessage::OpenDatabase => self.open_database_from_dialog()?,
+            GuiMessage::ShowRecentDatabases => self.show_recent_databases(),
+            GuiMessage::OpenDatabaseAt(path) => self.open_recent_database(path)?,
+            GuiMessage::PinRecentDatabase(path, pinned) => {
+                self.pin_recent_database(&path, pinned)?
+            }
+            GuiMessage::ForgetRecentDatabase(path) => self.forget_recent_database(&path)?,
+            GuiMessage::TabSelected(index) => self.select_tab(index),
+            GuiMessage::TabClosed(index) => self.close_tab(index),
             GuiMessage::EntityViewUpd(event) => self.update_entity_view(event)?,
             GuiMessage::HistoryViewUpd(event) => self.update_history_view(event)?,
             GuiMessage::RelationshipViewUpd(event) => self.update_relationship_view(event)?,
             GuiMessage::DialogUpdate(update) => self.update_dialog(update),
             GuiMessage::DialogSubmit => self.dialog_submit()?,
             GuiMessage::DialogClosed => self.dialog = None,
-            GuiMessage::NewEntity(data) => self.write_new_entity(data)?,
-            GuiMessage::RelabelEntity(data) => self.relabel_entity(data)?,
-            GuiMessage::DeleteEntity(label) => self.delete_entity(label)?,
-            GuiMessage::NewDescriptor(data) => self.write_new_descriptor(data)?,
-            GuiMessage::RenameDescriptor(data) => self.change_descriptor(data)?,
-            GuiMessage::DeleteDescriptor(label, descriptor) => {
-                self.delete_descriptor(label, descriptor)?
+            // Database writes are started in handle_confirmed_message().
+            GuiMessage::NewEntity(_)
+            | GuiMessage::RelabelEntity(_)
+            | GuiMessage::DeleteEntity(_)
+            | GuiMessage::CopyEntity(_)
+            | GuiMessage::DuplicateEntity(_)
+            | GuiMessage::MergeEntities(_)
+            | GuiMessage::ChangeCategory(_)
+            | GuiMessage::NewDescriptor(_)
+            | GuiMessage::RenameDescriptor(_)
+            | GuiMessage::RenameDescriptorEverywhere(_)
+            | GuiMessage::DeleteDescriptor(_, _)
+            | GuiMessage::NewHistoryItem(_)
+            | GuiMessage::RedateHistoryItem(_)
+            | GuiMessage::DeleteHistoryItem(_)
+            | GuiMessage::NewRelationship(_)
+            | GuiMessage::ChangeRole(_)
+            | GuiMessage::DeleteRelationship(_)
+            | GuiMessage::Undo
+            | GuiMessage::Redo => {}
+            GuiMessage::KeyPressed(key, modifiers) => self.handle_key_press(key, modifiers)?,
+            GuiMessage::ShowSettings => self.show_settings(),
+            GuiMessage::SettingsChanged(key_bindings, appearance) => {
+                self.change_settings(key_bindings, appearance)?
+            }
+            GuiMessage::EntityViewLoaded(loaded) => self.entity_view_loaded(loaded)?,
+            GuiMessage::HistoryViewLoaded(loaded) => self.history_view_loaded(loaded)?,
+            GuiMessage::RelationshipViewLoaded(loaded) => self.relationship_view_loaded(loaded)?,
+            GuiMessage::DashboardLoaded(loaded) => self.dashboard_loaded(loaded)?,
+            GuiMessage::WriteFinished(written) => self.write_finished(written)?,
+            GuiMessage::DatabaseFileChanged(path, stamp) => {
+                self.database_file_changed(&path, stamp)?
+            }
+            GuiMessage::KeepMine(conflict) => self.keep_mine(conflict),
+            GuiMessage::TakeTheirs(conflict) => self.take_theirs(conflict),
+            GuiMessage::SaveAndContinue(message) => self.save_and_continue(*message)?,
+            GuiMessage::DiscardAndContinue(message) => self.discard_and_continue(*message)?,
+            GuiMessage::WindowCloseRequested => self.exit_requested = true,
+            GuiMessage::AutosaveDrafts => {
+                self.autosave_drafts()?;
+                self.store_sessions()?
             }
-            GuiMessage::NewHistoryItem(data) => self.write_new_history(data)?,
-            GuiMessage::RedateHistoryItem(data) => self.redate_history_item(data)?,
-            GuiMessage::DeleteHistoryItem(timestamp) => self.delete_history_item(timestamp)?,
-            GuiMessage::NewRelationship(data) => self.write_new_relationship(data)?,
-            GuiMessage::ChangeRole(data) => self.change_relationship_role(data)?,
-            GuiMessage::DeleteRelationship(relationship) => {
-                self.delete_relationship(relationship)?
+            GuiMessage::ShowDraftRecovery => self.show_draft_recovery()?,
+            GuiMessage::RestoreDraft(draft) => self.restore_draft(draft)?,
+            GuiMessage::DiscardDraft(draft) => self.discard_draft(draft)?,
+            GuiMessage::GlobalSearchUpd(event) => self.update_global_search(event),
+            GuiMessage::GlobalSearchLoaded(loaded) => self.global_search_loaded(loaded)?,
+            GuiMessage::SearchIndexUpdated(database, result) => {
+                self.search_index_updated(database, result)?
             }
+            GuiMessage::JumpTo(target) => self.jump_to(target),
+            GuiMessage::ShowCommandPalette => self.show_command_palette()?,
+            GuiMessage::ToggleReadMode => self.toggle_read_mode()?,
+            GuiMessage::RunCommand(command) => self.run_command(*command)?,
+            GuiMessage::ErrorLogUpd(event) => self.update_error_log(event),
+            // Writing to the clipboard needs a task, so it is done in update().
+            GuiMessage::CopyToClipboard(_) => {}
+            GuiMessage::NotificationUpd(event) => self.update_notifications(event),
+            GuiMessage::DashboardUpd(event) => self.update_dashboard(event),
         }
-        self.entity_view_state.update(&self.lore_database)?;
-        self.history_view_state.update(&self.lore_database)?;
-        self.relationship_view_state.update(&self.lore_database)?;
         Ok(())
     }
 
@@ -130,7 +282,9 @@ mod tests {
     #[test]
     fn dialog_closed_closes_dialog() {
         let mut gui = SqlGui {
-            dialog: Some(Box::new(crate::dialog::new_entity::NewEntityDialog::new())),
+            dialog: Some(Box::new(crate::dialog::new_entity::NewEntityDialog::new(
+                vec![],
+            ))),
             ..Default::default()
         };
         let message = GuiMessage::DialogClosed;
@@ -209,6 +363,7 @@ mod tests {
         let description = data.description().to_string();
         let message = GuiMessage::NewDescriptor(data);
         gui.handle_message(message).unwrap();
+        gui.refresh_views_now().unwrap();
 
         assert_eq!(gui.get_selected_label(), Some(label));
         assert_eq!(gui.get_selected_descriptor(), Some(descriptor));
@@ -248,6 +403,7 @@ mod tests {
 
         let rename_message = GuiMessage::RenameDescriptor(rename_data);
         gui.handle_message(rename_message).unwrap();
+        gui.refresh_views_now().unwrap();
 
         assert_eq!(gui.get_selected_label(), Some(label));
         assert_eq!(gui.get_selected_descriptor(), Some(new_descriptor));
@@ -285,6 +441,7 @@ mod tests {
         let data = example_new_history_data();
         let message = GuiMessage::NewHistoryItem(data.clone());
         gui.handle_message(message).unwrap();
+        gui.refresh_views_now().unwrap();
 
         assert_eq!(gui.get_selected_year(), Some(data.year().to_owned()));
         assert_eq!(gui.get_selected_day(), Some(data.day().to_owned()));
@@ -313,6 +470,7 @@ mod tests {
         redate_data.set_new_day(new_day);
         let redate_message = GuiMessage::RedateHistoryItem(redate_data.clone());
         gui.handle_message(redate_message).unwrap();
+        gui.refresh_views_now().unwrap();
 
         assert_eq!(gui.get_selected_year(), Some(new_year));
         assert_eq!(gui.get_selected_day(), Some(new_day));
@@ -1,32 +1,95 @@
 use lorecore::{sql::lore_database::LoreDatabase, types::*};
+use serde::{Deserialize, Serialize};
 
 use super::{
-    dialog::Dialog, entity_view::EntityViewState, history_view::HistoryViewState,
+    appearance::Appearance,
+    dashboard::DashboardState,
+    dialog::Dialog,
+    entity_view::EntityViewState,
+    error_log::ErrorLog,
+    file_watcher::FileStamp,
+    global_search::GlobalSearchState,
+    history_view::HistoryViewState,
+    notifications::Notifications,
+    query_cache::{SharedQueryCache, Table},
+    references::ReadMode,
     relationship_view::RelationshipViewState,
+    search_index::SearchIndexState,
+    shortcuts::KeyBindings,
+    status_bar::StatusBar,
+    user_preferences::{DatabaseSessions, RecentDatabases},
 };
-
+use autosave::DraftFiles;
+use refreshing::RefreshGenerations;
+use tabs::DatabaseTab;
+use undo_redo::UndoStack;
+use writing::WriteQueue;
+
+mod autosave;
+mod commands;
+mod error_reporting;
+mod external_changes;
 pub(super) mod message_handling;
+mod notifying;
+mod reading;
+mod refreshing;
+mod session;
+mod shortcut_handling;
+mod status;
+mod tabs;
+mod undo_redo;
+mod unsaved_changes;
+mod updating_dashboard;
 mod updating_database;
 mod updating_entity_view;
+mod updating_global_search;
 mod updating_history_view;
 mod updating_relationship_view;
 mod widget;
+mod writing;
 
 pub(crate) struct SqlGui {
     selected_view: ViewType,
     entity_view_state: EntityViewState,
     history_view_state: HistoryViewState,
     relationship_view_state: RelationshipViewState,
+    dashboard_state: DashboardState,
+    global_search: GlobalSearchState,
+    error_log: ErrorLog,
+    notifications: Notifications,
+    status_bar: StatusBar,
+    read_mode: ReadMode,
     lore_database: Option<LoreDatabase>,
+    undo_stack: UndoStack,
+    query_cache: SharedQueryCache,
+    search_index: SearchIndexState,
+    changed_tables: Vec<Table>,
+    writes: WriteQueue,
+    database_stamp: Option<FileStamp>,
+    draft_files: DraftFiles,
+    refresh_generations: RefreshGenerations,
+    tabs: Vec<DatabaseTab>,
+    active_tab: usize,
+    recent_databases: RecentDatabases,
+    database_sessions: DatabaseSessions,
+    /// The sessions are stored together with the drafts, instead of after every message.
+    sessions_changed: bool,
+    key_bindings: KeyBindings,
+    appearance: Appearance,
+    /// Preferences are only written back once they have been read, so that defaults never overwrite them.
+    preferences_loaded: bool,
+    focused_column: usize,
+    exit_requested: bool,
     pub(crate) dialog: Option<Box<dyn Dialog>>,
 }
 
-#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
 pub(crate) enum ViewType {
     #[default]
     Entity,
     History,
     Relationship,
+    Dashboard,
 }
 
 impl SqlGui {
@@ -55,7 +118,6 @@ impl SqlGui {
         self.entity_view_state.set_description_text(text);
     }
 
-    #[cfg(test)]
     pub(crate) fn get_selected_year(&self) -> Option<Year> {
         self.history_view_state.get_selected_year()
     }
@@ -64,7 +126,6 @@ impl SqlGui {
         self.history_view_state.set_selected_year(year);
     }
 
-    #[cfg(test)]
     pub(crate) fn get_selected_day(&self) -> Option<Day> {
         self.history_view_state.get_selected_day()
     }
@@ -89,7 +150,6 @@ impl SqlGui {
         self.history_view_state.set_content_text(text);
     }
 
-    #[cfg(test)]
     pub(crate) fn get_selected_parent(&self) -> Option<Parent> {
         self.relationship_view_state.get_selected_parent()
     }
@@ -98,7 +158,6 @@ impl SqlGui {
         self.relationship_view_state.set_selected_parent(parent);
     }
 
-    #[cfg(test)]
     pub(crate) fn get_selected_child(&self) -> Option<Child> {
         self.relationship_view_state.get_selected_child()
     }
@@ -107,7 +166,6 @@ impl SqlGui {
         self.relationship_view_state.set_selected_child(child);
     }
 
-    #[cfg(test)]
     pub(crate) fn get_selected_role(&self) -> Option<Role> {
         self.relationship_view_state.get_selected_role()
     }
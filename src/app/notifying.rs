@@ -0,0 +1,248 @@
+use lorecore::sql::search_params::{RelationshipSearchParams, SqlSearchText};
+use lorecore::types::*;
+
+use super::{message_handling::GuiMessage, SqlGui};
+use crate::{
+    entity_view::EntityViewMessage,
+    error_log::ErrorLogMessage,
+    history_view::HistoryViewMessage,
+    localisation::{tr, tr_args},
+    notifications::{NotificationLevel, NotificationMessage},
+    user_preferences::now_in_seconds,
+};
+
+impl SqlGui {
+    pub(super) fn notify(&mut self, level: NotificationLevel, text: String) {
+        self.notifications.push(level, text, now_in_seconds());
+    }
+
+    pub(super) fn update_notifications(&mut self, event: NotificationMessage) {
+        match event {
+            NotificationMessage::Tick => self.notifications.expire(
+                now_in_seconds(),
+                self.appearance.notification_seconds.into(),
+            ),
+            NotificationMessage::Dismiss(id) => self.notifications.dismiss(id),
+            NotificationMessage::ShowHistory => {
+                self.error_log.update(ErrorLogMessage::Hide);
+                self.notifications.set_history_shown(true)
+            }
+            NotificationMessage::HideHistory => self.notifications.set_history_shown(false),
+            NotificationMessage::ClearHistory => self.notifications.clear_history(),
+        }
+    }
+
+    /// What to tell the user once the database write of `message` succeeded, worded before it is started.
+    pub(super) fn success_notification(&self, message: &GuiMessage) -> Option<String> {
+        let text = match message {
+            GuiMessage::NewEntity(data) => {
+                tr_args("notify-entity-created", &[("label", data.label())])
+            }
+            GuiMessage::RelabelEntity(data) => tr_args(
+                "notify-entity-relabelled",
+                &[("old", data.old_label()), ("new", data.new_label())],
+            ),
+            GuiMessage::DeleteEntity(label) => tr_args(
+                "notify-entity-deleted",
+                &[
+                    ("label", label),
+                    ("relationships", &self.count_relationships_of(label)),
+                ],
+            ),
+            GuiMessage::CopyEntity(data) => tr_args(
+                "notify-entity-copied",
+                &[
+                    ("label", data.label()),
+                    ("database", &data.target_database()),
+                ],
+            ),
+            GuiMessage::DuplicateEntity(data) => tr_args(
+                "notify-entity-duplicated",
+                &[
+                    ("label", data.label()),
+                    ("new", data.new_label()),
+                    ("relationships", &data.with_relationships().to_string()),
+                ],
+            ),
+            GuiMessage::MergeEntities(data) => tr_args(
+                "notify-entities-merged",
+                &[("source", data.source()), ("target", data.target())],
+            ),
+            GuiMessage::ChangeCategory(data) => tr_args(
+                "notify-category-changed",
+                &[
+                    ("label", data.label()),
+                    ("category", &data.category().trim()),
+                ],
+            ),
+            GuiMessage::NewDescriptor(data) => tr_args(
+                "notify-descriptor-created",
+                &[("label", data.label()), ("descriptor", data.descriptor())],
+            ),
+            GuiMessage::RenameDescriptor(data) => tr_args(
+                "notify-descriptor-renamed",
+                &[
+                    ("label", data.label()),
+                    ("old", data.old_descriptor()),
+                    ("new", data.new_descriptor()),
+                ],
+            ),
+            GuiMessage::RenameDescriptorEverywhere(data) => {
+                let summary = tr_args(
+                    "notify-descriptor-renamed-everywhere",
+                    &[
+                        ("old", data.old_descriptor()),
+                        ("new", data.new_descriptor()),
+                        ("count", &data.labels().len()),
+                    ],
+                );
+                if data.collisions().is_empty() {
+                    summary
+                } else {
+                    tr_args(
+                        "notify-collisions-skipped",
+                        &[("summary", &summary), ("skipped", &data.collisions().len())],
+                    )
+                }
+            }
+            GuiMessage::DeleteDescriptor(label, descriptor) => tr_args(
+                "notify-descriptor-deleted",
+                &[("label", label), ("descriptor", descriptor)],
+            ),
+            GuiMessage::EntityViewUpd(EntityViewMessage::DescriptionSave) => {
+                tr("notify-description-saved")
+            }
+            GuiMessage::NewHistoryItem(data) => tr_args(
+                "notify-history-item-created",
+                &[("year", data.year()), ("day", data.day())],
+            ),
+            GuiMessage::RedateHistoryItem(data) => tr_args(
+                "notify-history-item-redated",
+                &[
+                    ("timestamp", &data.timestamp()),
+                    ("year", &data.new_year()),
+                    ("day", &data.new_day()),
+                ],
+            ),
+            GuiMessage::DeleteHistoryItem(timestamp) => {
+                tr_args("notify-history-item-deleted", &[("timestamp", timestamp)])
+            }
+            GuiMessage::HistoryViewUpd(HistoryViewMessage::ContentSave) => {
+                tr("notify-history-content-saved")
+            }
+            GuiMessage::NewRelationship(data) => tr_args(
+                "notify-relationship-created",
+                &[("parent", data.parent()), ("child", data.child())],
+            ),
+            GuiMessage::ChangeRole(data) => tr_args(
+                "notify-role-changed",
+                &[
+                    ("parent", data.parent()),
+                    ("child", data.child()),
+                    ("role", data.new_role()),
+                ],
+            ),
+            GuiMessage::DeleteRelationship(relationship) => tr_args(
+                "notify-relationship-deleted",
+                &[
+                    ("parent", &relationship.parent),
+                    ("child", &relationship.child),
+                ],
+            ),
+            GuiMessage::Undo => tr("notify-undone"),
+            GuiMessage::Redo => tr("notify-redone"),
+            _ => return None,
+        };
+        Some(text)
+    }
+
+    /// The relationships that are deleted together with the entity.
+    fn count_relationships_of(&self, label: &Label) -> usize {
+        let db = match self.lore_database.as_ref() {
+            Some(db) => db,
+            None => return 0,
+        };
+        let as_parent =
+            RelationshipSearchParams::new(Some(SqlSearchText::exact(label.to_str())), None);
+        let as_child =
+            RelationshipSearchParams::new(None, Some(SqlSearchText::exact(label.to_str())));
+        let mut relationships = db.read_relationships(as_parent).unwrap_or_default();
+        for relationship in db.read_relationships(as_child).unwrap_or_default() {
+            if !relationships.contains(&relationship) {
+                relationships.push(relationship);
+            }
+        }
+        relationships.len()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        dialog::new_entity::tests::example_new_entity_data,
+        tests::{example_database, example_labels},
+    };
+
+    #[test]
+    fn successful_operations_are_notified() {
+        let mut gui = SqlGui {
+            lore_database: Some(example_database()),
+            ..Default::default()
+        };
+        let data = example_new_entity_data();
+        gui.handle_message(GuiMessage::NewEntity(data.clone()))
+            .unwrap();
+
+        let texts: Vec<&str> = gui
+            .notifications
+            .active()
+            .map(|notification| notification.text.as_str())
+            .collect();
+        assert_eq!(texts, vec![format!("Entity '{}' created", data.label())]);
+    }
+
+    #[test]
+    fn failed_operations_are_not_notified() {
+        let mut gui = SqlGui {
+            lore_database: None,
+            ..Default::default()
+        };
+        let _ = gui.handle_message(GuiMessage::DeleteEntity(example_labels()[0].clone()));
+        assert!(gui.notifications.history().is_empty());
+    }
+
+    #[test]
+    fn saving_without_a_selection_is_not_notified() {
+        let mut gui = SqlGui {
+            lore_database: Some(example_database()),
+            ..Default::default()
+        };
+        gui.handle_message(GuiMessage::EntityViewUpd(
+            EntityViewMessage::DescriptionSave,
+        ))
+        .unwrap();
+        gui.handle_message(GuiMessage::HistoryViewUpd(HistoryViewMessage::ContentSave))
+            .unwrap();
+        assert!(gui.notifications.history().is_empty());
+        assert!(!gui.undo_stack.can_undo());
+    }
+
+    #[test]
+    fn deleting_an_entity_counts_its_relationships() {
+        let gui = SqlGui {
+            lore_database: Some(example_database()),
+            ..Default::default()
+        };
+        let label = example_labels()[0].clone();
+        let expected = gui.count_relationships_of(&label);
+        assert!(expected > 0);
+        let text = gui
+            .success_notification(&GuiMessage::DeleteEntity(label.clone()))
+            .unwrap();
+        assert_eq!(
+            text,
+            format!("Entity '{}' and {} relationships deleted", label, expected)
+        );
+    }
+}
@@ -0,0 +1,73 @@
+use super::SqlGui;
+use crate::{errors::LoreGuiError, query_cache::CachedReader};
+
+impl SqlGui {
+    pub(super) fn toggle_read_mode(&mut self) -> Result<(), LoreGuiError> {
+        self.read_mode.toggle();
+        self.refresh_read_mode()
+    }
+
+    /// Has to be called after the changes of a message were applied, so that references to new or deleted entities are shown correctly.
+    pub(super) fn refresh_read_mode(&mut self) -> Result<(), LoreGuiError> {
+        let reader = self
+            .lore_database
+            .as_ref()
+            .map(|db| CachedReader::new(db, &self.query_cache));
+        self.read_mode.refresh(reader.as_ref())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use lorecore::types::*;
+
+    use super::*;
+    use crate::{
+        app::{message_handling::GuiMessage, ViewType},
+        global_search::SearchTarget,
+        tests::{example_database, example_labels},
+    };
+
+    #[test]
+    fn read_mode_knows_only_existing_labels() {
+        let mut gui = SqlGui {
+            lore_database: Some(example_database()),
+            ..Default::default()
+        };
+        let labels = example_labels();
+        assert!(!gui.read_mode.exists(labels[0].to_str()));
+
+        gui.handle_message(GuiMessage::ToggleReadMode).unwrap();
+        assert!(gui.read_mode.is_active());
+        assert!(gui.read_mode.exists(labels[0].to_str()));
+        assert!(!gui.read_mode.exists("nobody"));
+
+        gui.handle_message(GuiMessage::DeleteEntity(labels[0].clone()))
+            .unwrap();
+        gui.apply_changes();
+        gui.refresh_read_mode().unwrap();
+        assert!(!gui.read_mode.exists(labels[0].to_str()));
+    }
+
+    #[test]
+    fn clicking_a_reference_in_the_history_view_selects_the_entity() {
+        let mut gui = SqlGui {
+            lore_database: Some(example_database()),
+            ..Default::default()
+        };
+        gui.handle_message(GuiMessage::ViewSelected(ViewType::History))
+            .unwrap();
+        gui.handle_message(GuiMessage::ToggleReadMode).unwrap();
+        let label: Label = example_labels()[1].clone();
+
+        gui.handle_message(GuiMessage::JumpTo(SearchTarget::Entity(
+            label.clone(),
+            None,
+        )))
+        .unwrap();
+
+        assert_eq!(gui.selected_view, ViewType::Entity);
+        assert_eq!(gui.get_selected_label(), Some(label));
+        assert!(gui.read_mode.is_active());
+    }
+}
@@ -0,0 +1,637 @@
+use std::{mem, path::PathBuf, sync::PoisonError};
+
+use iced::Task;
+use lorecore::sql::lore_database::LoreDatabase;
+
+use super::{message_handling::GuiMessage, SqlGui, ViewType};
+use crate::{
+    dashboard::DashboardData,
+    entity_view::EntityViewData,
+    errors::LoreGuiError,
+    global_search::SearchResults,
+    history_view::HistoryViewData,
+    query_cache::{CachedReader, SharedQueryCache, Table},
+    relationship_view::RelationshipViewData,
+};
+
+/// The result of reading a view's contents in the background.
+///
+/// Results are discarded if the database was switched or a newer refresh of the same view has been started in the meantime.
+#[derive(Debug, Clone)]
+pub(crate) struct Loaded<T> {
+    database: String,
+    generation: u64,
+    result: Result<T, LoreGuiError>,
+}
+
+/// Counts the refreshes started per view, so that only the latest result is applied.
+#[derive(Debug, Clone, Default)]
+pub(super) struct RefreshGenerations {
+    entity: u64,
+    history: u64,
+    relationship: u64,
+    dashboard: u64,
+    search: u64,
+}
+
+fn load<Q, T>(
+    database: String,
+    generation: u64,
+    cache: SharedQueryCache,
+    query: Q,
+    run: fn(&Q, &CachedReader) -> Result<T, LoreGuiError>,
+    to_message: fn(Loaded<T>) -> GuiMessage,
+) -> Task<GuiMessage>
+where
+    Q: Send + 'static,
+    T: Send + 'static,
+{
+    let path = PathBuf::from(&database);
+    Task::perform(
+        async move {
+            let result = LoreDatabase::open(path)
+                .map_err(LoreGuiError::from)
+                .and_then(|db| run(&query, &CachedReader::new(&db, &cache)));
+            Loaded {
+                database,
+                generation,
+                result,
+            }
+        },
+        to_message,
+    )
+}
+
+impl SqlGui {
+    pub(super) fn record_changes(&mut self, tables: &[Table]) {
+        if !tables.is_empty() {
+            self.status_bar.outdated = true;
+        }
+        for table in tables {
+            if !self.changed_tables.contains(table) {
+                self.changed_tables.push(*table);
+            }
+        }
+    }
+
+    /// Drops cached reads of the changed tables and marks the views showing them as outdated.
+    ///
+    /// This has to happen after the changes were written, otherwise a read running in the background could cache their previous state.
+    pub(super) fn apply_changes(&mut self) {
+        let mut cache = self
+            .query_cache
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner);
+        for table in mem::take(&mut self.changed_tables) {
+            cache.invalidate(table);
+            self.global_search.invalidate();
+            match table {
+                Table::Entities => self.entity_view_state.invalidate(),
+                Table::History => self.history_view_state.invalidate(),
+                Table::Relationships => self.relationship_view_state.invalidate(),
+            }
+            self.dashboard_state.invalidate();
+        }
+    }
+
+    /// Reads the visible view again if it is outdated. Hidden views are read once they are selected.
+    pub(super) fn refresh_visible_view(&mut self) -> Task<GuiMessage> {
+        self.apply_changes();
+        let database = match self.lore_database.as_ref() {
+            Some(db) => db.path_as_string(),
+            None => return Task::none(),
+        };
+        let view_task = match self.selected_view {
+            ViewType::Entity => self.refresh_entity_view(database.clone()),
+            ViewType::History => self.refresh_history_view(database.clone()),
+            ViewType::Relationship => self.refresh_relationship_view(database.clone()),
+            ViewType::Dashboard => self.refresh_dashboard(database.clone()),
+        };
+        Task::batch([
+            view_task,
+            self.update_search_index(database.clone()),
+            self.refresh_global_search(database),
+        ])
+    }
+
+    /// Brings the search index up to date in the background. Until then, searches read the whole database.
+    fn update_search_index(&mut self, database: String) -> Task<GuiMessage> {
+        let update = match self.search_index.next_update() {
+            Some(update) => update,
+            None => return Task::none(),
+        };
+        Task::perform(
+            async move {
+                let result = update.run(&database);
+                (database, result)
+            },
+            |(database, result)| GuiMessage::SearchIndexUpdated(database, result),
+        )
+    }
+
+    pub(super) fn search_index_updated(
+        &mut self,
+        database: String,
+        result: Result<(), LoreGuiError>,
+    ) -> Result<(), LoreGuiError> {
+        match self.find_tab(&database) {
+            Some(index) if index == self.active_tab => {
+                self.search_index.update_finished(result.is_ok())
+            }
+            Some(index) => self.tabs[index].search_index_updated(result.is_ok()),
+            None => {}
+        }
+        result
+    }
+
+    /// Searches again if the results are shown and outdated.
+    fn refresh_global_search(&mut self, database: String) -> Task<GuiMessage> {
+        let index = self
+            .lore_database
+            .as_ref()
+            .and_then(|db| self.search_index.database_path(db));
+        let query = match self.global_search.query_if_changed(index) {
+            Some(query) => query,
+            None => return Task::none(),
+        };
+        self.refresh_generations.search += 1;
+        self.global_search.set_loading(true);
+        load(
+            database,
+            self.refresh_generations.search,
+            self.query_cache.clone(),
+            query,
+            |query, reader| query.run(reader),
+            GuiMessage::GlobalSearchLoaded,
+        )
+    }
+
+    fn refresh_entity_view(&mut self, database: String) -> Task<GuiMessage> {
+        let query = match self.entity_view_state.query_if_changed() {
+            Some(query) => query,
+            None => return Task::none(),
+        };
+        self.refresh_generations.entity += 1;
+        self.entity_view_state.set_loading(true);
+        load(
+            database,
+            self.refresh_generations.entity,
+            self.query_cache.clone(),
+            query,
+            |query, reader| query.run(reader),
+            GuiMessage::EntityViewLoaded,
+        )
+    }
+
+    fn refresh_history_view(&mut self, database: String) -> Task<GuiMessage> {
+        let query = match self.history_view_state.query_if_changed() {
+            Some(query) => query,
+            None => return Task::none(),
+        };
+        self.refresh_generations.history += 1;
+        self.history_view_state.set_loading(true);
+        load(
+            database,
+            self.refresh_generations.history,
+            self.query_cache.clone(),
+            query,
+            |query, reader| query.run(reader),
+            GuiMessage::HistoryViewLoaded,
+        )
+    }
+
+    fn refresh_relationship_view(&mut self, database: String) -> Task<GuiMessage> {
+        let query = match self.relationship_view_state.query_if_changed() {
+            Some(query) => query,
+            None => return Task::none(),
+        };
+        self.refresh_generations.relationship += 1;
+        self.relationship_view_state.set_loading(true);
+        load(
+            database,
+            self.refresh_generations.relationship,
+            self.query_cache.clone(),
+            query,
+            |query, reader| query.run(reader),
+            GuiMessage::RelationshipViewLoaded,
+        )
+    }
+
+    fn refresh_dashboard(&mut self, database: String) -> Task<GuiMessage> {
+        let query = match self.dashboard_state.query_if_changed() {
+            Some(query) => query,
+            None => return Task::none(),
+        };
+        self.refresh_generations.dashboard += 1;
+        self.dashboard_state.set_loading(true);
+        load(
+            database,
+            self.refresh_generations.dashboard,
+            self.query_cache.clone(),
+            query,
+            |query, reader| query.run(reader),
+            GuiMessage::DashboardLoaded,
+        )
+    }
+
+    fn is_current<T>(&self, loaded: &Loaded<T>, latest_generation: u64) -> bool {
+        let is_active_database = self
+            .lore_database
+            .as_ref()
+            .is_some_and(|db| db.path_as_string() == loaded.database);
+        is_active_database && loaded.generation == latest_generation
+    }
+
+    pub(super) fn entity_view_loaded(
+        &mut self,
+        loaded: Loaded<EntityViewData>,
+    ) -> Result<(), LoreGuiError> {
+        if !self.is_current(&loaded, self.refresh_generations.entity) {
+            return Ok(());
+        }
+        self.entity_view_state.set_loading(false);
+        match loaded.result {
+            Ok(data) => self.entity_view_state.apply(data),
+            Err(e) => {
+                self.entity_view_state.invalidate();
+                return Err(e);
+            }
+        }
+        Ok(())
+    }
+
+    pub(super) fn history_view_loaded(
+        &mut self,
+        loaded: Loaded<HistoryViewData>,
+    ) -> Result<(), LoreGuiError> {
+        if !self.is_current(&loaded, self.refresh_generations.history) {
+            return Ok(());
+        }
+        self.history_view_state.set_loading(false);
+        match loaded.result {
+            Ok(data) => self.history_view_state.apply(data),
+            Err(e) => {
+                self.history_view_state.invalidate();
+                return Err(e);
+            }
+        }
+        Ok(())
+    }
+
+    pub(super) fn relationship_view_loaded(
+        &mut self,
+        loaded: Loaded<RelationshipViewData>,
+    ) -> Result<(), LoreGuiError> {
+        if !self.is_current(&loaded, self.refresh_generations.relationship) {
+            return Ok(());
+        }
+        self.relationship_view_state.set_loading(false);
+        match loaded.result {
+            Ok(data) => self.relationship_view_state.apply(data),
+            Err(e) => {
+                self.relationship_view_state.invalidate();
+                return Err(e);
+            }
+        }
+        Ok(())
+    }
+
+    pub(super) fn dashboard_loaded(
+        &mut self,
+        loaded: Loaded<DashboardData>,
+    ) -> Result<(), LoreGuiError> {
+        if !self.is_current(&loaded, self.refresh_generations.dashboard) {
+            return Ok(());
+        }
+        self.dashboard_state.set_loading(false);
+        match loaded.result {
+            Ok(data) => self.dashboard_state.apply(data),
+            Err(e) => {
+                self.dashboard_state.invalidate();
+                return Err(e);
+            }
+        }
+        Ok(())
+    }
+
+    pub(super) fn global_search_loaded(
+        &mut self,
+        loaded: Loaded<SearchResults>,
+    ) -> Result<(), LoreGuiError> {
+        if !self.is_current(&loaded, self.refresh_generations.search) {
+            return Ok(());
+        }
+        self.global_search.set_loading(false);
+        match loaded.result {
+            Ok(results) => self.global_search.apply(results),
+            Err(e) => {
+                self.global_search.invalidate();
+                return Err(e);
+            }
+        }
+        Ok(())
+    }
+
+    /// Synchronous counterpart of `refresh_visible_view`, so that tests can inspect the refreshed view.
+    #[cfg(test)]
+    pub(super) fn refresh_visible_view_now(&mut self) -> Result<(), LoreGuiError> {
+        self.apply_changes();
+        let db = match self.lore_database.as_ref() {
+            Some(db) => db,
+            None => return Ok(()),
+        };
+        self.search_index.update_now(db);
+        let reader = CachedReader::new(db, &self.query_cache);
+        match self.selected_view {
+            ViewType::Entity => {
+                if let Some(query) = self.entity_view_state.query_if_changed() {
+                    self.entity_view_state.apply(query.run(&reader)?);
+                }
+            }
+            ViewType::History => {
+                if let Some(query) = self.history_view_state.query_if_changed() {
+                    self.history_view_state.apply(query.run(&reader)?);
+                }
+            }
+            ViewType::Relationship => {
+                if let Some(query) = self.relationship_view_state.query_if_changed() {
+                    self.relationship_view_state.apply(query.run(&reader)?);
+                }
+            }
+            ViewType::Dashboard => {
+                if let Some(query) = self.dashboard_state.query_if_changed() {
+                    self.dashboard_state.apply(query.run(&reader)?);
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Reads all views synchronously, regardless of which one is visible, so that tests can inspect them.
+    #[cfg(test)]
+    pub(super) fn refresh_views_now(&mut self) -> Result<(), LoreGuiError> {
+        self.apply_changes();
+        let db = match self.lore_database.as_ref() {
+            Some(db) => db,
+            None => return Ok(()),
+        };
+        self.search_index.update_now(db);
+        let reader = CachedReader::new(db, &self.query_cache);
+        let entity_data = self.entity_view_state.query().run(&reader)?;
+        let history_data = self.history_view_state.query().run(&reader)?;
+        let relationship_data = self.relationship_view_state.query().run(&reader)?;
+        self.entity_view_state.apply(entity_data);
+        self.history_view_state.apply(history_data);
+        self.relationship_view_state.apply(relationship_data);
+        self.dashboard_state
+            .apply(crate::dashboard::DashboardQuery.run(&reader)?);
+        if let Some(query) = self
+            .global_search
+            .query_if_changed(self.search_index.database_path(db))
+        {
+            self.global_search.apply(query.run(&reader)?);
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{sync::Mutex, time::Instant};
+
+    use lorecore::types::*;
+
+    use super::*;
+    use crate::{
+        db_col_view::{entry::DbColViewEntry, ColViewMes},
+        entity_view::EntityViewMessage,
+        tests::{example_database, example_labels},
+    };
+
+    fn loaded_entity_view(gui: &SqlGui) -> Loaded<EntityViewData> {
+        let db = gui.lore_database.as_ref().unwrap();
+        Loaded {
+            database: db.path_as_string(),
+            generation: gui.refresh_generations.entity,
+            result: gui
+                .entity_view_state
+                .query()
+                .run(&CachedReader::new(db, &gui.query_cache)),
+        }
+    }
+
+    #[test]
+    fn current_result_is_applied() {
+        let mut gui = SqlGui {
+            lore_database: Some(example_database()),
+            ..Default::default()
+        };
+        let _ = gui.refresh_visible_view();
+        let loaded = loaded_entity_view(&gui);
+
+        gui.entity_view_loaded(loaded).unwrap();
+
+        let entries = gui.entity_view_state.label_view_state.get_entries();
+        assert_eq!(entries.len(), example_labels().len());
+        assert!(!gui.entity_view_state.label_view_state.is_loading());
+    }
+
+    #[test]
+    fn superseded_result_is_discarded() {
+        let mut gui = SqlGui {
+            lore_database: Some(example_database()),
+            ..Default::default()
+        };
+        let _ = gui.refresh_visible_view();
+        let stale = loaded_entity_view(&gui);
+        gui.set_selected_label(Some(example_labels()[0].clone()));
+        let _ = gui.refresh_visible_view();
+
+        gui.entity_view_loaded(stale).unwrap();
+
+        assert!(gui
+            .entity_view_state
+            .label_view_state
+            .get_entries()
+            .is_empty());
+        assert!(gui.entity_view_state.label_view_state.is_loading());
+    }
+
+    #[test]
+    fn result_for_other_database_is_discarded() {
+        let mut gui = SqlGui {
+            lore_database: Some(example_database()),
+            ..Default::default()
+        };
+        let _ = gui.refresh_visible_view();
+        let stale = loaded_entity_view(&gui);
+        gui.lore_database = Some(example_database());
+
+        gui.entity_view_loaded(stale).unwrap();
+
+        assert!(gui
+            .entity_view_state
+            .label_view_state
+            .get_entries()
+            .is_empty());
+    }
+
+    #[test]
+    fn refresh_keeps_unsaved_description() {
+        let mut gui = SqlGui {
+            lore_database: Some(example_database()),
+            ..Default::default()
+        };
+        let labels = example_labels();
+        gui.set_selected_label(Some(labels[0].clone()));
+        gui.refresh_views_now().unwrap();
+        gui.entity_view_state
+            .current_description
+            .perform(iced::widget::text_editor::Action::Edit(
+                iced::widget::text_editor::Edit::Insert('x'),
+            ));
+
+        gui.refresh_views_now().unwrap();
+
+        assert_eq!(gui.get_description_text(), "x\n");
+    }
+
+    fn large_database(column_count: usize) -> LoreDatabase {
+        let db = example_database();
+        let columns = (0..column_count)
+            .map(|i| EntityColumn {
+                label: format!("entity_{:05}", i / 10).into(),
+                descriptor: format!("descriptor_{}", i % 10).into(),
+                description: format!("Description {}\n", i).into(),
+            })
+            .collect();
+        db.write_entity_columns(columns).unwrap();
+        db
+    }
+
+    fn browsing_messages() -> Vec<GuiMessage> {
+        let entity_message = |message| GuiMessage::EntityViewUpd(message);
+        let mut messages = vec![];
+        for i in 0..10 {
+            let label: Label = format!("entity_{:05}", i).into();
+            let descriptor: Descriptor = format!("descriptor_{}", i).into();
+            messages.push(entity_message(EntityViewMessage::LabelViewUpdate(
+                ColViewMes::Selected(i, DbColViewEntry(Some(label))),
+            )));
+            messages.push(entity_message(EntityViewMessage::DescriptorViewUpdate(
+                ColViewMes::Selected(i, DbColViewEntry(Some(descriptor))),
+            )));
+            messages.push(GuiMessage::ViewSelected(ViewType::History));
+            messages.push(GuiMessage::ViewSelected(ViewType::Entity));
+            messages.push(entity_message(EntityViewMessage::DescriptorViewUpdate(
+                ColViewMes::SearchFieldUpd("desc".to_string()),
+            )));
+            messages.push(entity_message(EntityViewMessage::DescriptorViewUpdate(
+                ColViewMes::SearchFieldUpd(String::new()),
+            )));
+        }
+        messages.push(entity_message(EntityViewMessage::DescriptionSave));
+        messages
+    }
+
+    /// Reads every view without a cache after every message, as was done before views were tracked.
+    fn refresh_all_views_uncached(gui: &mut SqlGui) {
+        let db = gui.lore_database.as_ref().unwrap();
+        let cache = Mutex::default();
+        let reader = CachedReader::new(db, &cache);
+        let entity_data = gui.entity_view_state.query().run(&reader).unwrap();
+        let history_data = gui.history_view_state.query().run(&reader).unwrap();
+        let relationship_data = gui.relationship_view_state.query().run(&reader).unwrap();
+        gui.entity_view_state.apply(entity_data);
+        gui.history_view_state.apply(history_data);
+        gui.relationship_view_state.apply(relationship_data);
+    }
+
+    /// Run with `cargo test --release benchmark -- --ignored --nocapture`.
+    #[test]
+    #[ignore]
+    fn benchmark_refreshing_50k_entity_columns() {
+        let db = large_database(50_000);
+        let path = PathBuf::from(db.path_as_string());
+
+        let mut gui = SqlGui {
+            lore_database: Some(db),
+            ..Default::default()
+        };
+        let start = Instant::now();
+        for message in browsing_messages() {
+            gui.handle_message(message).unwrap();
+            refresh_all_views_uncached(&mut gui);
+        }
+        let uncached = start.elapsed();
+
+        let mut gui = SqlGui {
+            lore_database: Some(LoreDatabase::open(path).unwrap()),
+            ..Default::default()
+        };
+        let start = Instant::now();
+        for message in browsing_messages() {
+            gui.handle_message(message).unwrap();
+            gui.refresh_visible_view_now().unwrap();
+        }
+        let tracked = start.elapsed();
+
+        println!("Reading all views after every message: {:?}", uncached);
+        println!("Reading only outdated views, with cache: {:?}", tracked);
+        assert!(tracked < uncached);
+    }
+
+    #[test]
+    fn only_the_visible_view_is_read() {
+        let mut gui = SqlGui {
+            lore_database: Some(example_database()),
+            ..Default::default()
+        };
+        let _ = gui.refresh_visible_view();
+        assert!(gui.entity_view_state.label_view_state.is_loading());
+        assert!(!gui.history_view_state.year_view_state.is_loading());
+    }
+
+    #[test]
+    fn unchanged_view_is_not_read_again() {
+        let mut gui = SqlGui {
+            lore_database: Some(example_database()),
+            ..Default::default()
+        };
+        gui.refresh_visible_view_now().unwrap();
+        gui.handle_message(GuiMessage::ViewSelected(ViewType::History))
+            .unwrap();
+        gui.refresh_visible_view_now().unwrap();
+        gui.handle_message(GuiMessage::ViewSelected(ViewType::Entity))
+            .unwrap();
+
+        assert!(gui.entity_view_state.query_if_changed().is_none());
+        assert!(gui.history_view_state.query_if_changed().is_none());
+    }
+
+    #[test]
+    fn writing_an_entity_outdates_only_the_entity_view() {
+        let mut gui = SqlGui {
+            lore_database: Some(example_database()),
+            ..Default::default()
+        };
+        gui.refresh_views_now().unwrap();
+        for view in [ViewType::Entity, ViewType::History, ViewType::Relationship] {
+            gui.selected_view = view;
+            gui.refresh_visible_view_now().unwrap();
+        }
+        gui.selected_view = ViewType::Entity;
+
+        let label = example_labels()[0].clone();
+        gui.handle_message(GuiMessage::DeleteDescriptor(
+            label,
+            crate::tests::example_descriptors()[0].0.clone(),
+        ))
+        .unwrap();
+        gui.apply_changes();
+
+        assert!(gui.entity_view_state.query_if_changed().is_some());
+        assert!(gui.history_view_state.query_if_changed().is_none());
+        assert!(gui.relationship_view_state.query_if_changed().is_none());
+    }
+}
@@ -0,0 +1,243 @@
+use std::path::PathBuf;
+
+use lorecore::types::*;
+
+use super::SqlGui;
+use crate::{
+    db_col_view::state::DbColViewState,
+    errors::LoreGuiError,
+    user_preferences::{store_database_sessions, DatabaseSession},
+};
+
+fn search_text<E: Clone>(state: &DbColViewState<E>) -> String {
+    state.get_search_text().unwrap_or_default().to_string()
+}
+
+fn text_of<T: ToString>(selected: Option<T>) -> Option<String> {
+    selected.map(|value| value.to_string())
+}
+
+impl SqlGui {
+    fn current_session(&self) -> DatabaseSession {
+        let entity = &self.entity_view_state;
+        let history = &self.history_view_state;
+        let relationship = &self.relationship_view_state;
+        DatabaseSession {
+            view: self.selected_view.clone(),
+            label: text_of(self.get_selected_label()),
+            descriptor: text_of(self.get_selected_descriptor()),
+            year: text_of(self.get_selected_year()),
+            day: text_of(self.get_selected_day()),
+            timestamp: history.timestamp_text(),
+            parent: text_of(self.get_selected_parent()),
+            child: text_of(self.get_selected_child()),
+            role: text_of(self.get_selected_role()),
+            label_search: search_text(&entity.label_view_state),
+            descriptor_search: search_text(&entity.descriptor_view_state),
+            year_search: search_text(&history.year_view_state),
+            day_search: search_text(&history.day_view_state),
+            timestamp_search: search_text(&history.timestamp_view_state),
+            parent_search: search_text(&relationship.parent_view_state),
+            child_search: search_text(&relationship.child_view_state),
+            role_search: search_text(&relationship.role_view_state),
+        }
+    }
+
+    /// Remembers where the user is in the active database, until the sessions are stored.
+    pub(super) fn remember_session(&mut self) {
+        let path = match self.lore_database.as_ref() {
+            Some(db) => PathBuf::from(db.path_as_string()),
+            None => return,
+        };
+        let session = self.current_session();
+        if self.database_sessions.update(path, session) {
+            self.sessions_changed = true;
+        }
+    }
+
+    /// Stores the remembered sessions, if they changed since they were last stored.
+    pub(super) fn store_sessions(&mut self) -> Result<(), LoreGuiError> {
+        if !self.sessions_changed {
+            return Ok(());
+        }
+        self.save_database_sessions()?;
+        self.sessions_changed = false;
+        Ok(())
+    }
+
+    pub(super) fn save_database_sessions(&self) -> Result<(), LoreGuiError> {
+        if !self.preferences_loaded {
+            return Ok(());
+        }
+        store_database_sessions(&self.database_sessions)
+    }
+
+    /// Returns to where the user was when they last worked with the active database.
+    pub(super) fn restore_session(&mut self) {
+        let session = match self.lore_database.as_ref() {
+            Some(db) => self
+                .database_sessions
+                .get(&PathBuf::from(db.path_as_string()))
+                .cloned(),
+            None => None,
+        };
+        let session = match session {
+            Some(session) => session,
+            None => return,
+        };
+        self.selected_view = session.view;
+
+        let entity = &mut self.entity_view_state;
+        entity
+            .label_view_state
+            .set_search_text(session.label_search);
+        entity
+            .descriptor_view_state
+            .set_search_text(session.descriptor_search);
+        entity.set_selected_label(session.label.map(Label::from));
+        entity.set_selected_descriptor(session.descriptor.map(Descriptor::from));
+
+        let history = &mut self.history_view_state;
+        history.year_view_state.set_search_text(session.year_search);
+        history.day_view_state.set_search_text(session.day_search);
+        history
+            .timestamp_view_state
+            .set_search_text(session.timestamp_search);
+        history.set_selected_year(session.year.and_then(|year| Year::try_from(year).ok()));
+        history.set_selected_day(session.day.and_then(|day| Day::try_from(day).ok()));
+        history.restore_timestamp(session.timestamp);
+
+        let relationship = &mut self.relationship_view_state;
+        relationship
+            .parent_view_state
+            .set_search_text(session.parent_search);
+        relationship
+            .child_view_state
+            .set_search_text(session.child_search);
+        relationship
+            .role_view_state
+            .set_search_text(session.role_search);
+        relationship.set_selected_parent(session.parent.map(Parent::from));
+        relationship.set_selected_child(session.child.map(Child::from));
+        relationship.set_selected_role(session.role.map(Role::from));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use lorecore::sql::lore_database::LoreDatabase;
+
+    use super::*;
+    use crate::{
+        app::ViewType,
+        tests::{
+            example_database, example_days, example_descriptors, example_history_content,
+            example_labels, example_years,
+        },
+    };
+
+    fn reopened(gui: &SqlGui) -> SqlGui {
+        let path = gui.lore_database.as_ref().unwrap().path_as_string();
+        let mut reopened = SqlGui {
+            lore_database: Some(LoreDatabase::open(path.clone().into()).unwrap()),
+            ..Default::default()
+        };
+        reopened
+            .database_sessions
+            .update(path.into(), gui.current_session());
+        reopened.restore_session();
+        reopened
+    }
+
+    #[test]
+    fn entity_selections_and_search_texts_are_restored() {
+        let mut gui = SqlGui {
+            lore_database: Some(example_database()),
+            ..Default::default()
+        };
+        gui.set_selected_label(Some(example_labels()[1].clone()));
+        gui.set_selected_descriptor(Some(example_descriptors()[2].0.clone()));
+        gui.entity_view_state
+            .label_view_state
+            .set_search_text("label".to_string());
+
+        let mut reopened = reopened(&gui);
+        reopened.refresh_views_now().unwrap();
+
+        assert_eq!(reopened.selected_view, ViewType::Entity);
+        assert_eq!(reopened.get_selected_label(), gui.get_selected_label());
+        assert_eq!(
+            reopened.get_selected_descriptor(),
+            gui.get_selected_descriptor()
+        );
+        assert_eq!(
+            reopened
+                .entity_view_state
+                .label_view_state
+                .get_search_text(),
+            Some("label")
+        );
+        assert_eq!(
+            reopened.get_description_text(),
+            example_descriptors()[2].1.to_str()
+        );
+    }
+
+    #[test]
+    fn history_item_is_restored_once_its_timestamp_is_read() {
+        let year = example_years()[1];
+        let day = example_days()[1];
+        let mut gui = SqlGui {
+            lore_database: Some(example_database()),
+            selected_view: ViewType::History,
+            ..Default::default()
+        };
+        gui.set_selected_year(Some(year));
+        gui.set_selected_day(Some(day));
+        gui.refresh_views_now().unwrap();
+        let timestamp = gui.history_view_state.timestamp_view_state.get_entries()[0].clone();
+        gui.set_selected_timestamp(timestamp.0);
+
+        let mut reopened = reopened(&gui);
+        assert_eq!(reopened.get_selected_timestamp(), None);
+        assert_eq!(reopened.current_session(), gui.current_session());
+        reopened.refresh_views_now().unwrap();
+        reopened.refresh_views_now().unwrap();
+
+        assert_eq!(reopened.selected_view, ViewType::History);
+        assert_eq!(reopened.get_selected_year(), Some(year));
+        assert_eq!(reopened.get_selected_day(), Some(day));
+        assert_eq!(reopened.get_selected_timestamp(), timestamp.0);
+        assert_eq!(
+            reopened.get_history_text(),
+            example_history_content(year, day).to_str()
+        );
+    }
+
+    #[test]
+    fn sessions_are_only_stored_once_changed() {
+        let mut gui = SqlGui {
+            lore_database: Some(example_database()),
+            ..Default::default()
+        };
+        gui.remember_session();
+        assert!(gui.sessions_changed);
+        gui.store_sessions().unwrap();
+        assert!(!gui.sessions_changed);
+
+        gui.remember_session();
+        assert!(!gui.sessions_changed);
+        gui.set_selected_label(Some(example_labels()[0].clone()));
+        gui.remember_session();
+        assert!(gui.sessions_changed);
+    }
+
+    #[test]
+    fn selecting_a_timestamp_drops_the_one_to_restore() {
+        let mut gui = SqlGui::default();
+        gui.history_view_state
+            .restore_timestamp(Some("123".to_string()));
+        gui.set_selected_timestamp(None);
+        assert_eq!(gui.current_session().timestamp, None);
+    }
+}
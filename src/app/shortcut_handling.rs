@@ -0,0 +1,375 @@
+use iced::keyboard::{Key, Modifiers};
+use lorecore::types::*;
+
+use super::{message_handling::GuiMessage, SqlGui, ViewType};
+use crate::{
+    appearance::Appearance,
+    db_col_view::ColViewMes,
+    dialog::{settings::SettingsDialog, DialogUpdate},
+    entity_view::EntityViewMessage,
+    errors::LoreGuiError,
+    history_view::HistoryViewMessage,
+    localisation::tr,
+    relationship_view::RelationshipViewMessage,
+    shortcuts::{KeyBinding, KeyBindings, ShortcutAction},
+    user_preferences::{store_appearance, store_key_bindings},
+};
+
+impl SqlGui {
+    pub(super) fn handle_key_press(
+        &mut self,
+        key: Key,
+        modifiers: Modifiers,
+    ) -> Result<(), LoreGuiError> {
+        let binding = match KeyBinding::from_key(&key, modifiers) {
+            Some(binding) => binding,
+            None => return Ok(()),
+        };
+        if let Some(dialog) = self.dialog.as_mut() {
+            if dialog.captures_keys() {
+                if !binding.is_modifier() {
+                    dialog.update(DialogUpdate::KeyPress(binding));
+                }
+                return Ok(());
+            }
+        }
+        match self.key_bindings.action_for(&binding) {
+            Some(action) => self.handle_shortcut(action),
+            None => Ok(()),
+        }
+    }
+
+    fn handle_shortcut(&mut self, action: ShortcutAction) -> Result<(), LoreGuiError> {
+        let message = if self.dialog.is_some() {
+            match action {
+                ShortcutAction::SubmitDialog => Some(GuiMessage::DialogSubmit),
+                ShortcutAction::CloseDialog => Some(GuiMessage::DialogClosed),
+                ShortcutAction::SelectPrevious => {
+                    Some(GuiMessage::DialogUpdate(DialogUpdate::SelectPrevious))
+                }
+                ShortcutAction::SelectNext => {
+                    Some(GuiMessage::DialogUpdate(DialogUpdate::SelectNext))
+                }
+                _ => None,
+            }
+        } else {
+            self.shortcut_message(action)
+        };
+        match message {
+            Some(message) => self.handle_message(message),
+            None => Ok(()),
+        }
+    }
+
+    fn shortcut_message(&mut self, action: ShortcutAction) -> Option<GuiMessage> {
+        if action == ShortcutAction::CommandPalette {
+            return Some(GuiMessage::ShowCommandPalette);
+        }
+        self.lore_database.as_ref()?;
+        match action {
+            ShortcutAction::Save => self.save_message(),
+            ShortcutAction::New => self.new_message(),
+            ShortcutAction::Delete => self.delete_message(),
+            ShortcutAction::SubmitDialog
+            | ShortcutAction::CloseDialog
+            | ShortcutAction::CommandPalette => None,
+            ShortcutAction::Undo => Some(GuiMessage::Undo),
+            ShortcutAction::Redo => Some(GuiMessage::Redo),
+            ShortcutAction::EntityView => Some(GuiMessage::ViewSelected(ViewType::Entity)),
+            ShortcutAction::HistoryView => Some(GuiMessage::ViewSelected(ViewType::History)),
+            ShortcutAction::RelationshipView => {
+                Some(GuiMessage::ViewSelected(ViewType::Relationship))
+            }
+            ShortcutAction::DashboardView => Some(GuiMessage::ViewSelected(ViewType::Dashboard)),
+            ShortcutAction::ToggleReadMode => Some(GuiMessage::ToggleReadMode),
+            ShortcutAction::SelectPrevious => self.move_selection_message(-1),
+            ShortcutAction::SelectNext => self.move_selection_message(1),
+            ShortcutAction::PreviousColumn => {
+                self.focused_column = self.focused_column().saturating_sub(1);
+                None
+            }
+            ShortcutAction::NextColumn => {
+                self.focused_column = (self.focused_column() + 1).min(self.column_count() - 1);
+                None
+            }
+        }
+    }
+
+    fn save_message(&self) -> Option<GuiMessage> {
+        match self.selected_view {
+            ViewType::Entity => Some(GuiMessage::EntityViewUpd(
+                EntityViewMessage::DescriptionSave,
+            )),
+            ViewType::History => Some(GuiMessage::HistoryViewUpd(HistoryViewMessage::ContentSave)),
+            ViewType::Relationship | ViewType::Dashboard => None,
+        }
+    }
+
+    fn new_message(&self) -> Option<GuiMessage> {
+        let message = match self.selected_view {
+            ViewType::Entity => GuiMessage::EntityViewUpd(EntityViewMessage::NewEntity),
+            ViewType::History => GuiMessage::HistoryViewUpd(HistoryViewMessage::NewHistoryItem),
+            ViewType::Relationship => {
+                GuiMessage::RelationshipViewUpd(RelationshipViewMessage::NewRelationship)
+            }
+            ViewType::Dashboard => return None,
+        };
+        Some(message)
+    }
+
+    fn delete_message(&self) -> Option<GuiMessage> {
+        match self.selected_view {
+            ViewType::Entity => {
+                let label = self.get_selected_label()?;
+                let message = match self.get_selected_descriptor() {
+                    Some(descriptor) => EntityViewMessage::DeleteDescriptor(label, descriptor),
+                    None => EntityViewMessage::DeleteEntity(label),
+                };
+                Some(GuiMessage::EntityViewUpd(message))
+            }
+            ViewType::History => {
+                let timestamp = self.get_selected_timestamp()?;
+                Some(GuiMessage::HistoryViewUpd(
+                    HistoryViewMessage::DeleteHistoryItem(timestamp),
+                ))
+            }
+            ViewType::Relationship => {
+                let relationship = EntityRelationship {
+                    parent: self.get_selected_parent()?,
+                    child: self.get_selected_child()?,
+                    role: self.get_selected_role()?,
+                };
+                Some(GuiMessage::RelationshipViewUpd(
+                    RelationshipViewMessage::DeleteRelationship(relationship),
+                ))
+            }
+            ViewType::Dashboard => None,
+        }
+    }
+
+    fn column_count(&self) -> usize {
+        match self.selected_view {
+            ViewType::Entity => 2,
+            ViewType::History => 3,
+            ViewType::Relationship => 3,
+            ViewType::Dashboard => 1,
+        }
+    }
+
+    pub(super) fn focused_column(&self) -> usize {
+        self.focused_column.min(self.column_count() - 1)
+    }
+
+    fn move_selection_message(&self, offset: isize) -> Option<GuiMessage> {
+        let column = self.focused_column();
+        let message = match self.selected_view {
+            ViewType::Entity => {
+                let state = &self.entity_view_state;
+                if column == 0 {
+                    let (i, e) = state.label_view_state.neighbour_of_selected(offset)?;
+                    GuiMessage::EntityViewUpd(EntityViewMessage::LabelViewUpdate(
+                        ColViewMes::Selected(i, e),
+                    ))
+                } else {
+                    let (i, e) = state.descriptor_view_state.neighbour_of_selected(offset)?;
+                    GuiMessage::EntityViewUpd(EntityViewMessage::DescriptorViewUpdate(
+                        ColViewMes::Selected(i, e),
+                    ))
+                }
+            }
+            ViewType::History => {
+                let state = &self.history_view_state;
+                let message = match column {
+                    0 => {
+                        let (i, e) = state.year_view_state.neighbour_of_selected(offset)?;
+                        HistoryViewMessage::YearViewUpdate(ColViewMes::Selected(i, e))
+                    }
+                    1 => {
+                        let (i, e) = state.day_view_state.neighbour_of_selected(offset)?;
+                        HistoryViewMessage::DayViewUpdate(ColViewMes::Selected(i, e))
+                    }
+                    _ => {
+                        let (i, e) = state.timestamp_view_state.neighbour_of_selected(offset)?;
+                        HistoryViewMessage::HistoryTimestampViewUpdate(ColViewMes::Selected(i, e))
+                    }
+                };
+                GuiMessage::HistoryViewUpd(message)
+            }
+            ViewType::Relationship => {
+                let state = &self.relationship_view_state;
+                let message = match column {
+                    0 => {
+                        let (i, e) = state.parent_view_state.neighbour_of_selected(offset)?;
+                        RelationshipViewMessage::ParentViewUpdate(ColViewMes::Selected(i, e))
+                    }
+                    1 => {
+                        let (i, e) = state.child_view_state.neighbour_of_selected(offset)?;
+                        RelationshipViewMessage::ChildViewUpdate(ColViewMes::Selected(i, e))
+                    }
+                    _ => {
+                        let (i, e) = state.role_view_state.neighbour_of_selected(offset)?;
+                        RelationshipViewMessage::RoleViewUpdate(ColViewMes::Selected(i, e))
+                    }
+                };
+                GuiMessage::RelationshipViewUpd(message)
+            }
+            ViewType::Dashboard => return None,
+        };
+        Some(message)
+    }
+
+    pub(super) fn show_settings(&mut self) {
+        self.dialog = Some(Box::new(SettingsDialog::new(
+            self.key_bindings.clone(),
+            self.appearance.clone(),
+        )));
+    }
+
+    pub(super) fn change_settings(
+        &mut self,
+        key_bindings: KeyBindings,
+        appearance: Appearance,
+    ) -> Result<(), LoreGuiError> {
+        if !key_bindings.conflicts().is_empty() {
+            return Err(LoreGuiError::InputError(tr("error-shortcut-conflicts")));
+        }
+        if self.preferences_loaded {
+            store_key_bindings(&key_bindings)?;
+            store_appearance(&appearance)?;
+        }
+        self.key_bindings = key_bindings;
+        self.appearance = appearance;
+        self.dialog = None;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use iced::keyboard::key::Named;
+
+    use super::*;
+    use crate::{
+        appearance::{SizeSetting, ThemeChoice},
+        tests::{example_database, example_labels},
+    };
+
+    fn example_gui() -> SqlGui {
+        let mut gui = SqlGui {
+            lore_database: Some(example_database()),
+            key_bindings: KeyBindings::default(),
+            ..Default::default()
+        };
+        gui.refresh_views_now().unwrap();
+        gui
+    }
+
+    fn press(gui: &mut SqlGui, key: Key, modifiers: Modifiers) {
+        gui.handle_message(GuiMessage::KeyPressed(key, modifiers))
+            .unwrap();
+    }
+
+    #[test]
+    fn ctrl_number_switches_view() {
+        let mut gui = example_gui();
+        press(&mut gui, Key::Character("2".into()), Modifiers::COMMAND);
+        assert_eq!(gui.selected_view, ViewType::History);
+        press(&mut gui, Key::Character("3".into()), Modifiers::COMMAND);
+        assert_eq!(gui.selected_view, ViewType::Relationship);
+    }
+
+    #[test]
+    fn arrow_keys_move_through_labels() {
+        let mut gui = example_gui();
+        let labels = example_labels();
+        gui.set_selected_label(Some(labels[0].clone()));
+
+        press(&mut gui, Key::Named(Named::ArrowDown), Modifiers::empty());
+        assert_eq!(gui.get_selected_label(), Some(labels[1].clone()));
+
+        press(&mut gui, Key::Named(Named::ArrowUp), Modifiers::empty());
+        assert_eq!(gui.get_selected_label(), Some(labels[0].clone()));
+    }
+
+    #[test]
+    fn escape_closes_dialog() {
+        let mut gui = example_gui();
+        press(&mut gui, Key::Character("n".into()), Modifiers::COMMAND);
+        assert!(gui.dialog.is_some());
+        press(&mut gui, Key::Named(Named::Escape), Modifiers::empty());
+        assert!(gui.dialog.is_none());
+    }
+
+    #[test]
+    fn ctrl_r_toggles_read_mode() {
+        let mut gui = example_gui();
+        press(&mut gui, Key::Character("r".into()), Modifiers::COMMAND);
+        assert!(gui.read_mode.is_active());
+        press(&mut gui, Key::Character("r".into()), Modifiers::COMMAND);
+        assert!(!gui.read_mode.is_active());
+    }
+
+    #[test]
+    fn new_does_nothing_on_the_dashboard() {
+        let mut gui = example_gui();
+        gui.selected_view = ViewType::Dashboard;
+        press(&mut gui, Key::Character("n".into()), Modifiers::COMMAND);
+        assert!(gui.dialog.is_none());
+    }
+
+    #[test]
+    fn delete_asks_for_confirmation_before_deleting_entity() {
+        let mut gui = example_gui();
+        let labels = example_labels();
+        gui.set_selected_label(Some(labels[0].clone()));
+
+        press(&mut gui, Key::Named(Named::Delete), Modifiers::empty());
+        assert!(gui.dialog.is_some());
+        press(&mut gui, Key::Named(Named::Enter), Modifiers::empty());
+
+        gui.refresh_views_now().unwrap();
+        let remaining = gui.entity_view_state.label_view_state.get_entries();
+        assert!(!remaining.contains(&labels[0].clone().into()));
+    }
+
+    #[test]
+    fn settings_dialog_records_next_key_press() {
+        let mut gui = example_gui();
+        gui.show_settings();
+        gui.handle_message(GuiMessage::DialogUpdate(DialogUpdate::RecordKeyBinding(
+            ShortcutAction::Save,
+        )))
+        .unwrap();
+        press(&mut gui, Key::Named(Named::F5), Modifiers::empty());
+        assert!(gui.dialog.is_some());
+
+        let submitted = gui.dialog.as_ref().unwrap().submit();
+        let key_bindings = match submitted {
+            GuiMessage::SettingsChanged(key_bindings, _) => key_bindings,
+            _ => panic!("Settings dialog should submit key bindings."),
+        };
+        let f5 = KeyBinding::from_key(&Key::Named(Named::F5), Modifiers::empty()).unwrap();
+        assert_eq!(key_bindings.action_for(&f5), Some(ShortcutAction::Save));
+    }
+
+    #[test]
+    fn appearance_is_previewed_until_the_settings_are_closed() {
+        let mut gui = example_gui();
+        gui.show_settings();
+        gui.handle_message(GuiMessage::DialogUpdate(DialogUpdate::Theme(
+            ThemeChoice::Dark,
+        )))
+        .unwrap();
+        gui.handle_message(GuiMessage::DialogUpdate(DialogUpdate::Size(
+            SizeSetting::Spacing,
+            8.0,
+        )))
+        .unwrap();
+        assert_eq!(gui.theme(), iced::Theme::Dark);
+        assert_eq!(gui.sizes().spacing, 8.0);
+
+        gui.handle_message(GuiMessage::DialogClosed).unwrap();
+        assert_eq!(gui.theme(), gui.appearance.theme());
+        assert_eq!(gui.sizes(), gui.appearance.sizes());
+    }
+}
@@ -0,0 +1,132 @@
+use std::path::Path;
+
+use super::{SqlGui, ViewType};
+use crate::{
+    query_cache::CachedReader,
+    status_bar::{DatabaseStatistics, FileInfo, StatusBar},
+};
+
+impl SqlGui {
+    /// Has to be called after the changes of a message were applied, so that the statistics are not read from an outdated cache.
+    pub(super) fn refresh_status_bar(&mut self) {
+        let db = match self.lore_database.as_ref() {
+            Some(db) => db,
+            None => {
+                self.status_bar = StatusBar::default();
+                return;
+            }
+        };
+        let (search_matches, unsaved_changes) = match self.selected_view {
+            ViewType::Entity => (
+                self.entity_view_state.search_matches(),
+                Some(self.entity_view_state.current_description.is_changed()),
+            ),
+            ViewType::History => (
+                self.history_view_state.search_matches(),
+                Some(self.history_view_state.current_content.is_changed()),
+            ),
+            ViewType::Relationship => (self.relationship_view_state.search_matches(), None),
+            ViewType::Dashboard => (None, None),
+        };
+        self.status_bar.search_matches = search_matches;
+        self.status_bar.unsaved_changes = unsaved_changes;
+        let path = db.path_as_string();
+        if !self.status_bar.outdated && self.status_bar.database.as_ref() == Some(&path) {
+            return;
+        }
+        let reader = CachedReader::new(db, &self.query_cache);
+        self.status_bar.statistics = DatabaseStatistics::read(&reader).ok();
+        self.status_bar.file = FileInfo::read(Path::new(&path));
+        self.status_bar.database = Some(path);
+        self.status_bar.outdated = false;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use iced::widget::text_editor::{Action, Edit};
+
+    use super::*;
+    use crate::{
+        app::message_handling::GuiMessage,
+        db_col_view::ColViewMes,
+        entity_view::EntityViewMessage,
+        query_cache::Table,
+        tests::{example_database, example_descriptors, example_labels},
+    };
+
+    #[test]
+    fn status_bar_counts_the_database_contents() {
+        let mut gui = SqlGui {
+            lore_database: Some(example_database()),
+            ..Default::default()
+        };
+        gui.refresh_status_bar();
+
+        let labels = example_labels().len();
+        let statistics = gui.status_bar.statistics.unwrap();
+        assert_eq!(statistics.entities, labels);
+        assert_eq!(statistics.descriptors, labels * example_descriptors().len());
+        assert_eq!(statistics.relationships, labels * (labels - 1));
+        assert!(statistics.history_items > 0);
+        assert!(gui.status_bar.file.unwrap().size > 0);
+        assert_eq!(gui.status_bar.search_matches, None);
+        assert_eq!(gui.status_bar.unsaved_changes, Some(false));
+    }
+
+    #[test]
+    fn status_bar_follows_searches_and_edits() {
+        let mut gui = SqlGui {
+            lore_database: Some(example_database()),
+            ..Default::default()
+        };
+        let search = example_labels()[0].to_str().to_string();
+        gui.handle_message(GuiMessage::EntityViewUpd(
+            EntityViewMessage::LabelViewUpdate(ColViewMes::SearchFieldUpd(search)),
+        ))
+        .unwrap();
+        gui.set_selected_label(Some(example_labels()[0].clone()));
+        gui.set_selected_descriptor(Some(example_descriptors()[0].0.clone()));
+        gui.refresh_views_now().unwrap();
+        gui.entity_view_state
+            .current_description
+            .perform(Action::Edit(Edit::Insert('x')));
+        gui.refresh_status_bar();
+
+        assert_eq!(gui.status_bar.search_matches, Some(1));
+        assert_eq!(gui.status_bar.unsaved_changes, Some(true));
+    }
+
+    #[test]
+    fn statistics_are_only_read_again_after_changes() {
+        let mut gui = SqlGui {
+            lore_database: Some(example_database()),
+            ..Default::default()
+        };
+        gui.refresh_status_bar();
+        let read = gui.status_bar.statistics.unwrap();
+        let cached = DatabaseStatistics {
+            entities: 0,
+            ..read
+        };
+        gui.status_bar.statistics = Some(cached);
+
+        gui.refresh_status_bar();
+        assert_eq!(gui.status_bar.statistics, Some(cached));
+
+        gui.record_changes(&[Table::Entities]);
+        gui.apply_changes();
+        gui.refresh_status_bar();
+        assert_eq!(gui.status_bar.statistics, Some(read));
+    }
+
+    #[test]
+    fn status_bar_is_empty_without_database() {
+        let mut gui = SqlGui {
+            lore_database: None,
+            ..Default::default()
+        };
+        gui.refresh_status_bar();
+        assert_eq!(gui.status_bar, StatusBar::default());
+    }
+}
@@ -0,0 +1,335 @@
+use std::{mem, path::Path, sync::PoisonError};
+
+use lorecore::sql::lore_database::LoreDatabase;
+
+use super::{
+    undo_redo::{Selection, UndoStack},
+    writing::{DatabaseWrite, WrittenSnapshots},
+    SqlGui, ViewType,
+};
+use crate::{
+    dashboard::DashboardState,
+    drafts::Draft,
+    entity_view::EntityViewState,
+    file_watcher::FileStamp,
+    history_view::HistoryViewState,
+    localisation::tr,
+    query_cache::{SharedQueryCache, Table},
+    relationship_view::RelationshipViewState,
+    search_index::SearchIndexState,
+};
+
+/// Everything that belongs to one open database.
+///
+/// The state of the active tab lives directly in the fields of `SqlGui`, its slot in `SqlGui::tabs` only holds an empty placeholder.
+#[derive(Default)]
+pub(super) struct DatabaseTab {
+    selected_view: ViewType,
+    entity_view_state: EntityViewState,
+    history_view_state: HistoryViewState,
+    relationship_view_state: RelationshipViewState,
+    dashboard_state: DashboardState,
+    lore_database: Option<LoreDatabase>,
+    undo_stack: UndoStack,
+    query_cache: SharedQueryCache,
+    search_index: SearchIndexState,
+    database_stamp: Option<FileStamp>,
+}
+
+impl DatabaseTab {
+    pub(super) fn lore_database(&self) -> Option<&LoreDatabase> {
+        self.lore_database.as_ref()
+    }
+
+    /// Used when a table of an inactive tab's database has been written to.
+    pub(super) fn invalidate(&mut self, table: Table) {
+        self.query_cache
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .invalidate(table);
+        self.search_index.record_everything();
+        match table {
+            Table::Entities => self.entity_view_state.invalidate(),
+            Table::History => self.history_view_state.invalidate(),
+            Table::Relationships => self.relationship_view_state.invalidate(),
+        }
+        self.dashboard_state.invalidate();
+    }
+
+    /// Used when the file of an inactive tab's database has changed.
+    pub(super) fn database_file_changed(&mut self, stamp: FileStamp) {
+        if self.database_stamp == Some(stamp) {
+            return;
+        }
+        self.database_stamp = Some(stamp);
+        for table in Table::ALL {
+            self.invalidate(table);
+        }
+    }
+
+    /// Used when a write to an inactive tab's database has finished.
+    pub(super) fn write_finished(
+        &mut self,
+        write: &DatabaseWrite,
+        succeeded: bool,
+        snapshots: WrittenSnapshots,
+        selection_before: Selection,
+    ) {
+        for table in write.changed_tables() {
+            self.invalidate(*table);
+        }
+        match write {
+            DatabaseWrite::Undo(Some(step)) => {
+                self.undo_stack.undo_finished(step.clone(), succeeded)
+            }
+            DatabaseWrite::Redo(Some(step)) => {
+                self.undo_stack.redo_finished(step.clone(), succeeded)
+            }
+            _ if succeeded => {
+                write.mark_saved(&mut self.entity_view_state, &mut self.history_view_state)
+            }
+            _ => {}
+        }
+        if let Some((before, after)) = snapshots {
+            self.undo_stack
+                .record(before, after, selection_before.clone(), selection_before);
+        }
+    }
+
+    pub(super) fn search_index_updated(&mut self, succeeded: bool) {
+        self.search_index.update_finished(succeeded);
+    }
+
+    pub(super) fn has_unsaved_changes(&self) -> bool {
+        self.entity_view_state.current_description.is_changed()
+            || self.history_view_state.current_content.is_changed()
+    }
+
+    pub(super) fn drafts(&self) -> Vec<Draft> {
+        [
+            self.entity_view_state.draft(),
+            self.history_view_state.draft(),
+        ]
+        .into_iter()
+        .flatten()
+        .collect()
+    }
+
+    pub(super) fn discard_unsaved_changes(&mut self) {
+        self.entity_view_state.current_description.reset();
+        self.history_view_state.current_content.reset();
+    }
+}
+
+pub(super) fn tab_title(db: Option<&LoreDatabase>) -> String {
+    match db {
+        Some(db) => {
+            let path = db.path_as_string();
+            Path::new(&path)
+                .file_name()
+                .map(|name| name.to_string_lossy().to_string())
+                .unwrap_or(path)
+        }
+        None => tr("tab-no-database"),
+    }
+}
+
+impl SqlGui {
+    fn take_active_tab(&mut self) -> DatabaseTab {
+        self.entity_view_state.abandon_loading();
+        self.history_view_state.abandon_loading();
+        self.relationship_view_state.abandon_loading();
+        self.dashboard_state.abandon_loading();
+        DatabaseTab {
+            selected_view: mem::take(&mut self.selected_view),
+            entity_view_state: mem::take(&mut self.entity_view_state),
+            history_view_state: mem::take(&mut self.history_view_state),
+            relationship_view_state: mem::take(&mut self.relationship_view_state),
+            dashboard_state: mem::take(&mut self.dashboard_state),
+            lore_database: self.lore_database.take(),
+            undo_stack: mem::take(&mut self.undo_stack),
+            query_cache: mem::take(&mut self.query_cache),
+            search_index: mem::take(&mut self.search_index),
+            database_stamp: self.database_stamp.take(),
+        }
+    }
+
+    fn put_active_tab(&mut self, tab: DatabaseTab) {
+        self.selected_view = tab.selected_view;
+        self.entity_view_state = tab.entity_view_state;
+        self.history_view_state = tab.history_view_state;
+        self.relationship_view_state = tab.relationship_view_state;
+        self.dashboard_state = tab.dashboard_state;
+        self.lore_database = tab.lore_database;
+        self.undo_stack = tab.undo_stack;
+        self.query_cache = tab.query_cache;
+        self.search_index = tab.search_index;
+        self.database_stamp = tab.database_stamp;
+        self.global_search.invalidate();
+    }
+
+    pub(super) fn tab_titles(&self) -> Vec<String> {
+        self.tabs
+            .iter()
+            .enumerate()
+            .map(|(index, tab)| {
+                if index == self.active_tab {
+                    tab_title(self.lore_database.as_ref())
+                } else {
+                    tab_title(tab.lore_database())
+                }
+            })
+            .collect()
+    }
+
+    pub(super) fn select_tab(&mut self, index: usize) {
+        if index == self.active_tab || index >= self.tabs.len() {
+            return;
+        }
+        self.tabs[self.active_tab] = self.take_active_tab();
+        let tab = mem::take(&mut self.tabs[index]);
+        self.put_active_tab(tab);
+        self.active_tab = index;
+    }
+
+    pub(super) fn new_tab(&mut self) {
+        self.tabs[self.active_tab] = self.take_active_tab();
+        self.tabs.push(DatabaseTab::default());
+        self.active_tab = self.tabs.len() - 1;
+    }
+
+    pub(super) fn close_tab(&mut self, index: usize) {
+        if index >= self.tabs.len() {
+            return;
+        }
+        if self.tabs.len() == 1 {
+            self.take_active_tab();
+            return;
+        }
+        if index == self.active_tab {
+            self.take_active_tab();
+            self.tabs.remove(index);
+            self.active_tab = index.min(self.tabs.len() - 1);
+            let tab = mem::take(&mut self.tabs[self.active_tab]);
+            self.put_active_tab(tab);
+        } else {
+            self.tabs.remove(index);
+            if index < self.active_tab {
+                self.active_tab -= 1;
+            }
+        }
+    }
+
+    pub(super) fn find_tab(&self, path: &str) -> Option<usize> {
+        if let Some(db) = self.lore_database.as_ref() {
+            if db.path_as_string() == path {
+                return Some(self.active_tab);
+            }
+        }
+        self.tabs.iter().position(|tab| {
+            tab.lore_database()
+                .is_some_and(|db| db.path_as_string() == path)
+        })
+    }
+
+    pub(super) fn database_of_tab(&self, index: usize) -> Option<&LoreDatabase> {
+        if index == self.active_tab {
+            self.lore_database.as_ref()
+        } else {
+            self.tabs.get(index).and_then(|tab| tab.lore_database())
+        }
+    }
+
+    pub(super) fn open_database_paths(&self) -> Vec<String> {
+        (0..self.tabs.len())
+            .filter_map(|index| self.database_of_tab(index))
+            .map(|db| db.path_as_string())
+            .collect()
+    }
+
+    /// Paths of all databases that are open in tabs other than the active one.
+    pub(super) fn other_database_paths(&self) -> Vec<String> {
+        (0..self.tabs.len())
+            .filter(|index| *index != self.active_tab)
+            .filter_map(|index| self.database_of_tab(index))
+            .map(|db| db.path_as_string())
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::{example_database, example_labels};
+
+    fn gui_with_two_tabs() -> SqlGui {
+        let mut gui = SqlGui {
+            lore_database: Some(example_database()),
+            ..Default::default()
+        };
+        gui.new_tab();
+        gui.lore_database = Some(example_database());
+        gui
+    }
+
+    #[test]
+    fn switching_tabs_keeps_selections_per_tab() {
+        let mut gui = gui_with_two_tabs();
+        let labels = example_labels();
+        gui.set_selected_label(Some(labels[1].clone()));
+
+        gui.select_tab(0);
+        assert_eq!(gui.get_selected_label(), None);
+        gui.set_selected_label(Some(labels[0].clone()));
+
+        gui.select_tab(1);
+        assert_eq!(gui.get_selected_label(), Some(labels[1].clone()));
+        gui.select_tab(0);
+        assert_eq!(gui.get_selected_label(), Some(labels[0].clone()));
+    }
+
+    #[test]
+    fn switching_tabs_keeps_database_stamps_per_tab() {
+        let mut gui = gui_with_two_tabs();
+        gui.remember_database_stamp();
+        let second_stamp = gui.database_stamp;
+
+        gui.select_tab(0);
+        assert_eq!(gui.database_stamp, None);
+        gui.remember_database_stamp();
+
+        gui.select_tab(1);
+        assert_eq!(gui.database_stamp, second_stamp);
+    }
+
+    #[test]
+    fn closing_active_tab_activates_neighbour() {
+        let mut gui = gui_with_two_tabs();
+        let first_path = gui.database_of_tab(0).unwrap().path_as_string();
+
+        gui.close_tab(1);
+
+        assert_eq!(gui.tabs.len(), 1);
+        assert_eq!(gui.active_tab, 0);
+        let active_path = gui.lore_database.as_ref().unwrap().path_as_string();
+        assert_eq!(active_path, first_path);
+    }
+
+    #[test]
+    fn closing_last_tab_leaves_empty_tab() {
+        let mut gui = SqlGui {
+            lore_database: Some(example_database()),
+            ..Default::default()
+        };
+        gui.close_tab(0);
+        assert_eq!(gui.tabs.len(), 1);
+        assert!(gui.lore_database.is_none());
+    }
+
+    #[test]
+    fn other_database_paths_exclude_active_tab() {
+        let gui = gui_with_two_tabs();
+        let first_path = gui.database_of_tab(0).unwrap().path_as_string();
+        assert_eq!(gui.other_database_paths(), vec![first_path]);
+    }
+}
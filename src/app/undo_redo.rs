@@ -0,0 +1,485 @@
+use lorecore::{
+    sql::{
+        lore_database::LoreDatabase,
+        search_params::{
+            EntityColumnSearchParams, HistoryItemSearchParams, RelationshipSearchParams,
+            SqlSearchText,
+        },
+    },
+    types::*,
+};
+
+use crate::errors::LoreGuiError;
+
+use super::{writing::DatabaseWrite, SqlGui, ViewType};
+
+#[derive(Debug, Clone, Default)]
+pub(super) struct UndoStack {
+    undo_steps: Vec<UndoStep>,
+    redo_steps: Vec<UndoStep>,
+}
+
+#[derive(Debug, Clone)]
+pub(super) struct UndoStep {
+    before: DatabaseSnapshot,
+    after: DatabaseSnapshot,
+    selection_before: Selection,
+    selection_after: Selection,
+}
+
+/// The part of the database that a write may touch.
+#[derive(Debug, Clone, Default)]
+pub(super) struct UndoScope {
+    labels: Vec<Label>,
+    timestamps: Vec<Timestamp>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub(super) struct DatabaseSnapshot {
+    entity_columns: Vec<EntityColumn>,
+    history_items: Vec<HistoryItem>,
+    relationships: Vec<EntityRelationship>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub(super) struct Selection {
+    view: ViewType,
+    label: Option<Label>,
+    descriptor: Option<Descriptor>,
+    year: Option<Year>,
+    day: Option<Day>,
+    timestamp: Option<Timestamp>,
+    parent: Option<Parent>,
+    child: Option<Child>,
+    role: Option<Role>,
+}
+
+impl UndoStack {
+    pub(super) fn can_undo(&self) -> bool {
+        !self.undo_steps.is_empty()
+    }
+
+    pub(super) fn can_redo(&self) -> bool {
+        !self.redo_steps.is_empty()
+    }
+
+    /// Records a write, unless it did not change anything.
+    pub(super) fn record(
+        &mut self,
+        before: DatabaseSnapshot,
+        after: DatabaseSnapshot,
+        selection_before: Selection,
+        selection_after: Selection,
+    ) {
+        if before == after {
+            return;
+        }
+        self.undo_steps.push(UndoStep {
+            before,
+            after,
+            selection_before,
+            selection_after,
+        });
+        self.redo_steps.clear();
+    }
+
+    pub(super) fn take_undo_step(&mut self) -> Option<UndoStep> {
+        self.undo_steps.pop()
+    }
+
+    pub(super) fn take_redo_step(&mut self) -> Option<UndoStep> {
+        self.redo_steps.pop()
+    }
+
+    /// Puts a step that was taken for undoing back, depending on whether undoing it succeeded.
+    pub(super) fn undo_finished(&mut self, step: UndoStep, succeeded: bool) {
+        if succeeded {
+            self.redo_steps.push(step);
+        } else {
+            self.undo_steps.push(step);
+        }
+    }
+
+    /// Puts a step that was taken for redoing back, depending on whether redoing it succeeded.
+    pub(super) fn redo_finished(&mut self, step: UndoStep, succeeded: bool) {
+        if succeeded {
+            self.undo_steps.push(step);
+        } else {
+            self.redo_steps.push(step);
+        }
+    }
+}
+
+impl UndoStep {
+    pub(super) fn undo(&self, db: &LoreDatabase) -> Result<(), LoreGuiError> {
+        self.after.transition_to(&self.before, db)
+    }
+
+    pub(super) fn redo(&self, db: &LoreDatabase) -> Result<(), LoreGuiError> {
+        self.before.transition_to(&self.after, db)
+    }
+}
+
+impl UndoScope {
+    pub(super) fn labels(&self) -> &[Label] {
+        &self.labels
+    }
+
+    pub(super) fn timestamps(&self) -> &[Timestamp] {
+        &self.timestamps
+    }
+
+    fn with_labels(labels: Vec<Label>) -> Self {
+        let mut scope = UndoScope::default();
+        for label in labels {
+            if !scope.labels.contains(&label) {
+                scope.labels.push(label);
+            }
+        }
+        scope
+    }
+
+    fn with_timestamp(timestamp: Timestamp) -> Self {
+        UndoScope {
+            labels: vec![],
+            timestamps: vec![timestamp],
+        }
+    }
+}
+
+impl DatabaseSnapshot {
+    pub(super) fn read(db: &LoreDatabase, scope: &UndoScope) -> Result<Self, LoreGuiError> {
+        let mut snapshot = DatabaseSnapshot::default();
+        for label in scope.labels.iter() {
+            let label = label.to_str();
+            let search_params =
+                EntityColumnSearchParams::new(Some(SqlSearchText::exact(label)), None);
+            snapshot
+                .entity_columns
+                .extend(db.read_entity_columns(search_params)?);
+
+            let as_parent = RelationshipSearchParams::new(Some(SqlSearchText::exact(label)), None);
+            let as_child = RelationshipSearchParams::new(None, Some(SqlSearchText::exact(label)));
+            let relationships = db
+                .read_relationships(as_parent)?
+                .into_iter()
+                .chain(db.read_relationships(as_child)?);
+            for relationship in relationships {
+                if !snapshot.relationships.contains(&relationship) {
+                    snapshot.relationships.push(relationship);
+                }
+            }
+        }
+        for timestamp in scope.timestamps.iter() {
+            let search_params = HistoryItemSearchParams::new(None, None, Some(*timestamp), None);
+            snapshot
+                .history_items
+                .extend(db.read_history_items(search_params)?);
+        }
+        Ok(snapshot)
+    }
+
+    /// Changes the database such that the part described by `self` becomes `target`.
+    pub(super) fn transition_to(
+        &self,
+        target: &Self,
+        db: &LoreDatabase,
+    ) -> Result<(), LoreGuiError> {
+        for col in self.entity_columns.iter() {
+            if !target.entity_columns.contains(col) {
+                db.delete_entity_column((col.label.clone(), col.descriptor.clone()))?;
+            }
+        }
+        for item in self.history_items.iter() {
+            if !target.history_items.contains(item) {
+                db.delete_history_item(item.timestamp)?;
+            }
+        }
+        for relationship in self.relationships.iter() {
+            if !target.relationships.contains(relationship) {
+                db.delete_relationship(relationship.clone())?;
+            }
+        }
+
+        let new_columns = only_in_first(&target.entity_columns, &self.entity_columns);
+        if !new_columns.is_empty() {
+            db.write_entity_columns(new_columns)?;
+        }
+        let new_items = only_in_first(&target.history_items, &self.history_items);
+        if !new_items.is_empty() {
+            db.write_history_items(new_items)?;
+        }
+        let new_relationships = only_in_first(&target.relationships, &self.relationships);
+        if !new_relationships.is_empty() {
+            db.write_relationships(new_relationships)?;
+        }
+        Ok(())
+    }
+}
+
+fn only_in_first<T: Clone + PartialEq>(first: &[T], second: &[T]) -> Vec<T> {
+    first
+        .iter()
+        .filter(|t| !second.contains(t))
+        .cloned()
+        .collect()
+}
+
+impl DatabaseWrite {
+    /// The part of the database that is read before and after the write, so that it can be undone.
+    pub(super) fn undo_scope(&self) -> Option<UndoScope> {
+        let scope = match self {
+            DatabaseWrite::NewEntity(data) => UndoScope::with_labels(vec![data.label().clone()]),
+            DatabaseWrite::RelabelEntity(data) => {
+                UndoScope::with_labels(vec![data.old_label().clone(), data.new_label().clone()])
+            }
+            DatabaseWrite::DeleteEntity(label) => UndoScope::with_labels(vec![label.clone()]),
+            DatabaseWrite::DuplicateEntity(data) => {
+                UndoScope::with_labels(vec![data.new_label().clone()])
+            }
+            DatabaseWrite::MergeEntities(data) => {
+                UndoScope::with_labels(vec![data.source().clone(), data.target().clone()])
+            }
+            DatabaseWrite::ChangeCategory(data) => {
+                UndoScope::with_labels(vec![data.label().clone()])
+            }
+            DatabaseWrite::NewDescriptor(data) => {
+                UndoScope::with_labels(vec![data.label().clone()])
+            }
+            DatabaseWrite::RenameDescriptor(data) => {
+                UndoScope::with_labels(vec![data.label().clone()])
+            }
+            DatabaseWrite::RenameDescriptorEverywhere(data) => {
+                UndoScope::with_labels(data.labels().to_vec())
+            }
+            DatabaseWrite::DeleteDescriptor(label, _)
+            | DatabaseWrite::ChangeDescription(label, _, _) => {
+                UndoScope::with_labels(vec![label.clone()])
+            }
+            DatabaseWrite::NewHistoryItem(data) => UndoScope::with_timestamp(*data.timestamp()),
+            DatabaseWrite::RedateHistoryItem(data) => UndoScope::with_timestamp(data.timestamp()),
+            DatabaseWrite::DeleteHistoryItem(timestamp)
+            | DatabaseWrite::ChangeContent(timestamp, _) => UndoScope::with_timestamp(*timestamp),
+            DatabaseWrite::NewRelationship(data) => UndoScope::with_labels(vec![
+                data.parent().to_str().into(),
+                data.child().to_str().into(),
+            ]),
+            DatabaseWrite::ChangeRole(data) => UndoScope::with_labels(vec![
+                data.parent().to_str().into(),
+                data.child().to_str().into(),
+            ]),
+            DatabaseWrite::DeleteRelationship(rel) => {
+                UndoScope::with_labels(vec![rel.parent.to_str().into(), rel.child.to_str().into()])
+            }
+            DatabaseWrite::CopyEntity(_) | DatabaseWrite::Undo(_) | DatabaseWrite::Redo(_) => {
+                return None
+            }
+        };
+        Some(scope)
+    }
+}
+
+impl SqlGui {
+    pub(super) fn undo_finished(&mut self, step: UndoStep, succeeded: bool) {
+        if succeeded {
+            self.restore_selection(step.selection_before.clone());
+        }
+        self.undo_stack.undo_finished(step, succeeded);
+    }
+
+    pub(super) fn redo_finished(&mut self, step: UndoStep, succeeded: bool) {
+        if succeeded {
+            self.restore_selection(step.selection_after.clone());
+        }
+        self.undo_stack.redo_finished(step, succeeded);
+    }
+
+    pub(super) fn current_selection(&self) -> Selection {
+        Selection {
+            view: self.selected_view.clone(),
+            label: self.get_selected_label(),
+            descriptor: self.get_selected_descriptor(),
+            year: self.get_selected_year(),
+            day: self.get_selected_day(),
+            timestamp: self.get_selected_timestamp(),
+            parent: self.get_selected_parent(),
+            child: self.get_selected_child(),
+            role: self.get_selected_role(),
+        }
+    }
+
+    fn restore_selection(&mut self, selection: Selection) {
+        self.selected_view = selection.view;
+        self.set_selected_label(selection.label);
+        self.set_selected_descriptor(selection.descriptor);
+        self.set_selected_year(selection.year);
+        self.set_selected_day(selection.day);
+        self.set_selected_timestamp(selection.timestamp);
+        self.set_selected_parent(selection.parent);
+        self.set_selected_child(selection.child);
+        self.set_selected_role(selection.role);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::app::message_handling::GuiMessage;
+    use crate::{
+        dialog::{
+            change_role::ChangeRoleData, new_entity::tests::example_new_entity_data,
+            redate_history::RedateHistoryData, relabel_entity::RelabelEntityData,
+        },
+        tests::{example_database, example_descriptors, example_labels, example_role},
+    };
+
+    fn example_gui() -> SqlGui {
+        SqlGui {
+            lore_database: Some(example_database()),
+            ..Default::default()
+        }
+    }
+
+    fn read_entity(gui: &SqlGui, label: &Label) -> Vec<EntityColumn> {
+        let db = gui.lore_database.as_ref().unwrap();
+        let search_params =
+            EntityColumnSearchParams::new(Some(SqlSearchText::exact(label.to_str())), None);
+        db.read_entity_columns(search_params).unwrap()
+    }
+
+    #[test]
+    fn undoing_entity_deletion_restores_all_descriptors_and_selection() {
+        let mut gui = example_gui();
+        let label = example_labels()[0].clone();
+        let descriptor = example_descriptors()[1].0.clone();
+        let columns_before = read_entity(&gui, &label);
+        gui.set_selected_label(Some(label.clone()));
+        gui.set_selected_descriptor(Some(descriptor.clone()));
+
+        gui.handle_message(GuiMessage::DeleteEntity(label.clone()))
+            .unwrap();
+        assert!(read_entity(&gui, &label).is_empty());
+        assert_eq!(gui.get_selected_label(), None);
+
+        gui.handle_message(GuiMessage::Undo).unwrap();
+        gui.refresh_views_now().unwrap();
+        assert_eq!(read_entity(&gui, &label), columns_before);
+        assert_eq!(gui.get_selected_label(), Some(label));
+        assert_eq!(gui.get_selected_descriptor(), Some(descriptor));
+        assert_eq!(
+            gui.get_description_text(),
+            example_descriptors()[1].1.to_str()
+        );
+    }
+
+    #[test]
+    fn redoing_entity_deletion_deletes_it_again() {
+        let mut gui = example_gui();
+        let label = example_labels()[0].clone();
+
+        gui.handle_message(GuiMessage::DeleteEntity(label.clone()))
+            .unwrap();
+        gui.handle_message(GuiMessage::Undo).unwrap();
+        assert!(!read_entity(&gui, &label).is_empty());
+
+        gui.handle_message(GuiMessage::Redo).unwrap();
+        assert!(read_entity(&gui, &label).is_empty());
+        assert_eq!(gui.get_selected_label(), None);
+    }
+
+    #[test]
+    fn undoing_relabel_restores_old_label() {
+        let mut gui = example_gui();
+        let old_label = example_labels()[0].clone();
+        let new_label = Label::from("relabelled_entity");
+        let columns_before = read_entity(&gui, &old_label);
+        let mut data = RelabelEntityData::new(old_label.clone());
+        data.set_new_label(new_label.clone());
+
+        gui.handle_message(GuiMessage::RelabelEntity(data)).unwrap();
+        assert!(read_entity(&gui, &old_label).is_empty());
+
+        gui.handle_message(GuiMessage::Undo).unwrap();
+        assert_eq!(read_entity(&gui, &old_label), columns_before);
+        assert!(read_entity(&gui, &new_label).is_empty());
+    }
+
+    #[test]
+    fn undoing_redate_restores_old_date() {
+        let mut gui = example_gui();
+        let db = gui.lore_database.as_ref().unwrap();
+        let item = db
+            .read_history_items(HistoryItemSearchParams::new(None, None, None, None))
+            .unwrap()[0]
+            .clone();
+        let mut data = RedateHistoryData::new(item.timestamp, item.year, item.day);
+        data.set_new_year(item.year + 1);
+
+        gui.handle_message(GuiMessage::RedateHistoryItem(data))
+            .unwrap();
+        assert_eq!(gui.get_selected_year(), Some(item.year + 1));
+
+        gui.handle_message(GuiMessage::Undo).unwrap();
+        let db = gui.lore_database.as_ref().unwrap();
+        let search_params = HistoryItemSearchParams::new(None, None, Some(item.timestamp), None);
+        let items = db.read_history_items(search_params).unwrap();
+        assert_eq!(items, vec![item]);
+        assert_eq!(gui.get_selected_year(), None);
+    }
+
+    #[test]
+    fn undoing_role_change_restores_old_role() {
+        let mut gui = example_gui();
+        let labels = example_labels();
+        let parent: Parent = labels[0].to_str().into();
+        let child: Child = labels[1].to_str().into();
+        let old_relationship = EntityRelationship {
+            role: example_role(&parent, &child),
+            parent,
+            child,
+        };
+        let mut data = ChangeRoleData::new(old_relationship.clone());
+        data.set_new_role("New Role".into());
+
+        gui.handle_message(GuiMessage::ChangeRole(data)).unwrap();
+        gui.handle_message(GuiMessage::Undo).unwrap();
+
+        let db = gui.lore_database.as_ref().unwrap();
+        let search_params = RelationshipSearchParams::new(
+            Some(SqlSearchText::exact(old_relationship.parent.to_str())),
+            Some(SqlSearchText::exact(old_relationship.child.to_str())),
+        );
+        let relationships = db.read_relationships(search_params).unwrap();
+        assert_eq!(relationships, vec![old_relationship]);
+    }
+
+    #[test]
+    fn new_mutation_clears_redo_steps() {
+        let mut gui = example_gui();
+        let label = example_labels()[0].clone();
+        gui.handle_message(GuiMessage::DeleteEntity(label)).unwrap();
+        gui.handle_message(GuiMessage::Undo).unwrap();
+        assert!(gui.undo_stack.can_redo());
+
+        let data = example_new_entity_data();
+        gui.handle_message(GuiMessage::NewEntity(data)).unwrap();
+        assert!(!gui.undo_stack.can_redo());
+        assert!(gui.undo_stack.can_undo());
+    }
+
+    #[test]
+    fn failed_mutation_is_not_recorded() {
+        let mut gui = example_gui();
+        let data = example_new_entity_data();
+        gui.handle_message(GuiMessage::NewEntity(data.clone()))
+            .unwrap();
+        gui.handle_message(GuiMessage::Undo).unwrap();
+        gui.handle_message(GuiMessage::NewEntity(data.clone()))
+            .unwrap();
+        assert!(gui.handle_message(GuiMessage::NewEntity(data)).is_err());
+
+        gui.handle_message(GuiMessage::Undo).unwrap();
+        assert!(!gui.undo_stack.can_undo());
+    }
+}
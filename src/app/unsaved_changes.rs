@@ -0,0 +1,384 @@
+use super::{message_handling::GuiMessage, tabs::tab_title, tabs::DatabaseTab, SqlGui};
+use crate::{
+    db_col_view::ColViewMes,
+    dialog::unsaved_changes::UnsavedChangesDialog,
+    drafts::DraftKey,
+    entity_view::EntityViewMessage,
+    errors::LoreGuiError,
+    global_search::SearchTarget,
+    history_view::HistoryViewMessage,
+    localisation::{tr, tr_args},
+};
+
+/// The editors whose unsaved changes would be thrown away by handling a message.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum UnsavedScope {
+    Description,
+    Content,
+    Tab(usize),
+    AllTabs,
+}
+
+impl SqlGui {
+    /// Shows a dialog instead of handling `message` if that would throw away unsaved changes.
+    ///
+    /// Returns whether the dialog was shown.
+    pub(super) fn ask_about_unsaved_changes(&mut self, message: &GuiMessage) -> bool {
+        let scope = match self.unsaved_scope(message) {
+            Some(scope) => scope,
+            None => return false,
+        };
+        let text = match scope {
+            UnsavedScope::Description => tr("unsaved-changes-description"),
+            UnsavedScope::Content => tr("unsaved-changes-history-item"),
+            UnsavedScope::Tab(index) => tr_args(
+                "unsaved-changes-database",
+                &[("database", &tab_title(self.database_of_tab(index)))],
+            ),
+            UnsavedScope::AllTabs => tr("unsaved-changes-all-databases"),
+        };
+        self.dialog = Some(Box::new(UnsavedChangesDialog::new(text, message.clone())));
+        true
+    }
+
+    fn unsaved_scope(&self, message: &GuiMessage) -> Option<UnsavedScope> {
+        let scope = match message {
+            GuiMessage::EntityViewUpd(
+                EntityViewMessage::LabelViewUpdate(ColViewMes::Selected(_, _))
+                | EntityViewMessage::DescriptorViewUpdate(ColViewMes::Selected(_, _)),
+            ) => UnsavedScope::Description,
+            GuiMessage::HistoryViewUpd(
+                HistoryViewMessage::YearViewUpdate(ColViewMes::Selected(_, _))
+                | HistoryViewMessage::DayViewUpdate(ColViewMes::Selected(_, _))
+                | HistoryViewMessage::HistoryTimestampViewUpdate(ColViewMes::Selected(_, _)),
+            ) => UnsavedScope::Content,
+            GuiMessage::RestoreDraft(draft) => match draft.key {
+                DraftKey::Description { .. } => UnsavedScope::Description,
+                DraftKey::HistoryContent { .. } => UnsavedScope::Content,
+            },
+            GuiMessage::JumpTo(SearchTarget::Entity(_, _)) => UnsavedScope::Description,
+            GuiMessage::JumpTo(SearchTarget::HistoryItem(_, _, _) | SearchTarget::Year(_)) => {
+                UnsavedScope::Content
+            }
+            GuiMessage::NewEntity(_)
+            | GuiMessage::RelabelEntity(_)
+            | GuiMessage::DeleteEntity(_)
+            | GuiMessage::DuplicateEntity(_)
+            | GuiMessage::MergeEntities(_)
+            | GuiMessage::NewDescriptor(_)
+            | GuiMessage::RenameDescriptor(_)
+            | GuiMessage::RenameDescriptorEverywhere(_)
+            | GuiMessage::DeleteDescriptor(_, _) => UnsavedScope::Description,
+            GuiMessage::ChangeCategory(data)
+                if self.get_selected_label().as_ref() != Some(data.label()) =>
+            {
+                UnsavedScope::Description
+            }
+            GuiMessage::NewHistoryItem(_)
+            | GuiMessage::RedateHistoryItem(_)
+            | GuiMessage::DeleteHistoryItem(_) => UnsavedScope::Content,
+            GuiMessage::Undo | GuiMessage::Redo => UnsavedScope::Tab(self.active_tab),
+            // Opening a database again resets the selections of its tab.
+            GuiMessage::OpenDatabaseAt(path) => {
+                UnsavedScope::Tab(self.find_tab(&path.to_string_lossy())?)
+            }
+            GuiMessage::TabClosed(index) => UnsavedScope::Tab(*index),
+            GuiMessage::WindowCloseRequested => UnsavedScope::AllTabs,
+            _ => return None,
+        };
+        self.has_unsaved_changes(scope).then_some(scope)
+    }
+
+    fn has_unsaved_changes(&self, scope: UnsavedScope) -> bool {
+        match scope {
+            UnsavedScope::Description => self.entity_view_state.current_description.is_changed(),
+            UnsavedScope::Content => self.history_view_state.current_content.is_changed(),
+            UnsavedScope::Tab(index) if index == self.active_tab => {
+                self.has_unsaved_changes(UnsavedScope::Description)
+                    || self.has_unsaved_changes(UnsavedScope::Content)
+            }
+            UnsavedScope::Tab(index) => self
+                .tabs
+                .get(index)
+                .is_some_and(DatabaseTab::has_unsaved_changes),
+            UnsavedScope::AllTabs => {
+                (0..self.tabs.len()).any(|index| self.has_unsaved_changes(UnsavedScope::Tab(index)))
+            }
+        }
+    }
+
+    pub(super) fn save_and_continue(&mut self, message: GuiMessage) -> Result<(), LoreGuiError> {
+        self.dialog = None;
+        let scope = self.unsaved_scope(&message);
+        // Undoing after saving would undo the save instead, and redoing would find nothing left to redo.
+        let continues_first = matches!(message, GuiMessage::Undo | GuiMessage::Redo);
+        if continues_first {
+            self.handle_confirmed_message(message.clone())?;
+        }
+        match scope {
+            Some(UnsavedScope::Description) => self.save_description()?,
+            Some(UnsavedScope::Content) => self.save_content()?,
+            Some(UnsavedScope::Tab(index)) => self.save_tab(index)?,
+            Some(UnsavedScope::AllTabs) => {
+                for index in 0..self.tabs.len() {
+                    if self.has_unsaved_changes(UnsavedScope::Tab(index)) {
+                        self.save_tab(index)?;
+                    }
+                }
+            }
+            None => (),
+        }
+        if continues_first {
+            return Ok(());
+        }
+        self.handle_confirmed_message(message)
+    }
+
+    pub(super) fn discard_and_continue(&mut self, message: GuiMessage) -> Result<(), LoreGuiError> {
+        self.dialog = None;
+        match self.unsaved_scope(&message) {
+            Some(UnsavedScope::Description) => self.entity_view_state.current_description.reset(),
+            Some(UnsavedScope::Content) => self.history_view_state.current_content.reset(),
+            Some(UnsavedScope::Tab(index)) => self.discard_tab(index),
+            Some(UnsavedScope::AllTabs) => {
+                for index in 0..self.tabs.len() {
+                    self.discard_tab(index);
+                }
+            }
+            None => (),
+        }
+        self.handle_confirmed_message(message)
+    }
+
+    fn save_description(&mut self) -> Result<(), LoreGuiError> {
+        self.handle_message(GuiMessage::EntityViewUpd(
+            EntityViewMessage::DescriptionSave,
+        ))
+    }
+
+    fn save_content(&mut self) -> Result<(), LoreGuiError> {
+        self.handle_message(GuiMessage::HistoryViewUpd(HistoryViewMessage::ContentSave))
+    }
+
+    /// Saves through the usual messages, so that the changes can be undone like any other.
+    fn save_tab(&mut self, index: usize) -> Result<(), LoreGuiError> {
+        let active_tab = self.active_tab;
+        self.select_tab(index);
+        if self.has_unsaved_changes(UnsavedScope::Description) {
+            self.save_description()?;
+        }
+        if self.has_unsaved_changes(UnsavedScope::Content) {
+            self.save_content()?;
+        }
+        if index != active_tab {
+            self.apply_changes();
+            self.select_tab(active_tab);
+        }
+        Ok(())
+    }
+
+    fn discard_tab(&mut self, index: usize) {
+        if index == self.active_tab {
+            self.entity_view_state.current_description.reset();
+            self.history_view_state.current_content.reset();
+        } else if let Some(tab) = self.tabs.get_mut(index) {
+            tab.discard_unsaved_changes();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use iced::widget::text_editor::{Action, Edit};
+    use lorecore::{
+        sql::{lore_database::LoreDatabase, search_params::RelationshipSearchParams},
+        types::*,
+    };
+
+    use super::*;
+    use crate::{
+        db_col_view::entry::DbColViewEntry,
+        dialog::{
+            new_descriptor::tests::example_new_descriptor_data,
+            new_relationship::tests::example_new_relationship_data,
+        },
+        tests::{example_database, example_descriptors, example_labels},
+    };
+
+    fn gui_editing_description() -> SqlGui {
+        let mut gui = SqlGui {
+            lore_database: Some(example_database()),
+            ..Default::default()
+        };
+        gui.set_selected_label(Some(example_labels()[0].clone()));
+        gui.set_selected_descriptor(Some(example_descriptors()[0].0.clone()));
+        gui.refresh_views_now().unwrap();
+        gui.entity_view_state
+            .current_description
+            .perform(Action::Edit(Edit::Insert('x')));
+        gui
+    }
+
+    fn select_label(label: &Label) -> GuiMessage {
+        GuiMessage::EntityViewUpd(EntityViewMessage::LabelViewUpdate(ColViewMes::Selected(
+            1,
+            DbColViewEntry(Some(label.clone())),
+        )))
+    }
+
+    fn stored_description(gui: &SqlGui) -> String {
+        let path = gui.lore_database.as_ref().unwrap().path_as_string();
+        let mut gui_reading = SqlGui {
+            lore_database: Some(LoreDatabase::open(path.into()).unwrap()),
+            ..Default::default()
+        };
+        gui_reading.set_selected_label(Some(example_labels()[0].clone()));
+        gui_reading.set_selected_descriptor(Some(example_descriptors()[0].0.clone()));
+        gui_reading.refresh_views_now().unwrap();
+        gui_reading.get_description_text()
+    }
+
+    #[test]
+    fn selecting_another_label_asks_about_unsaved_description() {
+        let mut gui = gui_editing_description();
+        gui.handle_message(select_label(&example_labels()[1]))
+            .unwrap();
+        assert!(gui.dialog.is_some());
+        assert_eq!(gui.get_selected_label(), Some(example_labels()[0].clone()));
+    }
+
+    #[test]
+    fn selecting_without_unsaved_changes_does_not_ask() {
+        let mut gui = SqlGui {
+            lore_database: Some(example_database()),
+            ..Default::default()
+        };
+        gui.handle_message(select_label(&example_labels()[1]))
+            .unwrap();
+        assert!(gui.dialog.is_none());
+        assert_eq!(gui.get_selected_label(), Some(example_labels()[1].clone()));
+    }
+
+    #[test]
+    fn saving_stores_description_and_continues() {
+        let mut gui = gui_editing_description();
+        let edited = gui.get_description_text();
+        gui.handle_message(select_label(&example_labels()[1]))
+            .unwrap();
+        gui.handle_message(GuiMessage::DialogSubmit).unwrap();
+
+        assert!(gui.dialog.is_none());
+        assert_eq!(gui.get_selected_label(), Some(example_labels()[1].clone()));
+        assert_eq!(stored_description(&gui), edited);
+        assert!(gui.undo_stack.can_undo());
+    }
+
+    #[test]
+    fn discarding_drops_description_and_continues() {
+        let mut gui = gui_editing_description();
+        let message = select_label(&example_labels()[1]);
+        gui.handle_message(message.clone()).unwrap();
+        gui.handle_message(GuiMessage::DiscardAndContinue(Box::new(message)))
+            .unwrap();
+
+        assert!(gui.dialog.is_none());
+        assert_eq!(gui.get_selected_label(), Some(example_labels()[1].clone()));
+        assert_eq!(
+            stored_description(&gui),
+            example_descriptors()[0].1.to_str()
+        );
+    }
+
+    #[test]
+    fn cancelling_keeps_selection_and_unsaved_changes() {
+        let mut gui = gui_editing_description();
+        let edited = gui.get_description_text();
+        gui.handle_message(select_label(&example_labels()[1]))
+            .unwrap();
+        gui.handle_message(GuiMessage::DialogClosed).unwrap();
+
+        assert_eq!(gui.get_selected_label(), Some(example_labels()[0].clone()));
+        assert_eq!(gui.get_description_text(), edited);
+        assert!(gui.entity_view_state.current_description.is_changed());
+    }
+
+    #[test]
+    fn creating_descriptor_asks_about_unsaved_description() {
+        let mut gui = gui_editing_description();
+        gui.handle_message(GuiMessage::NewDescriptor(example_new_descriptor_data()))
+            .unwrap();
+        assert!(gui.dialog.is_some());
+        assert_eq!(
+            gui.get_selected_descriptor(),
+            Some(example_descriptors()[0].0.clone())
+        );
+    }
+
+    #[test]
+    fn opening_the_active_database_again_asks_about_unsaved_changes() {
+        let mut gui = gui_editing_description();
+        let path = gui.lore_database.as_ref().unwrap().path_as_string();
+        gui.handle_message(GuiMessage::OpenDatabaseAt(path.into()))
+            .unwrap();
+        assert!(gui.dialog.is_some());
+        assert_eq!(gui.get_selected_label(), Some(example_labels()[0].clone()));
+        assert!(gui.entity_view_state.current_description.is_changed());
+    }
+
+    #[test]
+    fn saving_before_undoing_undoes_the_earlier_change() {
+        let mut gui = gui_editing_description();
+        let edited = gui.get_description_text();
+        let data = example_new_relationship_data();
+        let relationship = EntityRelationship {
+            parent: data.parent().clone(),
+            child: data.child().clone(),
+            role: data.role().clone(),
+        };
+        gui.handle_message(GuiMessage::NewRelationship(data))
+            .unwrap();
+
+        gui.handle_message(GuiMessage::Undo).unwrap();
+        assert!(gui.dialog.is_some());
+        gui.handle_message(GuiMessage::DialogSubmit).unwrap();
+
+        assert_eq!(stored_description(&gui), edited);
+        let db = gui.lore_database.as_ref().unwrap();
+        let search_params = RelationshipSearchParams::new(None, None);
+        assert!(!db
+            .read_relationships(search_params)
+            .unwrap()
+            .contains(&relationship));
+        gui.handle_message(GuiMessage::Undo).unwrap();
+        assert_eq!(
+            stored_description(&gui),
+            example_descriptors()[0].1.to_str()
+        );
+    }
+
+    #[test]
+    fn closing_window_asks_about_unsaved_changes_in_inactive_tabs() {
+        let mut gui = gui_editing_description();
+        gui.new_tab();
+        gui.lore_database = Some(example_database());
+
+        gui.handle_message(GuiMessage::WindowCloseRequested)
+            .unwrap();
+        assert!(gui.dialog.is_some());
+        assert!(!gui.exit_requested);
+
+        gui.handle_message(GuiMessage::DialogSubmit).unwrap();
+        assert!(gui.exit_requested);
+        assert_eq!(gui.active_tab, 1);
+        assert!(!gui.has_unsaved_changes(UnsavedScope::AllTabs));
+    }
+
+    #[test]
+    fn closing_window_without_unsaved_changes_exits() {
+        let mut gui = SqlGui::default();
+        gui.handle_message(GuiMessage::WindowCloseRequested)
+            .unwrap();
+        assert!(gui.dialog.is_none());
+        assert!(gui.exit_requested);
+    }
+}
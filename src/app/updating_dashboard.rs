@@ -0,0 +1,98 @@
+use super::SqlGui;
+use crate::dashboard::DashboardMessage;
+
+impl SqlGui {
+    pub(super) fn update_dashboard(&mut self, event: DashboardMessage) {
+        match event {
+            DashboardMessage::ToggleCategory(category) => {
+                self.dashboard_state.toggle_category(category)
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use lorecore::types::*;
+
+    use super::*;
+    use crate::{
+        app::{message_handling::GuiMessage, ViewType},
+        global_search::SearchTarget,
+        tests::{example_database, example_labels, example_role, example_years},
+    };
+
+    fn dashboard_gui() -> SqlGui {
+        let mut gui = SqlGui {
+            lore_database: Some(example_database()),
+            ..Default::default()
+        };
+        gui.handle_message(GuiMessage::ViewSelected(ViewType::Dashboard))
+            .unwrap();
+        gui.refresh_visible_view_now().unwrap();
+        gui
+    }
+
+    #[test]
+    fn categories_can_be_expanded() {
+        let mut gui = dashboard_gui();
+        let category = gui.dashboard_state.get_data().categories[0]
+            .category
+            .clone();
+        assert!(!gui.dashboard_state.is_expanded(&category));
+
+        gui.handle_message(GuiMessage::DashboardUpd(DashboardMessage::ToggleCategory(
+            category.clone(),
+        )))
+        .unwrap();
+        assert!(gui.dashboard_state.is_expanded(&category));
+    }
+
+    #[test]
+    fn clicking_a_year_selects_it_in_the_history_view() {
+        let mut gui = dashboard_gui();
+        let year = gui.dashboard_state.get_data().years[0].year;
+        assert_eq!(year, example_years()[1]);
+
+        gui.handle_message(GuiMessage::JumpTo(SearchTarget::Year(year)))
+            .unwrap();
+        gui.refresh_visible_view_now().unwrap();
+
+        assert_eq!(gui.selected_view, ViewType::History);
+        assert_eq!(gui.get_selected_year(), Some(year));
+        assert_eq!(gui.get_selected_timestamp(), None);
+    }
+
+    #[test]
+    fn writes_outdate_the_dashboard() {
+        let mut gui = dashboard_gui();
+        let labels = example_labels();
+        let parent: Parent = labels[0].to_str().into();
+        let child: Child = labels[1].to_str().into();
+        let relationship = EntityRelationship {
+            role: example_role(&parent, &child),
+            parent,
+            child,
+        };
+
+        gui.handle_message(GuiMessage::DeleteRelationship(relationship))
+            .unwrap();
+        gui.refresh_visible_view_now().unwrap();
+
+        let counts: Vec<(Label, usize)> = gui
+            .dashboard_state
+            .get_data()
+            .most_connected
+            .iter()
+            .map(|entity| (entity.label.clone(), entity.relationships))
+            .collect();
+        assert_eq!(
+            counts,
+            vec![
+                (labels[2].clone(), 4),
+                (labels[0].clone(), 3),
+                (labels[1].clone(), 3)
+            ]
+        );
+    }
+}
@@ -1,17 +1,20 @@
-use super::SqlGui;
-use crate::{errors::LoreGuiError, file_dialogs, user_preferences::store_database_path};
+use super::{message_handling::GuiMessage, undo_redo::UndoStack, SqlGui};
+use crate::{
+    dialog::database_chooser::DatabaseChooserDialog,
+    errors::{ErrorContext, LoreGuiError},
+    file_dialogs,
+    localisation::{tr, tr_args},
+    search_index::SearchIndexState,
+    user_preferences::{now_in_seconds, store_recent_databases},
+};
 use lorecore::sql::lore_database::LoreDatabase;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
 impl SqlGui {
-    pub(super) fn update_database_derived_data(&mut self) -> Result<(), LoreGuiError> {
-        self.entity_view_state
-            .reset_selections(&self.lore_database)?;
-        self.history_view_state
-            .reset_selections(&self.lore_database)?;
-        self.relationship_view_state
-            .reset_selections(&self.lore_database)?;
-        Ok(())
+    pub(super) fn update_database_derived_data(&mut self) {
+        self.entity_view_state.reset_selections();
+        self.history_view_state.reset_selections();
+        self.relationship_view_state.reset_selections();
     }
 
     pub(super) fn new_database_from_dialog(&mut self) -> Result<(), LoreGuiError> {
@@ -19,14 +22,22 @@ impl SqlGui {
             Some(path) => path,
             None => return Ok(()),
         };
+        if self.find_tab(&path.to_string_lossy()).is_some() {
+            return self.handle_message(GuiMessage::OpenDatabaseAt(path));
+        }
+        self.prepare_tab_for(&path);
         self.new_database(path.clone())?;
-        store_database_path(path)?;
+        self.dialog = None;
+        self.remember_database(path)?;
         Ok(())
     }
 
     pub(super) fn new_database(&mut self, path: PathBuf) -> Result<(), LoreGuiError> {
-        self.lore_database = Some(LoreDatabase::open(path)?);
-        self.update_database_derived_data()?;
+        let db = open_in_context(path, "command-new-database")?;
+        self.search_index = SearchIndexState::unbuilt();
+        self.lore_database = Some(db);
+        self.undo_stack = UndoStack::default();
+        self.update_database_derived_data();
         Ok(())
     }
 
@@ -35,14 +46,89 @@ impl SqlGui {
             Some(path) => path,
             None => return Ok(()),
         };
+        // Opening a database that is already open asks about the unsaved changes in its tab.
+        if self.find_tab(&path.to_string_lossy()).is_some() {
+            return self.handle_message(GuiMessage::OpenDatabaseAt(path));
+        }
+        self.prepare_tab_for(&path);
         self.open_database(path.clone())?;
-        store_database_path(path)?;
-        Ok(())
+        self.dialog = None;
+        self.remember_database(path)?;
+        self.offer_draft_recovery()
     }
 
     pub(super) fn open_database(&mut self, path: PathBuf) -> Result<(), LoreGuiError> {
-        self.lore_database = Some(LoreDatabase::open(path)?);
-        self.update_database_derived_data()?;
+        let db = open_in_context(path, "command-open-database")?;
+        self.search_index = SearchIndexState::unbuilt();
+        self.lore_database = Some(db);
+        self.undo_stack = UndoStack::default();
+        self.update_database_derived_data();
+        self.restore_session();
         Ok(())
     }
+
+    pub(super) fn show_recent_databases(&mut self) {
+        let entries = self.recent_databases.entries().to_vec();
+        self.dialog = Some(Box::new(DatabaseChooserDialog::new(entries)));
+    }
+
+    pub(super) fn open_recent_database(&mut self, path: PathBuf) -> Result<(), LoreGuiError> {
+        if !path.exists() {
+            return Err(LoreGuiError::FileError(tr_args(
+                "error-database-missing",
+                &[("path", &path.to_string_lossy())],
+            )));
+        }
+        self.prepare_tab_for(&path);
+        self.open_database(path.clone())?;
+        self.dialog = None;
+        self.remember_database(path)?;
+        self.offer_draft_recovery()
+    }
+
+    pub(super) fn pin_recent_database(
+        &mut self,
+        path: &Path,
+        pinned: bool,
+    ) -> Result<(), LoreGuiError> {
+        self.recent_databases.set_pinned(path, pinned);
+        self.show_recent_databases();
+        self.save_recent_databases()
+    }
+
+    pub(super) fn forget_recent_database(&mut self, path: &Path) -> Result<(), LoreGuiError> {
+        self.recent_databases.remove(path);
+        self.database_sessions.remove(path);
+        self.show_recent_databases();
+        self.save_database_sessions()?;
+        self.save_recent_databases()
+    }
+
+    /// Switches to the tab showing the database at `path`, or opens a new tab if the active one is in use.
+    fn prepare_tab_for(&mut self, path: &Path) {
+        if let Some(index) = self.find_tab(&path.to_string_lossy()) {
+            self.select_tab(index);
+        } else if self.lore_database.is_some() {
+            self.new_tab();
+        }
+    }
+
+    fn remember_database(&mut self, path: PathBuf) -> Result<(), LoreGuiError> {
+        self.recent_databases.touch(path, now_in_seconds());
+        self.save_recent_databases()
+    }
+
+    pub(super) fn save_recent_databases(&self) -> Result<(), LoreGuiError> {
+        if !self.preferences_loaded {
+            return Ok(());
+        }
+        store_recent_databases(&self.recent_databases)
+    }
+}
+
+/// Opens the database at `path`, naming `operation` and the path if that fails.
+fn open_in_context(path: PathBuf, operation: &str) -> Result<LoreDatabase, LoreGuiError> {
+    let context =
+        ErrorContext::new(tr(operation)).with(tr("error-subject-database"), path.to_string_lossy());
+    LoreDatabase::open(path).map_err(|error| LoreGuiError::from(error).in_context(context))
 }
@@ -1,16 +1,23 @@
-use lorecore::{sql::lore_database::LoreDatabase, types::*};
+use lorecore::types::*;
 
 use crate::{
-    db_col_view::{entry::DbColViewEntry, ColViewMes},
+    db_col_view::ColViewMes,
     dialog::{
+        change_category::{ChangeCategoryData, ChangeCategoryDialog},
         confirmation::ConfirmationDialog,
-        new_descriptor::{NewDescriptorData, NewDescriptorDialog},
-        new_entity::{NewEntityData, NewEntityDialog},
-        relabel_entity::{RelabelEntityData, RelabelEntityDialog},
-        rename_descriptor::{RenameDescriptorData, RenameDescriptorDialog},
+        copy_entity::CopyEntityDialog,
+        duplicate_entity::DuplicateEntityDialog,
+        merge_entities::MergeEntitiesDialog,
+        new_descriptor::NewDescriptorDialog,
+        new_entity::NewEntityDialog,
+        relabel_entity::RelabelEntityDialog,
+        rename_descriptor::RenameDescriptorDialog,
+        rename_descriptor_everywhere::RenameDescriptorEverywhereDialog,
     },
     entity_view::{EntityViewMessage, EntityViewState},
     errors::LoreGuiError,
+    localisation::tr_args,
+    query_cache::CachedReader,
 };
 
 use super::{message_handling::GuiMessage, SqlGui};
@@ -21,25 +28,81 @@ impl SqlGui {
         event: EntityViewMessage,
     ) -> Result<(), LoreGuiError> {
         match event {
-            EntityViewMessage::NewEntity => self.dialog = Some(Box::new(NewEntityDialog::new())),
+            EntityViewMessage::NewEntity => {
+                let categories = self.entity_view_state.get_categories().to_vec();
+                self.dialog = Some(Box::new(NewEntityDialog::new(categories)))
+            }
             EntityViewMessage::RelabelEntity(data) => {
                 self.dialog = Some(Box::new(RelabelEntityDialog::new(data)))
             }
             EntityViewMessage::DeleteEntity(label) => {
-                let message = format!("Do you really want to delete {}?", label);
+                let message = tr_args("confirm-delete-entity", &[("label", &label)]);
                 let on_confirm = GuiMessage::DeleteEntity(label);
                 self.dialog = Some(Box::new(ConfirmationDialog::new(message, on_confirm)))
             }
+            EntityViewMessage::CopyEntity(label) => {
+                let targets = self.other_database_paths();
+                self.dialog = Some(Box::new(CopyEntityDialog::new(label, targets)))
+            }
+            EntityViewMessage::DuplicateEntity(label) => {
+                self.dialog = Some(Box::new(DuplicateEntityDialog::new(label)))
+            }
+            EntityViewMessage::MergeEntities(label) => {
+                let db = self
+                    .lore_database
+                    .as_ref()
+                    .ok_or(LoreGuiError::NoDatabase)?;
+                let reader = CachedReader::new(db, &self.query_cache);
+                let columns = reader.read_entity_columns(None, None)?;
+                let relationships = reader.read_relationships(None, None)?;
+                self.dialog = Some(Box::new(MergeEntitiesDialog::new(
+                    columns,
+                    relationships,
+                    Some(label),
+                )))
+            }
+            EntityViewMessage::ChangeCategory(label) => {
+                let state = &self.entity_view_state;
+                let category = state
+                    .get_label_details()
+                    .iter()
+                    .find(|details| details.label == label)
+                    .and_then(|details| details.category.clone())
+                    .unwrap_or_default();
+                let data = ChangeCategoryData::new(label, category);
+                let categories = state.get_categories().to_vec();
+                self.dialog = Some(Box::new(ChangeCategoryDialog::new(data, categories)))
+            }
+            EntityViewMessage::CategoryFilterSelected(filter) => {
+                self.entity_view_state.set_category_filter(filter)
+            }
+            EntityViewMessage::ToggleGroupByCategory => {
+                self.entity_view_state.toggle_group_by_category()
+            }
+            EntityViewMessage::ToggleReservedDescriptors => {
+                self.entity_view_state.toggle_reserved_descriptors()
+            }
             EntityViewMessage::NewDescriptor(label) => {
                 self.dialog = Some(Box::new(NewDescriptorDialog::new(label.clone())))
             }
             EntityViewMessage::RenameDescriptor(data) => {
                 self.dialog = Some(Box::new(RenameDescriptorDialog::new(data)))
             }
+            EntityViewMessage::RenameDescriptorEverywhere(descriptor) => {
+                let db = self
+                    .lore_database
+                    .as_ref()
+                    .ok_or(LoreGuiError::NoDatabase)?;
+                let columns =
+                    CachedReader::new(db, &self.query_cache).read_entity_columns(None, None)?;
+                self.dialog = Some(Box::new(RenameDescriptorEverywhereDialog::new(
+                    columns, descriptor,
+                )))
+            }
             EntityViewMessage::DeleteDescriptor(label, descriptor) => {
-                let message = format!(
-                    "Do you really want to delete {}'s descriptor {}?",
-                    label, descriptor
+                let message = tr_args(
+                    "confirm-delete-descriptor",
+                    &[("label", &label), ("descriptor", &descriptor)],
                 );
                 let on_confirm = GuiMessage::DeleteDescriptor(label, descriptor);
                 self.dialog = Some(Box::new(ConfirmationDialog::new(message, on_confirm)))
@@ -52,25 +115,9 @@ impl SqlGui {
             EntityViewMessage::DescriptionDiscard => {
                 self.entity_view_state.current_description.reset()
             }
-            EntityViewMessage::DescriptionSave => {
-                let db = self
-                    .lore_database
-                    .as_ref()
-                    .ok_or(LoreGuiError::NoDatabase)?;
-                let label = match self.get_selected_label() {
-                    Some(label) => label,
-                    None => return Ok(()),
-                };
-                let descriptor = match self.get_selected_descriptor() {
-                    Some(descriptor) => descriptor,
-                    None => return Ok(()),
-                };
-                let description = self.get_description_text().into();
-                db.change_entity_description((&label, &descriptor), &description)?;
-                self.entity_view_state.current_description.saved();
-            }
+            // Saving is a database write, see SqlGui::database_write().
+            EntityViewMessage::DescriptionSave => {}
         };
-        self.entity_view_state.update(&self.lore_database)?;
         Ok(())
     }
 
@@ -86,9 +133,10 @@ impl SqlGui {
             ColViewMes::Selected(_index, label) => {
                 state.set_selected_label(label.0);
                 state.set_selected_descriptor(None);
+                state.set_description_text("");
+                state.invalidate();
             }
         };
-        self.entity_view_state.update(&self.lore_database)?;
         Ok(())
     }
 
@@ -103,144 +151,100 @@ impl SqlGui {
             }
             ColViewMes::Selected(_index, descriptor) => {
                 state.set_selected_descriptor(descriptor.0);
+                state.set_description_text("");
+                state.invalidate();
             }
         };
 
-        self.entity_view_state.update(&self.lore_database)?;
-        Ok(())
-    }
-
-    pub(super) fn write_new_entity(&mut self, data: NewEntityData) -> Result<(), LoreGuiError> {
-        let db = self
-            .lore_database
-            .as_ref()
-            .ok_or(LoreGuiError::NoDatabase)?;
-        let label = data.label().clone();
-        data.write_to_database(db)?;
-        self.set_selected_label(Some(label));
-        Ok(())
-    }
-
-    pub(super) fn relabel_entity(&mut self, data: RelabelEntityData) -> Result<(), LoreGuiError> {
-        let db = self
-            .lore_database
-            .as_ref()
-            .ok_or(LoreGuiError::NoDatabase)?;
-        let new_label = data.new_label().clone();
-        data.update_label_in_database(db)?;
-        self.set_selected_label(Some(new_label));
-        Ok(())
-    }
-
-    pub(super) fn delete_entity(&mut self, label: Label) -> Result<(), LoreGuiError> {
-        let db = self
-            .lore_database
-            .as_ref()
-            .ok_or(LoreGuiError::NoDatabase)?;
-        db.delete_entity(label)?;
-        self.set_selected_label(None);
-        Ok(())
-    }
-
-    pub(super) fn write_new_descriptor(
-        &mut self,
-        data: NewDescriptorData,
-    ) -> Result<(), LoreGuiError> {
-        let db = self
-            .lore_database
-            .as_ref()
-            .ok_or(LoreGuiError::NoDatabase)?;
-        let label = data.label().clone();
-        let descriptor = data.descriptor().clone();
-        data.write_to_database(db)?;
-        self.set_selected_label(Some(label));
-        self.set_selected_descriptor(Some(descriptor));
-        Ok(())
-    }
-
-    pub(super) fn change_descriptor(
-        &mut self,
-        data: RenameDescriptorData,
-    ) -> Result<(), LoreGuiError> {
-        let db = self
-            .lore_database
-            .as_ref()
-            .ok_or(LoreGuiError::NoDatabase)?;
-        let label = data.label().clone();
-        let descriptor = data.new_descriptor().clone();
-        data.update_descriptor_in_database(db)?;
-        self.set_selected_label(Some(label));
-        self.set_selected_descriptor(Some(descriptor));
-        Ok(())
-    }
-
-    pub(super) fn delete_descriptor(
-        &mut self,
-        label: Label,
-        descriptor: Descriptor,
-    ) -> Result<(), LoreGuiError> {
-        let db = self
-            .lore_database
-            .as_ref()
-            .ok_or(LoreGuiError::NoDatabase)?;
-        db.delete_entity_column((label.clone(), descriptor))?;
-        self.set_selected_label(Some(label));
-        self.set_selected_descriptor(None);
         Ok(())
     }
 }
 
 impl EntityViewState {
-    pub(super) fn reset_selections(
-        &mut self,
-        db: &Option<LoreDatabase>,
-    ) -> Result<(), LoreGuiError> {
+    pub(super) fn reset_selections(&mut self) {
         self.set_selected_label(None);
         self.set_selected_descriptor(None);
         self.set_description_text("");
-        self.update(db)?;
-        Ok(())
     }
+}
 
-    pub(super) fn update(&mut self, db: &Option<LoreDatabase>) -> Result<(), LoreGuiError> {
-        self.update_labels(db)?;
-        self.update_descriptors(db)?;
-        self.update_description(db)?;
-        Ok(())
-    }
+#[cfg(test)]
+mod tests {
+    use super::*;
 
-    fn update_labels(&mut self, db: &Option<LoreDatabase>) -> Result<(), LoreGuiError> {
-        let labels = self
-            .get_current_labels(db)?
-            .into_iter()
-            .map(|l| DbColViewEntry(Some(l)))
-            .collect();
-        self.label_view_state.set_entries(labels);
-        Ok(())
+    use crate::db_col_view::entry::DbColViewEntry;
+    use crate::dialog::{
+        copy_entity::CopyEntityData,
+        duplicate_entity::DuplicateEntityData,
+        merge_entities::{tests::example_merge_dialog, MergeEntitiesData, MergeResolution},
+        new_entity::tests::example_new_entity_data,
+        rename_descriptor_everywhere::RenameDescriptorEverywhereData,
+        Dialog, DialogUpdate,
+    };
+    use crate::entity_view::{CategoryFilter, LabelRow, CATEGORY_DESCRIPTOR, NAME_DESCRIPTOR};
+    use crate::tests::{example_database, example_descriptors, example_labels, example_role};
+    use iced::widget::text_editor::{Action, Edit};
+    use lorecore::sql::search_params::{
+        EntityColumnSearchParams, RelationshipSearchParams, SqlSearchText,
+    };
+
+    fn gui_editing_first_description() -> SqlGui {
+        let mut gui = SqlGui {
+            lore_database: Some(example_database()),
+            ..Default::default()
+        };
+        gui.set_selected_label(Some(example_labels()[0].clone()));
+        gui.set_selected_descriptor(Some(example_descriptors()[0].0.clone()));
+        gui.refresh_views_now().unwrap();
+        gui.entity_view_state
+            .current_description
+            .perform(Action::Edit(Edit::Insert('x')));
+        gui
     }
 
-    fn update_descriptors(&mut self, db: &Option<LoreDatabase>) -> Result<(), LoreGuiError> {
-        let descriptors = self
-            .get_current_descriptors(db)?
-            .into_iter()
-            .map(|d| DbColViewEntry(Some(d)))
-            .collect();
-        self.descriptor_view_state.set_entries(descriptors);
-        Ok(())
+    fn stored_description(gui: &SqlGui, descriptor: &Descriptor) -> String {
+        let db = gui.lore_database.as_ref().unwrap();
+        let search_params = EntityColumnSearchParams::new(
+            Some(SqlSearchText::exact(example_labels()[0].to_str())),
+            Some(SqlSearchText::exact(descriptor.to_str())),
+        );
+        db.read_entity_columns(search_params).unwrap()[0]
+            .description
+            .to_string()
     }
 
-    fn update_description(&mut self, db: &Option<LoreDatabase>) -> Result<(), LoreGuiError> {
-        let description = self.get_current_description(db)?;
-        self.set_description_text(description.to_str());
-        Ok(())
+    #[test]
+    fn unsaved_description_does_not_follow_a_changed_selection() {
+        let mut gui = gui_editing_first_description();
+        let descriptors = example_descriptors();
+
+        gui.set_selected_descriptor(Some(descriptors[1].0.clone()));
+        gui.refresh_views_now().unwrap();
+
+        assert_eq!(gui.get_description_text(), descriptors[1].1.to_str());
+        assert!(gui.entity_view_state.draft().is_none());
     }
-}
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+    #[test]
+    fn saving_writes_the_description_to_the_column_it_was_edited_for() {
+        let mut gui = gui_editing_first_description();
+        let descriptors = example_descriptors();
+        let edited = gui.get_description_text();
+        let draft = gui.entity_view_state.draft().unwrap();
 
-    use crate::tests::{example_database, example_descriptors, example_labels};
+        gui.set_selected_descriptor(Some(descriptors[1].0.clone()));
+        assert_eq!(gui.entity_view_state.draft(), Some(draft));
+        gui.handle_message(GuiMessage::EntityViewUpd(
+            EntityViewMessage::DescriptionSave,
+        ))
+        .unwrap();
+
+        assert_eq!(stored_description(&gui, &descriptors[0].0), edited);
+        assert_eq!(
+            stored_description(&gui, &descriptors[1].0),
+            descriptors[1].1.to_str()
+        );
+    }
 
     #[test]
     fn selecting_label_deselects_descriptor() {
@@ -278,9 +282,452 @@ mod tests {
         let new_descriptor = descriptors[1].0.clone();
         let event = ColViewMes::Selected(1, DbColViewEntry(Some(new_descriptor.clone())));
         gui.update_descriptor_view(event).unwrap();
+        gui.refresh_views_now().unwrap();
 
         assert_eq!(gui.get_selected_label(), Some(labels[0].clone()));
         assert_eq!(gui.get_selected_descriptor(), Some(new_descriptor));
         assert_eq!(gui.get_description_text(), descriptors[1].1.to_str());
     }
+
+    #[test]
+    fn copying_entity_writes_all_descriptors_to_other_tab() {
+        let mut gui = SqlGui {
+            lore_database: Some(example_database()),
+            ..Default::default()
+        };
+        gui.new_tab();
+        gui.lore_database = Some(example_database());
+        let target_path = gui.lore_database.as_ref().unwrap().path_as_string();
+        gui.select_tab(0);
+
+        let labels = example_labels();
+        let new_label: Label = "copied_entity".into();
+        let mut data = CopyEntityData::new(labels[0].clone());
+        data.set_new_label(new_label.clone());
+        data.set_target_database(target_path);
+        gui.handle_message(GuiMessage::CopyEntity(data)).unwrap();
+
+        gui.select_tab(1);
+        let target = gui.lore_database.as_ref().unwrap();
+        let search_params =
+            EntityColumnSearchParams::new(Some(SqlSearchText::exact("copied_entity")), None);
+        let copied = target.read_entity_columns(search_params).unwrap();
+        assert_eq!(copied.len(), example_descriptors().len());
+        assert!(copied.iter().all(|col| col.label == new_label));
+    }
+
+    #[test]
+    fn copying_entity_onto_existing_label_produces_error() {
+        let mut gui = SqlGui {
+            lore_database: Some(example_database()),
+            ..Default::default()
+        };
+        gui.new_tab();
+        gui.lore_database = Some(example_database());
+        let target_path = gui.lore_database.as_ref().unwrap().path_as_string();
+        gui.select_tab(0);
+
+        let mut data = CopyEntityData::new(example_labels()[0].clone());
+        data.set_target_database(target_path);
+        assert!(gui.handle_message(GuiMessage::CopyEntity(data)).is_err());
+    }
+
+    #[test]
+    fn copying_entity_into_same_database_produces_error() {
+        let mut gui = SqlGui {
+            lore_database: Some(example_database()),
+            ..Default::default()
+        };
+        let path = gui.lore_database.as_ref().unwrap().path_as_string();
+        let mut data = CopyEntityData::new(example_labels()[0].clone());
+        data.set_new_label("copied_entity".into());
+        data.set_target_database(path);
+        assert!(gui.handle_message(GuiMessage::CopyEntity(data)).is_err());
+    }
+
+    fn duplicate(gui: &mut SqlGui, data: DuplicateEntityData) {
+        gui.handle_message(GuiMessage::DuplicateEntity(data))
+            .unwrap();
+        gui.refresh_views_now().unwrap();
+    }
+
+    fn read_relationships_of(gui: &SqlGui, label: &str) -> Vec<EntityRelationship> {
+        let db = gui.lore_database.as_ref().unwrap();
+        let as_parent = RelationshipSearchParams::new(Some(SqlSearchText::exact(label)), None);
+        let as_child = RelationshipSearchParams::new(None, Some(SqlSearchText::exact(label)));
+        let mut relationships = db.read_relationships(as_parent).unwrap();
+        relationships.extend(db.read_relationships(as_child).unwrap());
+        relationships
+    }
+
+    #[test]
+    fn duplicating_entity_copies_descriptors_and_selects_copy() {
+        let mut gui = SqlGui {
+            lore_database: Some(example_database()),
+            ..Default::default()
+        };
+        let new_label: Label = "duplicated_entity".into();
+        let mut data = DuplicateEntityData::new(example_labels()[0].clone());
+        data.set_new_label(new_label.clone());
+        data.set_new_name("Twin".to_string());
+        duplicate(&mut gui, data);
+
+        assert_eq!(gui.get_selected_label(), Some(new_label.clone()));
+        let db = gui.lore_database.as_ref().unwrap();
+        let search_params =
+            EntityColumnSearchParams::new(Some(SqlSearchText::exact("duplicated_entity")), None);
+        let columns = db.read_entity_columns(search_params).unwrap();
+        assert_eq!(columns.len(), example_descriptors().len() + 1);
+        let name = columns
+            .iter()
+            .find(|col| col.descriptor.to_str() == NAME_DESCRIPTOR)
+            .unwrap();
+        assert_eq!(name.description.to_str(), "Twin");
+        assert!(read_relationships_of(&gui, "duplicated_entity").is_empty());
+    }
+
+    #[test]
+    fn duplicating_entity_with_relationships_substitutes_its_label() {
+        let mut gui = SqlGui {
+            lore_database: Some(example_database()),
+            ..Default::default()
+        };
+        let labels = example_labels();
+        let mut data = DuplicateEntityData::new(labels[0].clone());
+        data.set_new_label("duplicated_entity".into());
+        data.set_with_relationships(true);
+        duplicate(&mut gui, data);
+
+        let original = read_relationships_of(&gui, labels[0].to_str());
+        let duplicated = read_relationships_of(&gui, "duplicated_entity");
+        assert_eq!(duplicated.len(), original.len());
+        for relationship in original {
+            let substitute = |name: &str| {
+                if name == labels[0].to_str() {
+                    "duplicated_entity".to_string()
+                } else {
+                    name.to_string()
+                }
+            };
+            let expected = EntityRelationship {
+                parent: substitute(relationship.parent.to_str()).into(),
+                child: substitute(relationship.child.to_str()).into(),
+                role: relationship.role,
+            };
+            assert!(duplicated.contains(&expected));
+        }
+        let notification = gui.notifications.history().last().unwrap();
+        assert_eq!(
+            notification.text,
+            format!(
+                "Entity '{}' duplicated as 'duplicated_entity' with its relationships",
+                labels[0]
+            )
+        );
+
+        gui.handle_message(GuiMessage::Undo).unwrap();
+        assert!(read_relationships_of(&gui, "duplicated_entity").is_empty());
+    }
+
+    #[test]
+    fn duplicating_entity_onto_existing_label_produces_error() {
+        let mut gui = SqlGui {
+            lore_database: Some(example_database()),
+            ..Default::default()
+        };
+        let labels = example_labels();
+        let mut data = DuplicateEntityData::new(labels[0].clone());
+        data.set_new_label(labels[1].clone());
+        assert!(gui
+            .handle_message(GuiMessage::DuplicateEntity(data))
+            .is_err());
+    }
+
+    fn example_merge(gui: &SqlGui) -> MergeEntitiesData {
+        let mut dialog = example_merge_dialog(gui.lore_database.as_ref().unwrap());
+        dialog.update(DialogUpdate::MergeResolution(
+            example_descriptors()[0].0.clone(),
+            MergeResolution::KeepSource,
+        ));
+        dialog.data().unwrap()
+    }
+
+    #[test]
+    fn merging_entities_moves_relationships_and_deletes_source() {
+        let mut gui = SqlGui {
+            lore_database: Some(example_database()),
+            ..Default::default()
+        };
+        let labels = example_labels();
+        let source_description = {
+            let db = gui.lore_database.as_ref().unwrap();
+            let search_params =
+                EntityColumnSearchParams::new(Some(SqlSearchText::exact(labels[0].to_str())), None);
+            db.read_entity_columns(search_params).unwrap()[0]
+                .description
+                .clone()
+        };
+        gui.handle_message(GuiMessage::MergeEntities(example_merge(&gui)))
+            .unwrap();
+        gui.refresh_views_now().unwrap();
+
+        assert_eq!(gui.get_selected_label(), Some(labels[1].clone()));
+        let db = gui.lore_database.as_ref().unwrap();
+        let search_params =
+            EntityColumnSearchParams::new(Some(SqlSearchText::exact(labels[0].to_str())), None);
+        assert!(db.read_entity_columns(search_params).unwrap().is_empty());
+        let search_params =
+            EntityColumnSearchParams::new(Some(SqlSearchText::exact(labels[1].to_str())), None);
+        let target_columns = db.read_entity_columns(search_params).unwrap();
+        let merged = target_columns
+            .iter()
+            .find(|col| col.descriptor == example_descriptors()[0].0)
+            .unwrap();
+        assert_eq!(merged.description, source_description);
+
+        assert!(read_relationships_of(&gui, labels[0].to_str()).is_empty());
+        let target_relationships = read_relationships_of(&gui, labels[1].to_str());
+        assert!(target_relationships
+            .iter()
+            .all(|rel| rel.parent.to_str() != rel.child.to_str()));
+        let parent: Parent = labels[1].to_str().into();
+        let child: Child = labels[2].to_str().into();
+        let moved = EntityRelationship {
+            role: example_role(&labels[0].to_str().into(), &child),
+            parent,
+            child,
+        };
+        assert!(target_relationships.contains(&moved));
+    }
+
+    #[test]
+    fn merging_entities_is_undone_in_one_step() {
+        let mut gui = SqlGui {
+            lore_database: Some(example_database()),
+            ..Default::default()
+        };
+        let labels = example_labels();
+        let relationships_per_entity = read_relationships_of(&gui, labels[1].to_str()).len();
+        gui.handle_message(GuiMessage::MergeEntities(example_merge(&gui)))
+            .unwrap();
+        gui.handle_message(GuiMessage::Undo).unwrap();
+
+        let db = gui.lore_database.as_ref().unwrap();
+        let search_params =
+            EntityColumnSearchParams::new(Some(SqlSearchText::exact(labels[0].to_str())), None);
+        assert_eq!(
+            db.read_entity_columns(search_params).unwrap().len(),
+            example_descriptors().len()
+        );
+        assert_eq!(
+            read_relationships_of(&gui, labels[0].to_str()).len(),
+            relationships_per_entity
+        );
+        assert_eq!(
+            read_relationships_of(&gui, labels[1].to_str()).len(),
+            relationships_per_entity
+        );
+    }
+
+    fn read_descriptor(gui: &SqlGui, descriptor: &str) -> Vec<EntityColumn> {
+        let db = gui.lore_database.as_ref().unwrap();
+        let search_params =
+            EntityColumnSearchParams::new(None, Some(SqlSearchText::exact(descriptor)));
+        db.read_entity_columns(search_params).unwrap()
+    }
+
+    #[test]
+    fn renaming_descriptor_everywhere_skips_collisions() {
+        let mut gui = SqlGui {
+            lore_database: Some(example_database()),
+            ..Default::default()
+        };
+        let labels = example_labels();
+        let old_descriptor = example_descriptors()[0].0.clone();
+        let new_descriptor: Descriptor = "renamed".into();
+        gui.lore_database
+            .as_ref()
+            .unwrap()
+            .write_entity_columns(vec![EntityColumn {
+                label: labels[0].clone(),
+                descriptor: new_descriptor.clone(),
+                description: "Already there".into(),
+            }])
+            .unwrap();
+        let columns = read_descriptor(&gui, old_descriptor.to_str())
+            .into_iter()
+            .chain(read_descriptor(&gui, "renamed"))
+            .collect::<Vec<_>>();
+        let data =
+            RenameDescriptorEverywhereData::new(&columns, old_descriptor.clone(), new_descriptor);
+        assert_eq!(data.collisions().to_vec(), vec![labels[0].clone()]);
+        gui.set_selected_label(Some(labels[0].clone()));
+        gui.set_selected_descriptor(Some(old_descriptor.clone()));
+
+        gui.handle_message(GuiMessage::RenameDescriptorEverywhere(data))
+            .unwrap();
+        assert_eq!(gui.get_selected_descriptor(), Some(old_descriptor.clone()));
+
+        let remaining: Vec<Label> = read_descriptor(&gui, old_descriptor.to_str())
+            .into_iter()
+            .map(|col| col.label)
+            .collect();
+        assert_eq!(remaining, vec![labels[0].clone()]);
+        assert_eq!(read_descriptor(&gui, "renamed").len(), labels.len());
+        let notification = gui.notifications.history().last().unwrap();
+        assert_eq!(
+            notification.text,
+            format!(
+                "Descriptor '{}' renamed to 'renamed' on {} entities, 1 skipped",
+                old_descriptor,
+                labels.len() - 1
+            )
+        );
+
+        gui.handle_message(GuiMessage::Undo).unwrap();
+        assert_eq!(
+            read_descriptor(&gui, old_descriptor.to_str()).len(),
+            labels.len()
+        );
+        assert_eq!(read_descriptor(&gui, "renamed").len(), 1);
+    }
+
+    #[test]
+    fn renaming_descriptor_everywhere_onto_new_collision_produces_error() {
+        let mut gui = SqlGui {
+            lore_database: Some(example_database()),
+            ..Default::default()
+        };
+        let old_descriptor = example_descriptors()[0].0.clone();
+        let columns = read_descriptor(&gui, old_descriptor.to_str());
+        let data = RenameDescriptorEverywhereData::new(&columns, old_descriptor, "renamed".into());
+        gui.lore_database
+            .as_ref()
+            .unwrap()
+            .write_entity_columns(vec![EntityColumn {
+                label: example_labels()[1].clone(),
+                descriptor: "renamed".into(),
+                description: "Added meanwhile".into(),
+            }])
+            .unwrap();
+        assert!(gui
+            .handle_message(GuiMessage::RenameDescriptorEverywhere(data))
+            .is_err());
+        assert_eq!(read_descriptor(&gui, "renamed").len(), 1);
+    }
+
+    fn entity_message(gui: &mut SqlGui, message: EntityViewMessage) {
+        gui.handle_message(GuiMessage::EntityViewUpd(message))
+            .unwrap();
+        gui.refresh_views_now().unwrap();
+    }
+
+    #[test]
+    fn reserved_descriptors_are_hidden_unless_toggled() {
+        let mut gui = SqlGui {
+            lore_database: Some(example_database()),
+            ..Default::default()
+        };
+        let data = example_new_entity_data();
+        let label = data.label().clone();
+        gui.handle_message(GuiMessage::NewEntity(data)).unwrap();
+        gui.set_selected_label(Some(label));
+        gui.refresh_views_now().unwrap();
+        assert!(gui
+            .entity_view_state
+            .descriptor_view_state
+            .get_entries()
+            .is_empty());
+
+        entity_message(&mut gui, EntityViewMessage::ToggleReservedDescriptors);
+        let descriptors: Vec<String> = gui
+            .entity_view_state
+            .descriptor_view_state
+            .get_entries()
+            .iter()
+            .map(|entry| entry.to_string())
+            .collect();
+        assert_eq!(descriptors, vec![CATEGORY_DESCRIPTOR, NAME_DESCRIPTOR]);
+    }
+
+    #[test]
+    fn labels_can_be_filtered_and_grouped_by_category() {
+        let mut gui = SqlGui {
+            lore_database: Some(example_database()),
+            ..Default::default()
+        };
+        let labels = example_labels();
+        let data = ChangeCategoryData::new(labels[1].clone(), " Place ".to_string());
+        gui.handle_message(GuiMessage::ChangeCategory(data))
+            .unwrap();
+        gui.refresh_views_now().unwrap();
+        assert_eq!(
+            gui.entity_view_state.get_categories().to_vec(),
+            vec!["Place".to_string()]
+        );
+
+        entity_message(&mut gui, EntityViewMessage::ToggleGroupByCategory);
+        let grouped: Vec<(Label, Option<String>)> = gui
+            .entity_view_state
+            .get_label_details()
+            .iter()
+            .map(|details| (details.label.clone(), details.category.clone()))
+            .collect();
+        assert_eq!(
+            grouped,
+            vec![
+                (labels[1].clone(), Some("Place".to_string())),
+                (labels[0].clone(), None),
+                (labels[2].clone(), None),
+            ]
+        );
+        let rows = gui.entity_view_state.get_label_rows();
+        let headings: Vec<&LabelRow> = rows
+            .iter()
+            .filter(|row| matches!(row, LabelRow::Heading(_)))
+            .collect();
+        assert_eq!(
+            headings,
+            vec![
+                &LabelRow::Heading(Some("Place".to_string())),
+                &LabelRow::Heading(None)
+            ]
+        );
+        assert_eq!(rows.len(), labels.len() + 2);
+
+        entity_message(
+            &mut gui,
+            EntityViewMessage::CategoryFilterSelected(CategoryFilter::Uncategorised),
+        );
+        let entries = gui.entity_view_state.label_view_state.get_entries();
+        assert_eq!(entries.len(), labels.len() - 1);
+        assert!(!entries.contains(&labels[1].clone().into()));
+    }
+
+    #[test]
+    fn changing_category_is_undone() {
+        let mut gui = SqlGui {
+            lore_database: Some(example_database()),
+            ..Default::default()
+        };
+        let data = example_new_entity_data();
+        let label = data.label().clone();
+        gui.handle_message(GuiMessage::NewEntity(data)).unwrap();
+        let data = ChangeCategoryData::new(label.clone(), "Other Category".to_string());
+        gui.handle_message(GuiMessage::ChangeCategory(data))
+            .unwrap();
+        gui.refresh_views_now().unwrap();
+        let category = |gui: &SqlGui| {
+            gui.entity_view_state
+                .get_label_details()
+                .iter()
+                .find(|details| details.label == label)
+                .and_then(|details| details.category.clone())
+        };
+        assert_eq!(category(&gui), Some("Other Category".to_string()));
+
+        gui.handle_message(GuiMessage::Undo).unwrap();
+        gui.refresh_views_now().unwrap();
+        assert_eq!(category(&gui), Some("Some Category".to_string()));
+    }
 }
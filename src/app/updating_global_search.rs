@@ -0,0 +1,226 @@
+use super::{SqlGui, ViewType};
+use crate::entity_view::{is_reserved, CategoryFilter};
+use crate::global_search::{GlobalSearchMessage, SearchTarget};
+
+impl SqlGui {
+    pub(super) fn update_global_search(&mut self, event: GlobalSearchMessage) {
+        match event {
+            GlobalSearchMessage::SearchTextChanged(text) => {
+                self.global_search.set_search_text(text)
+            }
+            GlobalSearchMessage::ShowResults => self.global_search.show(),
+            GlobalSearchMessage::HideResults => self.global_search.hide(),
+        }
+    }
+
+    /// Selects a search hit in its view. Column searches and filters that could hide it are cleared.
+    pub(super) fn jump_to(&mut self, target: SearchTarget) {
+        match target {
+            SearchTarget::Entity(label, descriptor) => {
+                self.selected_view = ViewType::Entity;
+                let state = &mut self.entity_view_state;
+                state.label_view_state.set_search_text(String::new());
+                state.descriptor_view_state.set_search_text(String::new());
+                state.set_category_filter(CategoryFilter::All);
+                if descriptor.as_ref().is_some_and(is_reserved)
+                    && !state.shows_reserved_descriptors()
+                {
+                    state.toggle_reserved_descriptors();
+                }
+                state.set_selected_label(Some(label));
+                state.set_selected_descriptor(descriptor);
+                state.set_description_text("");
+            }
+            SearchTarget::HistoryItem(year, day, timestamp) => {
+                self.selected_view = ViewType::History;
+                let state = &mut self.history_view_state;
+                state.year_view_state.set_search_text(String::new());
+                state.day_view_state.set_search_text(String::new());
+                state.timestamp_view_state.set_search_text(String::new());
+                state.set_selected_year(Some(year));
+                state.set_selected_day(Some(day));
+                state.set_selected_timestamp(Some(timestamp));
+                state.set_content_text("");
+            }
+            SearchTarget::Relationship(parent, child, role) => {
+                self.selected_view = ViewType::Relationship;
+                let state = &mut self.relationship_view_state;
+                state.parent_view_state.set_search_text(String::new());
+                state.child_view_state.set_search_text(String::new());
+                state.role_view_state.set_search_text(String::new());
+                state.set_selected_parent(Some(parent));
+                state.set_selected_child(Some(child));
+                state.set_selected_role(Some(role));
+            }
+            SearchTarget::Year(year) => {
+                self.selected_view = ViewType::History;
+                let state = &mut self.history_view_state;
+                state.year_view_state.set_search_text(String::new());
+                state.set_selected_year(Some(year));
+                state.set_selected_day(None);
+                state.set_selected_timestamp(None);
+                state.set_content_text("");
+            }
+        }
+        self.global_search.hide();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use iced::widget::text_editor::{Action, Edit};
+
+    use super::*;
+    use crate::{
+        app::message_handling::GuiMessage,
+        entity_view::EntityViewMessage,
+        search_index::SearchIndexState,
+        tests::{example_database, example_descriptors, example_labels},
+    };
+
+    fn search(gui: &mut SqlGui, text: &str) {
+        gui.handle_message(GuiMessage::GlobalSearchUpd(
+            GlobalSearchMessage::SearchTextChanged(text.to_string()),
+        ))
+        .unwrap();
+        gui.refresh_views_now().unwrap();
+    }
+
+    #[test]
+    fn clicking_a_history_hit_selects_it() {
+        let mut gui = SqlGui {
+            lore_database: Some(example_database()),
+            ..Default::default()
+        };
+        gui.history_view_state
+            .timestamp_view_state
+            .set_search_text("no match".to_string());
+        search(&mut gui, "-22-11");
+        assert!(gui.global_search.is_shown());
+        let hit = gui.global_search.get_results().history_items[0].clone();
+
+        gui.handle_message(GuiMessage::JumpTo(hit.target)).unwrap();
+        gui.refresh_views_now().unwrap();
+
+        assert!(!gui.global_search.is_shown());
+        assert_eq!(gui.selected_view, ViewType::History);
+        assert_eq!(gui.get_selected_year(), Some((-22).into()));
+        assert_eq!(
+            gui.history_view_state
+                .timestamp_view_state
+                .get_search_text(),
+            None
+        );
+        assert_eq!(gui.get_history_text(), "-22-11\n");
+    }
+
+    #[test]
+    fn clicking_an_entity_hit_selects_it() {
+        let mut gui = SqlGui {
+            lore_database: Some(example_database()),
+            ..Default::default()
+        };
+        gui.entity_view_state
+            .label_view_state
+            .set_search_text("no match".to_string());
+        search(&mut gui, "description 3");
+        let hit = gui.global_search.get_results().entities[1].clone();
+
+        gui.handle_message(GuiMessage::JumpTo(hit.target)).unwrap();
+        gui.refresh_views_now().unwrap();
+
+        assert_eq!(gui.get_selected_label(), Some(example_labels()[1].clone()));
+        assert_eq!(
+            gui.get_selected_descriptor(),
+            Some(example_descriptors()[2].0.clone())
+        );
+        assert_eq!(
+            gui.entity_view_state.label_view_state.get_search_text(),
+            None
+        );
+        assert_eq!(
+            gui.get_description_text(),
+            example_descriptors()[2].1.to_str()
+        );
+    }
+
+    #[test]
+    fn jumping_away_from_unsaved_description_asks_first() {
+        let mut gui = SqlGui {
+            lore_database: Some(example_database()),
+            ..Default::default()
+        };
+        gui.set_selected_label(Some(example_labels()[0].clone()));
+        gui.set_selected_descriptor(Some(example_descriptors()[0].0.clone()));
+        gui.refresh_views_now().unwrap();
+        gui.entity_view_state
+            .current_description
+            .perform(Action::Edit(Edit::Insert('x')));
+
+        let target = SearchTarget::Entity(example_labels()[1].clone(), None);
+        gui.handle_message(GuiMessage::JumpTo(target)).unwrap();
+
+        assert!(gui.dialog.is_some());
+        assert_eq!(gui.get_selected_label(), Some(example_labels()[0].clone()));
+    }
+
+    #[test]
+    fn writes_refresh_search_results() {
+        let mut gui = SqlGui {
+            lore_database: Some(example_database()),
+            ..Default::default()
+        };
+        search(&mut gui, "-22-11");
+        let hit = gui.global_search.get_results().history_items[0].clone();
+        let timestamp = match hit.target {
+            SearchTarget::HistoryItem(_, _, timestamp) => timestamp,
+            _ => panic!("History hit should target a history item."),
+        };
+
+        gui.handle_message(GuiMessage::DeleteHistoryItem(timestamp))
+            .unwrap();
+        gui.refresh_views_now().unwrap();
+
+        assert!(gui.global_search.get_results().history_items.is_empty());
+    }
+
+    #[test]
+    fn saved_descriptions_are_indexed() {
+        let db = example_database();
+        let mut gui = SqlGui {
+            search_index: SearchIndexState::build(&db),
+            lore_database: Some(db),
+            ..Default::default()
+        };
+        gui.set_selected_label(Some(example_labels()[1].clone()));
+        gui.set_selected_descriptor(Some(example_descriptors()[0].0.clone()));
+        gui.refresh_views_now().unwrap();
+        gui.entity_view_state
+            .current_description
+            .perform(Action::Edit(Edit::Paste("Griffins ".to_string().into())));
+        gui.handle_message(GuiMessage::EntityViewUpd(
+            EntityViewMessage::DescriptionSave,
+        ))
+        .unwrap();
+
+        search(&mut gui, "griff*");
+        let targets: Vec<SearchTarget> = gui
+            .global_search
+            .get_results()
+            .entities
+            .iter()
+            .map(|hit| hit.target.clone())
+            .collect();
+        assert_eq!(
+            targets,
+            vec![SearchTarget::Entity(
+                example_labels()[1].clone(),
+                Some(example_descriptors()[0].0.clone())
+            )]
+        );
+
+        gui.handle_message(GuiMessage::Undo).unwrap();
+        search(&mut gui, "griffins");
+        assert!(gui.global_search.get_results().entities.is_empty());
+    }
+}
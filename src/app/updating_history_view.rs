@@ -1,15 +1,15 @@
-use lorecore::{sql::lore_database::LoreDatabase, types::*};
+use lorecore::types::*;
 
 use crate::{
     db_col_view::{entry::DbColViewEntry, ColViewMes},
     dialog::{
-        confirmation::ConfirmationDialog,
-        new_history_item::{NewHistoryData, NewHistoryDialog},
-        redate_history::{RedateHistoryData, RedateHistoryDialog},
+        confirmation::ConfirmationDialog, new_history_item::NewHistoryDialog,
+        redate_history::RedateHistoryDialog,
     },
     editor::EditorState,
     errors::LoreGuiError,
     history_view::{HistoryViewMessage, HistoryViewState},
+    localisation::tr_args,
 };
 
 use super::{message_handling::GuiMessage, SqlGui};
@@ -27,7 +27,7 @@ impl SqlGui {
                 self.dialog = Some(Box::new(RedateHistoryDialog::new(data)))
             }
             HistoryViewMessage::DeleteHistoryItem(timestamp) => {
-                let message = format!("Do you really want to delete {}?", timestamp);
+                let message = tr_args("confirm-delete-history-item", &[("timestamp", &timestamp)]);
                 let on_confirm = GuiMessage::DeleteHistoryItem(timestamp);
                 self.dialog = Some(Box::new(ConfirmationDialog::new(message, on_confirm)))
             }
@@ -40,21 +40,9 @@ impl SqlGui {
                 self.history_view_state.current_content.perform(action)
             }
             HistoryViewMessage::ContentDiscard => self.history_view_state.current_content.reset(),
-            HistoryViewMessage::ContentSave => {
-                let db = self
-                    .lore_database
-                    .as_ref()
-                    .ok_or(LoreGuiError::NoDatabase)?;
-                let timestamp = match self.get_selected_timestamp() {
-                    Some(t) => t,
-                    None => return Ok(()),
-                };
-                let content = HistoryItemContent::from(self.get_history_text());
-                db.change_history_item_content(timestamp, &content)?;
-                self.history_view_state.current_content.saved();
-            }
+            // Saving is a database write, see SqlGui::database_write().
+            HistoryViewMessage::ContentSave => {}
         };
-        self.history_view_state.update(&self.lore_database)?;
         Ok(())
     }
 
@@ -68,9 +56,10 @@ impl SqlGui {
                 state.set_selected_year(year.0);
                 state.set_selected_day(None);
                 state.set_selected_timestamp(None);
+                state.set_content_text("");
+                state.invalidate();
             }
         };
-        self.history_view_state.update(&self.lore_database)?;
         Ok(())
     }
 
@@ -83,9 +72,10 @@ impl SqlGui {
             ColViewMes::Selected(_index, day) => {
                 state.set_selected_day(day.0);
                 state.set_selected_timestamp(None);
+                state.set_content_text("");
+                state.invalidate();
             }
         };
-        self.history_view_state.update(&self.lore_database)?;
         Ok(())
     }
 
@@ -100,114 +90,20 @@ impl SqlGui {
             }
             ColViewMes::Selected(_index, timestamp) => {
                 state.set_selected_timestamp(timestamp.0);
+                state.set_content_text("");
+                state.invalidate();
             }
         };
-        self.history_view_state.update(&self.lore_database)?;
-        Ok(())
-    }
-
-    pub(super) fn write_new_history(&mut self, data: NewHistoryData) -> Result<(), LoreGuiError> {
-        let db = self
-            .lore_database
-            .as_ref()
-            .ok_or(LoreGuiError::NoDatabase)?;
-        let year = data.year().clone();
-        let day = data.day().clone();
-        let timestamp = data.timestamp().clone();
-        data.write_to_database(db)?;
-        self.set_selected_year(Some(year));
-        self.set_selected_day(Some(day));
-        self.set_selected_timestamp(Some(timestamp));
-        Ok(())
-    }
-
-    pub(super) fn redate_history_item(
-        &mut self,
-        data: RedateHistoryData,
-    ) -> Result<(), LoreGuiError> {
-        let db = self
-            .lore_database
-            .as_ref()
-            .ok_or(LoreGuiError::NoDatabase)?;
-        let year = data.new_year().clone();
-        let day = data.new_day().clone();
-        let timestamp = data.timestamp();
-        data.update_date_in_database(db)?;
-        self.set_selected_year(Some(year));
-        self.set_selected_day(Some(day));
-        self.set_selected_timestamp(Some(timestamp));
-        Ok(())
-    }
-
-    pub(super) fn delete_history_item(&mut self, timestamp: Timestamp) -> Result<(), LoreGuiError> {
-        let db = self
-            .lore_database
-            .as_ref()
-            .ok_or(LoreGuiError::NoDatabase)?;
-        db.delete_history_item(timestamp)?;
-        self.set_selected_year(None);
-        self.set_selected_day(None);
-        self.set_selected_timestamp(None);
-        self.set_history_text("");
         Ok(())
     }
 }
 
 impl HistoryViewState {
-    pub(super) fn reset_selections(
-        &mut self,
-        db: &Option<LoreDatabase>,
-    ) -> Result<(), LoreGuiError> {
+    pub(super) fn reset_selections(&mut self) {
         self.year_view_state.set_selected(DbColViewEntry::NONE);
         self.day_view_state.set_selected(DbColViewEntry::NONE);
         self.timestamp_view_state.set_selected(DbColViewEntry::NONE);
-        self.current_content = EditorState::default();
-        self.update_years(db)?;
-        Ok(())
-    }
-
-    pub(super) fn update(&mut self, db: &Option<LoreDatabase>) -> Result<(), LoreGuiError> {
-        self.update_years(db)?;
-        self.update_days(db)?;
-        self.update_timestamps(db)?;
-        self.update_content(db)?;
-        Ok(())
-    }
-
-    fn update_years(&mut self, db: &Option<LoreDatabase>) -> Result<(), LoreGuiError> {
-        let years = self
-            .get_current_years(db)?
-            .into_iter()
-            .map(|y| DbColViewEntry(Some(y)))
-            .collect();
-        self.year_view_state.set_entries(years);
-        Ok(())
-    }
-
-    fn update_days(&mut self, db: &Option<LoreDatabase>) -> Result<(), LoreGuiError> {
-        let days = self
-            .get_current_days(db)?
-            .into_iter()
-            .map(|d| DbColViewEntry(Some(d)))
-            .collect();
-        self.day_view_state.set_entries(days);
-        Ok(())
-    }
-
-    fn update_timestamps(&mut self, db: &Option<LoreDatabase>) -> Result<(), LoreGuiError> {
-        let timestamps = self
-            .get_current_timestamps(db)?
-            .into_iter()
-            .map(|t| DbColViewEntry(Some(t)))
-            .collect();
-        self.timestamp_view_state.set_entries(timestamps);
-        Ok(())
-    }
-
-    fn update_content(&mut self, db: &Option<LoreDatabase>) -> Result<(), LoreGuiError> {
-        let content = self.get_current_content(db)?;
-        self.current_content = EditorState::new(content.to_str());
-        Ok(())
+        self.set_content_editor(EditorState::default());
     }
 }
 
@@ -7,11 +7,11 @@ use lorecore::{
 use crate::{
     db_col_view::{entry::DbColViewEntry, ColViewMes},
     dialog::{
-        change_role::{ChangeRoleData, ChangeRoleDialog},
-        confirmation::ConfirmationDialog,
-        new_relationship::{NewRelationshipData, NewRelationshipDialog},
+        change_role::ChangeRoleDialog, confirmation::ConfirmationDialog,
+        new_relationship::NewRelationshipDialog,
     },
     errors::LoreGuiError,
+    localisation::tr_args,
     relationship_view::{RelationshipViewMessage, RelationshipViewState},
 };
 
@@ -34,9 +34,13 @@ impl SqlGui {
                 self.dialog = Some(Box::new(ChangeRoleDialog::new(data.clone())));
             }
             RelationshipViewMessage::DeleteRelationship(rel) => {
-                let message = format!(
-                    "Do you really want to delete the {} relationship between {} and {}?",
-                    rel.role, rel.parent, rel.child
+                let message = tr_args(
+                    "confirm-delete-relationship",
+                    &[
+                        ("role", &rel.role),
+                        ("parent", &rel.parent),
+                        ("child", &rel.child),
+                    ],
                 );
                 let on_confirm = GuiMessage::DeleteRelationship(rel);
                 self.dialog = Some(Box::new(ConfirmationDialog::new(message, on_confirm)))
@@ -51,7 +55,6 @@ impl SqlGui {
                 self.update_role_view(event)?;
             }
         };
-        self.relationship_view_state.update(&self.lore_database)?;
         Ok(())
     }
 
@@ -69,7 +72,6 @@ impl SqlGui {
                 state.set_selected_role(None);
             }
         };
-        self.relationship_view_state.update(&self.lore_database)?;
         Ok(())
     }
 
@@ -87,7 +89,6 @@ impl SqlGui {
                 state.set_selected_role(None);
             }
         };
-        self.relationship_view_state.update(&self.lore_database)?;
         Ok(())
     }
 
@@ -101,58 +102,6 @@ impl SqlGui {
                 state.set_selected_role(role.0);
             }
         };
-        self.relationship_view_state.update(&self.lore_database)?;
-        Ok(())
-    }
-
-    pub(super) fn write_new_relationship(
-        &mut self,
-        data: NewRelationshipData,
-    ) -> Result<(), LoreGuiError> {
-        let db = self
-            .lore_database
-            .as_ref()
-            .ok_or(LoreGuiError::NoDatabase)?;
-        let parent = data.parent().clone();
-        let child = data.child().clone();
-        let role = data.role().clone();
-        data.write_to_database(db)?;
-        self.set_selected_parent(Some(parent));
-        self.set_selected_child(Some(child));
-        self.set_selected_role(Some(role));
-        Ok(())
-    }
-
-    pub(super) fn change_relationship_role(
-        &mut self,
-        data: ChangeRoleData,
-    ) -> Result<(), LoreGuiError> {
-        let db = self
-            .lore_database
-            .as_ref()
-            .ok_or(LoreGuiError::NoDatabase)?;
-        let parent = data.parent().clone().into();
-        let child = data.child().clone().into();
-        let role = data.new_role().clone().into();
-        data.write_to_database(db)?;
-        self.set_selected_parent(parent);
-        self.set_selected_child(child);
-        self.set_selected_role(role);
-        Ok(())
-    }
-
-    pub(super) fn delete_relationship(
-        &mut self,
-        rel: EntityRelationship,
-    ) -> Result<(), LoreGuiError> {
-        let db = self
-            .lore_database
-            .as_ref()
-            .ok_or(LoreGuiError::NoDatabase)?;
-        db.delete_relationship(rel)?;
-        self.set_selected_parent(None);
-        self.set_selected_child(None);
-        self.set_selected_role(None);
         Ok(())
     }
 
@@ -169,54 +118,10 @@ impl SqlGui {
 }
 
 impl RelationshipViewState {
-    pub(super) fn reset_selections(
-        &mut self,
-        db: &Option<LoreDatabase>,
-    ) -> Result<(), LoreGuiError> {
+    pub(super) fn reset_selections(&mut self) {
         self.parent_view_state.set_selected(DbColViewEntry::NONE);
         self.child_view_state.set_selected(DbColViewEntry::NONE);
         self.role_view_state.set_selected(DbColViewEntry::NONE);
-        self.update_parents(db)?;
-        self.update_children(db)?;
-        self.update_role(db)?;
-        Ok(())
-    }
-
-    pub(super) fn update(&mut self, db: &Option<LoreDatabase>) -> Result<(), LoreGuiError> {
-        self.update_parents(db)?;
-        self.update_children(db)?;
-        self.update_role(db)?;
-        Ok(())
-    }
-
-    fn update_parents(&mut self, db: &Option<LoreDatabase>) -> Result<(), LoreGuiError> {
-        let parents = self
-            .get_current_parents(db)?
-            .into_iter()
-            .map(|p| DbColViewEntry(Some(p)))
-            .collect();
-        self.parent_view_state.set_entries(parents);
-        Ok(())
-    }
-
-    fn update_children(&mut self, db: &Option<LoreDatabase>) -> Result<(), LoreGuiError> {
-        let children = self
-            .get_current_children(db)?
-            .into_iter()
-            .map(|c| DbColViewEntry(Some(c)))
-            .collect();
-        self.child_view_state.set_entries(children);
-        Ok(())
-    }
-
-    fn update_role(&mut self, db: &Option<LoreDatabase>) -> Result<(), LoreGuiError> {
-        let roles = self
-            .get_current_roles(db)?
-            .into_iter()
-            .map(|r| DbColViewEntry(Some(r)))
-            .collect();
-        self.role_view_state.set_entries(roles);
-        Ok(())
     }
 }
 
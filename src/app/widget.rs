@@ -1,116 +1,350 @@
-use std::path::PathBuf;
-
-use super::{message_handling::GuiMessage, SqlGui, ViewType};
+use super::{
+    autosave::DraftFiles, message_handling::GuiMessage, refreshing::RefreshGenerations,
+    tabs::DatabaseTab, undo_redo::UndoStack, writing::WriteQueue, SqlGui, ViewType,
+};
 use crate::{
-    dialog::error::ErrorDialog,
+    appearance::Appearance,
+    commands::button_row,
+    dashboard::{self, DashboardState},
+    dialog::database_chooser::DatabaseChooserDialog,
+    drafts::autosave_ticks,
     entity_view::{self, EntityViewState},
-    errors::LoreGuiError,
+    error_log::{self, ErrorLog},
+    file_watcher::watch_database,
+    global_search::{self, GlobalSearchState},
     history_view::{self, HistoryViewState},
+    localisation::{self, tr},
+    notifications::{self, notification_ticks, Notifications},
+    query_cache::SharedQueryCache,
+    references::{ReadMode, Reading},
     relationship_view::{self, RelationshipViewState},
-    user_preferences::load_database_path,
+    search_index::SearchIndexState,
+    shortcuts::KeyBindings,
+    status_bar::{self, StatusBar},
+    style::Sizes,
+    user_preferences::{
+        load_appearance, load_database_sessions, load_key_bindings, load_recent_databases,
+        DatabaseSessions, RecentDatabases,
+    },
 };
 use iced::{
-    widget::{button, opaque, stack, Button, Column, Container, Row, Text},
-    Alignment, Element, Length,
+    keyboard,
+    widget::{button, opaque, stack, text_input, tooltip, Button, Column, Container, Row},
+    window, Alignment, Element, Length, Subscription, Task, Theme,
 };
 
 impl SqlGui {
+    /// A state without a database, which neither reads nor writes the user's preferences.
     fn new() -> Self {
-        let mut gui = SqlGui {
+        SqlGui {
             selected_view: super::ViewType::default(),
             entity_view_state: EntityViewState::default(),
             history_view_state: HistoryViewState::default(),
             relationship_view_state: RelationshipViewState::default(),
+            dashboard_state: DashboardState::default(),
+            global_search: GlobalSearchState::default(),
+            error_log: ErrorLog::default(),
+            notifications: Notifications::default(),
+            status_bar: StatusBar::default(),
+            read_mode: ReadMode::default(),
             lore_database: None,
+            undo_stack: UndoStack::default(),
+            query_cache: SharedQueryCache::default(),
+            search_index: SearchIndexState::default(),
+            changed_tables: vec![],
+            writes: WriteQueue::default(),
+            database_stamp: None,
+            draft_files: DraftFiles::default(),
+            refresh_generations: RefreshGenerations::default(),
+            tabs: vec![DatabaseTab::default()],
+            active_tab: 0,
+            recent_databases: RecentDatabases::default(),
+            database_sessions: DatabaseSessions::default(),
+            sessions_changed: false,
+            key_bindings: KeyBindings::default(),
+            appearance: Appearance::default(),
+            preferences_loaded: false,
+            focused_column: 0,
+            exit_requested: false,
             dialog: None,
-        };
-        if let Some(path) = load_database_path() {
-            match gui.initialise(path) {
-                Ok(_) => (),
-                Err(e) => gui.dialog = Some(Box::new(ErrorDialog::new(e))),
-            };
         }
-        gui
     }
 
-    pub(crate) fn update(&mut self, message: GuiMessage) {
-        if let Err(e) = self.handle_message(message) {
-            self.dialog = Some(Box::new(ErrorDialog::new(e)));
+    /// Creates the initial state together with the task reading the views of the last used database.
+    pub(crate) fn init() -> (Self, Task<GuiMessage>) {
+        let mut gui = SqlGui::new();
+        gui.writes.run_in_background();
+        gui.load_preferences();
+        gui.open_last_database();
+        gui.refresh_status_bar();
+        let task = gui.refresh_visible_view();
+        (gui, task)
+    }
+
+    /// Reads the user's preferences. Only from then on are changes to them stored.
+    fn load_preferences(&mut self) {
+        self.recent_databases = load_recent_databases();
+        self.database_sessions = load_database_sessions();
+        self.key_bindings = load_key_bindings();
+        self.appearance = load_appearance();
+        self.preferences_loaded = true;
+    }
+
+    /// Opens the most recently used database, or offers the other recent ones if that fails.
+    fn open_last_database(&mut self) {
+        let last_database = self
+            .recent_databases
+            .most_recent()
+            .map(|db| db.path.clone());
+        let recent_before_pruning = self.recent_databases.clone();
+        self.recent_databases.prune_missing();
+        if self.recent_databases != recent_before_pruning {
+            if let Err(e) = self.save_recent_databases() {
+                self.report_warning(e);
+            }
+        }
+        if let Some(path) = last_database {
+            if let Err(e) = self.open_database(path) {
+                self.error_log.push(e.clone());
+                let entries = self.recent_databases.entries().to_vec();
+                self.dialog = Some(Box::new(DatabaseChooserDialog::after_error(entries, e)));
+            } else if let Err(e) = self.offer_draft_recovery() {
+                self.report_error(e);
+            }
+        }
+    }
+
+    pub(crate) fn update(&mut self, message: GuiMessage) -> Task<GuiMessage> {
+        if let GuiMessage::CopyToClipboard(text) = message {
+            return iced::clipboard::write(text);
+        }
+        let had_dialog = self.dialog.is_some();
+        let in_background = matches!(
+            message,
+            GuiMessage::AutosaveDrafts | GuiMessage::SearchIndexUpdated(..)
+        );
+        match self.handle_message(message) {
+            Err(e) if in_background => self.report_warning(e),
+            Err(e) => self.report_error(e),
+            Ok(()) => {}
+        }
+        let focus = match self
+            .dialog
+            .as_ref()
+            .and_then(|dialog| dialog.focused_input())
+        {
+            Some(input) if !had_dialog => text_input::focus(input),
+            _ => Task::none(),
+        };
+        if !self.changed_tables.is_empty() {
+            self.remember_database_stamp();
+        }
+        self.remember_session();
+        // Exiting waits for the writes that were started before.
+        // If the drafts cannot be written, LoreGui stays open, so that the unsaved changes can still be saved.
+        if self.exit_requested && self.writes.is_empty() {
+            if let Err(e) = self.store_sessions() {
+                self.report_warning(e);
+            }
+            match self.autosave_drafts() {
+                Ok(()) => return iced::exit(),
+                Err(e) => {
+                    self.exit_requested = false;
+                    self.report_error(e);
+                }
+            }
+        }
+        // The language is previewed like the rest of the appearance.
+        localisation::apply(self.current_appearance().locale);
+        let write = self.run_queued_write();
+        let refresh = self.refresh_visible_view();
+        self.refresh_status_bar();
+        if let Err(e) = self.refresh_read_mode() {
+            self.report_warning(e);
         }
+        Task::batch([focus, write, refresh])
+    }
+
+    /// The appearance being edited in the settings, otherwise the saved one.
+    fn current_appearance(&self) -> &Appearance {
+        self.dialog
+            .as_ref()
+            .and_then(|dialog| dialog.appearance_preview())
+            .unwrap_or(&self.appearance)
+    }
+
+    pub(crate) fn theme(&self) -> Theme {
+        self.current_appearance().theme()
+    }
+
+    pub(crate) fn sizes(&self) -> Sizes {
+        self.current_appearance().sizes()
+    }
+
+    pub(crate) fn subscription(&self) -> Subscription<GuiMessage> {
+        let key_presses =
+            keyboard::on_key_press(|key, modifiers| Some(GuiMessage::KeyPressed(key, modifiers)));
+        let close_requests = window::close_requests().map(|_| GuiMessage::WindowCloseRequested);
+        let database_paths = self.open_database_paths();
+        let autosave = if database_paths.is_empty() {
+            Subscription::none()
+        } else {
+            autosave_ticks()
+        };
+        let notification_expiry = if self.notifications.has_active() {
+            notification_ticks()
+        } else {
+            Subscription::none()
+        };
+        let database_watchers = database_paths.into_iter().map(watch_database);
+        Subscription::batch(
+            [key_presses, close_requests, autosave, notification_expiry]
+                .into_iter()
+                .chain(database_watchers),
+        )
     }
 
     pub(crate) fn view(&self) -> iced::Element<'_, GuiMessage> {
+        let sizes = self.sizes();
+        let mut layers = stack![self.main_view()];
         if let Some(dialog) = self.dialog.as_ref() {
-            stack![self.main_view(), opaque(dialog.to_element())].into()
-        } else {
-            self.main_view()
+            layers = layers.push(opaque(dialog.to_element(sizes)));
+        }
+        if self.notifications.has_active() {
+            layers = layers.push(notifications::widget::toasts(&self.notifications, sizes));
         }
+        layers.into()
     }
 
     fn main_view(&self) -> Element<'_, GuiMessage> {
+        let sizes = self.sizes();
+        let theme = self.theme();
+        let reading = Reading {
+            read_mode: &self.read_mode,
+            theme: &theme,
+        };
         let mut col = Column::new()
             .push(self.menu_bar())
+            .push(self.tab_bar())
             .push(self.current_database_display());
-        if self.lore_database.is_some() {
+        if self.error_log.is_shown() {
+            col = col.push(error_log::widget::panel(&self.error_log, sizes));
+        } else if self.notifications.is_history_shown() {
+            col = col.push(notifications::widget::history(&self.notifications, sizes));
+        } else if self.lore_database.is_some() && self.global_search.is_shown() {
+            col = col.push(global_search::widget::results(&self.global_search, sizes));
+        } else if self.lore_database.is_some() {
             col = col.push(self.view_selection_bar());
             match self.selected_view {
                 ViewType::Entity => {
-                    col = col.push(entity_view::widget::new(&self.entity_view_state))
+                    col = col.push(entity_view::widget::new(
+                        &self.entity_view_state,
+                        self.tabs.len() > 1,
+                        reading,
+                        self.focused_column(),
+                        sizes,
+                    ))
                 }
                 ViewType::History => {
-                    col = col.push(history_view::widget::new(&self.history_view_state))
+                    col = col.push(history_view::widget::new(
+                        &self.history_view_state,
+                        reading,
+                        self.focused_column(),
+                        sizes,
+                    ))
                 }
                 ViewType::Relationship => {
                     col = col.push(relationship_view::widget::new(
                         &self.relationship_view_state,
+                        self.focused_column(),
+                        sizes,
                     ))
                 }
+                ViewType::Dashboard => {
+                    col = col.push(dashboard::widget::new(&self.dashboard_state, sizes))
+                }
             }
         }
-        col.height(Length::Fill).into()
+        Column::new()
+            .push(col.height(Length::Fill))
+            .push_maybe(
+                self.lore_database
+                    .is_some()
+                    .then(|| status_bar::widget::new(&self.status_bar, sizes)),
+            )
+            .height(Length::Fill)
+            .into()
     }
 
     fn menu_bar(&self) -> Element<'_, GuiMessage> {
-        Row::new()
-            .push(Button::new("New Lore Database").on_press(GuiMessage::NewDatabase))
-            .push(Button::new("Open Lore Database").on_press(GuiMessage::OpenDatabase))
+        let sizes = self.sizes();
+        button_row(&self.menu_commands(), sizes)
+            .push(global_search::widget::search_box(
+                &self.global_search,
+                sizes,
+            ))
+            .push_maybe((!self.orphaned_drafts().is_empty()).then(|| {
+                Button::new(sizes.text(tr("command-recover-drafts")))
+                    .on_press(GuiMessage::ShowDraftRecovery)
+            }))
             .align_y(Alignment::Center)
             .width(Length::Fill)
-            .padding(5)
-            .spacing(5)
+            .padding(sizes.spacing)
+            .spacing(sizes.spacing)
             .into()
     }
 
+    fn tab_bar(&self) -> Element<'_, GuiMessage> {
+        let sizes = self.sizes();
+        let mut row = Row::new().spacing(sizes.spacing).padding(sizes.spacing);
+        for (index, title) in self.tab_titles().into_iter().enumerate() {
+            let mut tab_button = button(sizes.text(title));
+            if index != self.active_tab {
+                tab_button = tab_button.on_press(GuiMessage::TabSelected(index));
+            }
+            let close_button =
+                button(sizes.text(tr("tab-close"))).on_press(GuiMessage::TabClosed(index));
+            let close_button = tooltip(
+                close_button,
+                sizes.text(tr("tab-close-tooltip")),
+                tooltip::Position::Bottom,
+            );
+            row = row.push(Row::new().push(tab_button).push(close_button));
+        }
+        row.into()
+    }
+
     fn current_database_display(&self) -> Element<'_, GuiMessage> {
+        let sizes = self.sizes();
         let content = match self.lore_database.as_ref() {
             Some(db) => db.path_as_string(),
-            None => "[No database loaded]".to_string(),
+            None => tr("no-database-loaded"),
         };
-        Container::new(Text::new(content)).padding(5).into()
+        Container::new(sizes.text(content))
+            .padding(sizes.spacing)
+            .into()
     }
 
     fn view_selection_bar(&self) -> Element<'_, GuiMessage> {
-        let entity_button =
-            button(Text::new("Entities")).on_press(GuiMessage::ViewSelected(ViewType::Entity));
-        let history_items_button = button(Text::new("History Items"))
+        let sizes = self.sizes();
+        let entity_button = button(sizes.text(tr("view-entities")))
+            .on_press(GuiMessage::ViewSelected(ViewType::Entity));
+        let history_items_button = button(sizes.text(tr("view-history-items")))
             .on_press(GuiMessage::ViewSelected(ViewType::History));
-        let relationships_button = button(Text::new("Relationships"))
+        let relationships_button = button(sizes.text(tr("view-relationships")))
             .on_press(GuiMessage::ViewSelected(ViewType::Relationship));
+        let dashboard_button = button(sizes.text(tr("view-dashboard")))
+            .on_press(GuiMessage::ViewSelected(ViewType::Dashboard));
         Row::new()
             .push(entity_button)
             .push(history_items_button)
             .push(relationships_button)
+            .push(dashboard_button)
             .width(Length::Fill)
-            .padding(5)
-            .spacing(5)
+            .padding(self.sizes().spacing)
+            .spacing(self.sizes().spacing)
             .into()
     }
-
-    fn initialise(&mut self, path: PathBuf) -> Result<(), LoreGuiError> {
-        self.open_database(path)?;
-        self.update_database_derived_data()?;
-        Ok(())
-    }
 }
 
 impl Default for SqlGui {
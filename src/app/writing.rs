@@ -0,0 +1,636 @@
+use std::{collections::VecDeque, path::PathBuf};
+
+use iced::Task;
+use lorecore::{sql::lore_database::LoreDatabase, types::*};
+
+use super::{
+    message_handling::GuiMessage,
+    undo_redo::{DatabaseSnapshot, Selection, UndoScope, UndoStep},
+    SqlGui,
+};
+use crate::{
+    dialog::{
+        change_category::ChangeCategoryData, change_role::ChangeRoleData,
+        copy_entity::CopyEntityData, duplicate_entity::DuplicateEntityData,
+        merge_entities::MergeEntitiesData, new_descriptor::NewDescriptorData,
+        new_entity::NewEntityData, new_history_item::NewHistoryData,
+        new_relationship::NewRelationshipData, redate_history::RedateHistoryData,
+        relabel_entity::RelabelEntityData, rename_descriptor::RenameDescriptorData,
+        rename_descriptor_everywhere::RenameDescriptorEverywhereData,
+    },
+    entity_view::{EntityViewMessage, EntityViewState},
+    errors::{ErrorContext, LoreGuiError},
+    history_view::{HistoryViewMessage, HistoryViewState},
+    localisation::tr,
+    notifications::NotificationLevel,
+    query_cache::Table,
+};
+
+/// A change to a database, detached from the GUI state so that it can run in the background.
+#[derive(Debug, Clone)]
+pub(super) enum DatabaseWrite {
+    NewEntity(NewEntityData),
+    RelabelEntity(RelabelEntityData),
+    DeleteEntity(Label),
+    CopyEntity(CopyEntityData),
+    DuplicateEntity(DuplicateEntityData),
+    MergeEntities(MergeEntitiesData),
+    ChangeCategory(ChangeCategoryData),
+    NewDescriptor(NewDescriptorData),
+    RenameDescriptor(RenameDescriptorData),
+    RenameDescriptorEverywhere(RenameDescriptorEverywhereData),
+    DeleteDescriptor(Label, Descriptor),
+    ChangeDescription(Label, Descriptor, Description),
+    NewHistoryItem(NewHistoryData),
+    RedateHistoryItem(RedateHistoryData),
+    DeleteHistoryItem(Timestamp),
+    ChangeContent(Timestamp, HistoryItemContent),
+    NewRelationship(NewRelationshipData),
+    ChangeRole(ChangeRoleData),
+    DeleteRelationship(EntityRelationship),
+    /// The step is only taken off the undo stack once the write starts, so that it follows all writes queued before.
+    Undo(Option<UndoStep>),
+    Redo(Option<UndoStep>),
+}
+
+impl DatabaseWrite {
+    fn run(self, db: &LoreDatabase) -> Result<(), LoreGuiError> {
+        match self {
+            DatabaseWrite::NewEntity(data) => data.write_to_database(db)?,
+            DatabaseWrite::RelabelEntity(data) => data.update_label_in_database(db)?,
+            DatabaseWrite::DeleteEntity(label) => db.delete_entity(label)?,
+            DatabaseWrite::CopyEntity(data) => {
+                let target = LoreDatabase::open(PathBuf::from(data.target_database()))?;
+                data.copy_between(db, &target)?
+            }
+            DatabaseWrite::DuplicateEntity(data) => data.write_to_database(db)?,
+            DatabaseWrite::MergeEntities(data) => data.write_to_database(db)?,
+            DatabaseWrite::ChangeCategory(data) => data.write_to_database(db)?,
+            DatabaseWrite::NewDescriptor(data) => data.write_to_database(db)?,
+            DatabaseWrite::RenameDescriptor(data) => data.update_descriptor_in_database(db)?,
+            DatabaseWrite::RenameDescriptorEverywhere(data) => {
+                data.update_descriptors_in_database(db)?
+            }
+            DatabaseWrite::DeleteDescriptor(label, descriptor) => {
+                db.delete_entity_column((label, descriptor))?
+            }
+            DatabaseWrite::ChangeDescription(label, descriptor, description) => {
+                db.change_entity_description((&label, &descriptor), &description)?
+            }
+            DatabaseWrite::NewHistoryItem(data) => data.write_to_database(db)?,
+            DatabaseWrite::RedateHistoryItem(data) => data.update_date_in_database(db)?,
+            DatabaseWrite::DeleteHistoryItem(timestamp) => db.delete_history_item(timestamp)?,
+            DatabaseWrite::ChangeContent(timestamp, content) => {
+                db.change_history_item_content(timestamp, &content)?
+            }
+            DatabaseWrite::NewRelationship(data) => data.write_to_database(db)?,
+            DatabaseWrite::ChangeRole(data) => data.write_to_database(db)?,
+            DatabaseWrite::DeleteRelationship(relationship) => {
+                db.delete_relationship(relationship)?
+            }
+            DatabaseWrite::Undo(step) => {
+                if let Some(step) = step {
+                    step.undo(db)?
+                }
+            }
+            DatabaseWrite::Redo(step) => {
+                if let Some(step) = step {
+                    step.redo(db)?
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// The tables of the written database that may have changed.
+    pub(super) fn changed_tables(&self) -> &'static [Table] {
+        match self {
+            DatabaseWrite::NewEntity(_)
+            | DatabaseWrite::NewDescriptor(_)
+            | DatabaseWrite::RenameDescriptor(_)
+            | DatabaseWrite::RenameDescriptorEverywhere(_)
+            | DatabaseWrite::ChangeCategory(_)
+            | DatabaseWrite::DeleteDescriptor(_, _)
+            | DatabaseWrite::ChangeDescription(_, _, _) => &[Table::Entities],
+            DatabaseWrite::RelabelEntity(_)
+            | DatabaseWrite::DeleteEntity(_)
+            | DatabaseWrite::DuplicateEntity(_)
+            | DatabaseWrite::MergeEntities(_) => &[Table::Entities, Table::Relationships],
+            DatabaseWrite::NewHistoryItem(_)
+            | DatabaseWrite::RedateHistoryItem(_)
+            | DatabaseWrite::DeleteHistoryItem(_)
+            | DatabaseWrite::ChangeContent(_, _) => &[Table::History],
+            DatabaseWrite::NewRelationship(_)
+            | DatabaseWrite::ChangeRole(_)
+            | DatabaseWrite::DeleteRelationship(_) => &[Table::Relationships],
+            // Only the database of another tab is written to.
+            DatabaseWrite::CopyEntity(_) => &[],
+            DatabaseWrite::Undo(_) | DatabaseWrite::Redo(_) => &Table::ALL,
+        }
+    }
+
+    /// Marks the text of an editor as saved, if this write saved it for the item the editor belongs to.
+    pub(super) fn mark_saved(
+        &self,
+        entity_view_state: &mut EntityViewState,
+        history_view_state: &mut HistoryViewState,
+    ) {
+        match self {
+            DatabaseWrite::ChangeDescription(label, descriptor, description) => {
+                entity_view_state.description_saved(label, descriptor, description)
+            }
+            DatabaseWrite::ChangeContent(timestamp, content) => {
+                history_view_state.content_saved(*timestamp, content)
+            }
+            _ => {}
+        }
+    }
+}
+
+/// The part of the database a write touched, before and after it.
+pub(super) type WrittenSnapshots = Option<(DatabaseSnapshot, DatabaseSnapshot)>;
+
+/// Runs `write`, reading the part of the database in `scope` before and after it so that it can be undone.
+///
+/// A write that fails halfway is rolled back, so that writes of several steps are applied completely or not at all.
+fn run_write(
+    db: &LoreDatabase,
+    write: DatabaseWrite,
+    scope: Option<&UndoScope>,
+) -> Result<WrittenSnapshots, LoreGuiError> {
+    let scope = match scope {
+        Some(scope) => scope,
+        None => return write.run(db).map(|()| None),
+    };
+    let before = DatabaseSnapshot::read(db, scope)?;
+    if let Err(e) = write.run(db) {
+        roll_back(db, scope, &before);
+        return Err(e);
+    }
+    let after = DatabaseSnapshot::read(db, scope)?;
+    Ok(Some((before, after)))
+}
+
+/// Restores the part of the database in `scope` to `before`. If this fails as well, the error of the write is the one reported.
+fn roll_back(db: &LoreDatabase, scope: &UndoScope, before: &DatabaseSnapshot) {
+    if let Ok(current) = DatabaseSnapshot::read(db, scope) {
+        let _ = current.transition_to(before, db);
+    }
+}
+
+/// The outcome of a write that ran in the background.
+#[derive(Debug, Clone)]
+pub(crate) struct Written {
+    result: Result<WrittenSnapshots, LoreGuiError>,
+}
+
+/// A write together with what is needed to finish it once its outcome is known.
+struct PendingWrite {
+    database: String,
+    write: DatabaseWrite,
+    scope: Option<UndoScope>,
+    selection_before: Selection,
+    context: Option<ErrorContext>,
+    notification: Option<String>,
+}
+
+/// Writes run one after another, in the order they were made.
+#[derive(Default)]
+pub(super) struct WriteQueue {
+    /// Without this, writes run right away, which is what tests rely on.
+    in_background: bool,
+    queued: VecDeque<PendingWrite>,
+    running: Option<PendingWrite>,
+}
+
+impl WriteQueue {
+    pub(super) fn run_in_background(&mut self) {
+        self.in_background = true;
+    }
+
+    pub(super) fn is_empty(&self) -> bool {
+        self.running.is_none() && self.queued.is_empty()
+    }
+
+    /// Whether a write to the database at `path` has been started, but not yet finished.
+    pub(super) fn is_writing_to(&self, path: &str) -> bool {
+        self.running
+            .iter()
+            .chain(self.queued.iter())
+            .any(|pending| pending.database == path)
+    }
+}
+
+impl SqlGui {
+    /// The write to the active database that handling `message` consists of, if any.
+    pub(super) fn database_write(
+        &self,
+        message: &GuiMessage,
+    ) -> Result<Option<DatabaseWrite>, LoreGuiError> {
+        let write = match message.clone() {
+            GuiMessage::NewEntity(data) => DatabaseWrite::NewEntity(data),
+            GuiMessage::RelabelEntity(data) => DatabaseWrite::RelabelEntity(data),
+            GuiMessage::DeleteEntity(label) => DatabaseWrite::DeleteEntity(label),
+            GuiMessage::CopyEntity(data) => {
+                let target_tab = self
+                    .find_tab(data.target_database())
+                    .filter(|index| *index != self.active_tab);
+                if target_tab.is_none() {
+                    return Err(LoreGuiError::InputError(tr("error-copy-target-missing")));
+                }
+                DatabaseWrite::CopyEntity(data)
+            }
+            GuiMessage::DuplicateEntity(data) => DatabaseWrite::DuplicateEntity(data),
+            GuiMessage::MergeEntities(data) => DatabaseWrite::MergeEntities(data),
+            GuiMessage::ChangeCategory(data) => DatabaseWrite::ChangeCategory(data),
+            GuiMessage::NewDescriptor(data) => DatabaseWrite::NewDescriptor(data),
+            GuiMessage::RenameDescriptor(data) => DatabaseWrite::RenameDescriptor(data),
+            GuiMessage::RenameDescriptorEverywhere(data) => {
+                DatabaseWrite::RenameDescriptorEverywhere(data)
+            }
+            GuiMessage::DeleteDescriptor(label, descriptor) => {
+                DatabaseWrite::DeleteDescriptor(label, descriptor)
+            }
+            GuiMessage::EntityViewUpd(EntityViewMessage::DescriptionSave) => {
+                let (label, descriptor) = match self.entity_view_state.description_source() {
+                    Some(source) => source.clone(),
+                    None => return Ok(None),
+                };
+                let description = self.get_description_text().into();
+                DatabaseWrite::ChangeDescription(label, descriptor, description)
+            }
+            GuiMessage::NewHistoryItem(data) => DatabaseWrite::NewHistoryItem(data),
+            GuiMessage::RedateHistoryItem(data) => DatabaseWrite::RedateHistoryItem(data),
+            GuiMessage::DeleteHistoryItem(timestamp) => DatabaseWrite::DeleteHistoryItem(timestamp),
+            GuiMessage::HistoryViewUpd(HistoryViewMessage::ContentSave) => {
+                let timestamp = match self.history_view_state.content_timestamp() {
+                    Some(timestamp) => timestamp,
+                    None => return Ok(None),
+                };
+                let content = self.get_history_text().into();
+                DatabaseWrite::ChangeContent(timestamp, content)
+            }
+            GuiMessage::NewRelationship(data) => DatabaseWrite::NewRelationship(data),
+            GuiMessage::ChangeRole(data) => DatabaseWrite::ChangeRole(data),
+            GuiMessage::DeleteRelationship(relationship) => {
+                DatabaseWrite::DeleteRelationship(relationship)
+            }
+            GuiMessage::Undo => DatabaseWrite::Undo(None),
+            GuiMessage::Redo => DatabaseWrite::Redo(None),
+            _ => return Ok(None),
+        };
+        if self.lore_database.is_none() {
+            return Err(LoreGuiError::NoDatabase);
+        }
+        Ok(Some(write))
+    }
+
+    /// Writes to the active database, or queues the write if writes run in the background.
+    ///
+    /// A write running in the background reports its error and notification once it has finished.
+    pub(super) fn start_write(
+        &mut self,
+        write: DatabaseWrite,
+        context: Option<ErrorContext>,
+        notification: Option<String>,
+    ) -> Result<(), LoreGuiError> {
+        let database = self
+            .lore_database
+            .as_ref()
+            .ok_or(LoreGuiError::NoDatabase)?
+            .path_as_string();
+        let pending = PendingWrite {
+            database,
+            scope: write.undo_scope(),
+            write,
+            selection_before: self.current_selection(),
+            context,
+            notification,
+        };
+        if self.writes.in_background {
+            self.writes.queued.push_back(pending);
+            return Ok(());
+        }
+        let pending = self.take_undo_step_for(pending);
+        let db = self
+            .lore_database
+            .as_ref()
+            .ok_or(LoreGuiError::NoDatabase)?;
+        let result = run_write(db, pending.write.clone(), pending.scope.as_ref());
+        self.finish_write(pending, result)
+    }
+
+    /// Starts the next queued write in the background, unless a write is still running.
+    pub(super) fn run_queued_write(&mut self) -> Task<GuiMessage> {
+        if self.writes.running.is_some() {
+            return Task::none();
+        }
+        let pending = match self.writes.queued.pop_front() {
+            Some(pending) => pending,
+            None => return Task::none(),
+        };
+        let pending = self.take_undo_step_for(pending);
+        let path = PathBuf::from(&pending.database);
+        let write = pending.write.clone();
+        let scope = pending.scope.clone();
+        self.writes.running = Some(pending);
+        Task::perform(
+            async move {
+                let result = LoreDatabase::open(path)
+                    .map_err(LoreGuiError::from)
+                    .and_then(|db| run_write(&db, write, scope.as_ref()));
+                Written { result }
+            },
+            GuiMessage::WriteFinished,
+        )
+    }
+
+    /// Undo and redo take their step off the stack of the database they were made for, if it is still active.
+    fn take_undo_step_for(&mut self, mut pending: PendingWrite) -> PendingWrite {
+        let is_active = self
+            .lore_database
+            .as_ref()
+            .is_some_and(|db| db.path_as_string() == pending.database);
+        match &mut pending.write {
+            DatabaseWrite::Undo(step) if is_active => *step = self.undo_stack.take_undo_step(),
+            DatabaseWrite::Redo(step) if is_active => *step = self.undo_stack.take_redo_step(),
+            _ => {}
+        }
+        pending
+    }
+
+    pub(super) fn write_finished(&mut self, written: Written) -> Result<(), LoreGuiError> {
+        match self.writes.running.take() {
+            Some(pending) => self.finish_write(pending, written.result),
+            None => Ok(()),
+        }
+    }
+
+    fn finish_write(
+        &mut self,
+        pending: PendingWrite,
+        result: Result<WrittenSnapshots, LoreGuiError>,
+    ) -> Result<(), LoreGuiError> {
+        let succeeded = result.is_ok();
+        let snapshots = result.as_ref().ok().cloned().flatten();
+        match self.find_tab(&pending.database) {
+            Some(index) if index == self.active_tab => {
+                self.record_changes(pending.write.changed_tables());
+                match pending.scope.as_ref() {
+                    Some(scope) => self.search_index.record(scope.labels(), scope.timestamps()),
+                    None => self.search_index.record_everything(),
+                }
+                if succeeded {
+                    pending
+                        .write
+                        .mark_saved(&mut self.entity_view_state, &mut self.history_view_state);
+                }
+                self.select_written(&pending.write, succeeded);
+                if let Some((before, after)) = snapshots {
+                    let selection_after = self.current_selection();
+                    self.undo_stack.record(
+                        before,
+                        after,
+                        pending.selection_before,
+                        selection_after,
+                    );
+                }
+            }
+            Some(index) => self.tabs[index].write_finished(
+                &pending.write,
+                succeeded,
+                snapshots,
+                pending.selection_before,
+            ),
+            None => {}
+        }
+        if let (DatabaseWrite::CopyEntity(data), true) = (&pending.write, succeeded) {
+            self.invalidate_database(data.target_database(), Table::Entities);
+        }
+        match result {
+            Ok(_) => {
+                if let Some(text) = pending.notification {
+                    self.notify(NotificationLevel::Success, text);
+                }
+                Ok(())
+            }
+            Err(error) => Err(match pending.context {
+                Some(context) => error.in_context(context),
+                None => error,
+            }),
+        }
+    }
+
+    fn invalidate_database(&mut self, path: &str, table: Table) {
+        match self.find_tab(path) {
+            Some(index) if index == self.active_tab => {
+                self.record_changes(&[table]);
+                self.search_index.record_everything();
+            }
+            Some(index) => self.tabs[index].invalidate(table),
+            None => {}
+        }
+    }
+
+    /// Selects what a write created or changed.
+    fn select_written(&mut self, write: &DatabaseWrite, succeeded: bool) {
+        match write {
+            DatabaseWrite::Undo(Some(step)) => self.undo_finished(step.clone(), succeeded),
+            DatabaseWrite::Redo(Some(step)) => self.redo_finished(step.clone(), succeeded),
+            _ if !succeeded => {}
+            DatabaseWrite::NewEntity(data) => self.set_selected_label(Some(data.label().clone())),
+            DatabaseWrite::RelabelEntity(data) => {
+                self.set_selected_label(Some(data.new_label().clone()))
+            }
+            DatabaseWrite::DeleteEntity(_) => self.set_selected_label(None),
+            DatabaseWrite::DuplicateEntity(data) => {
+                self.set_selected_label(Some(data.new_label().clone()));
+                self.set_selected_descriptor(None);
+            }
+            DatabaseWrite::MergeEntities(data) => {
+                self.set_selected_label(Some(data.target().clone()));
+                self.set_selected_descriptor(None);
+            }
+            DatabaseWrite::ChangeCategory(data) => {
+                self.set_selected_label(Some(data.label().clone()))
+            }
+            DatabaseWrite::NewDescriptor(data) => {
+                self.set_selected_label(Some(data.label().clone()));
+                self.set_selected_descriptor(Some(data.descriptor().clone()));
+            }
+            DatabaseWrite::RenameDescriptor(data) => {
+                self.set_selected_label(Some(data.label().clone()));
+                self.set_selected_descriptor(Some(data.new_descriptor().clone()));
+            }
+            // An entity skipped because of a collision still has the old descriptor.
+            DatabaseWrite::RenameDescriptorEverywhere(data) => {
+                if self
+                    .get_selected_label()
+                    .is_some_and(|label| data.labels().contains(&label))
+                {
+                    self.set_selected_descriptor(Some(data.new_descriptor().clone()));
+                }
+            }
+            DatabaseWrite::DeleteDescriptor(label, _) => {
+                self.set_selected_label(Some(label.clone()));
+                self.set_selected_descriptor(None);
+            }
+            DatabaseWrite::NewHistoryItem(data) => {
+                self.set_selected_year(Some(*data.year()));
+                self.set_selected_day(Some(*data.day()));
+                self.set_selected_timestamp(Some(*data.timestamp()));
+            }
+            DatabaseWrite::RedateHistoryItem(data) => {
+                self.set_selected_year(Some(data.new_year()));
+                self.set_selected_day(Some(data.new_day()));
+                self.set_selected_timestamp(Some(data.timestamp()));
+            }
+            DatabaseWrite::DeleteHistoryItem(_) => {
+                self.set_selected_year(None);
+                self.set_selected_day(None);
+                self.set_selected_timestamp(None);
+                self.set_history_text("");
+            }
+            DatabaseWrite::NewRelationship(data) => {
+                self.set_selected_parent(Some(data.parent().clone()));
+                self.set_selected_child(Some(data.child().clone()));
+                self.set_selected_role(Some(data.role().clone()));
+            }
+            DatabaseWrite::ChangeRole(data) => {
+                self.set_selected_parent(Some(data.parent().clone()));
+                self.set_selected_child(Some(data.child().clone()));
+                self.set_selected_role(Some(data.new_role().clone()));
+            }
+            DatabaseWrite::DeleteRelationship(_) => {
+                self.set_selected_parent(None);
+                self.set_selected_child(None);
+                self.set_selected_role(None);
+            }
+            DatabaseWrite::CopyEntity(_)
+            | DatabaseWrite::ChangeDescription(_, _, _)
+            | DatabaseWrite::ChangeContent(_, _)
+            | DatabaseWrite::Undo(None)
+            | DatabaseWrite::Redo(None) => {}
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use lorecore::sql::search_params::EntityColumnSearchParams;
+    use rusqlite::Connection;
+
+    use super::*;
+
+    use crate::{
+        dialog::{
+            merge_entities::tests::example_merge_dialog, new_entity::tests::example_new_entity_data,
+        },
+        tests::{example_database, example_descriptors, example_labels},
+    };
+
+    /// Makes the database abort statements of the kind `event` on entities for which `condition` holds.
+    fn refuse_on_entities(db: &LoreDatabase, event: &str, condition: &str) {
+        let connection = Connection::open(db.path_as_string()).unwrap();
+        connection
+            .execute_batch(&format!(
+                "CREATE TRIGGER refuse BEFORE {event} ON entities WHEN {condition} \
+                 BEGIN SELECT RAISE(ABORT, 'refused'); END;"
+            ))
+            .unwrap();
+    }
+
+    /// Runs the write that was started in the background as the task would.
+    fn finish_running_write(gui: &mut SqlGui) {
+        let _ = gui.run_queued_write();
+        let running = gui.writes.running.as_ref().unwrap();
+        let db = gui.lore_database.as_ref().unwrap();
+        let result = run_write(db, running.write.clone(), running.scope.as_ref());
+        gui.handle_message(GuiMessage::WriteFinished(Written { result }))
+            .unwrap();
+    }
+
+    #[test]
+    fn background_write_is_applied_once_it_has_finished() {
+        let mut gui = SqlGui {
+            lore_database: Some(example_database()),
+            ..Default::default()
+        };
+        gui.writes.run_in_background();
+        let path = gui.lore_database.as_ref().unwrap().path_as_string();
+        let data = example_new_entity_data();
+
+        gui.handle_message(GuiMessage::NewEntity(data.clone()))
+            .unwrap();
+        assert!(gui.writes.is_writing_to(&path));
+        assert_eq!(gui.get_selected_label(), None);
+
+        finish_running_write(&mut gui);
+        assert!(gui.writes.is_empty());
+        assert_eq!(gui.get_selected_label(), Some(data.label().clone()));
+        assert!(gui.undo_stack.can_undo());
+    }
+
+    #[test]
+    fn undo_queued_behind_a_write_undoes_that_write() {
+        let mut gui = SqlGui {
+            lore_database: Some(example_database()),
+            ..Default::default()
+        };
+        gui.writes.run_in_background();
+        let data = example_new_entity_data();
+
+        gui.handle_message(GuiMessage::NewEntity(data)).unwrap();
+        gui.handle_message(GuiMessage::Undo).unwrap();
+        finish_running_write(&mut gui);
+        finish_running_write(&mut gui);
+
+        assert!(!gui.undo_stack.can_undo());
+        assert!(gui.undo_stack.can_redo());
+        assert_eq!(gui.get_selected_label(), None);
+    }
+
+    #[test]
+    fn write_failing_halfway_is_rolled_back() {
+        let db = example_database();
+        let data = example_merge_dialog(&db).data().unwrap();
+        // Deleting the source is the last step, after the relationships have been moved.
+        refuse_on_entities(&db, "DELETE", &format!("OLD.label = '{}'", data.source()));
+        let write = DatabaseWrite::MergeEntities(data);
+        let scope = write.undo_scope().unwrap();
+        let before = DatabaseSnapshot::read(&db, &scope).unwrap();
+
+        assert!(run_write(&db, write, Some(&scope)).is_err());
+        assert_eq!(DatabaseSnapshot::read(&db, &scope).unwrap(), before);
+    }
+
+    #[test]
+    fn rename_everywhere_failing_halfway_is_rolled_back() {
+        let mut gui = SqlGui {
+            lore_database: Some(example_database()),
+            ..Default::default()
+        };
+        let db = gui.lore_database.as_ref().unwrap();
+        let columns_before = db
+            .read_entity_columns(EntityColumnSearchParams::new(None, None))
+            .unwrap();
+        let data = RenameDescriptorEverywhereData::new(
+            &columns_before,
+            example_descriptors()[0].0.clone(),
+            "renamed".into(),
+        );
+        let last_label = data.labels().last().unwrap().clone();
+        refuse_on_entities(db, "UPDATE", &format!("OLD.label = '{}'", last_label));
+
+        assert!(gui
+            .handle_message(GuiMessage::RenameDescriptorEverywhere(data))
+            .is_err());
+        let db = gui.lore_database.as_ref().unwrap();
+        assert_eq!(
+            db.read_entity_columns(EntityColumnSearchParams::new(None, None))
+                .unwrap(),
+            columns_before
+        );
+        assert!(!gui.undo_stack.can_undo());
+    }
+}
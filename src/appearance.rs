@@ -0,0 +1,338 @@
+use std::fmt::Display;
+
+use iced::{theme::Palette, Color, Theme};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    errors::LoreGuiError,
+    localisation::{tr, tr_args, Locale},
+    style::Sizes,
+};
+
+pub(crate) const NOTIFICATION_SECONDS: std::ops::RangeInclusive<u32> = 1..=30;
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub(crate) enum ThemeChoice {
+    /// Earlier versions offered a system theme, which always turned out light.
+    #[default]
+    #[serde(alias = "System")]
+    Light,
+    Dark,
+    Custom(String),
+}
+
+impl Display for ThemeChoice {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ThemeChoice::Light => write!(f, "{}", tr("theme-light")),
+            ThemeChoice::Dark => write!(f, "{}", tr("theme-dark")),
+            ThemeChoice::Custom(name) => write!(f, "{}", name),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum PaletteColor {
+    Background,
+    Text,
+    Primary,
+    Success,
+    Danger,
+}
+
+impl PaletteColor {
+    pub(crate) const ALL: [PaletteColor; 5] = [
+        PaletteColor::Background,
+        PaletteColor::Text,
+        PaletteColor::Primary,
+        PaletteColor::Success,
+        PaletteColor::Danger,
+    ];
+
+    pub(crate) fn name(&self) -> String {
+        match self {
+            PaletteColor::Background => tr("palette-background"),
+            PaletteColor::Text => tr("palette-text"),
+            PaletteColor::Primary => tr("palette-primary"),
+            PaletteColor::Success => tr("palette-success"),
+            PaletteColor::Danger => tr("palette-danger"),
+        }
+    }
+}
+
+/// A theme defined by the user, with colours written as hex codes like "#1e2430".
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub(crate) struct CustomPalette {
+    pub(crate) name: String,
+    background: String,
+    text: String,
+    primary: String,
+    success: String,
+    danger: String,
+}
+
+impl Default for CustomPalette {
+    fn default() -> Self {
+        let hex = |color: Color| {
+            let [r, g, b, _] = color.into_rgba8();
+            format!("#{:02x}{:02x}{:02x}", r, g, b)
+        };
+        let palette = Palette::DARK;
+        CustomPalette {
+            name: String::new(),
+            background: hex(palette.background),
+            text: hex(palette.text),
+            primary: hex(palette.primary),
+            success: hex(palette.success),
+            danger: hex(palette.danger),
+        }
+    }
+}
+
+impl CustomPalette {
+    pub(crate) fn get(&self, color: PaletteColor) -> &str {
+        match color {
+            PaletteColor::Background => &self.background,
+            PaletteColor::Text => &self.text,
+            PaletteColor::Primary => &self.primary,
+            PaletteColor::Success => &self.success,
+            PaletteColor::Danger => &self.danger,
+        }
+    }
+
+    pub(crate) fn set(&mut self, color: PaletteColor, value: String) {
+        match color {
+            PaletteColor::Background => self.background = value,
+            PaletteColor::Text => self.text = value,
+            PaletteColor::Primary => self.primary = value,
+            PaletteColor::Success => self.success = value,
+            PaletteColor::Danger => self.danger = value,
+        }
+    }
+
+    pub(crate) fn to_palette(&self) -> Result<Palette, LoreGuiError> {
+        let parse = |color: PaletteColor| {
+            Color::parse(self.get(color)).ok_or_else(|| {
+                LoreGuiError::InputError(tr_args(
+                    "error-palette-colour",
+                    &[
+                        ("colour", &color.name()),
+                        ("value", &self.get(color)),
+                        ("palette", &self.name),
+                    ],
+                ))
+            })
+        };
+        Ok(Palette {
+            background: parse(PaletteColor::Background)?,
+            text: parse(PaletteColor::Text)?,
+            primary: parse(PaletteColor::Primary)?,
+            success: parse(PaletteColor::Success)?,
+            danger: parse(PaletteColor::Danger)?,
+        })
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum SizeSetting {
+    FontSize,
+    HeaderSize,
+    RowHeight,
+    Spacing,
+}
+
+impl SizeSetting {
+    pub(crate) const ALL: [SizeSetting; 4] = [
+        SizeSetting::FontSize,
+        SizeSetting::HeaderSize,
+        SizeSetting::RowHeight,
+        SizeSetting::Spacing,
+    ];
+
+    pub(crate) fn name(&self) -> String {
+        match self {
+            SizeSetting::FontSize => tr("size-font"),
+            SizeSetting::HeaderSize => tr("size-header"),
+            SizeSetting::RowHeight => tr("size-row-height"),
+            SizeSetting::Spacing => tr("size-spacing"),
+        }
+    }
+
+    pub(crate) fn range(&self) -> std::ops::RangeInclusive<f32> {
+        match self {
+            SizeSetting::FontSize => 10.0..=32.0,
+            SizeSetting::HeaderSize => 12.0..=48.0,
+            SizeSetting::RowHeight => 12.0..=48.0,
+            SizeSetting::Spacing => 0.0..=20.0,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub(crate) struct Appearance {
+    pub(crate) locale: Locale,
+    pub(crate) theme: ThemeChoice,
+    pub(crate) custom_palettes: Vec<CustomPalette>,
+    font_size: f32,
+    header_size: f32,
+    row_height: f32,
+    spacing: f32,
+    /// How long toasts are shown before they disappear on their own.
+    pub(crate) notification_seconds: u32,
+}
+
+impl Default for Appearance {
+    fn default() -> Self {
+        let sizes = Sizes::default();
+        Appearance {
+            locale: Locale::default(),
+            theme: ThemeChoice::default(),
+            custom_palettes: vec![],
+            font_size: sizes.text,
+            header_size: sizes.header,
+            row_height: sizes.row_height,
+            spacing: sizes.spacing,
+            notification_seconds: 4,
+        }
+    }
+}
+
+impl Appearance {
+    pub(crate) fn get(&self, setting: SizeSetting) -> f32 {
+        match setting {
+            SizeSetting::FontSize => self.font_size,
+            SizeSetting::HeaderSize => self.header_size,
+            SizeSetting::RowHeight => self.row_height,
+            SizeSetting::Spacing => self.spacing,
+        }
+    }
+
+    /// Values outside of the setting's range are clamped.
+    pub(crate) fn set(&mut self, setting: SizeSetting, value: f32) {
+        let range = setting.range();
+        let value = value.clamp(*range.start(), *range.end()).round();
+        match setting {
+            SizeSetting::FontSize => self.font_size = value,
+            SizeSetting::HeaderSize => self.header_size = value,
+            SizeSetting::RowHeight => self.row_height = value,
+            SizeSetting::Spacing => self.spacing = value,
+        }
+    }
+
+    /// The built in themes followed by the custom palettes.
+    pub(crate) fn theme_choices(&self) -> Vec<ThemeChoice> {
+        let mut choices = vec![ThemeChoice::Light, ThemeChoice::Dark];
+        choices.extend(
+            self.custom_palettes
+                .iter()
+                .map(|palette| ThemeChoice::Custom(palette.name.clone())),
+        );
+        choices
+    }
+
+    /// Adds or replaces the custom palette with the same name.
+    pub(crate) fn add_palette(&mut self, palette: CustomPalette) -> Result<(), LoreGuiError> {
+        if palette.name.trim().is_empty() {
+            return Err(LoreGuiError::InputError(tr("error-palette-name")));
+        }
+        palette.to_palette()?;
+        self.custom_palettes.retain(|p| p.name != palette.name);
+        self.custom_palettes.push(palette);
+        Ok(())
+    }
+
+    pub(crate) fn remove_palette(&mut self, name: &str) {
+        self.custom_palettes.retain(|p| p.name != name);
+        if self.theme == ThemeChoice::Custom(name.to_string()) {
+            self.theme = ThemeChoice::default();
+        }
+    }
+
+    /// Custom palettes that no longer exist or cannot be parsed fall back to the light theme.
+    pub(crate) fn theme(&self) -> Theme {
+        match &self.theme {
+            ThemeChoice::Light => Theme::Light,
+            ThemeChoice::Dark => Theme::Dark,
+            ThemeChoice::Custom(name) => self
+                .custom_palettes
+                .iter()
+                .find(|palette| palette.name == *name)
+                .and_then(|palette| palette.to_palette().ok())
+                .map(|palette| Theme::custom(name.clone(), palette))
+                .unwrap_or(Theme::Light),
+        }
+    }
+
+    pub(crate) fn sizes(&self) -> Sizes {
+        Sizes {
+            text: self.font_size,
+            header: self.header_size,
+            row_height: self.row_height,
+            spacing: self.spacing,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn example_palette() -> CustomPalette {
+        CustomPalette {
+            name: "Forest".to_string(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn custom_palettes_become_themes() {
+        let mut appearance = Appearance::default();
+        appearance.add_palette(example_palette()).unwrap();
+        appearance.theme = ThemeChoice::Custom("Forest".to_string());
+
+        let theme = appearance.theme();
+        assert_eq!(theme.to_string(), "Forest");
+        assert_eq!(theme.palette(), example_palette().to_palette().unwrap());
+        assert_eq!(appearance.theme_choices().len(), 3);
+
+        appearance.remove_palette("Forest");
+        assert_eq!(appearance.theme, ThemeChoice::Light);
+    }
+
+    #[test]
+    fn invalid_palettes_are_rejected() {
+        let mut appearance = Appearance::default();
+        let mut palette = example_palette();
+        palette.set(PaletteColor::Primary, "green".to_string());
+        assert!(appearance.add_palette(palette).is_err());
+        assert!(appearance.add_palette(CustomPalette::default()).is_err());
+        assert!(appearance.custom_palettes.is_empty());
+    }
+
+    #[test]
+    fn sizes_are_clamped() {
+        let mut appearance = Appearance::default();
+        assert_eq!(appearance.sizes().text, Sizes::default().text);
+        appearance.set(SizeSetting::FontSize, 24.0);
+        assert_eq!(appearance.sizes().text, 24.0);
+        appearance.set(SizeSetting::FontSize, 100.0);
+        assert_eq!(appearance.sizes().text, 32.0);
+        appearance.set(SizeSetting::Spacing, -3.0);
+        assert_eq!(appearance.sizes().spacing, 0.0);
+    }
+
+    #[test]
+    fn stored_system_theme_becomes_light() {
+        let appearance: Appearance = serde_json::from_str(r#"{"theme":"System"}"#).unwrap();
+        assert_eq!(appearance.theme, ThemeChoice::Light);
+    }
+
+    #[test]
+    fn missing_settings_get_defaults() {
+        let appearance: Appearance = serde_json::from_str(r#"{"theme":"Dark"}"#).unwrap();
+        assert_eq!(appearance.theme, ThemeChoice::Dark);
+        assert_eq!(appearance.sizes(), Sizes::default());
+        assert_eq!(appearance.locale, Locale::English);
+    }
+}
@@ -0,0 +1,428 @@
+use std::{fmt::Display, path::PathBuf};
+
+use lorecore::{
+    extractions::extract_labels,
+    sql::{
+        lore_database::LoreDatabase,
+        search_params::{
+            EntityColumnSearchParams, HistoryItemSearchParams, RelationshipSearchParams,
+            SqlSearchText,
+        },
+    },
+    types::*,
+};
+
+use crate::{
+    dialog::{
+        change_role::ChangeRoleData, new_descriptor::NewDescriptorData, new_entity::NewEntityData,
+        new_history_item::NewHistoryData, new_relationship::NewRelationshipData,
+        redate_history::RedateHistoryData, relabel_entity::RelabelEntityData,
+    },
+    errors::LoreGuiError,
+    localisation::{tr, tr_args},
+};
+
+const COMMANDS: &str = "\
+  entity list [SEARCH]
+  entity show <LABEL>
+  entity new <LABEL> <NAME> <CATEGORY>
+  entity relabel <OLD_LABEL> <NEW_LABEL>
+  entity delete <LABEL>
+  descriptor set <LABEL> <DESCRIPTOR> <DESCRIPTION>
+  history list
+  history add <YEAR> <DAY|-> <CONTENT>
+  history redate <TIMESTAMP> <YEAR> <DAY|->
+  relationship list
+  relationship add <PARENT> <CHILD> <ROLE>
+  relationship change-role <PARENT> <CHILD> <OLD_ROLE> <NEW_ROLE>
+";
+
+enum Command {
+    ListEntities(Option<String>),
+    ShowEntity(Label),
+    NewEntity(NewEntityData),
+    RelabelEntity(RelabelEntityData),
+    DeleteEntity(Label),
+    SetDescriptor(NewDescriptorData),
+    ListHistoryItems,
+    AddHistoryItem(NewHistoryData),
+    RedateHistoryItem(Timestamp, Year, Day),
+    ListRelationships,
+    AddRelationship(NewRelationshipData),
+    ChangeRole(ChangeRoleData),
+}
+
+/// Runs the command line interface and returns the process exit code.
+pub(super) fn run(args: Vec<String>) -> i32 {
+    match execute(&args) {
+        Ok(output) => {
+            print!("{}", output);
+            0
+        }
+        Err(e) => {
+            eprintln!("{}", e);
+            1
+        }
+    }
+}
+
+fn execute(args: &[String]) -> Result<String, LoreGuiError> {
+    let args: Vec<&str> = args.iter().map(|a| a.as_str()).collect();
+    let (path, command) = match args.as_slice() {
+        ["-h"] | ["--help"] | ["help"] => return Ok(usage()),
+        [path, command @ ..] => (PathBuf::from(path), parse_command(command)?),
+        [] => return Err(usage_error(&tr("cli-no-database"))),
+    };
+    if !path.exists() {
+        return Err(LoreGuiError::FileError(tr_args(
+            "error-database-missing",
+            &[("path", &path.to_string_lossy())],
+        )));
+    }
+    let db = LoreDatabase::open(path)?;
+    execute_command(&db, command)
+}
+
+fn usage() -> String {
+    format!(
+        "{}\n\n{}\n{}\n{}\n",
+        tr("cli-usage"),
+        tr("cli-commands"),
+        COMMANDS,
+        tr("cli-gui-hint")
+    )
+}
+
+fn usage_error(message: &str) -> LoreGuiError {
+    LoreGuiError::InputError(format!("{}\n\n{}", message, usage()))
+}
+
+fn parse_command(args: &[&str]) -> Result<Command, LoreGuiError> {
+    let command = match args {
+        ["entity", "list"] => Command::ListEntities(None),
+        ["entity", "list", search] => Command::ListEntities(Some(search.to_string())),
+        ["entity", "show", label] => Command::ShowEntity((*label).into()),
+        ["entity", "new", label, name, category] => {
+            let mut data = NewEntityData::new();
+            data.set_label((*label).into());
+            data.set_name(name.to_string());
+            data.set_category(category.to_string());
+            Command::NewEntity(data)
+        }
+        ["entity", "relabel", old_label, new_label] => {
+            let mut data = RelabelEntityData::new((*old_label).into());
+            data.set_new_label((*new_label).into());
+            Command::RelabelEntity(data)
+        }
+        ["entity", "delete", label] => Command::DeleteEntity((*label).into()),
+        ["descriptor", "set", label, descriptor, description] => {
+            let mut data = NewDescriptorData::new((*label).into());
+            data.set_descriptor((*descriptor).into());
+            data.set_description((*description).into());
+            Command::SetDescriptor(data)
+        }
+        ["history", "list"] => Command::ListHistoryItems,
+        ["history", "add", year, day, content] => {
+            let data = NewHistoryData::new(parse_year(year)?, parse_day(day)?, (*content).into());
+            Command::AddHistoryItem(data)
+        }
+        ["history", "redate", timestamp, year, day] => Command::RedateHistoryItem(
+            parse_timestamp(timestamp)?,
+            parse_year(year)?,
+            parse_day(day)?,
+        ),
+        ["relationship", "list"] => Command::ListRelationships,
+        ["relationship", "add", parent, child, role] => {
+            let mut data = NewRelationshipData::new();
+            data.set_parent((*parent).into());
+            data.set_child((*child).into());
+            data.set_role((*role).into());
+            Command::AddRelationship(data)
+        }
+        ["relationship", "change-role", parent, child, old_role, new_role] => {
+            let relationship = EntityRelationship {
+                parent: (*parent).into(),
+                child: (*child).into(),
+                role: (*old_role).into(),
+            };
+            let mut data = ChangeRoleData::new(relationship);
+            data.set_new_role((*new_role).into());
+            Command::ChangeRole(data)
+        }
+        _ => {
+            return Err(usage_error(&tr_args(
+                "cli-unknown-command",
+                &[("command", &args.join(" "))],
+            )))
+        }
+    };
+    Ok(command)
+}
+
+fn parse_year(year: &str) -> Result<Year, LoreGuiError> {
+    Year::try_from(year.to_string()).map_err(LoreGuiError::from)
+}
+
+fn parse_timestamp(timestamp: &str) -> Result<Timestamp, LoreGuiError> {
+    timestamp.parse::<i64>().map(Timestamp::from).map_err(|_| {
+        LoreGuiError::InputError(tr_args(
+            "cli-invalid-timestamp",
+            &[("timestamp", &timestamp)],
+        ))
+    })
+}
+
+fn parse_day(day: &str) -> Result<Day, LoreGuiError> {
+    if day == "-" {
+        Ok(Day::NONE)
+    } else {
+        Day::try_from(day.to_string()).map_err(LoreGuiError::from)
+    }
+}
+
+fn execute_command(db: &LoreDatabase, command: Command) -> Result<String, LoreGuiError> {
+    let output = match command {
+        Command::ListEntities(search) => {
+            let label_search_text = search.as_deref().map(SqlSearchText::partial);
+            let search_params = EntityColumnSearchParams::new(label_search_text, None);
+            let entity_columns = db.read_entity_columns(search_params)?;
+            lines(extract_labels(&entity_columns))
+        }
+        Command::ShowEntity(label) => {
+            let columns = read_entity(db, &label)?;
+            if columns.is_empty() {
+                return Err(entity_missing(&label));
+            }
+            lines(
+                columns
+                    .into_iter()
+                    .map(|col| format!("{}:\n{}", col.descriptor, col.description.to_str())),
+            )
+        }
+        Command::NewEntity(data) => {
+            let label = data.label().clone();
+            data.write_to_database(db)?;
+            line(tr_args("cli-entity-created", &[("label", &label)]))
+        }
+        Command::RelabelEntity(data) => {
+            let message = line(tr_args(
+                "cli-entity-relabelled",
+                &[
+                    ("old_label", data.old_label()),
+                    ("new_label", data.new_label()),
+                ],
+            ));
+            data.update_label_in_database(db)?;
+            message
+        }
+        Command::DeleteEntity(label) => {
+            db.delete_entity(label.clone())?;
+            line(tr_args("cli-entity-deleted", &[("label", &label)]))
+        }
+        Command::SetDescriptor(data) => {
+            let label = data.label().clone();
+            let descriptor = data.descriptor().clone();
+            // Entities are only created with a name and a category, as in the GUI.
+            let columns = read_entity(db, &label)?;
+            if columns.is_empty() {
+                return Err(entity_missing(&label));
+            }
+            let args: [(&str, &dyn Display); 2] = [("descriptor", &descriptor), ("label", &label)];
+            if columns.iter().any(|col| col.descriptor == descriptor) {
+                db.change_entity_description((&label, &descriptor), data.description())?;
+                line(tr_args("cli-descriptor-changed", &args))
+            } else {
+                data.write_to_database(db)?;
+                line(tr_args("cli-descriptor-added", &args))
+            }
+        }
+        Command::ListHistoryItems => {
+            let search_params = HistoryItemSearchParams::new(None, None, None, None);
+            let items = db.read_history_items(search_params)?;
+            lines(items.into_iter().map(|item| {
+                let first_line = item.content.to_str().lines().next().unwrap_or("");
+                format!(
+                    "{}\t{}\t{}\t{}",
+                    item.timestamp, item.year, item.day, first_line
+                )
+            }))
+        }
+        Command::AddHistoryItem(data) => {
+            let timestamp = *data.timestamp();
+            data.write_to_database(db)?;
+            line(tr_args(
+                "cli-history-item-created",
+                &[("timestamp", &timestamp)],
+            ))
+        }
+        Command::RedateHistoryItem(timestamp, year, day) => {
+            let search_params = HistoryItemSearchParams::new(None, None, Some(timestamp), None);
+            let item = db
+                .read_history_items(search_params)?
+                .into_iter()
+                .next()
+                .ok_or_else(|| {
+                    LoreGuiError::InputError(tr_args(
+                        "cli-history-item-missing",
+                        &[("timestamp", &timestamp)],
+                    ))
+                })?;
+            let mut data = RedateHistoryData::new(item.timestamp, item.year, item.day);
+            data.set_new_year(year);
+            data.set_new_day(day);
+            data.update_date_in_database(db)?;
+            line(tr_args(
+                "cli-history-item-redated",
+                &[("timestamp", &timestamp)],
+            ))
+        }
+        Command::ListRelationships => {
+            let search_params = RelationshipSearchParams::new(None, None);
+            let relationships = db.read_relationships(search_params)?;
+            lines(
+                relationships
+                    .into_iter()
+                    .map(|rel| format!("{}\t{}\t{}", rel.parent, rel.child, rel.role)),
+            )
+        }
+        Command::AddRelationship(data) => {
+            let message = line(tr_args(
+                "cli-relationship-created",
+                &[("parent", data.parent()), ("child", data.child())],
+            ));
+            data.write_to_database(db)?;
+            message
+        }
+        Command::ChangeRole(data) => {
+            let message = line(tr_args(
+                "cli-role-changed",
+                &[
+                    ("parent", data.parent()),
+                    ("child", data.child()),
+                    ("role", data.new_role()),
+                ],
+            ));
+            data.write_to_database(db)?;
+            message
+        }
+    };
+    Ok(output)
+}
+
+fn read_entity(db: &LoreDatabase, label: &Label) -> Result<Vec<EntityColumn>, LoreGuiError> {
+    let search_params =
+        EntityColumnSearchParams::new(Some(SqlSearchText::exact(label.to_str())), None);
+    Ok(db.read_entity_columns(search_params)?)
+}
+
+fn entity_missing(label: &Label) -> LoreGuiError {
+    LoreGuiError::InputError(tr_args("error-entity-missing", &[("label", label)]))
+}
+
+fn line(text: String) -> String {
+    text + "\n"
+}
+
+fn lines<T: ToString>(entries: impl IntoIterator<Item = T>) -> String {
+    entries
+        .into_iter()
+        .map(|entry| entry.to_string() + "\n")
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::tests::{example_database, example_labels};
+
+    fn run_on(db: &LoreDatabase, command: &[&str]) -> Result<String, LoreGuiError> {
+        let mut args = vec![db.path_as_string()];
+        args.extend(command.iter().map(|c| c.to_string()));
+        execute(&args)
+    }
+
+    #[test]
+    fn listing_entities_prints_all_labels() {
+        let db = example_database();
+        let output = run_on(&db, &["entity", "list"]).unwrap();
+        for label in example_labels() {
+            assert!(output.contains(label.to_str()));
+        }
+    }
+
+    #[test]
+    fn creating_entity_makes_it_listable() {
+        let db = example_database();
+        run_on(&db, &["entity", "new", "gandalf", "Gandalf", "Wizard"]).unwrap();
+        let output = run_on(&db, &["entity", "list", "gand"]).unwrap();
+        assert_eq!(output, "gandalf\n");
+    }
+
+    #[test]
+    fn creating_entity_without_name_produces_error() {
+        let db = example_database();
+        let result = run_on(&db, &["entity", "new", "gandalf", "", "Wizard"]);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn setting_descriptor_twice_changes_description() {
+        let db = example_database();
+        run_on(&db, &["entity", "new", "gandalf", "Gandalf", "Wizard"]).unwrap();
+        run_on(&db, &["descriptor", "set", "gandalf", "colour", "grey"]).unwrap();
+        run_on(&db, &["descriptor", "set", "gandalf", "colour", "white"]).unwrap();
+        let output = run_on(&db, &["entity", "show", "gandalf"]).unwrap();
+        assert!(output.contains("colour:\nwhite\n"));
+        assert!(!output.contains("grey"));
+    }
+
+    #[test]
+    fn setting_descriptor_of_unknown_entity_produces_error() {
+        let db = example_database();
+        let result = run_on(&db, &["descriptor", "set", "gandalf", "colour", "grey"]);
+        assert!(result.is_err());
+        assert!(run_on(&db, &["entity", "show", "gandalf"]).is_err());
+    }
+
+    #[test]
+    fn redating_history_item_changes_its_year() {
+        let db = example_database();
+        let data = NewHistoryData::new(1234.into(), Day::NONE, "Some content".into());
+        let timestamp = data.timestamp().to_string();
+        data.write_to_database(&db).unwrap();
+
+        run_on(&db, &["history", "redate", &timestamp, "4321", "-"]).unwrap();
+
+        let output = run_on(&db, &["history", "list"]).unwrap();
+        let line = output
+            .lines()
+            .find(|line| line.starts_with(&timestamp))
+            .unwrap();
+        assert!(line.contains("4321"));
+    }
+
+    #[test]
+    fn redating_needs_a_numeric_timestamp() {
+        let db = example_database();
+        let result = run_on(&db, &["history", "redate", "yesterday", "4321", "-"]);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn unknown_command_produces_error() {
+        let db = example_database();
+        let result = run_on(&db, &["entity", "explode"]);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn missing_database_produces_error() {
+        let args = vec![
+            "/this/path/does/not/exist.db".to_string(),
+            "entity".to_string(),
+            "list".to_string(),
+        ];
+        assert!(execute(&args).is_err());
+    }
+}
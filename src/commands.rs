@@ -0,0 +1,103 @@
+use iced::widget::{button, Button, Row};
+
+use crate::app::{message_handling::GuiMessage, ViewType};
+use crate::style::Sizes;
+
+/// An action offered by a button and in the command palette.
+///
+/// Disabled commands have no message, so that buttons and palette share the same enablement rules.
+#[derive(Debug, Clone)]
+pub(crate) struct Command {
+    pub(crate) name: String,
+    pub(crate) message: Option<GuiMessage>,
+    /// The view that shows the effect of the command, selected when it is run from the palette.
+    pub(crate) view: Option<ViewType>,
+}
+
+impl Command {
+    pub(crate) fn new(name: &str, message: Option<GuiMessage>) -> Self {
+        Command {
+            name: name.to_string(),
+            message,
+            view: None,
+        }
+    }
+
+    pub(crate) fn enabled(name: &str, message: GuiMessage) -> Self {
+        Command::new(name, Some(message))
+    }
+
+    pub(crate) fn in_view(mut self, view: ViewType) -> Self {
+        self.view = Some(view);
+        self
+    }
+
+    pub(crate) fn is_enabled(&self) -> bool {
+        self.message.is_some()
+    }
+
+    pub(crate) fn button<'a>(&self, sizes: Sizes) -> Button<'a, GuiMessage> {
+        button(sizes.text(self.name.clone())).on_press_maybe(self.message.clone())
+    }
+}
+
+pub(crate) fn button_row<'a>(commands: &[Command], sizes: Sizes) -> Row<'a, GuiMessage> {
+    commands
+        .iter()
+        .fold(Row::new(), |row, command| row.push(command.button(sizes)))
+        .spacing(sizes.spacing)
+        .padding(sizes.spacing)
+}
+
+/// How well `query` matches `text`, or `None` if its characters do not all occur in `text` in order.
+///
+/// Consecutive characters and characters at the start of words score higher.
+pub(crate) fn fuzzy_score(query: &str, text: &str) -> Option<i64> {
+    let query: Vec<char> = query
+        .chars()
+        .filter(|c| !c.is_whitespace())
+        .flat_map(char::to_lowercase)
+        .collect();
+    let text: Vec<char> = text.chars().flat_map(char::to_lowercase).collect();
+    let mut score = 0;
+    let mut position = 0;
+    let mut previous_match: Option<usize> = None;
+    for c in query {
+        let found = position + text[position..].iter().position(|t| *t == c)?;
+        score += 1;
+        if previous_match.is_some_and(|previous| previous + 1 == found) {
+            score += 5;
+        }
+        if found == 0 || !text[found - 1].is_alphanumeric() {
+            score += 3;
+        }
+        previous_match = Some(found);
+        position = found + 1;
+    }
+    Some(score * 100 - text.len() as i64)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fuzzy_matches_need_all_characters_in_order() {
+        assert!(fuzzy_score("nent", "New Entity").is_some());
+        assert!(fuzzy_score("NE", "new entity").is_some());
+        assert!(fuzzy_score("", "New Entity").is_some());
+        assert_eq!(fuzzy_score("etn", "New Entity"), None);
+        assert_eq!(fuzzy_score("x", "New Entity"), None);
+    }
+
+    #[test]
+    fn word_starts_and_consecutive_characters_score_higher() {
+        let delete_descriptor = fuzzy_score("dd", "Delete Descriptor").unwrap();
+        let added = fuzzy_score("dd", "Added").unwrap();
+        assert!(delete_descriptor > added);
+
+        let redate = fuzzy_score("redate", "Redate History Item").unwrap();
+        let scattered = fuzzy_score("redate", "Reload the update").unwrap();
+        assert!(redate > scattered);
+    }
+}
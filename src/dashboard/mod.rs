@@ -0,0 +1,349 @@
+use lorecore::{extractions::extract_labels, types::*};
+
+use crate::{entity_view::CATEGORY_DESCRIPTOR, errors::LoreGuiError, query_cache::CachedReader};
+
+pub(crate) mod widget;
+
+const MAX_RANKED_ENTRIES: usize = 10;
+
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct CategorySummary {
+    /// `None` collects the entities without a category.
+    pub(crate) category: Option<String>,
+    pub(crate) labels: Vec<Label>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct DescriptorUsage {
+    pub(crate) descriptor: Descriptor,
+    pub(crate) entities: usize,
+    /// The first entity with the descriptor, which is selected when the descriptor is clicked.
+    pub(crate) example: Label,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct YearSummary {
+    pub(crate) year: Year,
+    pub(crate) history_items: usize,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct ConnectedEntity {
+    pub(crate) label: Label,
+    pub(crate) relationships: usize,
+}
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub(crate) struct DashboardData {
+    pub(crate) categories: Vec<CategorySummary>,
+    pub(crate) descriptors: Vec<DescriptorUsage>,
+    pub(crate) years: Vec<YearSummary>,
+    pub(crate) most_connected: Vec<ConnectedEntity>,
+    pub(crate) orphans: Vec<Label>,
+    pub(crate) recent_history_items: Vec<HistoryItem>,
+}
+
+/// Reads the whole database to summarise it, detached from the dashboard state so that it can run in the background.
+#[derive(Debug, Clone, PartialEq)]
+pub(super) struct DashboardQuery;
+
+impl DashboardQuery {
+    pub(super) fn run(&self, reader: &CachedReader) -> Result<DashboardData, LoreGuiError> {
+        let entity_columns = reader.read_entity_columns(None, None)?;
+        let labels = extract_labels(&entity_columns);
+        let history_items = reader.read_history_items(None, None, None)?;
+        let relationships = reader.read_relationships(None, None)?;
+
+        let connections = count_relationships(&labels, &relationships);
+        let orphans = connections
+            .iter()
+            .filter(|entity| entity.relationships == 0)
+            .map(|entity| entity.label.clone())
+            .collect();
+        let mut most_connected: Vec<ConnectedEntity> = connections
+            .into_iter()
+            .filter(|entity| entity.relationships > 0)
+            .collect();
+        most_connected.sort_by_key(|entity| std::cmp::Reverse(entity.relationships));
+        most_connected.truncate(MAX_RANKED_ENTRIES);
+
+        let mut recent_history_items = history_items.to_vec();
+        recent_history_items.sort_by_key(|item| std::cmp::Reverse(item.timestamp));
+        recent_history_items.truncate(MAX_RANKED_ENTRIES);
+
+        Ok(DashboardData {
+            categories: categorise(&labels, &entity_columns),
+            descriptors: descriptor_usage(&entity_columns),
+            years: year_summaries(&history_items),
+            most_connected,
+            orphans,
+            recent_history_items,
+        })
+    }
+}
+
+/// Categories with the most entities come first, uncategorised entities last.
+fn categorise(labels: &[Label], entity_columns: &[EntityColumn]) -> Vec<CategorySummary> {
+    let mut categories: Vec<CategorySummary> = vec![];
+    for label in labels {
+        let category = entity_columns
+            .iter()
+            .find(|col| col.label == *label && col.descriptor.to_str() == CATEGORY_DESCRIPTOR)
+            .map(|col| col.description.to_str().trim().to_string())
+            .filter(|category| !category.is_empty());
+        match categories
+            .iter_mut()
+            .find(|summary| summary.category == category)
+        {
+            Some(summary) => summary.labels.push(label.clone()),
+            None => categories.push(CategorySummary {
+                category,
+                labels: vec![label.clone()],
+            }),
+        }
+    }
+    categories.sort_by(|a, b| {
+        a.category
+            .is_none()
+            .cmp(&b.category.is_none())
+            .then(b.labels.len().cmp(&a.labels.len()))
+            .then(a.category.cmp(&b.category))
+    });
+    categories
+}
+
+fn descriptor_usage(entity_columns: &[EntityColumn]) -> Vec<DescriptorUsage> {
+    let mut usages: Vec<DescriptorUsage> = vec![];
+    for col in entity_columns {
+        match usages
+            .iter_mut()
+            .find(|usage| usage.descriptor == col.descriptor)
+        {
+            Some(usage) => usage.entities += 1,
+            None => usages.push(DescriptorUsage {
+                descriptor: col.descriptor.clone(),
+                entities: 1,
+                example: col.label.clone(),
+            }),
+        }
+    }
+    usages.sort_by_key(|usage| std::cmp::Reverse(usage.entities));
+    usages.truncate(MAX_RANKED_ENTRIES);
+    usages
+}
+
+/// The number of history items per year, in chronological order.
+fn year_summaries(history_items: &[HistoryItem]) -> Vec<YearSummary> {
+    let mut years: Vec<YearSummary> = vec![];
+    for item in history_items {
+        match years.iter_mut().find(|summary| summary.year == item.year) {
+            Some(summary) => summary.history_items += 1,
+            None => years.push(YearSummary {
+                year: item.year,
+                history_items: 1,
+            }),
+        }
+    }
+    years.sort_by_key(|summary| summary.year);
+    years
+}
+
+/// Counts the relationships every entity takes part in, as parent or as child.
+fn count_relationships(
+    labels: &[Label],
+    relationships: &[EntityRelationship],
+) -> Vec<ConnectedEntity> {
+    labels
+        .iter()
+        .map(|label| ConnectedEntity {
+            label: label.clone(),
+            relationships: relationships
+                .iter()
+                .filter(|rel| {
+                    rel.parent.to_str() == label.to_str() || rel.child.to_str() == label.to_str()
+                })
+                .count(),
+        })
+        .collect()
+}
+
+#[derive(Debug, Clone)]
+pub(super) enum DashboardMessage {
+    ToggleCategory(Option<String>),
+}
+
+#[derive(Debug, Default)]
+pub(super) struct DashboardState {
+    data: DashboardData,
+    expanded_categories: Vec<Option<String>>,
+    loading: bool,
+    up_to_date: bool,
+}
+
+impl DashboardState {
+    pub(super) fn get_data(&self) -> &DashboardData {
+        &self.data
+    }
+
+    pub(super) fn is_expanded(&self, category: &Option<String>) -> bool {
+        self.expanded_categories.contains(category)
+    }
+
+    pub(super) fn toggle_category(&mut self, category: Option<String>) {
+        if self.is_expanded(&category) {
+            self.expanded_categories.retain(|c| *c != category);
+        } else {
+            self.expanded_categories.push(category);
+        }
+    }
+
+    pub(super) fn is_loading(&self) -> bool {
+        self.loading
+    }
+
+    pub(super) fn set_loading(&mut self, loading: bool) {
+        self.loading = loading;
+    }
+
+    /// The query to read the dashboard with, unless it has been read since the last change.
+    pub(super) fn query_if_changed(&mut self) -> Option<DashboardQuery> {
+        if self.up_to_date {
+            return None;
+        }
+        self.up_to_date = true;
+        Some(DashboardQuery)
+    }
+
+    /// Makes the next refresh read the dashboard again.
+    pub(super) fn invalidate(&mut self) {
+        self.up_to_date = false;
+    }
+
+    /// A read that is still running when the tab is put aside will never be applied, so it has to be repeated later.
+    pub(super) fn abandon_loading(&mut self) {
+        if self.loading {
+            self.set_loading(false);
+            self.invalidate();
+        }
+    }
+
+    pub(super) fn apply(&mut self, data: DashboardData) {
+        self.data = data;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Mutex;
+
+    use super::*;
+    use crate::{
+        query_cache::QueryCache,
+        tests::{example_database, example_descriptors, example_labels, example_years},
+    };
+
+    fn dashboard(db: &lorecore::sql::lore_database::LoreDatabase) -> DashboardData {
+        let cache = Mutex::new(QueryCache::default());
+        DashboardQuery.run(&CachedReader::new(db, &cache)).unwrap()
+    }
+
+    #[test]
+    fn example_database_is_summarised() {
+        let data = dashboard(&example_database());
+
+        assert_eq!(
+            data.categories,
+            vec![CategorySummary {
+                category: None,
+                labels: example_labels(),
+            }]
+        );
+        let descriptors: Vec<(Descriptor, usize)> = data
+            .descriptors
+            .iter()
+            .map(|usage| (usage.descriptor.clone(), usage.entities))
+            .collect();
+        let expected: Vec<(Descriptor, usize)> = example_descriptors()
+            .into_iter()
+            .map(|(descriptor, _)| (descriptor, example_labels().len()))
+            .collect();
+        assert_eq!(descriptors, expected);
+
+        let years: Vec<(Year, usize)> = data
+            .years
+            .iter()
+            .map(|summary| (summary.year, summary.history_items))
+            .collect();
+        assert_eq!(
+            years,
+            vec![
+                (example_years()[1], 3),
+                (example_years()[0], 3),
+                (example_years()[2], 3)
+            ]
+        );
+
+        assert_eq!(data.most_connected.len(), example_labels().len());
+        assert!(data
+            .most_connected
+            .iter()
+            .all(|entity| entity.relationships == 4));
+        assert!(data.orphans.is_empty());
+    }
+
+    #[test]
+    fn recent_history_items_come_first() {
+        let data = dashboard(&example_database());
+        let timestamps: Vec<Timestamp> = data
+            .recent_history_items
+            .iter()
+            .map(|item| item.timestamp)
+            .collect();
+        let mut sorted = timestamps.clone();
+        sorted.sort_by_key(|timestamp| std::cmp::Reverse(*timestamp));
+        assert_eq!(timestamps, sorted);
+        assert_eq!(timestamps.len(), 9);
+    }
+
+    #[test]
+    fn entities_are_grouped_by_category() {
+        let db = example_database();
+        let labels = example_labels();
+        let columns = [(&labels[0], "Character"), (&labels[1], "Character")]
+            .into_iter()
+            .map(|(label, category)| EntityColumn {
+                label: label.clone(),
+                descriptor: CATEGORY_DESCRIPTOR.into(),
+                description: category.into(),
+            })
+            .collect();
+        db.write_entity_columns(columns).unwrap();
+        let new_label: Label = "Lonely Place".into();
+        db.write_entity_columns(vec![EntityColumn {
+            label: new_label.clone(),
+            descriptor: CATEGORY_DESCRIPTOR.into(),
+            description: "Place".into(),
+        }])
+        .unwrap();
+
+        let data = dashboard(&db);
+        assert_eq!(
+            data.categories,
+            vec![
+                CategorySummary {
+                    category: Some("Character".to_string()),
+                    labels: vec![labels[0].clone(), labels[1].clone()],
+                },
+                CategorySummary {
+                    category: Some("Place".to_string()),
+                    labels: vec![new_label.clone()],
+                },
+                CategorySummary {
+                    category: None,
+                    labels: vec![labels[2].clone()],
+                },
+            ]
+        );
+        assert_eq!(data.orphans, vec![new_label]);
+    }
+}
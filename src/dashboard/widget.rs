@@ -0,0 +1,241 @@
+use iced::{
+    widget::{button, progress_bar, text, Button, Column, Row, Scrollable},
+    Alignment, Element, Length,
+};
+
+use crate::{
+    app::message_handling::GuiMessage,
+    global_search::SearchTarget,
+    localisation::{tr, tr_args},
+    style::Sizes,
+};
+
+use super::{CategorySummary, DashboardData, DashboardMessage, DashboardState};
+
+pub(crate) fn new(state: &DashboardState, sizes: Sizes) -> Element<'_, GuiMessage> {
+    let data = state.get_data();
+    let mut title = tr("view-dashboard");
+    if state.is_loading() {
+        title = tr_args("dashboard-loading", &[("title", &title)]);
+    }
+    let left = Column::new()
+        .push(categories(state, data, sizes))
+        .push(descriptors(data, sizes))
+        .push(years(data, sizes))
+        .width(Length::FillPortion(1))
+        .spacing(sizes.spacing * 4.0);
+    let right = Column::new()
+        .push(most_connected(data, sizes))
+        .push(orphans(data, sizes))
+        .push(recent_history_items(data, sizes))
+        .width(Length::FillPortion(1))
+        .spacing(sizes.spacing * 4.0);
+    let sections = Row::new()
+        .push(left)
+        .push(right)
+        .spacing(sizes.spacing * 4.0);
+    Column::new()
+        .push(sizes.header(title))
+        .push(Scrollable::new(sections).height(Length::Fill))
+        .padding(sizes.spacing)
+        .spacing(sizes.spacing)
+        .into()
+}
+
+fn section<'a>(
+    title: String,
+    entries: Vec<Element<'a, GuiMessage>>,
+    sizes: Sizes,
+) -> Column<'a, GuiMessage> {
+    let mut column = Column::new().push(sizes.text(title));
+    if entries.is_empty() {
+        column = column.push(sizes.text(tr("dashboard-empty")).style(text::secondary));
+    }
+    column.extend(entries).spacing(2)
+}
+
+fn link<'a>(
+    content: impl Into<Element<'a, GuiMessage>>,
+    target: SearchTarget,
+) -> Button<'a, GuiMessage> {
+    Button::new(content)
+        .style(button::text)
+        .width(Length::Fill)
+        .on_press(GuiMessage::JumpTo(target))
+}
+
+fn counted_row<'a>(name: String, count: String, sizes: Sizes) -> Row<'a, GuiMessage> {
+    Row::new()
+        .push(sizes.text(name).width(Length::Fill))
+        .push(sizes.text(count).style(text::secondary))
+        .spacing(sizes.spacing)
+}
+
+fn categories<'a>(
+    state: &'a DashboardState,
+    data: &'a DashboardData,
+    sizes: Sizes,
+) -> Column<'a, GuiMessage> {
+    let entries = data
+        .categories
+        .iter()
+        .map(|summary| category(state, summary, sizes))
+        .collect();
+    section(tr("dashboard-categories"), entries, sizes)
+}
+
+fn category<'a>(
+    state: &'a DashboardState,
+    summary: &'a CategorySummary,
+    sizes: Sizes,
+) -> Element<'a, GuiMessage> {
+    let expanded = state.is_expanded(&summary.category);
+    let marker = if expanded { "▾" } else { "▸" };
+    let name = match summary.category.as_ref() {
+        Some(category) => category.clone(),
+        None => tr("dashboard-uncategorised"),
+    };
+    let toggle = Button::new(counted_row(
+        format!("{} {}", marker, name),
+        tr_args(
+            "dashboard-entity-count",
+            &[("count", &summary.labels.len())],
+        ),
+        sizes,
+    ))
+    .style(button::text)
+    .width(Length::Fill)
+    .on_press(GuiMessage::DashboardUpd(DashboardMessage::ToggleCategory(
+        summary.category.clone(),
+    )));
+    let mut column = Column::new().push(toggle);
+    if expanded {
+        let labels = summary.labels.iter().map(|label| {
+            link(
+                sizes.text(label.to_str()),
+                SearchTarget::Entity(label.clone(), None),
+            )
+            .padding([2.0, sizes.spacing * 6.0])
+            .into()
+        });
+        column = column.extend(labels);
+    }
+    column.into()
+}
+
+fn descriptors(data: &DashboardData, sizes: Sizes) -> Column<'_, GuiMessage> {
+    let entries = data
+        .descriptors
+        .iter()
+        .map(|usage| {
+            let row = counted_row(
+                usage.descriptor.to_string(),
+                tr_args("dashboard-entity-count", &[("count", &usage.entities)]),
+                sizes,
+            );
+            let target =
+                SearchTarget::Entity(usage.example.clone(), Some(usage.descriptor.clone()));
+            link(row, target).into()
+        })
+        .collect();
+    section(tr("dashboard-descriptors"), entries, sizes)
+}
+
+fn years(data: &DashboardData, sizes: Sizes) -> Column<'_, GuiMessage> {
+    let most_items = data
+        .years
+        .iter()
+        .map(|summary| summary.history_items)
+        .max()
+        .unwrap_or_default();
+    let entries = data
+        .years
+        .iter()
+        .map(|summary| {
+            let bar = progress_bar(0.0..=most_items as f32, summary.history_items as f32)
+                .height(Length::Fixed(sizes.spacing * 2.0))
+                .width(Length::FillPortion(2));
+            let row = Row::new()
+                .push(
+                    sizes
+                        .text(summary.year.to_string())
+                        .width(Length::FillPortion(1)),
+                )
+                .push(bar)
+                .push(
+                    sizes
+                        .text(summary.history_items.to_string())
+                        .style(text::secondary),
+                )
+                .align_y(Alignment::Center)
+                .spacing(sizes.spacing);
+            link(row, SearchTarget::Year(summary.year)).into()
+        })
+        .collect();
+    section(tr("dashboard-years"), entries, sizes)
+}
+
+fn most_connected(data: &DashboardData, sizes: Sizes) -> Column<'_, GuiMessage> {
+    let entries = data
+        .most_connected
+        .iter()
+        .map(|entity| {
+            let row = counted_row(
+                entity.label.to_string(),
+                tr_args(
+                    "dashboard-relationship-count",
+                    &[("count", &entity.relationships)],
+                ),
+                sizes,
+            );
+            link(row, SearchTarget::Entity(entity.label.clone(), None)).into()
+        })
+        .collect();
+    section(tr("dashboard-most-connected"), entries, sizes)
+}
+
+fn orphans(data: &DashboardData, sizes: Sizes) -> Column<'_, GuiMessage> {
+    let entries = data
+        .orphans
+        .iter()
+        .map(|label| {
+            link(
+                sizes.text(label.to_str()),
+                SearchTarget::Entity(label.clone(), None),
+            )
+            .into()
+        })
+        .collect();
+    section(
+        tr_args("dashboard-orphans", &[("count", &data.orphans.len())]),
+        entries,
+        sizes,
+    )
+}
+
+fn recent_history_items(data: &DashboardData, sizes: Sizes) -> Column<'_, GuiMessage> {
+    let entries = data
+        .recent_history_items
+        .iter()
+        .map(|item| {
+            let title = tr_args(
+                "search-history-item",
+                &[
+                    ("year", &item.year),
+                    ("day", &item.day),
+                    ("timestamp", &item.timestamp),
+                ],
+            );
+            let first_line = item.content.to_str().lines().next().unwrap_or_default();
+            let content = Column::new()
+                .push(sizes.text(title))
+                .push(sizes.text(first_line.to_string()).style(text::secondary));
+            link(
+                content,
+                SearchTarget::HistoryItem(item.year, item.day, item.timestamp),
+            )
+            .into()
+        })
+        .collect();
+    section(tr("dashboard-recent-history-items"), entries, sizes)
+}
@@ -2,6 +2,8 @@ use std::fmt::Display;
 
 use lorecore::types::*;
 
+use crate::localisation::tr;
+
 #[derive(Clone, Debug, PartialEq, Eq, Hash)]
 pub(crate) struct DbColViewEntry<T>(pub(crate) Option<T>);
 
@@ -19,7 +21,7 @@ impl<T: Display> Display for DbColViewEntry<T> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match &self.0 {
             Some(value) => value.fmt(f),
-            None => "[none]".fmt(f),
+            None => tr("entry-none").fmt(f),
         }
     }
 }
@@ -8,6 +8,7 @@ pub(crate) struct DbColViewState<E> {
     entries: Vec<DbColViewEntry<E>>,
     selected_entry: DbColViewEntry<E>,
     add_none_option: bool,
+    loading: bool,
 }
 
 impl<E: Clone> DbColViewState<E> {
@@ -17,6 +18,7 @@ impl<E: Clone> DbColViewState<E> {
             entries: vec![],
             selected_entry: DbColViewEntry::NONE,
             add_none_option,
+            loading: false,
         };
         state.set_entries(entries);
         state
@@ -29,7 +31,7 @@ impl<E: Clone> DbColViewState<E> {
         self.entries = entries;
     }
 
-    pub(super) fn get_entries(&self) -> &Vec<DbColViewEntry<E>> {
+    pub(crate) fn get_entries(&self) -> &Vec<DbColViewEntry<E>> {
         &self.entries
     }
 
@@ -41,6 +43,14 @@ impl<E: Clone> DbColViewState<E> {
         &self.selected_entry
     }
 
+    pub(crate) fn set_loading(&mut self, loading: bool) {
+        self.loading = loading;
+    }
+
+    pub(crate) fn is_loading(&self) -> bool {
+        self.loading
+    }
+
     pub(crate) fn set_search_text(&mut self, text: String) {
         self.search_text = text;
     }
@@ -53,14 +63,54 @@ impl<E: Clone> DbColViewState<E> {
         }
     }
 
-    pub(crate) fn get_search_int(&self) -> Result<Option<i32>, LoreGuiError> {
-        let search_text = self.get_search_text().map(|t| t.parse::<i32>());
-        let search_int = match search_text {
-            Some(Ok(i)) => Some(i),
-            Some(Err(e)) => return Err(LoreGuiError::InputError(e.to_string())),
-            None => None,
+    /// The entries found with the search text, if one is entered.
+    pub(crate) fn search_matches(&self) -> Option<usize> {
+        self.get_search_text()?;
+        Some(
+            self.entries
+                .iter()
+                .filter(|entry| entry.0.is_some())
+                .count(),
+        )
+    }
+}
+
+/// The entries found in all columns with a search text, if any column has one.
+pub(crate) fn combined_search_matches(
+    matches: impl IntoIterator<Item = Option<usize>>,
+) -> Option<usize> {
+    matches
+        .into_iter()
+        .flatten()
+        .reduce(|sum, count| sum + count)
+}
+
+pub(crate) fn parse_search_int(search_text: Option<&str>) -> Result<Option<i32>, LoreGuiError> {
+    let search_text = search_text.map(|t| t.parse::<i32>());
+    let search_int = match search_text {
+        Some(Ok(i)) => Some(i),
+        Some(Err(e)) => return Err(LoreGuiError::InputError(e.to_string())),
+        None => None,
+    };
+    Ok(search_int)
+}
+
+impl<E: Clone + PartialEq> DbColViewState<E> {
+    /// The entry `offset` positions away from the selected one, clamped to the list.
+    pub(crate) fn neighbour_of_selected(
+        &self,
+        offset: isize,
+    ) -> Option<(usize, DbColViewEntry<E>)> {
+        if self.entries.is_empty() {
+            return None;
+        }
+        let index = match self.entries.iter().position(|e| *e == self.selected_entry) {
+            Some(current) => current
+                .saturating_add_signed(offset)
+                .min(self.entries.len() - 1),
+            None => 0,
         };
-        Ok(search_int)
+        Some((index, self.entries[index].clone()))
     }
 }
 
@@ -69,3 +119,35 @@ impl<E: Clone> Default for DbColViewState<E> {
         Self::new(vec![], false)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn state_with_entries() -> DbColViewState<i32> {
+        DbColViewState::new(vec![1.into(), 2.into(), 3.into()], false)
+    }
+
+    #[test]
+    fn neighbour_without_selection_is_first_entry() {
+        let state = state_with_entries();
+        assert_eq!(state.neighbour_of_selected(1), Some((0, 1.into())));
+    }
+
+    #[test]
+    fn neighbour_is_clamped_to_entries() {
+        let mut state = state_with_entries();
+        state.set_selected(3.into());
+        assert_eq!(state.neighbour_of_selected(1), Some((2, 3.into())));
+        assert_eq!(state.neighbour_of_selected(-1), Some((1, 2.into())));
+        state.set_selected(1.into());
+        assert_eq!(state.neighbour_of_selected(-1), Some((0, 1.into())));
+    }
+
+    #[test]
+    fn search_matches_of_all_searched_columns_are_added() {
+        assert_eq!(combined_search_matches([None, None]), None);
+        assert_eq!(combined_search_matches([None, Some(2)]), Some(2));
+        assert_eq!(combined_search_matches([Some(0), None, Some(3)]), Some(3));
+    }
+}
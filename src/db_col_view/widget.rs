@@ -1,6 +1,6 @@
 use iced::Font;
 use iced::{
-    widget::{Column, Text, TextInput},
+    widget::{container, Column, Container, TextInput},
     Element, Length,
 };
 use iced_aw::{style, SelectionList};
@@ -8,48 +8,82 @@ use std::fmt::Display;
 use std::hash::Hash;
 
 use super::{state::DbColViewState, ColViewMes};
-use crate::{app::message_handling::GuiMessage, style::header};
+use crate::{
+    app::message_handling::GuiMessage,
+    localisation::{tr, tr_args},
+    style::Sizes,
+};
 
 pub(crate) fn new<'a, M, E>(
-    title: &'static str,
+    title: String,
     gui_message: M,
     state: &'a DbColViewState<E>,
+    focused: bool,
+    sizes: Sizes,
 ) -> Element<'a, GuiMessage>
 where
     M: 'static + Clone + Fn(ColViewMes<E>) -> GuiMessage,
     E: 'static + Clone + Display + Eq + Hash,
 {
-    let title = header(title);
-
-    let selected_text = Text::new(format!("Selected: {}", state.get_selected()));
-
-    let search_text = state.get_search_text().unwrap_or("");
-    let m = gui_message.clone();
-    let search_field_updated = move |s| m(ColViewMes::SearchFieldUpd(s));
-    let search_field = TextInput::new("Type to search...", search_text)
-        .on_input(search_field_updated)
-        .width(Length::Fill);
-
     let m = gui_message.clone();
     let selection_list_updated = move |i, e| m(ColViewMes::Selected(i, e));
     let selection_list = SelectionList::new_with(
         state.get_entries(),
         selection_list_updated,
-        20.0,
-        0.0,
+        sizes.text,
+        sizes.list_padding(),
         style::selection_list::primary,
         None,
         Font::default(),
     );
+    with_list(title, gui_message, state, selection_list, focused, sizes)
+}
+
+/// A column whose entries are shown by the given list instead of a plain selection list.
+///
+/// The column that keyboard navigation moves through is drawn with a border.
+pub(crate) fn with_list<'a, M, E>(
+    title: String,
+    gui_message: M,
+    state: &'a DbColViewState<E>,
+    list: impl Into<Element<'a, GuiMessage>>,
+    focused: bool,
+    sizes: Sizes,
+) -> Element<'a, GuiMessage>
+where
+    M: 'static + Clone + Fn(ColViewMes<E>) -> GuiMessage,
+    E: 'static + Clone + Display,
+{
+    let title = sizes.header(title);
+
+    let mut selected_text = tr_args("column-selected", &[("entry", state.get_selected())]);
+    if state.is_loading() {
+        selected_text = tr_args("column-loading", &[("text", &selected_text)]);
+    }
+    let selected_text = sizes.text(selected_text);
 
-    Column::new()
+    let search_text = state.get_search_text().unwrap_or("");
+    let search_field_updated = move |s| gui_message(ColViewMes::SearchFieldUpd(s));
+    let search_field = TextInput::new(&tr("column-search"), search_text)
+        .size(sizes.text)
+        .on_input(search_field_updated)
+        .width(Length::Fill);
+
+    let column = Column::new()
         .push(title)
         .push(selected_text)
         .push(search_field)
-        .push(selection_list)
+        .push(list)
         .width(Length::Fill)
         .height(Length::Fill)
-        .padding(5)
-        .spacing(5)
-        .into()
+        .padding(sizes.spacing)
+        .spacing(sizes.spacing);
+    let container = Container::new(column)
+        .width(Length::Fill)
+        .height(Length::Fill);
+    if focused {
+        container.style(container::bordered_box).into()
+    } else {
+        container.into()
+    }
 }
@@ -0,0 +1,145 @@
+use iced::{
+    widget::{button, Button, Column, Row, TextInput},
+    Element,
+};
+use lorecore::{
+    sql::{
+        lore_database::LoreDatabase,
+        search_params::{EntityColumnSearchParams, SqlSearchText},
+    },
+    types::*,
+};
+
+use crate::{
+    app::message_handling::GuiMessage,
+    entity_view::CATEGORY_DESCRIPTOR,
+    errors::LoreGuiError,
+    localisation::{tr, tr_args},
+    style::Sizes,
+};
+
+use super::{Dialog, DialogUpdate};
+
+const MAX_SUGGESTIONS: usize = 8;
+
+/// Buttons for the existing categories that contain the typed text.
+pub(super) fn category_suggestions<'a>(
+    categories: &'a [String],
+    typed: &str,
+    sizes: Sizes,
+) -> Row<'a, GuiMessage> {
+    let typed = typed.trim().to_lowercase();
+    let suggestions = categories
+        .iter()
+        .filter(|category| {
+            let category = category.to_lowercase();
+            category.contains(&typed) && category != typed
+        })
+        .take(MAX_SUGGESTIONS)
+        .map(|category| {
+            Button::new(sizes.text(category.as_str()))
+                .style(button::secondary)
+                .on_press(GuiMessage::DialogUpdate(DialogUpdate::Category(
+                    category.clone(),
+                )))
+                .into()
+        });
+    Row::new().extend(suggestions).spacing(sizes.spacing)
+}
+
+#[derive(Debug, Clone)]
+pub(crate) struct ChangeCategoryDialog {
+    categories: Vec<String>,
+    data: ChangeCategoryData,
+}
+
+impl ChangeCategoryDialog {
+    pub(crate) fn new(data: ChangeCategoryData, categories: Vec<String>) -> Self {
+        ChangeCategoryDialog { categories, data }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub(crate) struct ChangeCategoryData {
+    pub(self) label: Label,
+    pub(self) category: String,
+}
+
+impl ChangeCategoryData {
+    pub(crate) fn new(label: Label, category: String) -> Self {
+        ChangeCategoryData { label, category }
+    }
+
+    pub(crate) fn write_to_database(self, db: &LoreDatabase) -> Result<(), LoreGuiError> {
+        let category = self.category.trim();
+        if category.is_empty() {
+            return Err(LoreGuiError::InputError(tr("error-empty-category")));
+        }
+        let descriptor: Descriptor = CATEGORY_DESCRIPTOR.into();
+        let search_params =
+            EntityColumnSearchParams::new(Some(SqlSearchText::exact(self.label.to_str())), None);
+        let columns = db.read_entity_columns(search_params)?;
+        if columns.is_empty() {
+            return Err(LoreGuiError::InputError(tr_args(
+                "error-entity-missing",
+                &[("label", &self.label)],
+            )));
+        }
+        let description: Description = category.into();
+        if columns.iter().any(|col| col.descriptor == descriptor) {
+            db.change_entity_description((&self.label, &descriptor), &description)?;
+        } else {
+            db.write_entity_columns(vec![EntityColumn {
+                label: self.label,
+                descriptor,
+                description,
+            }])?;
+        }
+        Ok(())
+    }
+
+    pub(crate) fn label(&self) -> &Label {
+        &self.label
+    }
+
+    pub(crate) fn category(&self) -> &str {
+        &self.category
+    }
+}
+
+impl Dialog for ChangeCategoryDialog {
+    fn header(&self) -> String {
+        tr_args("dialog-change-category", &[("label", &self.data.label)])
+    }
+
+    fn body(&self, sizes: Sizes) -> Element<'_, GuiMessage> {
+        let category_input = TextInput::new("", &self.data.category)
+            .size(sizes.text)
+            .on_input(|s| GuiMessage::DialogUpdate(DialogUpdate::Category(s)))
+            .on_submit(GuiMessage::DialogSubmit);
+        let submit_button =
+            Button::new(sizes.text(tr("button-change"))).on_press(GuiMessage::DialogSubmit);
+        Column::new()
+            .push(sizes.text(tr("field-category")))
+            .push(category_input)
+            .push(category_suggestions(
+                &self.categories,
+                &self.data.category,
+                sizes,
+            ))
+            .push(submit_button)
+            .padding(sizes.spacing)
+            .spacing(sizes.spacing)
+            .into()
+    }
+
+    fn update(&mut self, message: DialogUpdate) {
+        if let DialogUpdate::Category(category) = message {
+            self.data.category = category;
+        }
+    }
+
+    fn submit(&self) -> GuiMessage {
+        GuiMessage::ChangeCategory(self.data.to_owned())
+    }
+}
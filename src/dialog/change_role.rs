@@ -1,10 +1,15 @@
 use iced::{
-    widget::{Button, Column, Text, TextInput},
+    widget::{Button, Column, TextInput},
     Element,
 };
 use lorecore::{sql::lore_database::LoreDatabase, types::*};
 
-use crate::{app::message_handling::GuiMessage, errors::LoreGuiError};
+use crate::{
+    app::message_handling::GuiMessage,
+    errors::LoreGuiError,
+    localisation::{tr, tr_args},
+    style::Sizes,
+};
 
 use super::{Dialog, DialogUpdate};
 
@@ -50,7 +55,6 @@ impl ChangeRoleData {
         &self.new_role
     }
 
-    #[cfg(test)]
     pub(crate) fn set_new_role(&mut self, role: Role) {
         self.new_role = role;
     }
@@ -58,11 +62,13 @@ impl ChangeRoleData {
 
 impl Dialog for ChangeRoleDialog {
     fn header(&self) -> String {
-        format!(
-            "Change role {} for relationship between {} and {}",
-            self.data.old_relationship.role.to_str(),
-            self.data.old_relationship.parent,
-            self.data.old_relationship.child
+        tr_args(
+            "dialog-change-role",
+            &[
+                ("role", &self.data.old_relationship.role),
+                ("parent", &self.data.old_relationship.parent),
+                ("child", &self.data.old_relationship.child),
+            ],
         )
     }
 
@@ -72,17 +78,20 @@ impl Dialog for ChangeRoleDialog {
         }
     }
 
-    fn body(&self) -> Element<'_, GuiMessage> {
+    fn body(&self, sizes: Sizes) -> Element<'_, GuiMessage> {
         let new_role_str = self.data.new_role.to_str();
         let new_role_input = TextInput::new("", new_role_str)
-            .on_input(|i| GuiMessage::DialogUpdate(DialogUpdate::Role(i.into())));
-        let submit_button = Button::new(Text::new("Update")).on_press(GuiMessage::DialogSubmit);
+            .size(sizes.text)
+            .on_input(|i| GuiMessage::DialogUpdate(DialogUpdate::Role(i.into())))
+            .on_submit(GuiMessage::DialogSubmit);
+        let submit_button =
+            Button::new(sizes.text(tr("button-update"))).on_press(GuiMessage::DialogSubmit);
         Column::new()
-            .push(Text::new("New Role"))
+            .push(sizes.text(tr("field-new-role")))
             .push(new_role_input)
             .push(submit_button)
-            .padding(5)
-            .spacing(5)
+            .padding(sizes.spacing)
+            .spacing(sizes.spacing)
             .into()
     }
 
@@ -0,0 +1,230 @@
+use iced::{
+    widget::{button, text_input, Button, Column, TextInput},
+    Element, Length,
+};
+use lorecore::types::*;
+
+use crate::{
+    app::message_handling::GuiMessage,
+    commands::{fuzzy_score, Command},
+    global_search::SearchTarget,
+    localisation::{tr, tr_args},
+    style::Sizes,
+};
+
+use super::{Dialog, DialogUpdate};
+
+const MAX_SHOWN_ENTRIES: usize = 30;
+
+fn input_id() -> text_input::Id {
+    text_input::Id::new("command_palette")
+}
+
+#[derive(Debug, Clone)]
+enum PaletteEntry<'a> {
+    Command(&'a Command),
+    Entity(&'a Label),
+}
+
+impl PaletteEntry<'_> {
+    fn title(&self) -> String {
+        match self {
+            PaletteEntry::Command(command) => command.name.clone(),
+            PaletteEntry::Entity(label) => tr_args("command-go-to", &[("label", label)]),
+        }
+    }
+
+    fn message(&self) -> Option<GuiMessage> {
+        match self {
+            PaletteEntry::Command(command) => command
+                .is_enabled()
+                .then(|| GuiMessage::RunCommand(Box::new((*command).clone()))),
+            PaletteEntry::Entity(label) => {
+                let target = SearchTarget::Entity((*label).clone(), None);
+                let command = Command::enabled(&self.title(), GuiMessage::JumpTo(target));
+                Some(GuiMessage::RunCommand(Box::new(command)))
+            }
+        }
+    }
+}
+
+/// Lists all commands, greying out the ones that are currently not available, and jumps to entities.
+#[derive(Debug, Clone)]
+pub(crate) struct CommandPaletteDialog {
+    commands: Vec<Command>,
+    labels: Vec<Label>,
+    query: String,
+    highlighted: usize,
+}
+
+impl CommandPaletteDialog {
+    pub(crate) fn new(commands: Vec<Command>, labels: Vec<Label>) -> Self {
+        CommandPaletteDialog {
+            commands,
+            labels,
+            query: String::new(),
+            highlighted: 0,
+        }
+    }
+
+    /// Commands and, once something was typed, entities matching the query, best matches first.
+    fn entries(&self) -> Vec<PaletteEntry<'_>> {
+        let mut scored: Vec<(i64, PaletteEntry)> = self
+            .commands
+            .iter()
+            .filter_map(|command| {
+                let score = fuzzy_score(&self.query, &command.name)?;
+                Some((score, PaletteEntry::Command(command)))
+            })
+            .collect();
+        if !self.query.trim().is_empty() {
+            scored.extend(self.labels.iter().filter_map(|label| {
+                let score = fuzzy_score(&self.query, label.to_str())?;
+                Some((score, PaletteEntry::Entity(label)))
+            }));
+            scored.sort_by_key(|(score, _)| -score);
+        }
+        scored
+            .into_iter()
+            .map(|(_, entry)| entry)
+            .take(MAX_SHOWN_ENTRIES)
+            .collect()
+    }
+
+    fn enabled_entries(&self) -> Vec<PaletteEntry<'_>> {
+        self.entries()
+            .into_iter()
+            .filter(|entry| entry.message().is_some())
+            .collect()
+    }
+
+    fn move_highlight(&mut self, step: isize) {
+        let count = self.enabled_entries().len();
+        if count == 0 {
+            return;
+        }
+        self.highlighted = (self.highlighted as isize + step).rem_euclid(count as isize) as usize;
+    }
+}
+
+impl Dialog for CommandPaletteDialog {
+    fn header(&self) -> String {
+        tr("dialog-commands")
+    }
+
+    fn body(&self, sizes: Sizes) -> Element<'_, GuiMessage> {
+        let input: TextInput<'_, GuiMessage> =
+            TextInput::new(&tr("command-palette-search"), &self.query)
+                .size(sizes.text)
+                .id(input_id())
+                .on_input(|query| GuiMessage::DialogUpdate(DialogUpdate::Search(query)))
+                .on_submit(GuiMessage::DialogSubmit);
+        let mut column = Column::new().push(input).padding(sizes.spacing).spacing(2);
+        let entries = self.entries();
+        if entries.is_empty() {
+            column = column.push(sizes.text(tr("command-palette-nothing-found")));
+        }
+        let mut enabled_index = 0;
+        for entry in entries {
+            let message = entry.message();
+            let style = if message.is_some() && enabled_index == self.highlighted {
+                button::primary
+            } else {
+                button::text
+            };
+            if message.is_some() {
+                enabled_index += 1;
+            }
+            let entry_button: Button<'_, GuiMessage> = Button::new(sizes.text(entry.title()))
+                .on_press_maybe(message)
+                .style(style)
+                .width(Length::Fill);
+            column = column.push(entry_button);
+        }
+        column.into()
+    }
+
+    fn focused_input(&self) -> Option<text_input::Id> {
+        Some(input_id())
+    }
+
+    fn update(&mut self, message: DialogUpdate) {
+        match message {
+            DialogUpdate::Search(query) => {
+                self.query = query;
+                self.highlighted = 0;
+            }
+            DialogUpdate::SelectPrevious => self.move_highlight(-1),
+            DialogUpdate::SelectNext => self.move_highlight(1),
+            _ => (),
+        }
+    }
+
+    fn submit(&self) -> GuiMessage {
+        self.enabled_entries()
+            .get(self.highlighted)
+            .and_then(PaletteEntry::message)
+            .unwrap_or(GuiMessage::DialogClosed)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn example_palette() -> CommandPaletteDialog {
+        let commands = vec![
+            Command::enabled("New Entity", GuiMessage::Undo),
+            Command::new("Delete Entity", None),
+            Command::enabled("Delete Descriptor", GuiMessage::Redo),
+        ];
+        CommandPaletteDialog::new(commands, vec!["Elrond".into(), "Gandalf".into()])
+    }
+
+    fn titles(palette: &CommandPaletteDialog) -> Vec<String> {
+        palette.entries().iter().map(PaletteEntry::title).collect()
+    }
+
+    #[test]
+    fn empty_query_lists_all_commands_in_order() {
+        let palette = example_palette();
+        assert_eq!(
+            titles(&palette),
+            vec!["New Entity", "Delete Entity", "Delete Descriptor"]
+        );
+    }
+
+    #[test]
+    fn query_matches_commands_and_entities_fuzzily() {
+        let mut palette = example_palette();
+        palette.update(DialogUpdate::Search("gdf".to_string()));
+        assert_eq!(titles(&palette), vec!["Go to Gandalf"]);
+
+        palette.update(DialogUpdate::Search("del".to_string()));
+        assert_eq!(titles(&palette), vec!["Delete Entity", "Delete Descriptor"]);
+    }
+
+    #[test]
+    fn disabled_commands_are_skipped_when_submitting() {
+        let mut palette = example_palette();
+        palette.update(DialogUpdate::Search("del".to_string()));
+        match palette.submit() {
+            GuiMessage::RunCommand(command) => assert_eq!(command.name, "Delete Descriptor"),
+            _ => panic!("The enabled command should be run."),
+        }
+
+        palette.update(DialogUpdate::SelectNext);
+        palette.update(DialogUpdate::Search("delete entity".to_string()));
+        assert!(matches!(palette.submit(), GuiMessage::DialogClosed));
+    }
+
+    #[test]
+    fn highlight_wraps_around_enabled_entries() {
+        let mut palette = example_palette();
+        palette.update(DialogUpdate::SelectPrevious);
+        match palette.submit() {
+            GuiMessage::RunCommand(command) => assert_eq!(command.name, "Delete Descriptor"),
+            _ => panic!("The last enabled command should be highlighted."),
+        }
+    }
+}
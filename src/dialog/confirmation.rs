@@ -1,6 +1,8 @@
 use super::{CardStyle, Dialog};
 use crate::app::message_handling::GuiMessage;
-use iced::widget::{Button, Column, Row, Text};
+use crate::localisation::tr;
+use crate::style::Sizes;
+use iced::widget::{Button, Column, Row};
 use iced::{Alignment, Element, Length};
 
 #[derive(Debug, Clone)]
@@ -24,13 +26,14 @@ impl Dialog for ConfirmationDialog {
     }
 
     fn header(&self) -> String {
-        "Confirmation".to_string()
+        tr("dialog-confirmation")
     }
 
-    fn body(&self) -> Element<'_, GuiMessage> {
-        let message = Text::new(&self.message);
-        let yes_button = Button::new(Text::new("Yes")).on_press(self.on_confirm.clone());
-        let no_button = Button::new(Text::new("No")).on_press(GuiMessage::DialogClosed);
+    fn body(&self, sizes: Sizes) -> Element<'_, GuiMessage> {
+        let message = sizes.text(&self.message);
+        let yes_button =
+            Button::new(sizes.text(tr("button-yes"))).on_press(self.on_confirm.clone());
+        let no_button = Button::new(sizes.text(tr("button-no"))).on_press(GuiMessage::DialogClosed);
 
         let buttons = Row::new().push(yes_button).push(no_button).spacing(50);
 
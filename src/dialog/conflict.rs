@@ -0,0 +1,88 @@
+use iced::{
+    widget::{Button, Column, Row},
+    Element,
+};
+
+use crate::{app::message_handling::GuiMessage, localisation::tr, style::Sizes};
+
+use super::{diff_view, CardStyle, Dialog, DialogUpdate};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum ConflictingEditor {
+    Description,
+    HistoryContent,
+}
+
+/// Unsaved changes in an editor, while the text they are based on was changed in the database by someone else.
+#[derive(Debug, Clone)]
+pub(crate) struct EditConflict {
+    pub(crate) editor: ConflictingEditor,
+    pub(crate) mine: String,
+    pub(crate) theirs: String,
+}
+
+#[derive(Debug, Clone)]
+pub(crate) struct ConflictDialog {
+    conflict: EditConflict,
+    show_diff: bool,
+}
+
+impl ConflictDialog {
+    pub(crate) fn new(conflict: EditConflict) -> Self {
+        ConflictDialog {
+            conflict,
+            show_diff: false,
+        }
+    }
+}
+
+impl Dialog for ConflictDialog {
+    fn card_style(&self) -> CardStyle {
+        CardStyle::Warning
+    }
+
+    fn header(&self) -> String {
+        tr("dialog-conflict")
+    }
+
+    fn body(&self, sizes: Sizes) -> Element<'_, GuiMessage> {
+        let message = sizes.text(match self.conflict.editor {
+            ConflictingEditor::Description => tr("conflict-description"),
+            ConflictingEditor::HistoryContent => tr("conflict-history-item"),
+        });
+        let keep_button = Button::new(sizes.text(tr("button-keep-mine")))
+            .on_press(GuiMessage::KeepMine(self.conflict.clone()));
+        let take_button = Button::new(sizes.text(tr("button-take-theirs")))
+            .on_press(GuiMessage::TakeTheirs(self.conflict.clone()));
+        let diff_label = if self.show_diff {
+            tr("button-hide-diff")
+        } else {
+            tr("button-view-diff")
+        };
+        let diff_button = Button::new(sizes.text(diff_label))
+            .on_press(GuiMessage::DialogUpdate(DialogUpdate::ToggleDiff));
+        let buttons = Row::new()
+            .push(keep_button)
+            .push(take_button)
+            .push(diff_button)
+            .spacing(sizes.spacing);
+
+        let mut column = Column::new().push(message).push(buttons);
+        if self.show_diff {
+            column = column
+                .push(sizes.text(tr("conflict-diff-legend")))
+                .push(diff_view(&self.conflict.theirs, &self.conflict.mine, sizes));
+        }
+        column.padding(sizes.spacing).spacing(sizes.spacing).into()
+    }
+
+    fn update(&mut self, message: DialogUpdate) {
+        if let DialogUpdate::ToggleDiff = message {
+            self.show_diff = !self.show_diff;
+        }
+    }
+
+    fn submit(&self) -> GuiMessage {
+        GuiMessage::KeepMine(self.conflict.clone())
+    }
+}
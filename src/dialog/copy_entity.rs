@@ -0,0 +1,146 @@
+use iced::{
+    widget::{Button, Column, PickList, TextInput},
+    Element,
+};
+use lorecore::{sql::lore_database::LoreDatabase, types::*};
+
+use crate::{
+    app::message_handling::GuiMessage,
+    errors::LoreGuiError,
+    localisation::{tr, tr_args},
+    style::Sizes,
+};
+
+use super::{read_entity, Dialog, DialogUpdate};
+
+#[derive(Debug, Clone)]
+pub(crate) struct CopyEntityDialog {
+    target_databases: Vec<String>,
+    data: CopyEntityData,
+}
+
+impl CopyEntityDialog {
+    pub(crate) fn new(label: Label, target_databases: Vec<String>) -> Self {
+        let mut data = CopyEntityData::new(label);
+        if let [only_target] = target_databases.as_slice() {
+            data.target_database = only_target.clone();
+        }
+        CopyEntityDialog {
+            target_databases,
+            data,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub(crate) struct CopyEntityData {
+    pub(self) label: Label,
+    pub(self) new_label: Label,
+    pub(self) target_database: String,
+}
+
+impl CopyEntityData {
+    pub(crate) fn new(label: Label) -> Self {
+        CopyEntityData {
+            new_label: label.clone(),
+            label,
+            target_database: String::new(),
+        }
+    }
+
+    pub(crate) fn copy_between(
+        self,
+        source: &LoreDatabase,
+        target: &LoreDatabase,
+    ) -> Result<(), LoreGuiError> {
+        if self.new_label.to_str().is_empty() {
+            return Err(LoreGuiError::InputError(tr("error-copy-empty-label")));
+        }
+        if !read_entity(target, &self.new_label)?.is_empty() {
+            return Err(LoreGuiError::InputError(tr_args(
+                "error-copy-label-exists",
+                &[("label", &self.new_label)],
+            )));
+        }
+        let columns = read_entity(source, &self.label)?;
+        if columns.is_empty() {
+            return Err(LoreGuiError::InputError(tr_args(
+                "error-entity-missing",
+                &[("label", &self.label)],
+            )));
+        }
+        let columns = columns
+            .into_iter()
+            .map(|col| EntityColumn {
+                label: self.new_label.clone(),
+                descriptor: col.descriptor,
+                description: col.description,
+            })
+            .collect();
+        target.write_entity_columns(columns)?;
+        Ok(())
+    }
+
+    pub(crate) fn label(&self) -> &Label {
+        &self.label
+    }
+
+    pub(crate) fn target_database(&self) -> &str {
+        &self.target_database
+    }
+
+    #[cfg(test)]
+    pub(crate) fn set_new_label(&mut self, label: Label) {
+        self.new_label = label;
+    }
+
+    #[cfg(test)]
+    pub(crate) fn set_target_database(&mut self, path: String) {
+        self.target_database = path;
+    }
+}
+
+impl Dialog for CopyEntityDialog {
+    fn header(&self) -> String {
+        tr_args("dialog-copy-entity", &[("label", &self.data.label)])
+    }
+
+    fn body(&self, sizes: Sizes) -> Element<'_, GuiMessage> {
+        let selected_target = if self.data.target_database.is_empty() {
+            None
+        } else {
+            Some(self.data.target_database.clone())
+        };
+        let target_input = PickList::new(self.target_databases.clone(), selected_target, |s| {
+            GuiMessage::DialogUpdate(DialogUpdate::Database(s))
+        })
+        .text_size(sizes.text);
+        let new_label_input = TextInput::new("", self.data.new_label.to_str())
+            .size(sizes.text)
+            .on_input(|i| GuiMessage::DialogUpdate(DialogUpdate::Label(i.into())))
+            .on_submit(GuiMessage::DialogSubmit);
+        let submit_button =
+            Button::new(sizes.text(tr("button-copy"))).on_press(GuiMessage::DialogSubmit);
+        Column::new()
+            .push(sizes.text(tr("field-target-database")))
+            .push(target_input)
+            .push(sizes.text(tr("field-target-label")))
+            .push(new_label_input)
+            .push(submit_button)
+            .padding(sizes.spacing)
+            .spacing(sizes.spacing)
+            .into()
+    }
+
+    fn update(&mut self, message: DialogUpdate) {
+        match message {
+            DialogUpdate::Database(path) => self.data.target_database = path,
+            DialogUpdate::Label(label) => self.data.new_label = label,
+            _ => (),
+        }
+    }
+
+    fn submit(&self) -> GuiMessage {
+        GuiMessage::CopyEntity(self.data.to_owned())
+    }
+}
@@ -0,0 +1,111 @@
+use iced::{
+    widget::{Button, Column, Row},
+    Alignment, Element, Length,
+};
+
+use crate::{
+    app::message_handling::GuiMessage,
+    errors::LoreGuiError,
+    localisation::{tr, tr_args},
+    style::Sizes,
+    time_format::format_unix_time,
+    user_preferences::RecentDatabase,
+};
+
+use super::{CardStyle, Dialog, DialogUpdate};
+
+#[derive(Debug, Clone)]
+pub(crate) struct DatabaseChooserDialog {
+    recent_databases: Vec<RecentDatabase>,
+    error: Option<LoreGuiError>,
+}
+
+impl DatabaseChooserDialog {
+    pub(crate) fn new(recent_databases: Vec<RecentDatabase>) -> Self {
+        DatabaseChooserDialog {
+            recent_databases,
+            error: None,
+        }
+    }
+
+    /// Shown on startup, when the last used database could not be opened.
+    pub(crate) fn after_error(recent_databases: Vec<RecentDatabase>, error: LoreGuiError) -> Self {
+        DatabaseChooserDialog {
+            recent_databases,
+            error: Some(error),
+        }
+    }
+
+    fn database_row(recent: &RecentDatabase, sizes: Sizes) -> Row<'_, GuiMessage> {
+        let mut path = recent.path.to_string_lossy().to_string();
+        if !recent.exists() {
+            path = tr_args("recent-database-missing", &[("path", &path)]);
+        }
+        let mut open_button = Button::new(sizes.text(tr("button-open")));
+        if recent.exists() {
+            open_button = open_button.on_press(GuiMessage::OpenDatabaseAt(recent.path.clone()));
+        }
+        let pin_label = if recent.pinned {
+            tr("button-unpin")
+        } else {
+            tr("button-pin")
+        };
+        let pin_button = Button::new(sizes.text(pin_label)).on_press(
+            GuiMessage::PinRecentDatabase(recent.path.clone(), !recent.pinned),
+        );
+        let remove_button = Button::new(sizes.text(tr("button-remove")))
+            .on_press(GuiMessage::ForgetRecentDatabase(recent.path.clone()));
+        Row::new()
+            .push(sizes.text(path).width(Length::Fill))
+            .push(sizes.text(format_unix_time(recent.last_opened)))
+            .push(open_button)
+            .push(pin_button)
+            .push(remove_button)
+            .align_y(Alignment::Center)
+            .spacing(sizes.spacing)
+    }
+}
+
+impl Dialog for DatabaseChooserDialog {
+    fn card_style(&self) -> CardStyle {
+        if self.error.is_some() {
+            CardStyle::Warning
+        } else {
+            CardStyle::Primary
+        }
+    }
+
+    fn header(&self) -> String {
+        tr("dialog-recent-databases")
+    }
+
+    fn body(&self, sizes: Sizes) -> Element<'_, GuiMessage> {
+        let mut column = Column::new().padding(sizes.spacing).spacing(sizes.spacing);
+        if let Some(error) = self.error.as_ref() {
+            column = column.push(sizes.text(tr_args("recent-database-error", &[("error", error)])));
+        }
+        if self.recent_databases.is_empty() {
+            column = column.push(sizes.text(tr("recent-databases-none")));
+        }
+        for recent in self.recent_databases.iter() {
+            column = column.push(Self::database_row(recent, sizes));
+        }
+        let buttons = Row::new()
+            .push(
+                Button::new(sizes.text(tr("command-new-database")))
+                    .on_press(GuiMessage::NewDatabase),
+            )
+            .push(
+                Button::new(sizes.text(tr("command-open-database")))
+                    .on_press(GuiMessage::OpenDatabase),
+            )
+            .spacing(sizes.spacing);
+        column.push(buttons).into()
+    }
+
+    fn update(&mut self, _message: DialogUpdate) {}
+
+    fn submit(&self) -> GuiMessage {
+        GuiMessage::DialogClosed
+    }
+}
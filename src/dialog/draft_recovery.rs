@@ -0,0 +1,111 @@
+use iced::{
+    widget::{Button, Column, Row},
+    Alignment, Element,
+};
+
+use crate::{
+    app::message_handling::GuiMessage,
+    drafts::Draft,
+    localisation::{tr, tr_args},
+    style::Sizes,
+};
+
+use super::{diff_view, Dialog, DialogUpdate};
+
+/// A draft left behind by a previous session, together with the text that is stored in the database.
+#[derive(Debug, Clone)]
+pub(crate) struct RecoveredDraft {
+    pub(crate) draft: Draft,
+    /// `None` if what the draft belongs to no longer exists.
+    pub(crate) stored: Option<String>,
+}
+
+#[derive(Debug, Clone)]
+pub(crate) struct DraftRecoveryDialog {
+    drafts: Vec<RecoveredDraft>,
+    shown_diff: Option<usize>,
+}
+
+impl DraftRecoveryDialog {
+    pub(crate) fn new(drafts: Vec<RecoveredDraft>) -> Self {
+        DraftRecoveryDialog {
+            drafts,
+            shown_diff: None,
+        }
+    }
+
+    fn draft_row(
+        &self,
+        index: usize,
+        recovered: &RecoveredDraft,
+        sizes: Sizes,
+    ) -> Element<'_, GuiMessage> {
+        let mut restore_button = Button::new(sizes.text(tr("button-restore")));
+        if recovered.stored.is_some() {
+            restore_button =
+                restore_button.on_press(GuiMessage::RestoreDraft(recovered.draft.clone()));
+        }
+        let discard_button = Button::new(sizes.text(tr("button-discard")))
+            .on_press(GuiMessage::DiscardDraft(recovered.draft.clone()));
+        let diff_label = if self.shown_diff == Some(index) {
+            tr("button-hide-diff")
+        } else {
+            tr("button-view-diff")
+        };
+        let diff_button = Button::new(sizes.text(diff_label)).on_press(GuiMessage::DialogUpdate(
+            DialogUpdate::ToggleDraftDiff(index),
+        ));
+        let mut title = recovered.draft.title();
+        if recovered.stored.is_none() {
+            title = tr_args("draft-deleted", &[("title", &title)]);
+        }
+        let row = Row::new()
+            .push(sizes.text(title))
+            .push(restore_button)
+            .push(discard_button)
+            .push(diff_button)
+            .align_y(Alignment::Center)
+            .spacing(sizes.spacing);
+
+        let mut column = Column::new().push(row);
+        if self.shown_diff == Some(index) {
+            let stored = recovered.stored.as_deref().unwrap_or_default();
+            column = column.push(diff_view(stored, &recovered.draft.text, sizes));
+        }
+        column.spacing(sizes.spacing).into()
+    }
+}
+
+impl Dialog for DraftRecoveryDialog {
+    fn header(&self) -> String {
+        tr("dialog-draft-recovery")
+    }
+
+    fn body(&self, sizes: Sizes) -> Element<'_, GuiMessage> {
+        let mut column = Column::new()
+            .push(sizes.text(tr_args(
+                "draft-recovery-explanation",
+                &[("count", &self.drafts.len())],
+            )))
+            .padding(sizes.spacing)
+            .spacing(10);
+        for (index, recovered) in self.drafts.iter().enumerate() {
+            column = column.push(self.draft_row(index, recovered, sizes));
+        }
+        column.into()
+    }
+
+    fn update(&mut self, message: DialogUpdate) {
+        if let DialogUpdate::ToggleDraftDiff(index) = message {
+            if self.shown_diff == Some(index) {
+                self.shown_diff = None;
+            } else {
+                self.shown_diff = Some(index);
+            }
+        }
+    }
+
+    fn submit(&self) -> GuiMessage {
+        GuiMessage::DialogClosed
+    }
+}
@@ -0,0 +1,198 @@
+use iced::{
+    widget::{Button, Checkbox, Column, TextInput},
+    Element,
+};
+use lorecore::{sql::lore_database::LoreDatabase, types::*};
+
+use crate::{
+    app::message_handling::GuiMessage,
+    entity_view::NAME_DESCRIPTOR,
+    errors::LoreGuiError,
+    localisation::{tr, tr_args},
+    style::Sizes,
+};
+
+use super::{read_entity, relationships_of, Dialog, DialogUpdate};
+
+#[derive(Debug, Clone)]
+pub(crate) struct DuplicateEntityDialog {
+    data: DuplicateEntityData,
+}
+
+impl DuplicateEntityDialog {
+    pub(crate) fn new(label: Label) -> Self {
+        DuplicateEntityDialog {
+            data: DuplicateEntityData::new(label),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub(crate) struct DuplicateEntityData {
+    pub(self) label: Label,
+    pub(self) new_label: Label,
+    /// Replaces the `_name` of the copy, unless it is left empty.
+    pub(self) new_name: String,
+    pub(self) with_relationships: bool,
+}
+
+impl DuplicateEntityData {
+    pub(crate) fn new(label: Label) -> Self {
+        DuplicateEntityData {
+            new_label: label.clone(),
+            label,
+            new_name: String::new(),
+            with_relationships: false,
+        }
+    }
+
+    pub(crate) fn write_to_database(self, db: &LoreDatabase) -> Result<(), LoreGuiError> {
+        if self.new_label.to_str().is_empty() {
+            return Err(LoreGuiError::InputError(tr("error-empty-label")));
+        }
+        if !read_entity(db, &self.new_label)?.is_empty() {
+            return Err(LoreGuiError::InputError(tr_args(
+                "error-duplicate-label-exists",
+                &[("label", &self.new_label)],
+            )));
+        }
+        let columns = read_entity(db, &self.label)?;
+        if columns.is_empty() {
+            return Err(LoreGuiError::InputError(tr_args(
+                "error-entity-missing",
+                &[("label", &self.label)],
+            )));
+        }
+
+        let name_descriptor: Descriptor = NAME_DESCRIPTOR.into();
+        let mut columns: Vec<EntityColumn> = columns
+            .into_iter()
+            .filter(|col| self.new_name.is_empty() || col.descriptor != name_descriptor)
+            .map(|col| EntityColumn {
+                label: self.new_label.clone(),
+                descriptor: col.descriptor,
+                description: col.description,
+            })
+            .collect();
+        if !self.new_name.is_empty() {
+            columns.push(EntityColumn {
+                label: self.new_label.clone(),
+                descriptor: name_descriptor,
+                description: self.new_name.clone().into(),
+            });
+        }
+        db.write_entity_columns(columns)?;
+
+        if self.with_relationships {
+            let relationships = self.duplicated_relationships(db)?;
+            if !relationships.is_empty() {
+                db.write_relationships(relationships)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// The relationships of the source entity, with the new label taking its place as parent and child.
+    fn duplicated_relationships(
+        &self,
+        db: &LoreDatabase,
+    ) -> Result<Vec<EntityRelationship>, LoreGuiError> {
+        let label = self.label.to_str();
+        let mut duplicates: Vec<EntityRelationship> = vec![];
+        for relationship in relationships_of(db, &self.label)? {
+            let substitute = |name: &str| {
+                if name == label {
+                    self.new_label.to_str().to_string()
+                } else {
+                    name.to_string()
+                }
+            };
+            let duplicate = EntityRelationship {
+                parent: substitute(relationship.parent.to_str()).into(),
+                child: substitute(relationship.child.to_str()).into(),
+                role: relationship.role,
+            };
+            if !duplicates.contains(&duplicate) {
+                duplicates.push(duplicate);
+            }
+        }
+        Ok(duplicates)
+    }
+
+    pub(crate) fn label(&self) -> &Label {
+        &self.label
+    }
+
+    pub(crate) fn new_label(&self) -> &Label {
+        &self.new_label
+    }
+
+    pub(crate) fn with_relationships(&self) -> bool {
+        self.with_relationships
+    }
+
+    #[cfg(test)]
+    pub(crate) fn set_new_label(&mut self, label: Label) {
+        self.new_label = label;
+    }
+
+    #[cfg(test)]
+    pub(crate) fn set_new_name(&mut self, name: String) {
+        self.new_name = name;
+    }
+
+    #[cfg(test)]
+    pub(crate) fn set_with_relationships(&mut self, with_relationships: bool) {
+        self.with_relationships = with_relationships;
+    }
+}
+
+impl Dialog for DuplicateEntityDialog {
+    fn header(&self) -> String {
+        tr_args("dialog-duplicate-entity", &[("label", &self.data.label)])
+    }
+
+    fn body(&self, sizes: Sizes) -> Element<'_, GuiMessage> {
+        let new_label_input = TextInput::new("", self.data.new_label.to_str())
+            .size(sizes.text)
+            .on_input(|i| GuiMessage::DialogUpdate(DialogUpdate::Label(i.into())))
+            .on_submit(GuiMessage::DialogSubmit);
+        let new_name_input = TextInput::new(&tr("field-keep-name"), &self.data.new_name)
+            .size(sizes.text)
+            .on_input(|s| GuiMessage::DialogUpdate(DialogUpdate::Name(s)))
+            .on_submit(GuiMessage::DialogSubmit);
+        let relationships_checkbox = Checkbox::new(
+            tr("field-duplicate-relationships"),
+            self.data.with_relationships,
+        )
+        .text_size(sizes.text)
+        .on_toggle(|_| GuiMessage::DialogUpdate(DialogUpdate::ToggleRelationships));
+        let submit_button =
+            Button::new(sizes.text(tr("button-duplicate"))).on_press(GuiMessage::DialogSubmit);
+        Column::new()
+            .push(sizes.text(tr("field-new-label")))
+            .push(new_label_input)
+            .push(sizes.text(tr("field-new-name")))
+            .push(new_name_input)
+            .push(relationships_checkbox)
+            .push(submit_button)
+            .padding(sizes.spacing)
+            .spacing(sizes.spacing)
+            .into()
+    }
+
+    fn update(&mut self, message: DialogUpdate) {
+        match message {
+            DialogUpdate::Label(label) => self.data.new_label = label,
+            DialogUpdate::Name(name) => self.data.new_name = name,
+            DialogUpdate::ToggleRelationships => {
+                self.data.with_relationships = !self.data.with_relationships
+            }
+            _ => (),
+        }
+    }
+
+    fn submit(&self) -> GuiMessage {
+        GuiMessage::DuplicateEntity(self.data.to_owned())
+    }
+}
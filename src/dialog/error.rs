@@ -1,19 +1,25 @@
-use super::{CardStyle, Dialog};
+use super::{CardStyle, Dialog, DialogUpdate};
 use crate::app::message_handling::GuiMessage;
 use crate::errors::LoreGuiError;
+use crate::localisation::tr;
+use crate::style::Sizes;
 use iced::{
-    widget::{Button, Column, Text},
+    widget::{Button, Column, Row},
     Element,
 };
 
 #[derive(Debug, Clone)]
 pub(crate) struct ErrorDialog {
     error: LoreGuiError,
+    show_details: bool,
 }
 
 impl ErrorDialog {
     pub(crate) fn new(error: LoreGuiError) -> Self {
-        ErrorDialog { error }
+        ErrorDialog {
+            error,
+            show_details: false,
+        }
     }
 }
 
@@ -23,16 +29,38 @@ impl Dialog for ErrorDialog {
     }
 
     fn header(&self) -> String {
-        "Error".to_string()
+        tr("dialog-error")
     }
 
-    fn body(&self) -> Element<'_, GuiMessage> {
-        let text = Text::new(self.error.to_string());
-        let button = Button::new(Text::new("Ok")).on_press(GuiMessage::DialogClosed);
-        Column::new().push(text).push(button).into()
+    fn body(&self, sizes: Sizes) -> Element<'_, GuiMessage> {
+        let text = sizes.text(self.error.to_string());
+        let ok_button = Button::new(sizes.text(tr("button-ok"))).on_press(GuiMessage::DialogClosed);
+        let details_label = if self.show_details {
+            tr("button-hide-details")
+        } else {
+            tr("button-show-details")
+        };
+        let details_button = Button::new(sizes.text(details_label))
+            .on_press(GuiMessage::DialogUpdate(DialogUpdate::ToggleDetails));
+        let copy_button = Button::new(sizes.text(tr("button-copy-to-clipboard")))
+            .on_press(GuiMessage::CopyToClipboard(self.error.details()));
+        let buttons = Row::new()
+            .push(ok_button)
+            .push(details_button)
+            .push(copy_button)
+            .spacing(sizes.spacing);
+        let mut column = Column::new().push(text).push(buttons);
+        if self.show_details {
+            column = column.push(sizes.text(self.error.details()));
+        }
+        column.spacing(sizes.spacing).into()
     }
 
-    fn update(&mut self, _message: super::DialogUpdate) {}
+    fn update(&mut self, message: DialogUpdate) {
+        if let DialogUpdate::ToggleDetails = message {
+            self.show_details = !self.show_details;
+        }
+    }
 
     fn submit(&self) -> GuiMessage {
         GuiMessage::DialogClosed
@@ -0,0 +1,583 @@
+use std::sync::Arc;
+
+use iced::{
+    widget::{button, text, Button, Column, PickList, Row, TextInput},
+    Alignment, Element, Length,
+};
+use lorecore::{extractions::extract_labels, sql::lore_database::LoreDatabase, types::*};
+
+use crate::{
+    app::message_handling::GuiMessage,
+    errors::LoreGuiError,
+    localisation::{tr, tr_args},
+    style::Sizes,
+};
+
+use super::{read_entity, relationships_of, Dialog, DialogUpdate};
+
+/// How to combine the descriptions of a descriptor that both entities have.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) enum MergeResolution {
+    KeepTarget,
+    KeepSource,
+    Concatenate,
+    Edited(String),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum MergeStep {
+    ChooseEntities,
+    ResolveConflicts,
+    Preview,
+}
+
+/// The descriptions a descriptor has in the target and in the source entity.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct DescriptorComparison {
+    pub(crate) descriptor: Descriptor,
+    pub(crate) target: Option<Description>,
+    pub(crate) source: Option<Description>,
+}
+
+impl DescriptorComparison {
+    pub(crate) fn is_conflict(&self) -> bool {
+        matches!((&self.target, &self.source), (Some(target), Some(source)) if target != source)
+    }
+
+    pub(crate) fn resolve(&self, resolution: &MergeResolution) -> Description {
+        match (&self.target, &self.source) {
+            (Some(target), Some(source)) if target != source => match resolution {
+                MergeResolution::KeepTarget => target.clone(),
+                MergeResolution::KeepSource => source.clone(),
+                MergeResolution::Concatenate => concatenate(target, source),
+                MergeResolution::Edited(text) => text.as_str().into(),
+            },
+            (Some(description), _) | (None, Some(description)) => description.clone(),
+            (None, None) => Description::NONE,
+        }
+    }
+}
+
+fn concatenate(target: &Description, source: &Description) -> Description {
+    format!(
+        "{}\n\n{}",
+        target.to_str().trim_end(),
+        source.to_str().trim_start()
+    )
+    .into()
+}
+
+/// Lines up the descriptors of both entities, those of the target first.
+pub(crate) fn compare(
+    columns: &[EntityColumn],
+    source: &Label,
+    target: &Label,
+) -> Vec<DescriptorComparison> {
+    let mut comparisons: Vec<DescriptorComparison> = columns
+        .iter()
+        .filter(|col| col.label == *target)
+        .map(|col| DescriptorComparison {
+            descriptor: col.descriptor.clone(),
+            target: Some(col.description.clone()),
+            source: None,
+        })
+        .collect();
+    for col in columns.iter().filter(|col| col.label == *source) {
+        match comparisons
+            .iter_mut()
+            .find(|comparison| comparison.descriptor == col.descriptor)
+        {
+            Some(comparison) => comparison.source = Some(col.description.clone()),
+            None => comparisons.push(DescriptorComparison {
+                descriptor: col.descriptor.clone(),
+                target: None,
+                source: Some(col.description.clone()),
+            }),
+        }
+    }
+    comparisons
+}
+
+#[derive(Debug, Clone)]
+pub(crate) struct MergeEntitiesDialog {
+    columns: Arc<Vec<EntityColumn>>,
+    relationships: Arc<Vec<EntityRelationship>>,
+    labels: Vec<Label>,
+    source: Option<Label>,
+    target: Option<Label>,
+    resolutions: Vec<(Descriptor, MergeResolution)>,
+    step: MergeStep,
+}
+
+impl MergeEntitiesDialog {
+    pub(crate) fn new(
+        columns: Arc<Vec<EntityColumn>>,
+        relationships: Arc<Vec<EntityRelationship>>,
+        source: Option<Label>,
+    ) -> Self {
+        MergeEntitiesDialog {
+            labels: extract_labels(&columns),
+            columns,
+            relationships,
+            source,
+            target: None,
+            resolutions: vec![],
+            step: MergeStep::ChooseEntities,
+        }
+    }
+
+    fn comparisons(&self) -> Vec<DescriptorComparison> {
+        match (self.source.as_ref(), self.target.as_ref()) {
+            (Some(source), Some(target)) => compare(&self.columns, source, target),
+            _ => vec![],
+        }
+    }
+
+    fn resolution(&self, descriptor: &Descriptor) -> &MergeResolution {
+        self.resolutions
+            .iter()
+            .find(|(d, _)| d == descriptor)
+            .map(|(_, resolution)| resolution)
+            .unwrap_or(&MergeResolution::KeepTarget)
+    }
+
+    fn can_continue(&self) -> bool {
+        match (self.source.as_ref(), self.target.as_ref()) {
+            (Some(source), Some(target)) => source != target,
+            _ => false,
+        }
+    }
+
+    /// What will be written, once source and target are chosen.
+    pub(crate) fn data(&self) -> Option<MergeEntitiesData> {
+        if !self.can_continue() {
+            return None;
+        }
+        let target = self.target.clone()?;
+        let columns = self
+            .comparisons()
+            .iter()
+            .map(|comparison| EntityColumn {
+                label: target.clone(),
+                descriptor: comparison.descriptor.clone(),
+                description: comparison.resolve(self.resolution(&comparison.descriptor)),
+            })
+            .collect();
+        Some(MergeEntitiesData {
+            source: self.source.clone()?,
+            target,
+            columns,
+        })
+    }
+
+    fn moved_relationships(&self) -> usize {
+        let source = match self.source.as_ref() {
+            Some(source) => source.to_str(),
+            None => return 0,
+        };
+        self.relationships
+            .iter()
+            .filter(|rel| rel.parent.to_str() == source || rel.child.to_str() == source)
+            .count()
+    }
+
+    fn choose_entities(&self, sizes: Sizes) -> Column<'_, GuiMessage> {
+        let source_input = PickList::new(self.labels.clone(), self.source.clone(), |label| {
+            GuiMessage::DialogUpdate(DialogUpdate::Source(label))
+        })
+        .text_size(sizes.text);
+        let target_input = PickList::new(self.labels.clone(), self.target.clone(), |label| {
+            GuiMessage::DialogUpdate(DialogUpdate::Target(label))
+        })
+        .text_size(sizes.text);
+        let mut column = Column::new()
+            .push(sizes.text(tr("field-merge-source")))
+            .push(source_input)
+            .push(sizes.text(tr("field-merge-target")))
+            .push(target_input);
+        if self.source.is_some() && self.source == self.target {
+            column = column.push(sizes.text(tr("merge-same-entity")).style(text::danger));
+        }
+        column
+    }
+
+    fn resolve_conflicts(&self, sizes: Sizes) -> Column<'_, GuiMessage> {
+        let header = Row::new()
+            .push(
+                sizes
+                    .text(tr("column-descriptor"))
+                    .width(Length::FillPortion(1)),
+            )
+            .push(
+                sizes
+                    .text(tr("merge-target-description"))
+                    .width(Length::FillPortion(2)),
+            )
+            .push(
+                sizes
+                    .text(tr("merge-source-description"))
+                    .width(Length::FillPortion(2)),
+            )
+            .spacing(sizes.spacing);
+        let mut column = Column::new().push(header);
+        for comparison in self.comparisons() {
+            column = column.push(self.comparison_row(comparison, sizes));
+        }
+        column
+    }
+
+    fn comparison_row(
+        &self,
+        comparison: DescriptorComparison,
+        sizes: Sizes,
+    ) -> Element<'_, GuiMessage> {
+        let description = |description: &Option<Description>| {
+            let content = description
+                .as_ref()
+                .map(|description| description.to_string())
+                .unwrap_or_else(|| tr("merge-missing"));
+            sizes.text(content).width(Length::FillPortion(2))
+        };
+        let row = Row::new()
+            .push(
+                sizes
+                    .text(comparison.descriptor.to_string())
+                    .width(Length::FillPortion(1)),
+            )
+            .push(description(&comparison.target))
+            .push(description(&comparison.source))
+            .spacing(sizes.spacing);
+        if !comparison.is_conflict() {
+            return row.into();
+        }
+
+        let resolution = self.resolution(&comparison.descriptor);
+        let choice = |key: &str, choice: MergeResolution| {
+            let is_chosen = match (&choice, resolution) {
+                (MergeResolution::Edited(_), MergeResolution::Edited(_)) => true,
+                (choice, resolution) => choice == resolution,
+            };
+            let style = if is_chosen {
+                button::primary
+            } else {
+                button::secondary
+            };
+            Button::new(sizes.text(tr(key)))
+                .style(style)
+                .on_press(GuiMessage::DialogUpdate(DialogUpdate::MergeResolution(
+                    comparison.descriptor.clone(),
+                    choice,
+                )))
+        };
+        let edited_start = match resolution {
+            MergeResolution::Edited(text) => text.clone(),
+            _ => comparison
+                .resolve(&MergeResolution::Concatenate)
+                .to_string(),
+        };
+        let choices = Row::new()
+            .push(sizes.text(tr("merge-conflict")).style(text::danger))
+            .push(choice("button-keep-target", MergeResolution::KeepTarget))
+            .push(choice("button-keep-source", MergeResolution::KeepSource))
+            .push(choice("button-concatenate", MergeResolution::Concatenate))
+            .push(choice("button-edit", MergeResolution::Edited(edited_start)))
+            .align_y(Alignment::Center)
+            .spacing(sizes.spacing);
+        let mut column = Column::new().push(row).push(choices);
+        if let MergeResolution::Edited(text) = resolution {
+            let descriptor = comparison.descriptor.clone();
+            column = column.push(
+                TextInput::new("", text)
+                    .size(sizes.text)
+                    .on_input(move |text| {
+                        GuiMessage::DialogUpdate(DialogUpdate::MergeResolution(
+                            descriptor.clone(),
+                            MergeResolution::Edited(text),
+                        ))
+                    }),
+            );
+        }
+        column.spacing(sizes.spacing).into()
+    }
+
+    fn preview(&self, sizes: Sizes) -> Column<'_, GuiMessage> {
+        let mut column = Column::new();
+        let data = match self.data() {
+            Some(data) => data,
+            None => return column,
+        };
+        column = column.push(sizes.text(tr_args(
+            "merge-preview",
+            &[("source", &data.source), ("target", &data.target)],
+        )));
+        for col in data.columns {
+            column = column.push(
+                Row::new()
+                    .push(
+                        sizes
+                            .text(col.descriptor.to_string())
+                            .width(Length::FillPortion(1)),
+                    )
+                    .push(
+                        sizes
+                            .text(col.description.to_string())
+                            .width(Length::FillPortion(4)),
+                    )
+                    .spacing(sizes.spacing),
+            );
+        }
+        column.push(sizes.text(tr_args(
+            "merge-moved-relationships",
+            &[("count", &self.moved_relationships())],
+        )))
+    }
+}
+
+impl Dialog for MergeEntitiesDialog {
+    fn header(&self) -> String {
+        tr("dialog-merge-entities")
+    }
+
+    fn body(&self, sizes: Sizes) -> Element<'_, GuiMessage> {
+        let content = match self.step {
+            MergeStep::ChooseEntities => self.choose_entities(sizes),
+            MergeStep::ResolveConflicts => self.resolve_conflicts(sizes),
+            MergeStep::Preview => self.preview(sizes),
+        };
+        let back_button = Button::new(sizes.text(tr("button-back"))).on_press_maybe(
+            (self.step != MergeStep::ChooseEntities)
+                .then_some(GuiMessage::DialogUpdate(DialogUpdate::SelectPrevious)),
+        );
+        let next_button = match self.step {
+            MergeStep::Preview => Button::new(sizes.text(tr("button-merge"))),
+            _ => Button::new(sizes.text(tr("button-next"))),
+        }
+        .on_press_maybe(self.can_continue().then_some(GuiMessage::DialogSubmit));
+        let buttons = Row::new()
+            .push(back_button)
+            .push(next_button)
+            .spacing(sizes.spacing);
+        Column::new()
+            .push(content.spacing(sizes.spacing))
+            .push(buttons)
+            .padding(sizes.spacing)
+            .spacing(sizes.spacing)
+            .into()
+    }
+
+    fn update(&mut self, message: DialogUpdate) {
+        match message {
+            DialogUpdate::Source(label) => {
+                self.source = Some(label);
+                self.resolutions.clear();
+            }
+            DialogUpdate::Target(label) => {
+                self.target = Some(label);
+                self.resolutions.clear();
+            }
+            DialogUpdate::MergeResolution(descriptor, resolution) => {
+                self.resolutions.retain(|(d, _)| *d != descriptor);
+                self.resolutions.push((descriptor, resolution));
+            }
+            DialogUpdate::SelectNext if self.can_continue() => {
+                self.step = match self.step {
+                    MergeStep::ChooseEntities => MergeStep::ResolveConflicts,
+                    _ => MergeStep::Preview,
+                }
+            }
+            DialogUpdate::SelectPrevious => {
+                self.step = match self.step {
+                    MergeStep::Preview => MergeStep::ResolveConflicts,
+                    _ => MergeStep::ChooseEntities,
+                }
+            }
+            _ => (),
+        }
+    }
+
+    /// Advances to the next step, and merges from the preview.
+    fn submit(&self) -> GuiMessage {
+        match (self.step, self.data()) {
+            (MergeStep::Preview, Some(data)) => GuiMessage::MergeEntities(data),
+            _ => GuiMessage::DialogUpdate(DialogUpdate::SelectNext),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct MergeEntitiesData {
+    pub(self) source: Label,
+    pub(self) target: Label,
+    /// The columns the target has after the merge.
+    pub(self) columns: Vec<EntityColumn>,
+}
+
+impl MergeEntitiesData {
+    pub(crate) fn source(&self) -> &Label {
+        &self.source
+    }
+
+    pub(crate) fn target(&self) -> &Label {
+        &self.target
+    }
+
+    /// Moves the relationships of the source to the target, writes the resolved descriptions and deletes the source.
+    pub(crate) fn write_to_database(self, db: &LoreDatabase) -> Result<(), LoreGuiError> {
+        if self.source == self.target {
+            return Err(LoreGuiError::InputError(tr("merge-same-entity")));
+        }
+        for label in [&self.source, &self.target] {
+            if read_entity(db, label)?.is_empty() {
+                return Err(LoreGuiError::InputError(tr_args(
+                    "error-entity-missing",
+                    &[("label", label)],
+                )));
+            }
+        }
+
+        let target_columns = read_entity(db, &self.target)?;
+        let mut new_columns = vec![];
+        for col in self.columns {
+            match target_columns
+                .iter()
+                .find(|existing| existing.descriptor == col.descriptor)
+            {
+                Some(existing) if existing.description == col.description => {}
+                Some(_) => {
+                    db.change_entity_description((&self.target, &col.descriptor), &col.description)?
+                }
+                None => new_columns.push(col),
+            }
+        }
+        if !new_columns.is_empty() {
+            db.write_entity_columns(new_columns)?;
+        }
+
+        let target_relationships = relationships_of(db, &self.target)?;
+        let mut moved_relationships: Vec<EntityRelationship> = vec![];
+        for relationship in relationships_of(db, &self.source)? {
+            db.delete_relationship(relationship.clone())?;
+            let substitute = |name: &str| {
+                if name == self.source.to_str() {
+                    self.target.to_string()
+                } else {
+                    name.to_string()
+                }
+            };
+            let moved = EntityRelationship {
+                parent: substitute(relationship.parent.to_str()).into(),
+                child: substitute(relationship.child.to_str()).into(),
+                role: relationship.role,
+            };
+            let is_self_reference = moved.parent.to_str() == moved.child.to_str();
+            if !is_self_reference
+                && !target_relationships.contains(&moved)
+                && !moved_relationships.contains(&moved)
+            {
+                moved_relationships.push(moved);
+            }
+        }
+        if !moved_relationships.is_empty() {
+            db.write_relationships(moved_relationships)?;
+        }
+
+        db.delete_entity(self.source)?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use lorecore::sql::search_params::{EntityColumnSearchParams, RelationshipSearchParams};
+
+    use super::*;
+    use crate::tests::{example_database, example_descriptors, example_labels};
+
+    pub(crate) fn example_merge_dialog(db: &LoreDatabase) -> MergeEntitiesDialog {
+        let columns = db
+            .read_entity_columns(EntityColumnSearchParams::new(None, None))
+            .unwrap();
+        let relationships = db
+            .read_relationships(RelationshipSearchParams::new(None, None))
+            .unwrap();
+        let labels = example_labels();
+        let mut dialog = MergeEntitiesDialog::new(
+            Arc::new(columns),
+            Arc::new(relationships),
+            Some(labels[0].clone()),
+        );
+        dialog.update(DialogUpdate::Target(labels[1].clone()));
+        dialog
+    }
+
+    #[test]
+    fn only_differing_descriptions_are_conflicts() {
+        let source: Label = "source".into();
+        let target: Label = "target".into();
+        let col = |label: &Label, descriptor: &str, description: &str| EntityColumn {
+            label: label.clone(),
+            descriptor: descriptor.into(),
+            description: description.into(),
+        };
+        let columns = vec![
+            col(&target, "Same", "Equal"),
+            col(&target, "Different", "Old"),
+            col(&source, "Different", "New"),
+            col(&source, "Same", "Equal"),
+            col(&source, "Extra", "Only here"),
+        ];
+        let comparisons = compare(&columns, &source, &target);
+        let conflicts: Vec<bool> = comparisons.iter().map(|c| c.is_conflict()).collect();
+        assert_eq!(conflicts, vec![false, true, false]);
+        assert_eq!(
+            comparisons[1].resolve(&MergeResolution::Concatenate),
+            "Old\n\nNew".into()
+        );
+        assert_eq!(
+            comparisons[1].resolve(&MergeResolution::Edited("Mine".to_string())),
+            "Mine".into()
+        );
+        assert_eq!(
+            comparisons[2].resolve(&MergeResolution::KeepTarget),
+            "Only here".into()
+        );
+    }
+
+    #[test]
+    fn wizard_walks_through_the_steps_before_merging() {
+        let db = example_database();
+        let mut dialog = example_merge_dialog(&db);
+        assert!(matches!(
+            dialog.submit(),
+            GuiMessage::DialogUpdate(DialogUpdate::SelectNext)
+        ));
+        dialog.update(DialogUpdate::SelectNext);
+        dialog.update(DialogUpdate::SelectNext);
+        assert_eq!(dialog.step, MergeStep::Preview);
+
+        let descriptor = example_descriptors()[0].0.clone();
+        dialog.update(DialogUpdate::MergeResolution(
+            descriptor.clone(),
+            MergeResolution::KeepSource,
+        ));
+        let data = match dialog.submit() {
+            GuiMessage::MergeEntities(data) => data,
+            _ => panic!("The preview should submit the merge."),
+        };
+        assert_eq!(data.columns.len(), example_descriptors().len());
+        assert!(data
+            .columns
+            .iter()
+            .all(|col| col.label == example_labels()[1]));
+    }
+
+    #[test]
+    fn source_and_target_have_to_differ() {
+        let db = example_database();
+        let mut dialog = example_merge_dialog(&db);
+        dialog.update(DialogUpdate::Target(example_labels()[0].clone()));
+        dialog.update(DialogUpdate::SelectNext);
+        assert_eq!(dialog.step, MergeStep::ChooseEntities);
+        assert!(dialog.data().is_none());
+    }
+}
@@ -1,14 +1,39 @@
 use super::app::message_handling::GuiMessage;
 use iced::{
-    widget::{Container, Scrollable, Text},
+    widget::{text, text_input, Column, Container, Scrollable, Text},
     Element,
 };
 use iced_aw::{style, Card};
-use lorecore::{errors::LoreCoreError, types::*};
+use lorecore::{
+    errors::LoreCoreError,
+    sql::{
+        lore_database::LoreDatabase,
+        search_params::{EntityColumnSearchParams, RelationshipSearchParams, SqlSearchText},
+    },
+    types::*,
+};
+
+use crate::{
+    appearance::{Appearance, PaletteColor, SizeSetting, ThemeChoice},
+    dialog::merge_entities::MergeResolution,
+    diff::{diff_lines, DiffLine},
+    errors::LoreGuiError,
+    localisation::Locale,
+    shortcuts::{KeyBinding, ShortcutAction},
+    style::Sizes,
+};
 
+pub(crate) mod change_category;
 pub(crate) mod change_role;
+pub(crate) mod command_palette;
 pub(crate) mod confirmation;
+pub(crate) mod conflict;
+pub(crate) mod copy_entity;
+pub(crate) mod database_chooser;
+pub(crate) mod draft_recovery;
+pub(crate) mod duplicate_entity;
 pub(crate) mod error;
+pub(crate) mod merge_entities;
 pub(crate) mod new_descriptor;
 pub(crate) mod new_entity;
 pub(crate) mod new_history_item;
@@ -16,6 +41,9 @@ pub(crate) mod new_relationship;
 pub(crate) mod redate_history;
 pub(crate) mod relabel_entity;
 pub(crate) mod rename_descriptor;
+pub(crate) mod rename_descriptor_everywhere;
+pub(crate) mod settings;
+pub(crate) mod unsaved_changes;
 
 pub(crate) enum CardStyle {
     Primary,
@@ -30,15 +58,30 @@ pub(crate) trait Dialog {
 
     fn header(&self) -> String;
 
-    fn body(&self) -> Element<'_, GuiMessage>;
+    fn body(&self, sizes: Sizes) -> Element<'_, GuiMessage>;
 
     fn update(&mut self, message: DialogUpdate);
 
     fn submit(&self) -> GuiMessage;
 
-    fn to_element<'a>(&'a self) -> Element<'a, GuiMessage> {
-        let header: Text<'a> = Text::new(self.header());
-        let body = self.body();
+    /// Dialogs that are waiting for a key press receive it instead of the shortcut handling.
+    fn captures_keys(&self) -> bool {
+        false
+    }
+
+    /// Appearance changes that are shown while the dialog is open, before they are saved.
+    fn appearance_preview(&self) -> Option<&Appearance> {
+        None
+    }
+
+    /// The text input that receives the keyboard focus when the dialog is opened.
+    fn focused_input(&self) -> Option<text_input::Id> {
+        None
+    }
+
+    fn to_element<'a>(&'a self, sizes: Sizes) -> Element<'a, GuiMessage> {
+        let header: Text<'a> = sizes.text(self.header());
+        let body = self.body(sizes);
         let card =
             Card::new::<Element<'a, GuiMessage>, Element<'a, GuiMessage>>(header.into(), body)
                 .on_close(GuiMessage::DialogClosed);
@@ -51,17 +94,74 @@ pub(crate) trait Dialog {
     }
 }
 
+fn read_entity(db: &LoreDatabase, label: &Label) -> Result<Vec<EntityColumn>, LoreGuiError> {
+    let search_params =
+        EntityColumnSearchParams::new(Some(SqlSearchText::exact(label.to_str())), None);
+    Ok(db.read_entity_columns(search_params)?)
+}
+
+/// The relationships `label` takes part in, as parent or as child.
+fn relationships_of(
+    db: &LoreDatabase,
+    label: &Label,
+) -> Result<Vec<EntityRelationship>, LoreGuiError> {
+    let as_parent = RelationshipSearchParams::new(Some(SqlSearchText::exact(label.to_str())), None);
+    let as_child = RelationshipSearchParams::new(None, Some(SqlSearchText::exact(label.to_str())));
+    let mut relationships = db.read_relationships(as_parent)?;
+    for relationship in db.read_relationships(as_child)? {
+        if !relationships.contains(&relationship) {
+            relationships.push(relationship);
+        }
+    }
+    Ok(relationships)
+}
+
+/// Lines only in `old` are marked -, lines only in `new` +.
+fn diff_view<'a>(old: &str, new: &str, sizes: Sizes) -> Column<'a, GuiMessage> {
+    let mut column = Column::new();
+    for line in diff_lines(old, new) {
+        let line = match line {
+            DiffLine::Unchanged(line) => sizes.text(format!("  {}", line)),
+            DiffLine::Removed(line) => sizes.text(format!("- {}", line)).style(text::danger),
+            DiffLine::Added(line) => sizes.text(format!("+ {}", line)).style(text::success),
+        };
+        column = column.push(line);
+    }
+    column
+}
+
 #[derive(Debug, Clone)]
 pub(crate) enum DialogUpdate {
+    AddPalette,
     Category(String),
     Child(Child),
     Content(HistoryItemContent),
+    Database(String),
     Day(Result<Day, LoreCoreError>),
+    DeletePalette(String),
     Description(Description),
     Descriptor(Descriptor),
+    KeyPress(KeyBinding),
     Label(Label),
+    Locale(Locale),
+    MergeResolution(Descriptor, MergeResolution),
     Name(String),
+    NotificationSeconds(u32),
+    PaletteColor(PaletteColor, String),
     Parent(Parent),
+    RecordKeyBinding(ShortcutAction),
+    ResetKeyBindings,
     Role(Role),
+    Search(String),
+    Source(Label),
+    SelectNext,
+    SelectPrevious,
+    Size(SizeSetting, f32),
+    Target(Label),
+    Theme(ThemeChoice),
+    ToggleDetails,
+    ToggleDiff,
+    ToggleDraftDiff(usize),
+    ToggleRelationships,
     Year(Result<Year, LoreCoreError>),
 }
@@ -1,10 +1,12 @@
 use iced::{
-    widget::{Button, Column, Text, TextInput},
+    widget::{Button, Column, TextInput},
     Element,
 };
 use lorecore::{sql::lore_database::LoreDatabase, types::*};
 
-use crate::{app::message_handling::GuiMessage, errors::LoreGuiError};
+use crate::{
+    app::message_handling::GuiMessage, errors::LoreGuiError, localisation::tr, style::Sizes,
+};
 
 use super::{Dialog, DialogUpdate};
 
@@ -39,9 +41,7 @@ impl NewDescriptorData {
 
     pub(crate) fn write_to_database(self, db: &LoreDatabase) -> Result<(), LoreGuiError> {
         if self.descriptor.to_str().is_empty() {
-            return Err(LoreGuiError::InputError(
-                "Cannot create empty descriptor.".to_string(),
-            ));
+            return Err(LoreGuiError::InputError(tr("error-empty-descriptor")));
         }
 
         let col = EntityColumn {
@@ -61,17 +61,14 @@ impl NewDescriptorData {
         &self.descriptor
     }
 
-    #[cfg(test)]
     pub(crate) fn set_descriptor(&mut self, descriptor: Descriptor) {
         self.descriptor = descriptor;
     }
 
-    #[cfg(test)]
     pub(crate) fn description(&self) -> &Description {
         &self.description
     }
 
-    #[cfg(test)]
     pub(crate) fn set_description(&mut self, description: Description) {
         self.description = description;
     }
@@ -79,23 +76,28 @@ impl NewDescriptorData {
 
 impl Dialog for NewDescriptorDialog {
     fn header(&self) -> String {
-        "New Descriptor".to_string()
+        tr("dialog-new-descriptor")
     }
 
-    fn body(&self) -> Element<'_, GuiMessage> {
+    fn body(&self, sizes: Sizes) -> Element<'_, GuiMessage> {
         let descriptor_input = TextInput::new("", self.data.descriptor.to_str())
-            .on_input(|i| GuiMessage::DialogUpdate(DialogUpdate::Descriptor(i.into())));
+            .size(sizes.text)
+            .on_input(|i| GuiMessage::DialogUpdate(DialogUpdate::Descriptor(i.into())))
+            .on_submit(GuiMessage::DialogSubmit);
         let description_input = TextInput::new("", self.data.description.to_str())
-            .on_input(|i| GuiMessage::DialogUpdate(DialogUpdate::Description(i.into())));
-        let submit_button = Button::new(Text::new("Create")).on_press(GuiMessage::DialogSubmit);
+            .size(sizes.text)
+            .on_input(|i| GuiMessage::DialogUpdate(DialogUpdate::Description(i.into())))
+            .on_submit(GuiMessage::DialogSubmit);
+        let submit_button =
+            Button::new(sizes.text(tr("button-create"))).on_press(GuiMessage::DialogSubmit);
         Column::new()
-            .push(Text::new("Descriptor:"))
+            .push(sizes.text(tr("field-descriptor")))
             .push(descriptor_input)
-            .push(Text::new("Description:"))
+            .push(sizes.text(tr("field-description")))
             .push(description_input)
             .push(submit_button)
-            .padding(5)
-            .spacing(5)
+            .padding(sizes.spacing)
+            .spacing(sizes.spacing)
             .into()
     }
 
@@ -1,22 +1,28 @@
 use iced::{
-    widget::{Button, Column, Text, TextInput},
+    widget::{Button, Column, TextInput},
     Element,
 };
 use lorecore::{sql::lore_database::LoreDatabase, types::*};
 
 use crate::app::message_handling::GuiMessage;
+use crate::entity_view::{CATEGORY_DESCRIPTOR, NAME_DESCRIPTOR};
 use crate::errors::LoreGuiError;
+use crate::localisation::tr;
+use crate::style::Sizes;
 
-use super::{Dialog, DialogUpdate};
+use super::{change_category::category_suggestions, Dialog, DialogUpdate};
 
 #[derive(Debug, Clone)]
 pub(crate) struct NewEntityDialog {
+    /// The existing categories, suggested while typing one.
+    categories: Vec<String>,
     data: NewEntityData,
 }
 
 impl NewEntityDialog {
-    pub(crate) fn new() -> Self {
+    pub(crate) fn new(categories: Vec<String>) -> Self {
         NewEntityDialog {
+            categories,
             data: NewEntityData::new(),
         }
     }
@@ -40,23 +46,17 @@ impl NewEntityData {
 
     pub(crate) fn write_to_database(self, db: &LoreDatabase) -> Result<(), LoreGuiError> {
         if self.label.to_str().is_empty() {
-            return Err(LoreGuiError::InputError(
-                "Cannot create entity with empty label.".to_string(),
-            ));
+            return Err(LoreGuiError::InputError(tr("error-empty-label")));
         }
         if self.name.is_empty() {
-            return Err(LoreGuiError::InputError(
-                "Cannot create entity with empty name.".to_string(),
-            ));
+            return Err(LoreGuiError::InputError(tr("error-empty-name")));
         }
         if self.category.is_empty() {
-            return Err(LoreGuiError::InputError(
-                "Cannot create entity with empty category.".to_string(),
-            ));
+            return Err(LoreGuiError::InputError(tr("error-empty-category")));
         }
 
-        let category_descriptor = "_category".into();
-        let name_descriptor = "_name".into();
+        let category_descriptor = CATEGORY_DESCRIPTOR.into();
+        let name_descriptor = NAME_DESCRIPTOR.into();
 
         let name_col = EntityColumn {
             label: self.label.clone(),
@@ -79,35 +79,54 @@ impl NewEntityData {
         &self.label
     }
 
-    #[cfg(test)]
     pub(crate) fn set_label(&mut self, label: Label) {
         self.label = label;
     }
+
+    pub(crate) fn set_name(&mut self, name: String) {
+        self.name = name;
+    }
+
+    pub(crate) fn set_category(&mut self, category: String) {
+        self.category = category;
+    }
 }
 
 impl Dialog for NewEntityDialog {
     fn header(&self) -> String {
-        "Create new entity".to_string()
+        tr("dialog-new-entity")
     }
 
-    fn body(&self) -> Element<'_, GuiMessage> {
+    fn body(&self, sizes: Sizes) -> Element<'_, GuiMessage> {
         let label_input = TextInput::new("", self.data.label.to_str())
-            .on_input(|i| GuiMessage::DialogUpdate(DialogUpdate::Label(i.into())));
+            .size(sizes.text)
+            .on_input(|i| GuiMessage::DialogUpdate(DialogUpdate::Label(i.into())))
+            .on_submit(GuiMessage::DialogSubmit);
         let name_input = TextInput::new("", &self.data.name)
-            .on_input(|s| GuiMessage::DialogUpdate(DialogUpdate::Name(s)));
+            .size(sizes.text)
+            .on_input(|s| GuiMessage::DialogUpdate(DialogUpdate::Name(s)))
+            .on_submit(GuiMessage::DialogSubmit);
         let category_input = TextInput::new("", &self.data.category)
-            .on_input(|s| GuiMessage::DialogUpdate(DialogUpdate::Category(s)));
-        let submit_button = Button::new(Text::new("Create")).on_press(GuiMessage::DialogSubmit);
+            .size(sizes.text)
+            .on_input(|s| GuiMessage::DialogUpdate(DialogUpdate::Category(s)))
+            .on_submit(GuiMessage::DialogSubmit);
+        let submit_button =
+            Button::new(sizes.text(tr("button-create"))).on_press(GuiMessage::DialogSubmit);
         Column::new()
-            .push(Text::new("Label:"))
+            .push(sizes.text(tr("field-label")))
             .push(label_input)
-            .push(Text::new("Name"))
+            .push(sizes.text(tr("field-name")))
             .push(name_input)
-            .push(Text::new("Category:"))
+            .push(sizes.text(tr("field-category")))
             .push(category_input)
+            .push(category_suggestions(
+                &self.categories,
+                &self.data.category,
+                sizes,
+            ))
             .push(submit_button)
-            .padding(5)
-            .spacing(5)
+            .padding(sizes.spacing)
+            .spacing(sizes.spacing)
             .into()
     }
 
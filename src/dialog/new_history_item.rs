@@ -1,10 +1,12 @@
 use iced::{
-    widget::{Button, Column, Text, TextInput},
+    widget::{Button, Column, TextInput},
     Element,
 };
 use lorecore::{sql::lore_database::LoreDatabase, timestamp::current_timestamp, types::*};
 
-use crate::{app::message_handling::GuiMessage, errors::LoreGuiError};
+use crate::{
+    app::message_handling::GuiMessage, errors::LoreGuiError, localisation::tr, style::Sizes,
+};
 
 use super::{Dialog, DialogUpdate};
 
@@ -16,13 +18,7 @@ pub(crate) struct NewHistoryDialog {
 impl NewHistoryDialog {
     pub(crate) fn new() -> Self {
         NewHistoryDialog {
-            data: NewHistoryData {
-                year: 0.into(),
-                day: Day::NONE,
-                content: "".into(),
-                properties: HistoryItemProperties::none(),
-                timestamp: current_timestamp(),
-            },
+            data: NewHistoryData::new(0.into(), Day::NONE, "".into()),
         }
     }
 }
@@ -37,6 +33,16 @@ pub(crate) struct NewHistoryData {
 }
 
 impl NewHistoryData {
+    pub(crate) fn new(year: Year, day: Day, content: HistoryItemContent) -> Self {
+        NewHistoryData {
+            timestamp: current_timestamp(),
+            year,
+            day,
+            content,
+            properties: HistoryItemProperties::none(),
+        }
+    }
+
     pub(crate) fn write_to_database(self, db: &LoreDatabase) -> Result<(), LoreGuiError> {
         let item = HistoryItem {
             timestamp: self.timestamp,
@@ -69,28 +75,35 @@ impl NewHistoryData {
 
 impl Dialog for NewHistoryDialog {
     fn header(&self) -> String {
-        "Create new history item".to_string()
+        tr("dialog-new-history-item")
     }
 
-    fn body(&self) -> Element<'_, GuiMessage> {
+    fn body(&self, sizes: Sizes) -> Element<'_, GuiMessage> {
         let year_input = TextInput::new("", &self.data.year.to_string())
-            .on_input(|i| GuiMessage::DialogUpdate(DialogUpdate::Year(i.try_into())));
+            .size(sizes.text)
+            .on_input(|i| GuiMessage::DialogUpdate(DialogUpdate::Year(i.try_into())))
+            .on_submit(GuiMessage::DialogSubmit);
         let day_string = format!("{}", self.data.day);
         let day_input = TextInput::new("", &day_string)
-            .on_input(|i| GuiMessage::DialogUpdate(DialogUpdate::Day(i.try_into())));
+            .size(sizes.text)
+            .on_input(|i| GuiMessage::DialogUpdate(DialogUpdate::Day(i.try_into())))
+            .on_submit(GuiMessage::DialogSubmit);
         let content_input = TextInput::new("", self.data.content.to_str())
-            .on_input(|i| GuiMessage::DialogUpdate(DialogUpdate::Content(i.into())));
-        let submit_button = Button::new("Create").on_press(GuiMessage::DialogSubmit);
+            .size(sizes.text)
+            .on_input(|i| GuiMessage::DialogUpdate(DialogUpdate::Content(i.into())))
+            .on_submit(GuiMessage::DialogSubmit);
+        let submit_button =
+            Button::new(sizes.text(tr("button-create"))).on_press(GuiMessage::DialogSubmit);
         Column::new()
-            .push(Text::new("Year:"))
+            .push(sizes.text(tr("field-year")))
             .push(year_input)
-            .push(Text::new("Day:"))
+            .push(sizes.text(tr("field-day")))
             .push(day_input)
-            .push(Text::new("Content:"))
+            .push(sizes.text(tr("field-content")))
             .push(content_input)
             .push(submit_button)
-            .padding(5)
-            .spacing(5)
+            .padding(sizes.spacing)
+            .spacing(sizes.spacing)
             .into()
     }
 
@@ -1,10 +1,12 @@
 use iced::{
-    widget::{Button, Column, PickList, Text, TextInput},
+    widget::{Button, Column, PickList, TextInput},
     Element,
 };
 use lorecore::{sql::lore_database::LoreDatabase, types::*};
 
-use crate::{app::message_handling::GuiMessage, errors::LoreGuiError};
+use crate::{
+    app::message_handling::GuiMessage, errors::LoreGuiError, localisation::tr, style::Sizes,
+};
 
 use super::{Dialog, DialogUpdate};
 
@@ -43,9 +45,7 @@ impl NewRelationshipData {
 
     pub(crate) fn write_to_database(self, db: &LoreDatabase) -> Result<(), LoreGuiError> {
         if self.parent.to_str().is_empty() || self.child.to_str().is_empty() {
-            return Err(LoreGuiError::InputError(
-                "Parent and child cannot be empty.".to_string(),
-            ));
+            return Err(LoreGuiError::InputError(tr("error-empty-parent-or-child")));
         }
 
         let rel = EntityRelationship {
@@ -68,14 +68,26 @@ impl NewRelationshipData {
     pub(crate) fn role(&self) -> &Role {
         &self.role
     }
+
+    pub(crate) fn set_parent(&mut self, parent: Parent) {
+        self.parent = parent;
+    }
+
+    pub(crate) fn set_child(&mut self, child: Child) {
+        self.child = child;
+    }
+
+    pub(crate) fn set_role(&mut self, role: Role) {
+        self.role = role;
+    }
 }
 
 impl Dialog for NewRelationshipDialog {
     fn header(&self) -> String {
-        "New Relationship".to_string()
+        tr("dialog-new-relationship")
     }
 
-    fn body(&self) -> Element<'_, GuiMessage> {
+    fn body(&self, sizes: Sizes) -> Element<'_, GuiMessage> {
         let selected_parent = if self.data.parent.to_str().is_empty() {
             None
         } else {
@@ -83,7 +95,8 @@ impl Dialog for NewRelationshipDialog {
         };
         let parent_input = PickList::new(self.parent_labels.clone(), selected_parent, |s| {
             GuiMessage::DialogUpdate(DialogUpdate::Parent(s))
-        });
+        })
+        .text_size(sizes.text);
         let selected_child = if self.data.child.to_str().is_empty() {
             None
         } else {
@@ -91,20 +104,24 @@ impl Dialog for NewRelationshipDialog {
         };
         let child_input = PickList::new(self.child_labels.clone(), selected_child, |s| {
             GuiMessage::DialogUpdate(DialogUpdate::Child(s))
-        });
+        })
+        .text_size(sizes.text);
         let role_input = TextInput::new("", self.data.role.to_str())
-            .on_input(|i| GuiMessage::DialogUpdate(DialogUpdate::Role(i.into())));
-        let submit_button = Button::new(Text::new("Create")).on_press(GuiMessage::DialogSubmit);
+            .size(sizes.text)
+            .on_input(|i| GuiMessage::DialogUpdate(DialogUpdate::Role(i.into())))
+            .on_submit(GuiMessage::DialogSubmit);
+        let submit_button =
+            Button::new(sizes.text(tr("button-create"))).on_press(GuiMessage::DialogSubmit);
         Column::new()
-            .push(Text::new("Parent:"))
+            .push(sizes.text(tr("field-parent")))
             .push(parent_input)
-            .push(Text::new("Child:"))
+            .push(sizes.text(tr("field-child")))
             .push(child_input)
-            .push(Text::new("Role:"))
+            .push(sizes.text(tr("field-role")))
             .push(role_input)
             .push(submit_button)
-            .padding(5)
-            .spacing(5)
+            .padding(sizes.spacing)
+            .spacing(sizes.spacing)
             .into()
     }
 
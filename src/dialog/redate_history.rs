@@ -1,10 +1,15 @@
 use iced::{
-    widget::{Button, Column, Text, TextInput},
+    widget::{Button, Column, TextInput},
     Element,
 };
 use lorecore::{sql::lore_database::LoreDatabase, types::*};
 
-use crate::{app::message_handling::GuiMessage, errors::LoreGuiError};
+use crate::{
+    app::message_handling::GuiMessage,
+    errors::LoreGuiError,
+    localisation::{tr, tr_args},
+    style::Sizes,
+};
 
 use super::{Dialog, DialogUpdate};
 
@@ -56,12 +61,10 @@ impl RedateHistoryData {
         self.timestamp
     }
 
-    #[cfg(test)]
     pub(crate) fn set_new_year(&mut self, year: Year) {
         self.new_year = year;
     }
 
-    #[cfg(test)]
     pub(crate) fn set_new_day(&mut self, day: Day) {
         self.new_day = day;
     }
@@ -69,26 +72,35 @@ impl RedateHistoryData {
 
 impl Dialog for RedateHistoryDialog {
     fn header(&self) -> String {
-        format!(
-            "Redate history for entity: year {}, day {} ({})",
-            self.data.old_year, self.data.old_day, self.data.timestamp
+        tr_args(
+            "dialog-redate-history-item",
+            &[
+                ("year", &self.data.old_year),
+                ("day", &self.data.old_day),
+                ("timestamp", &self.data.timestamp),
+            ],
         )
     }
 
-    fn body(&self) -> Element<'_, GuiMessage> {
+    fn body(&self, sizes: Sizes) -> Element<'_, GuiMessage> {
         let year_input = TextInput::new("", &self.data.new_year.to_string())
-            .on_input(|i| GuiMessage::DialogUpdate(DialogUpdate::Year(i.try_into())));
+            .size(sizes.text)
+            .on_input(|i| GuiMessage::DialogUpdate(DialogUpdate::Year(i.try_into())))
+            .on_submit(GuiMessage::DialogSubmit);
         let day_input = TextInput::new("", &self.data.new_day.to_string())
-            .on_input(|i| GuiMessage::DialogUpdate(DialogUpdate::Day(i.try_into())));
-        let submit_button = Button::new("Redate").on_press(GuiMessage::DialogSubmit);
+            .size(sizes.text)
+            .on_input(|i| GuiMessage::DialogUpdate(DialogUpdate::Day(i.try_into())))
+            .on_submit(GuiMessage::DialogSubmit);
+        let submit_button =
+            Button::new(sizes.text(tr("button-redate"))).on_press(GuiMessage::DialogSubmit);
         Column::new()
-            .push(Text::new("Year:"))
+            .push(sizes.text(tr("field-year")))
             .push(year_input)
-            .push(Text::new("Day (optional):"))
+            .push(sizes.text(tr("field-day-optional")))
             .push(day_input)
             .push(submit_button)
-            .padding(5)
-            .spacing(5)
+            .padding(sizes.spacing)
+            .spacing(sizes.spacing)
             .into()
     }
 
@@ -1,10 +1,15 @@
 use iced::{
-    widget::{Button, Column, Text, TextInput},
+    widget::{Button, Column, TextInput},
     Element,
 };
 use lorecore::{sql::lore_database::LoreDatabase, types::*};
 
-use crate::{app::message_handling::GuiMessage, errors::LoreGuiError};
+use crate::{
+    app::message_handling::GuiMessage,
+    errors::LoreGuiError,
+    localisation::{tr, tr_args},
+    style::Sizes,
+};
 
 use super::{Dialog, DialogUpdate};
 
@@ -35,14 +40,12 @@ impl RelabelEntityData {
 
     pub(crate) fn update_label_in_database(self, db: &LoreDatabase) -> Result<(), LoreGuiError> {
         if self.old_label.to_str().is_empty() {
-            return Err(LoreGuiError::InputError(
-                "Cannot relabel entity with empty label.".to_string(),
-            ));
+            return Err(LoreGuiError::InputError(tr("error-relabel-empty-label")));
         }
         if self.new_label.to_str().is_empty() {
-            return Err(LoreGuiError::InputError(
-                "Cannot relabel entity with empty new label.".to_string(),
-            ));
+            return Err(LoreGuiError::InputError(tr(
+                "error-relabel-empty-new-label",
+            )));
         }
 
         db.relabel_entity(&self.old_label, &self.new_label)?;
@@ -53,27 +56,33 @@ impl RelabelEntityData {
         &self.new_label
     }
 
-    #[cfg(test)]
     pub(crate) fn old_label(&self) -> &Label {
         &self.old_label
     }
+
+    pub(crate) fn set_new_label(&mut self, label: Label) {
+        self.new_label = label;
+    }
 }
 
 impl Dialog for RelabelEntityDialog {
     fn header(&self) -> String {
-        format!("Relabel entity: {}", self.data.old_label)
+        tr_args("dialog-relabel-entity", &[("label", &self.data.old_label)])
     }
 
-    fn body(&self) -> Element<'_, GuiMessage> {
+    fn body(&self, sizes: Sizes) -> Element<'_, GuiMessage> {
         let new_label_input = TextInput::new("", self.data.new_label.to_str())
-            .on_input(|i| GuiMessage::DialogUpdate(DialogUpdate::Label(i.into())));
-        let submit_button = Button::new(Text::new("Update")).on_press(GuiMessage::DialogSubmit);
+            .size(sizes.text)
+            .on_input(|i| GuiMessage::DialogUpdate(DialogUpdate::Label(i.into())))
+            .on_submit(GuiMessage::DialogSubmit);
+        let submit_button =
+            Button::new(sizes.text(tr("button-update"))).on_press(GuiMessage::DialogSubmit);
         Column::new()
-            .push(Text::new("New Label"))
+            .push(sizes.text(tr("field-new-label")))
             .push(new_label_input)
             .push(submit_button)
-            .padding(5)
-            .spacing(5)
+            .padding(sizes.spacing)
+            .spacing(sizes.spacing)
             .into()
     }
 
@@ -1,10 +1,15 @@
 use iced::{
-    widget::{Button, Column, Text, TextInput},
+    widget::{Button, Column, TextInput},
     Element,
 };
 use lorecore::{sql::lore_database::LoreDatabase, types::*};
 
-use crate::{app::message_handling::GuiMessage, errors::LoreGuiError};
+use crate::{
+    app::message_handling::GuiMessage,
+    errors::LoreGuiError,
+    localisation::{tr, tr_args},
+    style::Sizes,
+};
 
 use super::{Dialog, DialogUpdate};
 
@@ -40,14 +45,14 @@ impl RenameDescriptorData {
         db: &LoreDatabase,
     ) -> Result<(), LoreGuiError> {
         if self.old_descriptor.to_str().is_empty() {
-            return Err(LoreGuiError::InputError(
-                "Cannot rename descriptor with empty descriptor.".to_string(),
-            ));
+            return Err(LoreGuiError::InputError(tr(
+                "error-rename-empty-descriptor",
+            )));
         }
         if self.new_descriptor.to_str().is_empty() {
-            return Err(LoreGuiError::InputError(
-                "Cannot rename descriptor with empty new descriptor.".to_string(),
-            ));
+            return Err(LoreGuiError::InputError(tr(
+                "error-rename-empty-new-descriptor",
+            )));
         }
 
         db.change_entity_descriptor((&self.label, self.old_descriptor), &self.new_descriptor)?;
@@ -58,7 +63,6 @@ impl RenameDescriptorData {
         &self.label
     }
 
-    #[cfg(test)]
     pub(crate) fn old_descriptor(&self) -> &Descriptor {
         &self.old_descriptor
     }
@@ -70,22 +74,28 @@ impl RenameDescriptorData {
 
 impl Dialog for RenameDescriptorDialog {
     fn header(&self) -> String {
-        format!(
-            "Rename descriptor {} of entity {}",
-            self.data.old_descriptor, self.data.label
+        tr_args(
+            "dialog-rename-descriptor",
+            &[
+                ("descriptor", &self.data.old_descriptor),
+                ("label", &self.data.label),
+            ],
         )
     }
 
-    fn body(&self) -> Element<'_, GuiMessage> {
+    fn body(&self, sizes: Sizes) -> Element<'_, GuiMessage> {
         let new_descriptor_input = TextInput::new("", self.data.new_descriptor.to_str())
-            .on_input(|i| GuiMessage::DialogUpdate(DialogUpdate::Descriptor(i.into())));
-        let submit_button = Button::new(Text::new("Update")).on_press(GuiMessage::DialogSubmit);
+            .size(sizes.text)
+            .on_input(|i| GuiMessage::DialogUpdate(DialogUpdate::Descriptor(i.into())))
+            .on_submit(GuiMessage::DialogSubmit);
+        let submit_button =
+            Button::new(sizes.text(tr("button-update"))).on_press(GuiMessage::DialogSubmit);
         Column::new()
-            .push(Text::new("New Descriptor"))
+            .push(sizes.text(tr("field-new-descriptor")))
             .push(new_descriptor_input)
             .push(submit_button)
-            .padding(5)
-            .spacing(5)
+            .padding(sizes.spacing)
+            .spacing(sizes.spacing)
             .into()
     }
 
@@ -0,0 +1,230 @@
+use std::sync::Arc;
+
+use iced::{
+    widget::{text, Button, Column, TextInput},
+    Element,
+};
+use lorecore::{
+    sql::{
+        lore_database::LoreDatabase,
+        search_params::{EntityColumnSearchParams, SqlSearchText},
+    },
+    types::*,
+};
+
+use crate::{
+    app::message_handling::GuiMessage,
+    errors::LoreGuiError,
+    localisation::{tr, tr_args},
+    style::Sizes,
+};
+
+use super::{Dialog, DialogUpdate};
+
+#[derive(Debug, Clone)]
+pub(crate) struct RenameDescriptorEverywhereDialog {
+    columns: Arc<Vec<EntityColumn>>,
+    data: RenameDescriptorEverywhereData,
+}
+
+impl RenameDescriptorEverywhereDialog {
+    pub(crate) fn new(columns: Arc<Vec<EntityColumn>>, descriptor: Descriptor) -> Self {
+        let data = RenameDescriptorEverywhereData::new(&columns, descriptor.clone(), descriptor);
+        RenameDescriptorEverywhereDialog { columns, data }
+    }
+
+    fn can_submit(&self) -> bool {
+        !self.data.new_descriptor.to_str().is_empty()
+            && self.data.new_descriptor != self.data.old_descriptor
+            && !self.data.labels.is_empty()
+    }
+}
+
+/// Renames a descriptor on every entity having it, except on those that already have the new descriptor.
+#[derive(Debug, Clone)]
+pub(crate) struct RenameDescriptorEverywhereData {
+    pub(self) old_descriptor: Descriptor,
+    pub(self) new_descriptor: Descriptor,
+    /// The entities whose descriptor is renamed.
+    pub(self) labels: Vec<Label>,
+    /// The entities having both descriptors, which are left untouched.
+    pub(self) collisions: Vec<Label>,
+}
+
+impl RenameDescriptorEverywhereData {
+    pub(crate) fn new(
+        columns: &[EntityColumn],
+        old_descriptor: Descriptor,
+        new_descriptor: Descriptor,
+    ) -> Self {
+        let labels_with = |descriptor: &Descriptor| -> Vec<Label> {
+            columns
+                .iter()
+                .filter(|col| col.descriptor == *descriptor)
+                .map(|col| col.label.clone())
+                .collect()
+        };
+        let mut labels = labels_with(&old_descriptor);
+        let mut collisions = vec![];
+        if new_descriptor != old_descriptor {
+            let existing = labels_with(&new_descriptor);
+            (collisions, labels) = labels
+                .into_iter()
+                .partition(|label| existing.contains(label));
+        }
+        RenameDescriptorEverywhereData {
+            old_descriptor,
+            new_descriptor,
+            labels,
+            collisions,
+        }
+    }
+
+    pub(crate) fn update_descriptors_in_database(
+        self,
+        db: &LoreDatabase,
+    ) -> Result<(), LoreGuiError> {
+        if self.old_descriptor.to_str().is_empty() {
+            return Err(LoreGuiError::InputError(tr(
+                "error-rename-empty-descriptor",
+            )));
+        }
+        if self.new_descriptor.to_str().is_empty() {
+            return Err(LoreGuiError::InputError(tr(
+                "error-rename-empty-new-descriptor",
+            )));
+        }
+        let search_params = EntityColumnSearchParams::new(
+            None,
+            Some(SqlSearchText::exact(self.new_descriptor.to_str())),
+        );
+        let collision = db
+            .read_entity_columns(search_params)?
+            .into_iter()
+            .find(|col| self.labels.contains(&col.label));
+        if let Some(col) = collision {
+            return Err(LoreGuiError::InputError(tr_args(
+                "error-descriptor-exists",
+                &[("label", &col.label), ("descriptor", &col.descriptor)],
+            )));
+        }
+
+        for label in self.labels {
+            db.change_entity_descriptor(
+                (&label, self.old_descriptor.clone()),
+                &self.new_descriptor,
+            )?;
+        }
+        Ok(())
+    }
+
+    pub(crate) fn old_descriptor(&self) -> &Descriptor {
+        &self.old_descriptor
+    }
+
+    pub(crate) fn new_descriptor(&self) -> &Descriptor {
+        &self.new_descriptor
+    }
+
+    pub(crate) fn labels(&self) -> &[Label] {
+        &self.labels
+    }
+
+    pub(crate) fn collisions(&self) -> &[Label] {
+        &self.collisions
+    }
+}
+
+fn label_list(labels: &[Label], sizes: Sizes) -> Column<'_, GuiMessage> {
+    Column::new()
+        .extend(
+            labels
+                .iter()
+                .map(|label| sizes.text(format!("• {}", label)).into()),
+        )
+        .padding([0.0, sizes.spacing * 2.0])
+}
+
+impl Dialog for RenameDescriptorEverywhereDialog {
+    fn header(&self) -> String {
+        tr_args(
+            "dialog-rename-descriptor-everywhere",
+            &[("descriptor", &self.data.old_descriptor)],
+        )
+    }
+
+    fn body(&self, sizes: Sizes) -> Element<'_, GuiMessage> {
+        let new_descriptor_input = TextInput::new("", self.data.new_descriptor.to_str())
+            .size(sizes.text)
+            .on_input(|i| GuiMessage::DialogUpdate(DialogUpdate::Descriptor(i.into())))
+            .on_submit_maybe(self.can_submit().then_some(GuiMessage::DialogSubmit));
+        let submit_button = Button::new(sizes.text(tr("button-update")))
+            .on_press_maybe(self.can_submit().then_some(GuiMessage::DialogSubmit));
+        let mut column = Column::new()
+            .push(sizes.text(tr("field-new-descriptor")))
+            .push(new_descriptor_input)
+            .push(sizes.text(tr_args(
+                "rename-everywhere-affected",
+                &[("count", &self.data.labels.len())],
+            )))
+            .push(label_list(&self.data.labels, sizes));
+        if !self.data.collisions.is_empty() {
+            column = column
+                .push(
+                    sizes
+                        .text(tr_args(
+                            "rename-everywhere-collisions",
+                            &[
+                                ("count", &self.data.collisions.len()),
+                                ("descriptor", &self.data.new_descriptor),
+                            ],
+                        ))
+                        .style(text::danger),
+                )
+                .push(label_list(&self.data.collisions, sizes));
+        }
+        column
+            .push(submit_button)
+            .padding(sizes.spacing)
+            .spacing(sizes.spacing)
+            .into()
+    }
+
+    fn update(&mut self, message: DialogUpdate) {
+        if let DialogUpdate::Descriptor(new_descriptor) = message {
+            self.data = RenameDescriptorEverywhereData::new(
+                &self.columns,
+                self.data.old_descriptor.clone(),
+                new_descriptor,
+            );
+        }
+    }
+
+    fn submit(&self) -> GuiMessage {
+        GuiMessage::RenameDescriptorEverywhere(self.data.to_owned())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn entities_having_both_descriptors_are_collisions() {
+        let col = |label: &str, descriptor: &str| EntityColumn {
+            label: label.into(),
+            descriptor: descriptor.into(),
+            description: "".into(),
+        };
+        let columns = vec![
+            col("Alice", "hometown"),
+            col("Bob", "hometown"),
+            col("Bob", "birthplace"),
+            col("Carol", "birthplace"),
+        ];
+        let data =
+            RenameDescriptorEverywhereData::new(&columns, "hometown".into(), "birthplace".into());
+        assert_eq!(data.labels().to_vec(), vec![Label::from("Alice")]);
+        assert_eq!(data.collisions().to_vec(), vec![Label::from("Bob")]);
+    }
+}
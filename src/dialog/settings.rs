@@ -0,0 +1,246 @@
+use iced::{
+    widget::{text, Button, Column, PickList, Row, Slider, TextInput},
+    Alignment, Element, Length,
+};
+
+use crate::{
+    app::message_handling::GuiMessage,
+    appearance::{
+        Appearance, CustomPalette, PaletteColor, SizeSetting, ThemeChoice, NOTIFICATION_SECONDS,
+    },
+    localisation::{tr, tr_args, Locale},
+    shortcuts::{KeyBindings, ShortcutAction},
+    style::Sizes,
+};
+
+use super::{Dialog, DialogUpdate};
+
+#[derive(Debug, Clone)]
+pub(crate) struct SettingsDialog {
+    key_bindings: KeyBindings,
+    recording: Option<ShortcutAction>,
+    appearance: Appearance,
+    new_palette: CustomPalette,
+    palette_error: Option<String>,
+}
+
+impl SettingsDialog {
+    pub(crate) fn new(key_bindings: KeyBindings, appearance: Appearance) -> Self {
+        SettingsDialog {
+            key_bindings,
+            recording: None,
+            appearance,
+            new_palette: CustomPalette::default(),
+            palette_error: None,
+        }
+    }
+
+    fn appearance_section(&self, sizes: Sizes) -> Column<'_, GuiMessage> {
+        let theme_picker = PickList::new(
+            self.appearance.theme_choices(),
+            Some(self.appearance.theme.clone()),
+            |theme| GuiMessage::DialogUpdate(DialogUpdate::Theme(theme)),
+        )
+        .text_size(sizes.text);
+        let locale_picker = PickList::new(Locale::ALL, Some(self.appearance.locale), |locale| {
+            GuiMessage::DialogUpdate(DialogUpdate::Locale(locale))
+        })
+        .text_size(sizes.text);
+        let mut column = Column::new()
+            .push(sizes.header(tr("settings-appearance")))
+            .push(labelled_row(
+                &tr("settings-language"),
+                locale_picker.into(),
+                sizes,
+            ))
+            .push(labelled_row(
+                &tr("settings-theme"),
+                theme_picker.into(),
+                sizes,
+            ))
+            .spacing(sizes.spacing);
+        for setting in SizeSetting::ALL {
+            let value = self.appearance.get(setting);
+            let slider = Slider::new(setting.range(), value, move |value| {
+                GuiMessage::DialogUpdate(DialogUpdate::Size(setting, value))
+            })
+            .step(1.0)
+            .width(Length::Fixed(200.0));
+            let row = Row::new()
+                .push(slider)
+                .push(sizes.text(value.to_string()).width(Length::Fixed(40.0)))
+                .align_y(Alignment::Center)
+                .spacing(sizes.spacing);
+            column = column.push(labelled_row(&setting.name(), row.into(), sizes));
+        }
+        let seconds = self.appearance.notification_seconds;
+        let slider = Slider::new(NOTIFICATION_SECONDS, seconds, |seconds| {
+            GuiMessage::DialogUpdate(DialogUpdate::NotificationSeconds(seconds))
+        })
+        .width(Length::Fixed(200.0));
+        let row = Row::new()
+            .push(slider)
+            .push(
+                sizes
+                    .text(tr_args("settings-seconds", &[("seconds", &seconds)]))
+                    .width(Length::Fixed(40.0)),
+            )
+            .align_y(Alignment::Center)
+            .spacing(sizes.spacing);
+        column = column.push(labelled_row(
+            &tr("settings-notification-duration"),
+            row.into(),
+            sizes,
+        ));
+        column.push(self.palette_section(sizes))
+    }
+
+    fn palette_section(&self, sizes: Sizes) -> Column<'_, GuiMessage> {
+        let mut column = Column::new()
+            .push(sizes.text(tr("settings-custom-palettes")))
+            .spacing(sizes.spacing);
+        for palette in self.appearance.custom_palettes.iter() {
+            let delete_button = Button::new(sizes.text(tr("button-delete"))).on_press(
+                GuiMessage::DialogUpdate(DialogUpdate::DeletePalette(palette.name.clone())),
+            );
+            column = column.push(labelled_row(&palette.name, delete_button.into(), sizes));
+        }
+        let name_input = TextInput::new(&tr("settings-palette-name"), &self.new_palette.name)
+            .size(sizes.text)
+            .on_input(|name| GuiMessage::DialogUpdate(DialogUpdate::Name(name)));
+        column = column.push(labelled_row(
+            &tr("settings-new-palette"),
+            name_input.into(),
+            sizes,
+        ));
+        for color in PaletteColor::ALL {
+            let color_input = TextInput::new("#rrggbb", self.new_palette.get(color))
+                .size(sizes.text)
+                .on_input(move |hex| {
+                    GuiMessage::DialogUpdate(DialogUpdate::PaletteColor(color, hex))
+                });
+            column = column.push(labelled_row(&color.name(), color_input.into(), sizes));
+        }
+        if let Some(error) = self.palette_error.as_ref() {
+            column = column.push(sizes.text(error).style(text::danger));
+        }
+        let add_button = Button::new(sizes.text(tr("button-add-palette")))
+            .on_press(GuiMessage::DialogUpdate(DialogUpdate::AddPalette));
+        column.push(add_button)
+    }
+
+    fn key_binding_row(&self, action: ShortcutAction, sizes: Sizes) -> Row<'_, GuiMessage> {
+        let binding = if self.recording == Some(action) {
+            tr("settings-press-a-key")
+        } else {
+            match self.key_bindings.get(action) {
+                Some(binding) => binding.to_string(),
+                None => tr("settings-unbound"),
+            }
+        };
+        let mut binding_text = sizes.text(binding).width(Length::Fixed(150.0));
+        if self.key_bindings.conflicts().contains(&action) {
+            binding_text = binding_text.style(iced::widget::text::danger);
+        }
+        let change_button = Button::new(sizes.text(tr("button-change"))).on_press(
+            GuiMessage::DialogUpdate(DialogUpdate::RecordKeyBinding(action)),
+        );
+        Row::new()
+            .push(sizes.text(action.description()).width(Length::Fill))
+            .push(binding_text)
+            .push(change_button)
+            .align_y(Alignment::Center)
+            .spacing(sizes.spacing)
+    }
+}
+
+impl Dialog for SettingsDialog {
+    fn header(&self) -> String {
+        tr("dialog-settings")
+    }
+
+    fn body(&self, sizes: Sizes) -> Element<'_, GuiMessage> {
+        let mut column = Column::new()
+            .push(self.appearance_section(sizes))
+            .push(sizes.header(tr("settings-keyboard-shortcuts")))
+            .padding(sizes.spacing)
+            .spacing(sizes.spacing);
+        for action in ShortcutAction::ALL {
+            column = column.push(self.key_binding_row(action, sizes));
+        }
+        if !self.key_bindings.conflicts().is_empty() {
+            column = column.push(sizes.text(tr("settings-shortcut-conflicts")));
+        }
+        let reset_button = Button::new(sizes.text(tr("button-reset-to-defaults")))
+            .on_press(GuiMessage::DialogUpdate(DialogUpdate::ResetKeyBindings));
+        let save_button =
+            Button::new(sizes.text(tr("button-save"))).on_press(GuiMessage::DialogSubmit);
+        column
+            .push(
+                Row::new()
+                    .push(reset_button)
+                    .push(save_button)
+                    .spacing(sizes.spacing),
+            )
+            .into()
+    }
+
+    fn update(&mut self, message: DialogUpdate) {
+        match message {
+            DialogUpdate::RecordKeyBinding(action) => self.recording = Some(action),
+            DialogUpdate::KeyPress(binding) => {
+                if let Some(action) = self.recording.take() {
+                    self.key_bindings.set(action, binding);
+                }
+            }
+            DialogUpdate::ResetKeyBindings => {
+                self.key_bindings = KeyBindings::default();
+                self.recording = None;
+            }
+            DialogUpdate::Locale(locale) => self.appearance.locale = locale,
+            DialogUpdate::Theme(theme) => self.appearance.theme = theme,
+            DialogUpdate::Size(setting, value) => self.appearance.set(setting, value),
+            DialogUpdate::NotificationSeconds(seconds) => {
+                self.appearance.notification_seconds = seconds
+            }
+            DialogUpdate::Name(name) => self.new_palette.name = name,
+            DialogUpdate::PaletteColor(color, hex) => self.new_palette.set(color, hex),
+            DialogUpdate::AddPalette => {
+                match self.appearance.add_palette(self.new_palette.clone()) {
+                    Ok(()) => {
+                        self.appearance.theme = ThemeChoice::Custom(self.new_palette.name.clone());
+                        self.new_palette = CustomPalette::default();
+                        self.palette_error = None;
+                    }
+                    Err(e) => self.palette_error = Some(e.to_string()),
+                }
+            }
+            DialogUpdate::DeletePalette(name) => self.appearance.remove_palette(&name),
+            _ => (),
+        }
+    }
+
+    fn captures_keys(&self) -> bool {
+        self.recording.is_some()
+    }
+
+    fn submit(&self) -> GuiMessage {
+        GuiMessage::SettingsChanged(self.key_bindings.clone(), self.appearance.clone())
+    }
+
+    fn appearance_preview(&self) -> Option<&Appearance> {
+        Some(&self.appearance)
+    }
+}
+
+fn labelled_row<'a>(
+    label: &str,
+    content: Element<'a, GuiMessage>,
+    sizes: Sizes,
+) -> Row<'a, GuiMessage> {
+    Row::new()
+        .push(sizes.text(label.to_string()).width(Length::Fixed(150.0)))
+        .push(content)
+        .align_y(Alignment::Center)
+        .spacing(sizes.spacing)
+}
@@ -0,0 +1,62 @@
+use super::{CardStyle, Dialog};
+use crate::{app::message_handling::GuiMessage, localisation::tr, style::Sizes};
+use iced::widget::{Button, Column, Row};
+use iced::{Alignment, Element, Length};
+
+/// Asks what to do with unsaved editor changes before `on_continue` is handled.
+#[derive(Debug, Clone)]
+pub(crate) struct UnsavedChangesDialog {
+    message: String,
+    on_continue: GuiMessage,
+}
+
+impl UnsavedChangesDialog {
+    pub(crate) fn new(message: String, on_continue: GuiMessage) -> Self {
+        Self {
+            message,
+            on_continue,
+        }
+    }
+}
+
+impl Dialog for UnsavedChangesDialog {
+    fn card_style(&self) -> CardStyle {
+        CardStyle::Warning
+    }
+
+    fn header(&self) -> String {
+        tr("dialog-unsaved-changes")
+    }
+
+    fn body(&self, sizes: Sizes) -> Element<'_, GuiMessage> {
+        let message = sizes.text(&self.message);
+        let save_button = Button::new(sizes.text(tr("button-save"))).on_press(
+            GuiMessage::SaveAndContinue(Box::new(self.on_continue.clone())),
+        );
+        let discard_button = Button::new(sizes.text(tr("button-discard"))).on_press(
+            GuiMessage::DiscardAndContinue(Box::new(self.on_continue.clone())),
+        );
+        let cancel_button =
+            Button::new(sizes.text(tr("button-cancel"))).on_press(GuiMessage::DialogClosed);
+
+        let buttons = Row::new()
+            .push(save_button)
+            .push(discard_button)
+            .push(cancel_button)
+            .spacing(50);
+
+        Column::new()
+            .push(message)
+            .push(buttons)
+            .width(Length::Fill)
+            .align_x(Alignment::Center)
+            .spacing(sizes.spacing)
+            .into()
+    }
+
+    fn update(&mut self, _message: super::DialogUpdate) {}
+
+    fn submit(&self) -> GuiMessage {
+        GuiMessage::SaveAndContinue(Box::new(self.on_continue.clone()))
+    }
+}
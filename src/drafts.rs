@@ -0,0 +1,147 @@
+use std::{fs, path::PathBuf, time::Duration};
+
+use iced::Subscription;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    app::message_handling::GuiMessage, errors::LoreGuiError, localisation::tr_args, polling,
+};
+
+const AUTOSAVE_INTERVAL: Duration = Duration::from_secs(5);
+
+/// What an editor draft belongs to. Values are stored as they are displayed.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub(crate) enum DraftKey {
+    Description {
+        label: String,
+        descriptor: String,
+    },
+    HistoryContent {
+        year: String,
+        day: Option<String>,
+        timestamp: String,
+    },
+}
+
+/// Unsaved text of an editor.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub(crate) struct Draft {
+    pub(crate) key: DraftKey,
+    pub(crate) text: String,
+}
+
+impl Draft {
+    pub(crate) fn title(&self) -> String {
+        match &self.key {
+            DraftKey::Description { label, descriptor } => tr_args(
+                "draft-description",
+                &[("label", label), ("descriptor", descriptor)],
+            ),
+            DraftKey::HistoryContent {
+                year, timestamp, ..
+            } => tr_args(
+                "draft-history-item",
+                &[("timestamp", timestamp), ("year", year)],
+            ),
+        }
+    }
+}
+
+/// The recovery file lives next to the database it belongs to.
+fn drafts_path(database_path: &str) -> PathBuf {
+    PathBuf::from(format!("{}.drafts.json", database_path))
+}
+
+pub(crate) fn load_drafts(database_path: &str) -> Result<Vec<Draft>, LoreGuiError> {
+    let path = drafts_path(database_path);
+    if !path.exists() {
+        return Ok(vec![]);
+    }
+    let content = fs::read_to_string(&path).map_err(|e| {
+        LoreGuiError::FileError(tr_args(
+            "error-file-read",
+            &[("path", &path.display()), ("error", &e)],
+        ))
+    })?;
+    serde_json::from_str(&content).map_err(|e| {
+        LoreGuiError::FileError(tr_args(
+            "error-file-parse",
+            &[("path", &path.display()), ("error", &e)],
+        ))
+    })
+}
+
+/// Replaces the stored drafts. The file is written next to the old one and then renamed, so that a crash never leaves half of it behind.
+pub(crate) fn store_drafts(database_path: &str, drafts: &[Draft]) -> Result<(), LoreGuiError> {
+    let path = drafts_path(database_path);
+    if drafts.is_empty() {
+        if path.exists() {
+            fs::remove_file(&path).map_err(|e| {
+                LoreGuiError::FileError(tr_args(
+                    "error-file-remove",
+                    &[("path", &path.display()), ("error", &e)],
+                ))
+            })?;
+        }
+        return Ok(());
+    }
+    let content = serde_json::to_string_pretty(drafts).map_err(|e| {
+        LoreGuiError::FileError(tr_args("error-drafts-serialise", &[("error", &e)]))
+    })?;
+    let temp_path = path.with_extension("json.tmp");
+    fs::write(&temp_path, content)
+        .and_then(|_| fs::rename(&temp_path, &path))
+        .map_err(|e| {
+            LoreGuiError::FileError(tr_args(
+                "error-file-write",
+                &[("path", &path.display()), ("error", &e)],
+            ))
+        })
+}
+
+/// Asks for the drafts to be saved at a regular interval.
+pub(crate) fn autosave_ticks() -> Subscription<GuiMessage> {
+    polling::every("autosave", AUTOSAVE_INTERVAL, || {
+        Some(GuiMessage::AutosaveDrafts)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use tempfile::NamedTempFile;
+
+    use super::*;
+
+    fn example_drafts() -> Vec<Draft> {
+        vec![
+            Draft {
+                key: DraftKey::Description {
+                    label: "label".to_string(),
+                    descriptor: "descriptor".to_string(),
+                },
+                text: "description\n".to_string(),
+            },
+            Draft {
+                key: DraftKey::HistoryContent {
+                    year: "-22".to_string(),
+                    day: None,
+                    timestamp: "123".to_string(),
+                },
+                text: "content\n".to_string(),
+            },
+        ]
+    }
+
+    #[test]
+    fn stored_drafts_are_loaded() {
+        let database = NamedTempFile::new().unwrap();
+        let database_path = database.path().to_str().unwrap();
+        assert_eq!(load_drafts(database_path).unwrap(), vec![]);
+
+        store_drafts(database_path, &example_drafts()).unwrap();
+        assert_eq!(load_drafts(database_path).unwrap(), example_drafts());
+
+        store_drafts(database_path, &[]).unwrap();
+        assert!(!drafts_path(database_path).exists());
+    }
+}
@@ -28,6 +28,13 @@ impl EditorState {
         }
     }
 
+    /// An editor showing `text`, which has not yet been saved over `persisted_text`.
+    pub(super) fn with_unsaved_changes(persisted_text: &str, text: &str) -> Self {
+        let mut state = EditorState::new(text);
+        state.rebase_onto(persisted_text);
+        state
+    }
+
     pub(super) fn perform(&mut self, action: text_editor::Action) {
         self.current_content.perform(action);
     }
@@ -44,8 +51,14 @@ impl EditorState {
         self.current_content.text() != self.persisted_text
     }
 
-    pub(super) fn saved(&mut self) {
-        self.persisted_text = self.current_content.text();
+    /// Whether there are unsaved changes based on a text that is no longer the stored one.
+    pub(super) fn conflicts_with(&self, stored_text: &str) -> bool {
+        self.is_changed() && EditorState::new(stored_text).persisted_text != self.persisted_text
+    }
+
+    /// Keeps the unsaved changes, but bases them on `stored_text`, so that saving overwrites it.
+    pub(super) fn rebase_onto(&mut self, stored_text: &str) {
+        self.persisted_text = EditorState::new(stored_text).persisted_text;
     }
 }
 
@@ -62,4 +75,17 @@ mod tests {
         editor_state.reset();
         assert_eq!(editor_state.is_changed(), false);
     }
+
+    #[test]
+    fn unsaved_changes_conflict_only_with_other_stored_text() {
+        let mut editor_state = EditorState::new("original");
+        assert!(!editor_state.conflicts_with("changed elsewhere"));
+        editor_state.perform(text_editor::Action::Edit(text_editor::Edit::Insert('i')));
+        assert!(!editor_state.conflicts_with("original"));
+        assert!(editor_state.conflicts_with("changed elsewhere"));
+
+        editor_state.rebase_onto("changed elsewhere");
+        assert!(editor_state.is_changed());
+        assert!(!editor_state.conflicts_with("changed elsewhere"));
+    }
 }
@@ -1,36 +1,60 @@
 use iced::{
-    widget::{button, text_editor, Column},
-    Length,
+    widget::{button, text_editor, Column, Row, Scrollable},
+    Element, Length,
 };
 
-use crate::{app::message_handling::GuiMessage, style::header};
+use crate::{
+    app::message_handling::GuiMessage,
+    localisation::tr,
+    references::{self, Reading},
+    style::Sizes,
+};
 
 use super::EditorState;
 
 pub(crate) fn view<'a, M>(
-    title: &'static str,
+    title: String,
     state: &'a EditorState,
     on_action: M,
     on_discard: GuiMessage,
     on_save: GuiMessage,
+    reading: Reading,
+    sizes: Sizes,
 ) -> Column<'a, GuiMessage>
 where
     M: 'static + Clone + Fn(text_editor::Action) -> GuiMessage,
 {
-    let editor = text_editor(&state.current_content).on_action(on_action);
-    let mut discard_button = button("Discard Changes");
-    let mut save_button = button("Save Changes");
+    let content: Element<'a, GuiMessage> = if reading.read_mode.is_active() {
+        Scrollable::new(references::widget::view(&state.get_text(), reading, sizes)).into()
+    } else {
+        text_editor(&state.current_content)
+            .size(sizes.text)
+            .on_action(on_action)
+            .into()
+    };
+    let mode_button = button(sizes.text(tr(if reading.read_mode.is_active() {
+        "button-edit"
+    } else {
+        "button-read"
+    })))
+    .on_press(GuiMessage::ToggleReadMode);
+    let mut discard_button = button(sizes.text(tr("button-discard-changes")));
+    let mut save_button = button(sizes.text(tr("button-save-changes")));
     if state.is_changed() {
         discard_button = discard_button.on_press(on_discard);
         save_button = save_button.on_press(on_save);
     }
 
     Column::new()
-        .push(header(title))
-        .push(editor)
+        .push(
+            Row::new()
+                .push(sizes.header(title).width(Length::Fill))
+                .push(mode_button),
+        )
+        .push(content)
         .push(discard_button)
         .push(save_button)
-        .padding(5)
-        .spacing(5)
+        .padding(sizes.spacing)
+        .spacing(sizes.spacing)
         .width(Length::Fill)
 }
@@ -1,28 +1,240 @@
+use std::{collections::HashMap, fmt::Display};
+
 use iced::widget::text_editor;
 use lorecore::{
     extractions::{extract_descriptors, extract_labels},
-    sql::{
-        lore_database::LoreDatabase,
-        search_params::{EntityColumnSearchParams, SqlSearchText},
-    },
     types::*,
 };
 
 use crate::{
     db_col_view::{entry::DbColViewEntry, ColViewMes},
     dialog::{relabel_entity::RelabelEntityData, rename_descriptor::RenameDescriptorData},
+    drafts::{Draft, DraftKey},
     editor::EditorState,
     errors::LoreGuiError,
+    localisation::tr,
+    query_cache::{CachedReader, TextFilter},
 };
 
-use super::db_col_view::state::DbColViewState;
+use super::db_col_view::state::{combined_search_matches, DbColViewState};
 
 pub(crate) mod widget;
 
+/// The descriptor whose description is the display name of an entity.
+pub(crate) const NAME_DESCRIPTOR: &str = "_name";
+/// The descriptor whose description names the category of an entity.
+pub(crate) const CATEGORY_DESCRIPTOR: &str = "_category";
+
+/// Descriptors starting with an underscore are managed by the GUI and hidden by default.
+pub(crate) fn is_reserved(descriptor: &Descriptor) -> bool {
+    descriptor.to_str().starts_with('_')
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub(crate) enum CategoryFilter {
+    #[default]
+    All,
+    Uncategorised,
+    Category(String),
+}
+
+impl CategoryFilter {
+    fn matches(&self, category: &Option<String>) -> bool {
+        match self {
+            CategoryFilter::All => true,
+            CategoryFilter::Uncategorised => category.is_none(),
+            CategoryFilter::Category(c) => category.as_ref() == Some(c),
+        }
+    }
+}
+
+impl Display for CategoryFilter {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            CategoryFilter::All => tr("category-all").fmt(f),
+            CategoryFilter::Uncategorised => tr("dashboard-uncategorised").fmt(f),
+            CategoryFilter::Category(category) => category.fmt(f),
+        }
+    }
+}
+
+/// A label together with its reserved `_name` and `_category` descriptions.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct LabelDetails {
+    pub(crate) label: Label,
+    pub(crate) name: Option<String>,
+    pub(crate) category: Option<String>,
+}
+
+/// A row of the label column: an entity with its name, or the heading of a category when grouping by category.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub(crate) enum LabelRow {
+    Heading(Option<String>),
+    Entity(Label, Option<String>),
+}
+
+impl Display for LabelRow {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            LabelRow::Heading(Some(category)) => write!(f, "— {} —", category),
+            LabelRow::Heading(None) => write!(f, "— {} —", tr("dashboard-uncategorised")),
+            LabelRow::Entity(label, Some(name)) => write!(f, "{} ({})", label, name),
+            LabelRow::Entity(label, None) => label.fmt(f),
+        }
+    }
+}
+
+fn label_rows(labels: &[LabelDetails], group_by_category: bool) -> Vec<LabelRow> {
+    let mut rows = vec![];
+    let mut current_group = None;
+    for details in labels {
+        if group_by_category && current_group != Some(&details.category) {
+            current_group = Some(&details.category);
+            rows.push(LabelRow::Heading(details.category.clone()));
+        }
+        rows.push(LabelRow::Entity(
+            details.label.clone(),
+            details.name.clone(),
+        ));
+    }
+    rows
+}
+
+/// Everything needed to read the contents of the entity view, detached from the view state so that it can run in the background.
+#[derive(Debug, Clone, PartialEq)]
+pub(super) struct EntityViewQuery {
+    label_search_text: Option<String>,
+    descriptor_search_text: Option<String>,
+    label: Option<Label>,
+    descriptor: Option<Descriptor>,
+    category_filter: CategoryFilter,
+    group_by_category: bool,
+    show_reserved_descriptors: bool,
+}
+
+#[derive(Debug, Clone)]
+pub(super) struct EntityViewData {
+    labels: Vec<LabelDetails>,
+    categories: Vec<String>,
+    descriptors: Vec<Descriptor>,
+    description: Description,
+    /// The entity column the description belongs to.
+    source: Option<(Label, Descriptor)>,
+}
+
+impl EntityViewQuery {
+    pub(super) fn run(&self, reader: &CachedReader) -> Result<EntityViewData, LoreGuiError> {
+        Ok(EntityViewData {
+            labels: self.read_labels(reader)?,
+            categories: self.read_categories(reader)?,
+            descriptors: self.read_descriptors(reader)?,
+            description: self.read_description(reader)?,
+            source: self.label.clone().zip(self.descriptor.clone()),
+        })
+    }
+
+    /// Grouping sorts the labels by category, keeping their order within a category.
+    fn read_labels(&self, reader: &CachedReader) -> Result<Vec<LabelDetails>, LoreGuiError> {
+        let label_search_text = self.label_search_text.clone().map(TextFilter::Partial);
+        let entity_columns = reader.read_entity_columns(label_search_text, None)?;
+        let mut reserved: HashMap<(&Label, &str), String> = HashMap::new();
+        for col in entity_columns.iter() {
+            let descriptor = col.descriptor.to_str();
+            let description = col.description.to_str().trim();
+            if (descriptor == NAME_DESCRIPTOR || descriptor == CATEGORY_DESCRIPTOR)
+                && !description.is_empty()
+            {
+                reserved.insert((&col.label, descriptor), description.to_string());
+            }
+        }
+        let mut labels: Vec<LabelDetails> = extract_labels(&entity_columns)
+            .into_iter()
+            .map(|label| LabelDetails {
+                name: reserved.get(&(&label, NAME_DESCRIPTOR)).cloned(),
+                category: reserved.get(&(&label, CATEGORY_DESCRIPTOR)).cloned(),
+                label,
+            })
+            .filter(|details| self.category_filter.matches(&details.category))
+            .collect();
+        if self.group_by_category {
+            labels.sort_by(|a, b| {
+                a.category
+                    .is_none()
+                    .cmp(&b.category.is_none())
+                    .then(a.category.cmp(&b.category))
+            });
+        }
+        Ok(labels)
+    }
+
+    /// All categories in use, regardless of the label search.
+    fn read_categories(&self, reader: &CachedReader) -> Result<Vec<String>, LoreGuiError> {
+        let descriptor = TextFilter::Exact(CATEGORY_DESCRIPTOR.to_string());
+        let mut categories: Vec<String> = vec![];
+        for col in reader.read_entity_columns(None, Some(descriptor))?.iter() {
+            let category = col.description.to_str().trim();
+            if !category.is_empty() && !categories.iter().any(|c| c == category) {
+                categories.push(category.to_string());
+            }
+        }
+        categories.sort();
+        Ok(categories)
+    }
+
+    fn read_descriptors(&self, reader: &CachedReader) -> Result<Vec<Descriptor>, LoreGuiError> {
+        let label = match self.label.as_ref() {
+            Some(label) => Some(TextFilter::Exact(label.to_str().to_string())),
+            None => return Ok(vec![]),
+        };
+
+        let descriptor_search_text = self.descriptor_search_text.clone().map(TextFilter::Partial);
+        let entity_columns = reader.read_entity_columns(label, descriptor_search_text)?;
+        let descriptors = extract_descriptors(&entity_columns)
+            .into_iter()
+            .filter(|descriptor| self.show_reserved_descriptors || !is_reserved(descriptor))
+            .collect();
+        Ok(descriptors)
+    }
+
+    fn read_description(&self, reader: &CachedReader) -> Result<Description, LoreGuiError> {
+        let label = match self.label.as_ref() {
+            Some(label) => Some(TextFilter::Exact(label.to_str().to_string())),
+            None => return Ok(Description::NONE),
+        };
+        let descriptor = match self.descriptor.as_ref() {
+            Some(descriptor) => Some(TextFilter::Exact(descriptor.to_str().to_string())),
+            None => return Ok(Description::NONE),
+        };
+
+        let entity_columns = reader.read_entity_columns(label, descriptor)?;
+
+        if entity_columns.len() > 1 {
+            return Err(LoreGuiError::MultipleResults);
+        }
+
+        let description = entity_columns
+            .first()
+            .map(|col| col.description.clone())
+            .unwrap_or(Description::NONE);
+
+        Ok(description)
+    }
+}
+
 pub(super) struct EntityViewState {
     pub(super) label_view_state: DbColViewState<Label>,
     pub(super) descriptor_view_state: DbColViewState<Descriptor>,
     pub(super) current_description: EditorState,
+    /// The entity column whose description the editor was loaded with.
+    description_source: Option<(Label, Descriptor)>,
+    label_details: Vec<LabelDetails>,
+    /// The label column as shown, with a heading before every category when grouping.
+    label_rows: Vec<LabelRow>,
+    categories: Vec<String>,
+    category_filter: CategoryFilter,
+    group_by_category: bool,
+    show_reserved_descriptors: bool,
+    last_query: Option<EntityViewQuery>,
 }
 
 #[derive(Debug, Clone)]
@@ -30,8 +242,16 @@ pub(super) enum EntityViewMessage {
     NewEntity,
     RelabelEntity(RelabelEntityData),
     DeleteEntity(Label),
+    CopyEntity(Label),
+    DuplicateEntity(Label),
+    MergeEntities(Label),
+    ChangeCategory(Label),
+    CategoryFilterSelected(CategoryFilter),
+    ToggleGroupByCategory,
+    ToggleReservedDescriptors,
     NewDescriptor(Label),
     RenameDescriptor(RenameDescriptorData),
+    RenameDescriptorEverywhere(Descriptor),
     DeleteDescriptor(Label, Descriptor),
     LabelViewUpdate(ColViewMes<Label>),
     DescriptorViewUpdate(ColViewMes<Descriptor>),
@@ -46,81 +266,83 @@ impl EntityViewState {
             label_view_state: DbColViewState::default(),
             descriptor_view_state: DbColViewState::default(),
             current_description: EditorState::default(),
+            description_source: None,
+            label_details: vec![],
+            label_rows: vec![],
+            categories: vec![],
+            category_filter: CategoryFilter::All,
+            group_by_category: false,
+            show_reserved_descriptors: false,
+            last_query: None,
         }
     }
 
-    pub(super) fn get_current_labels(
-        &mut self,
-        db: &Option<LoreDatabase>,
-    ) -> Result<Vec<Label>, LoreGuiError> {
-        let db = match db {
-            Some(db) => db,
-            None => return Ok(vec![]),
-        };
-
-        let label_search_text = self
-            .label_view_state
-            .get_search_text()
-            .map(SqlSearchText::partial);
-        let search_params = EntityColumnSearchParams::new(label_search_text, None);
-        let entity_columns = db.read_entity_columns(search_params)?;
-        let labels = extract_labels(&entity_columns);
-        Ok(labels)
+    pub(super) fn query(&self) -> EntityViewQuery {
+        EntityViewQuery {
+            label_search_text: self.label_view_state.get_search_text().map(String::from),
+            descriptor_search_text: self
+                .descriptor_view_state
+                .get_search_text()
+                .map(String::from),
+            label: self.get_selected_label(),
+            descriptor: self.get_selected_descriptor(),
+            category_filter: self.category_filter.clone(),
+            group_by_category: self.group_by_category,
+            show_reserved_descriptors: self.show_reserved_descriptors,
+        }
     }
 
-    pub(super) fn get_current_descriptors(
-        &mut self,
-        db: &Option<LoreDatabase>,
-    ) -> Result<Vec<Descriptor>, LoreGuiError> {
-        let db = match db {
-            Some(db) => db,
-            None => return Ok(vec![]),
-        };
-        let label = match self.get_selected_label() {
-            Some(label) => Some(SqlSearchText::exact(label.to_str())),
-            None => return Ok(vec![]),
-        };
-
-        let descriptor_search_text = self
-            .descriptor_view_state
-            .get_search_text()
-            .map(SqlSearchText::partial);
-        let search_params = EntityColumnSearchParams::new(label, descriptor_search_text);
-        let entity_columns = db.read_entity_columns(search_params)?;
-        let descriptors = extract_descriptors(&entity_columns);
-        Ok(descriptors)
+    /// The query to read the view with, unless the view has already been read with it.
+    pub(super) fn query_if_changed(&mut self) -> Option<EntityViewQuery> {
+        let query = self.query();
+        if self.last_query.as_ref() == Some(&query) {
+            return None;
+        }
+        self.last_query = Some(query.clone());
+        Some(query)
     }
 
-    pub(super) fn get_current_description(
-        &self,
-        db: &Option<LoreDatabase>,
-    ) -> Result<Description, LoreGuiError> {
-        let db = match db {
-            Some(db) => db,
-            None => return Ok(Description::NONE),
-        };
-        let label = match self.get_selected_label() {
-            Some(label) => Some(SqlSearchText::exact(label.to_str())),
-            None => return Ok(Description::NONE),
-        };
-        let descriptor = match self.get_selected_descriptor() {
-            Some(descriptor) => Some(SqlSearchText::exact(descriptor.to_str())),
-            None => return Ok(Description::NONE),
-        };
+    /// Makes the next refresh read the view again, even if the query did not change.
+    pub(super) fn invalidate(&mut self) {
+        self.last_query = None;
+    }
 
-        let search_params = EntityColumnSearchParams::new(label, descriptor);
-        let entity_columns = db.read_entity_columns(search_params)?;
+    pub(super) fn set_loading(&mut self, loading: bool) {
+        self.label_view_state.set_loading(loading);
+        self.descriptor_view_state.set_loading(loading);
+    }
 
-        if entity_columns.len() > 1 {
-            return Err(LoreGuiError::MultipleResults);
+    /// A read that is still running when the tab is put aside will never be applied, so it has to be repeated later.
+    pub(super) fn abandon_loading(&mut self) {
+        if self.label_view_state.is_loading() {
+            self.set_loading(false);
+            self.invalidate();
         }
+    }
 
-        let description = entity_columns
-            .first()
-            .map(|col| col.description.clone())
-            .unwrap_or(Description::NONE);
-
-        Ok(description)
+    /// Unsaved changes in the description editor survive a refresh of the entity column they were made for.
+    pub(super) fn apply(&mut self, data: EntityViewData) {
+        let labels = data
+            .labels
+            .iter()
+            .map(|details| DbColViewEntry::from(details.label.clone()))
+            .collect();
+        self.label_view_state.set_entries(labels);
+        self.label_rows = label_rows(&data.labels, self.group_by_category);
+        self.label_details = data.labels;
+        self.categories = data.categories;
+        let descriptors = data
+            .descriptors
+            .into_iter()
+            .map(DbColViewEntry::from)
+            .collect();
+        self.descriptor_view_state.set_entries(descriptors);
+        let keeps_unsaved_changes =
+            self.current_description.is_changed() && self.description_source == data.source;
+        if !keeps_unsaved_changes {
+            self.current_description = EditorState::new(data.description.to_str());
+        }
+        self.description_source = data.source;
     }
 
     pub(super) fn get_selected_label(&self) -> Option<Label> {
@@ -131,6 +353,43 @@ impl EntityViewState {
         self.label_view_state.set_selected(DbColViewEntry(label));
     }
 
+    /// The labels in the order of the label column.
+    pub(super) fn get_label_details(&self) -> &[LabelDetails] {
+        &self.label_details
+    }
+
+    pub(super) fn get_label_rows(&self) -> &[LabelRow] {
+        &self.label_rows
+    }
+
+    pub(super) fn get_categories(&self) -> &[String] {
+        &self.categories
+    }
+
+    pub(super) fn get_category_filter(&self) -> &CategoryFilter {
+        &self.category_filter
+    }
+
+    pub(super) fn set_category_filter(&mut self, filter: CategoryFilter) {
+        self.category_filter = filter;
+    }
+
+    pub(super) fn is_grouped_by_category(&self) -> bool {
+        self.group_by_category
+    }
+
+    pub(super) fn toggle_group_by_category(&mut self) {
+        self.group_by_category = !self.group_by_category;
+    }
+
+    pub(super) fn shows_reserved_descriptors(&self) -> bool {
+        self.show_reserved_descriptors
+    }
+
+    pub(super) fn toggle_reserved_descriptors(&mut self) {
+        self.show_reserved_descriptors = !self.show_reserved_descriptors;
+    }
+
     pub(super) fn get_selected_descriptor(&self) -> Option<Descriptor> {
         self.descriptor_view_state.get_selected().0.clone()
     }
@@ -140,12 +399,61 @@ impl EntityViewState {
             .set_selected(DbColViewEntry(descriptor));
     }
 
+    /// The entries found in the columns with a search text.
+    pub(super) fn search_matches(&self) -> Option<usize> {
+        combined_search_matches([
+            self.label_view_state.search_matches(),
+            self.descriptor_view_state.search_matches(),
+        ])
+    }
+
     pub(super) fn get_description_text(&self) -> String {
         self.current_description.get_text()
     }
 
     pub(super) fn set_description_text(&mut self, text: &str) {
-        self.current_description = EditorState::new(text);
+        self.set_description_editor(EditorState::new(text));
+    }
+
+    /// Puts `editor` into the view as the editor of the selected entity column.
+    pub(super) fn set_description_editor(&mut self, editor: EditorState) {
+        self.current_description = editor;
+        self.description_source = self
+            .get_selected_label()
+            .zip(self.get_selected_descriptor());
+    }
+
+    /// The entity column the text in the description editor belongs to, which may no longer be the selected one.
+    pub(super) fn description_source(&self) -> Option<&(Label, Descriptor)> {
+        self.description_source.as_ref()
+    }
+
+    /// Text typed while the description was being saved stays unsaved.
+    pub(super) fn description_saved(
+        &mut self,
+        label: &Label,
+        descriptor: &Descriptor,
+        description: &Description,
+    ) {
+        if self.description_source == Some((label.clone(), descriptor.clone())) {
+            self.current_description.rebase_onto(description.to_str());
+        }
+    }
+
+    /// The unsaved changes of the description editor, if there are any.
+    pub(super) fn draft(&self) -> Option<Draft> {
+        if !self.current_description.is_changed() {
+            return None;
+        }
+        let (label, descriptor) = self.description_source.as_ref()?;
+        let key = DraftKey::Description {
+            label: label.to_string(),
+            descriptor: descriptor.to_string(),
+        };
+        Some(Draft {
+            key,
+            text: self.get_description_text(),
+        })
     }
 }
 
@@ -1,93 +1,234 @@
-use iced::widget::button;
 use iced::{
-    widget::{Column, Row},
-    Alignment, Element, Length,
+    widget::{Checkbox, Column, PickList, Row},
+    Alignment, Element, Font, Length,
 };
+use iced_aw::{style, SelectionList};
+
+use lorecore::types::*;
 
 use crate::app::message_handling::GuiMessage;
+use crate::commands::{button_row, Command};
 use crate::dialog::relabel_entity::RelabelEntityData;
 use crate::dialog::rename_descriptor::RenameDescriptorData;
-use crate::{db_col_view, editor};
+use crate::localisation::{tr, tr_args};
+use crate::references::Reading;
+use crate::style::Sizes;
+use crate::{
+    db_col_view::{self, ColViewMes},
+    editor,
+};
 
-use super::{EntityViewMessage, EntityViewState};
+use super::{CategoryFilter, EntityViewMessage, EntityViewState, LabelRow};
 
-pub(crate) fn new(state: &EntityViewState) -> Element<'_, GuiMessage> {
+pub(crate) fn new<'a>(
+    state: &'a EntityViewState,
+    can_copy: bool,
+    reading: Reading,
+    focused_column: usize,
+    sizes: Sizes,
+) -> Element<'a, GuiMessage> {
     Column::new()
-        .push(label_buttons(state))
-        .push(descriptor_buttons(state))
-        .push(col_views(state))
+        .push(button_row(&label_commands(state, can_copy), sizes))
+        .push(button_row(&descriptor_commands(state), sizes))
+        .push(col_views(state, reading, focused_column, sizes))
         .into()
 }
 
-fn label_buttons(state: &EntityViewState) -> Row<'_, GuiMessage> {
-    let new_entity =
-        button("New Entity").on_press(GuiMessage::EntityViewUpd(EntityViewMessage::NewEntity));
-    let mut relabel_entity = button("Relabel Entity");
-    let mut delete_entity = button("Delete Entity");
-    if let Some(label) = state.get_selected_label() {
-        let relabel_entity_data = RelabelEntityData::new(label.clone());
-        relabel_entity = relabel_entity.on_press(GuiMessage::EntityViewUpd(
-            EntityViewMessage::RelabelEntity(relabel_entity_data),
-        ));
-        delete_entity = delete_entity.on_press(GuiMessage::EntityViewUpd(
-            EntityViewMessage::DeleteEntity(label.clone()),
-        ));
-    }
-    Row::new()
-        .push(new_entity)
-        .push(relabel_entity)
-        .push(delete_entity)
-        .spacing(5)
-        .padding(5)
+pub(crate) fn label_commands(state: &EntityViewState, can_copy: bool) -> Vec<Command> {
+    let selected_label = state.get_selected_label();
+    let message = |m: fn(Label) -> EntityViewMessage| {
+        selected_label
+            .clone()
+            .map(|label| GuiMessage::EntityViewUpd(m(label)))
+    };
+    vec![
+        Command::enabled(
+            &tr("command-new-entity"),
+            GuiMessage::EntityViewUpd(EntityViewMessage::NewEntity),
+        ),
+        Command::new(
+            &tr("command-relabel-entity"),
+            message(|label| EntityViewMessage::RelabelEntity(RelabelEntityData::new(label))),
+        ),
+        Command::new(
+            &tr("command-delete-entity"),
+            message(EntityViewMessage::DeleteEntity),
+        ),
+        Command::new(
+            &tr("command-copy-entity"),
+            message(EntityViewMessage::CopyEntity).filter(|_| can_copy),
+        ),
+        Command::new(
+            &tr("command-duplicate-entity"),
+            message(EntityViewMessage::DuplicateEntity),
+        ),
+        Command::new(
+            &tr("command-merge-entities"),
+            message(EntityViewMessage::MergeEntities),
+        ),
+        Command::new(
+            &tr("command-change-category"),
+            message(EntityViewMessage::ChangeCategory),
+        ),
+    ]
 }
 
-fn descriptor_buttons(state: &EntityViewState) -> Row<'_, GuiMessage> {
-    let mut new_descriptor = button("New Descriptor");
-    let mut rename_descriptor = button("Rename Descriptor");
-    let mut delete_descriptor = button("Delete Descriptor");
-    if let Some(label) = state.get_selected_label() {
-        new_descriptor = new_descriptor.on_press(GuiMessage::EntityViewUpd(
-            EntityViewMessage::NewDescriptor(label.clone()),
-        ));
-        if let Some(descriptor) = state.get_selected_descriptor() {
-            let rename_descriptor_data =
-                RenameDescriptorData::new(label.clone(), descriptor.clone());
-            rename_descriptor = rename_descriptor.on_press(GuiMessage::EntityViewUpd(
-                EntityViewMessage::RenameDescriptor(rename_descriptor_data),
-            ));
-            delete_descriptor = delete_descriptor.on_press(GuiMessage::EntityViewUpd(
-                EntityViewMessage::DeleteDescriptor(label.clone(), descriptor.clone()),
-            ));
-        }
-    }
-    Row::new()
-        .push(new_descriptor)
-        .push(rename_descriptor)
-        .push(delete_descriptor)
-        .spacing(5)
-        .padding(5)
+pub(crate) fn descriptor_commands(state: &EntityViewState) -> Vec<Command> {
+    let label = state.get_selected_label();
+    let label_and_descriptor = label.clone().zip(state.get_selected_descriptor());
+    let message = |m: fn(Label, Descriptor) -> EntityViewMessage| {
+        label_and_descriptor
+            .clone()
+            .map(|(label, descriptor)| GuiMessage::EntityViewUpd(m(label, descriptor)))
+    };
+    vec![
+        Command::new(
+            &tr("command-new-descriptor"),
+            label.map(|label| GuiMessage::EntityViewUpd(EntityViewMessage::NewDescriptor(label))),
+        ),
+        Command::new(
+            &tr("command-rename-descriptor"),
+            message(|label, descriptor| {
+                EntityViewMessage::RenameDescriptor(RenameDescriptorData::new(label, descriptor))
+            }),
+        ),
+        Command::new(
+            &tr("command-rename-descriptor-everywhere"),
+            message(|_, descriptor| EntityViewMessage::RenameDescriptorEverywhere(descriptor)),
+        ),
+        Command::new(
+            &tr("command-delete-descriptor"),
+            message(EntityViewMessage::DeleteDescriptor),
+        ),
+        Command::enabled(
+            &tr(if state.shows_reserved_descriptors() {
+                "command-hide-reserved-descriptors"
+            } else {
+                "command-show-reserved-descriptors"
+            }),
+            GuiMessage::EntityViewUpd(EntityViewMessage::ToggleReservedDescriptors),
+        ),
+    ]
 }
 
-fn col_views(state: &EntityViewState) -> Row<'_, GuiMessage> {
+/// Filtering and grouping the label column, only offered by the command palette.
+pub(crate) fn category_commands(state: &EntityViewState) -> Vec<Command> {
+    let mut commands: Vec<Command> = category_filters(state)
+        .into_iter()
+        .map(|filter| {
+            Command::enabled(
+                &tr_args("command-filter-category", &[("category", &filter)]),
+                GuiMessage::EntityViewUpd(EntityViewMessage::CategoryFilterSelected(filter)),
+            )
+        })
+        .collect();
+    commands.push(Command::enabled(
+        &tr(if state.is_grouped_by_category() {
+            "command-ungroup-by-category"
+        } else {
+            "command-group-by-category"
+        }),
+        GuiMessage::EntityViewUpd(EntityViewMessage::ToggleGroupByCategory),
+    ));
+    commands
+}
+
+fn col_views<'a>(
+    state: &'a EntityViewState,
+    reading: Reading,
+    focused_column: usize,
+    sizes: Sizes,
+) -> Row<'a, GuiMessage> {
     Row::new()
-        .push(db_col_view::widget::new(
-            "Label",
+        .push(db_col_view::widget::with_list(
+            tr("column-label"),
             |m| GuiMessage::EntityViewUpd(EntityViewMessage::LabelViewUpdate(m)),
             &state.label_view_state,
+            label_list(state, sizes),
+            focused_column == 0,
+            sizes,
         ))
         .push(db_col_view::widget::new(
-            "Descriptor",
+            tr("column-descriptor"),
             |m| GuiMessage::EntityViewUpd(EntityViewMessage::DescriptorViewUpdate(m)),
             &state.descriptor_view_state,
+            focused_column == 1,
+            sizes,
         ))
         .push(editor::widget::view(
-            "Description",
+            tr("column-description"),
             &state.current_description,
             |a| GuiMessage::EntityViewUpd(EntityViewMessage::DescriptionUpdate(a)),
             GuiMessage::EntityViewUpd(EntityViewMessage::DescriptionDiscard),
             GuiMessage::EntityViewUpd(EntityViewMessage::DescriptionSave),
+            reading,
+            sizes,
         ))
         .align_y(Alignment::Start)
         .width(Length::Fill)
         .height(Length::Fill)
 }
+
+/// The labels with their names, optionally filtered and grouped by category.
+///
+/// Selecting the heading of a category shows only that category.
+fn label_list(state: &EntityViewState, sizes: Sizes) -> Column<'_, GuiMessage> {
+    let category_filter = PickList::new(
+        category_filters(state),
+        Some(state.get_category_filter().clone()),
+        |filter| GuiMessage::EntityViewUpd(EntityViewMessage::CategoryFilterSelected(filter)),
+    )
+    .text_size(sizes.text)
+    .width(Length::Fill);
+    let group_toggle = Checkbox::new(
+        tr("field-group-by-category"),
+        state.is_grouped_by_category(),
+    )
+    .text_size(sizes.text)
+    .on_toggle(|_| GuiMessage::EntityViewUpd(EntityViewMessage::ToggleGroupByCategory));
+
+    let selected = state.get_selected_label();
+    let selected_row = state.get_label_rows().iter().position(
+        |row| matches!(row, LabelRow::Entity(label, _) if Some(label) == selected.as_ref()),
+    );
+    let row_selected = |index, row| {
+        let message = match row {
+            LabelRow::Heading(Some(category)) => {
+                EntityViewMessage::CategoryFilterSelected(CategoryFilter::Category(category))
+            }
+            LabelRow::Heading(None) => {
+                EntityViewMessage::CategoryFilterSelected(CategoryFilter::Uncategorised)
+            }
+            LabelRow::Entity(label, _) => {
+                EntityViewMessage::LabelViewUpdate(ColViewMes::Selected(index, label.into()))
+            }
+        };
+        GuiMessage::EntityViewUpd(message)
+    };
+    let rows = SelectionList::new_with(
+        state.get_label_rows(),
+        row_selected,
+        sizes.text,
+        sizes.list_padding(),
+        style::selection_list::primary,
+        selected_row,
+        Font::default(),
+    );
+    Column::new()
+        .push(category_filter)
+        .push(group_toggle)
+        .push(rows)
+        .spacing(sizes.spacing)
+}
+
+fn category_filters(state: &EntityViewState) -> Vec<CategoryFilter> {
+    let mut filters = vec![CategoryFilter::All, CategoryFilter::Uncategorised];
+    filters.extend(
+        state
+            .get_categories()
+            .iter()
+            .cloned()
+            .map(CategoryFilter::Category),
+    );
+    filters
+}
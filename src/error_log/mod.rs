@@ -0,0 +1,111 @@
+use crate::{
+    errors::LoreGuiError, time_format::format_unix_time, user_preferences::now_in_seconds,
+};
+
+pub(crate) mod widget;
+
+#[derive(Debug, Clone)]
+pub(crate) struct LoggedError {
+    pub(crate) time: u64,
+    pub(crate) error: LoreGuiError,
+}
+
+impl LoggedError {
+    /// The details of the error, prefixed with when it occurred.
+    pub(crate) fn report(&self) -> String {
+        format!("[{}] {}", format_unix_time(self.time), self.error.details())
+    }
+}
+
+#[derive(Debug, Clone)]
+pub(crate) enum ErrorLogMessage {
+    Show,
+    Hide,
+    Clear,
+    ToggleDetails(usize),
+}
+
+/// Every error of the session, so that errors can still be looked at after their dialog was closed.
+#[derive(Debug, Default)]
+pub(crate) struct ErrorLog {
+    entries: Vec<LoggedError>,
+    expanded: Vec<usize>,
+    shown: bool,
+}
+
+impl ErrorLog {
+    pub(crate) fn push(&mut self, error: LoreGuiError) {
+        self.entries.push(LoggedError {
+            time: now_in_seconds(),
+            error,
+        });
+    }
+
+    pub(crate) fn entries(&self) -> &[LoggedError] {
+        &self.entries
+    }
+
+    pub(crate) fn is_shown(&self) -> bool {
+        self.shown
+    }
+
+    pub(crate) fn is_expanded(&self, index: usize) -> bool {
+        self.expanded.contains(&index)
+    }
+
+    pub(crate) fn update(&mut self, message: ErrorLogMessage) {
+        match message {
+            ErrorLogMessage::Show => self.shown = true,
+            ErrorLogMessage::Hide => self.shown = false,
+            ErrorLogMessage::Clear => {
+                self.entries.clear();
+                self.expanded.clear();
+            }
+            ErrorLogMessage::ToggleDetails(index) => {
+                if let Some(position) = self.expanded.iter().position(|i| *i == index) {
+                    self.expanded.remove(position);
+                } else {
+                    self.expanded.push(index);
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn details_are_toggled_per_entry() {
+        let mut log = ErrorLog::default();
+        log.push(LoreGuiError::NoDatabase);
+        log.push(LoreGuiError::MultipleResults);
+
+        log.update(ErrorLogMessage::ToggleDetails(1));
+        assert!(!log.is_expanded(0));
+        assert!(log.is_expanded(1));
+
+        log.update(ErrorLogMessage::ToggleDetails(1));
+        assert!(!log.is_expanded(1));
+    }
+
+    #[test]
+    fn clearing_removes_all_entries() {
+        let mut log = ErrorLog::default();
+        log.push(LoreGuiError::NoDatabase);
+        log.update(ErrorLogMessage::ToggleDetails(0));
+        log.update(ErrorLogMessage::Clear);
+        assert!(log.entries().is_empty());
+        assert!(!log.is_expanded(0));
+    }
+
+    #[test]
+    fn reports_start_with_the_time() {
+        let entry = LoggedError {
+            time: 0,
+            error: LoreGuiError::NoDatabase,
+        };
+        assert_eq!(entry.report(), "[1970-01-01 00:00] No database");
+    }
+}
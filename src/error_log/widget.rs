@@ -0,0 +1,75 @@
+use iced::{
+    widget::{button, text, Button, Column, Row, Scrollable},
+    Alignment, Element, Length,
+};
+
+use crate::{
+    app::message_handling::GuiMessage,
+    localisation::{tr, tr_args},
+    style::Sizes,
+    time_format::format_unix_time,
+};
+
+use super::{ErrorLog, ErrorLogMessage, LoggedError};
+
+pub(crate) fn panel(log: &ErrorLog, sizes: Sizes) -> Element<'_, GuiMessage> {
+    let entries = log.entries();
+    let header = Row::new()
+        .push(sizes.text(tr_args("error-log-title", &[("count", &entries.len())])))
+        .push(button(sizes.text(tr("button-clear"))).on_press_maybe(
+            (!entries.is_empty()).then_some(GuiMessage::ErrorLogUpd(ErrorLogMessage::Clear)),
+        ))
+        .push(
+            button(sizes.text(tr("button-close")))
+                .on_press(GuiMessage::ErrorLogUpd(ErrorLogMessage::Hide)),
+        )
+        .align_y(Alignment::Center)
+        .spacing(sizes.spacing);
+
+    let mut list = Column::new().spacing(sizes.spacing);
+    for (index, entry) in entries.iter().enumerate().rev() {
+        list = list.push(entry_view(index, entry, log.is_expanded(index), sizes));
+    }
+    Column::new()
+        .push(header)
+        .push(Scrollable::new(list).height(Length::Fill))
+        .padding(sizes.spacing)
+        .spacing(sizes.spacing)
+        .into()
+}
+
+fn entry_view(
+    index: usize,
+    entry: &LoggedError,
+    expanded: bool,
+    sizes: Sizes,
+) -> Column<'_, GuiMessage> {
+    let details_label = if expanded {
+        tr("button-hide-details")
+    } else {
+        tr("button-show-details")
+    };
+    let summary = Row::new()
+        .push(
+            sizes
+                .text(format_unix_time(entry.time))
+                .style(text::secondary),
+        )
+        .push(sizes.text(entry.error.to_string()).width(Length::Fill))
+        .push(
+            Button::new(sizes.text(details_label)).on_press(GuiMessage::ErrorLogUpd(
+                ErrorLogMessage::ToggleDetails(index),
+            )),
+        )
+        .push(
+            Button::new(sizes.text(tr("button-copy-to-clipboard")))
+                .on_press(GuiMessage::CopyToClipboard(entry.report())),
+        )
+        .align_y(Alignment::Center)
+        .spacing(sizes.spacing);
+    let mut column = Column::new().push(summary);
+    if expanded {
+        column = column.push(sizes.text(entry.error.details()));
+    }
+    column.spacing(sizes.spacing)
+}
@@ -2,6 +2,8 @@ use std::fmt::Display;
 
 use lorecore::errors::LoreCoreError;
 
+use crate::localisation::{tr, tr_args};
+
 #[derive(Debug, Clone)]
 pub enum LoreGuiError {
     FileError(String),
@@ -9,16 +11,128 @@ pub enum LoreGuiError {
     NoDatabase,
     MultipleResults,
     LoreCoreError(LoreCoreError),
+    /// An error that occurred while performing an operation, such as deleting an entity.
+    Context(Box<ErrorContext>, Box<LoreGuiError>),
+}
+
+/// What was being done when an error occurred, and what it was done to.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ErrorContext {
+    operation: String,
+    subjects: Vec<(String, String)>,
+}
+
+impl ErrorContext {
+    pub(crate) fn new(operation: String) -> Self {
+        ErrorContext {
+            operation,
+            subjects: vec![],
+        }
+    }
+
+    /// Adds an involved label, descriptor, timestamp or similar, named by `kind`.
+    pub(crate) fn with(mut self, kind: String, value: impl Display) -> Self {
+        self.subjects.push((kind, value.to_string()));
+        self
+    }
+
+    pub(crate) fn operation(&self) -> &str {
+        &self.operation
+    }
+
+    pub(crate) fn subjects(&self) -> &[(String, String)] {
+        &self.subjects
+    }
+}
+
+impl LoreGuiError {
+    pub(crate) fn in_context(self, context: ErrorContext) -> Self {
+        LoreGuiError::Context(Box::new(context), Box::new(self))
+    }
+
+    /// The contexts the error occurred in, outermost first.
+    pub(crate) fn contexts(&self) -> Vec<&ErrorContext> {
+        let mut contexts = vec![];
+        let mut error = self;
+        while let LoreGuiError::Context(context, cause) = error {
+            contexts.push(context.as_ref());
+            error = cause;
+        }
+        contexts
+    }
+
+    /// A one line description of what went wrong, without the causes.
+    pub(crate) fn summary(&self) -> String {
+        match self {
+            LoreGuiError::Context(context, _) => tr_args(
+                "error-operation-failed",
+                &[("operation", &context.operation())],
+            ),
+            error => error.to_string(),
+        }
+    }
+
+    /// The summaries of the errors that led to this one, outermost first.
+    pub(crate) fn causes(&self) -> Vec<String> {
+        let mut causes = vec![];
+        let mut error = self;
+        while let LoreGuiError::Context(_, cause) = error {
+            causes.push(cause.summary());
+            error = cause;
+        }
+        causes
+    }
+
+    /// A report of the error with everything that is known about it, suitable for bug reports.
+    pub(crate) fn details(&self) -> String {
+        let mut lines = vec![self.summary()];
+        for context in self.contexts() {
+            for (kind, value) in context.subjects() {
+                lines.push(format!("{}: {}", kind, value));
+            }
+        }
+        let causes = self.causes();
+        if !causes.is_empty() {
+            lines.push(tr("error-caused-by"));
+            for (index, cause) in causes.iter().enumerate() {
+                lines.push(format!("  {}. {}", index + 1, cause));
+            }
+        }
+        lines.join("\n")
+    }
 }
 
 impl Display for LoreGuiError {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
-            LoreGuiError::FileError(msg) => write!(f, "File error: {}", msg),
-            LoreGuiError::InputError(msg) => write!(f, "Input error: {}", msg),
-            LoreGuiError::NoDatabase => write!(f, "No database"),
-            LoreGuiError::MultipleResults => write!(f, "Multiple results"),
-            LoreGuiError::LoreCoreError(error) => write!(f, "Lore core error: {}", error),
+            LoreGuiError::FileError(msg) => {
+                write!(f, "{}", tr_args("error-file", &[("message", msg)]))
+            }
+            LoreGuiError::InputError(msg) => {
+                write!(f, "{}", tr_args("error-input", &[("message", msg)]))
+            }
+            LoreGuiError::NoDatabase => write!(f, "{}", tr("error-no-database")),
+            LoreGuiError::MultipleResults => write!(f, "{}", tr("error-multiple-results")),
+            LoreGuiError::LoreCoreError(error) => {
+                write!(f, "{}", tr_args("error-lore-core", &[("error", error)]))
+            }
+            LoreGuiError::Context(context, cause) => write!(
+                f,
+                "{}",
+                tr_args(
+                    "error-in-context",
+                    &[("operation", &context.operation()), ("cause", cause)]
+                )
+            ),
+        }
+    }
+}
+
+impl std::error::Error for LoreGuiError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            LoreGuiError::Context(_, cause) => Some(cause.as_ref()),
+            _ => None,
         }
     }
 }
@@ -28,3 +142,54 @@ impl From<LoreCoreError> for LoreGuiError {
         LoreGuiError::LoreCoreError(error)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn nested_error() -> LoreGuiError {
+        let inner = ErrorContext::new("Save".to_string()).with("Label".to_string(), "Andor");
+        let outer = ErrorContext::new("Undo".to_string());
+        LoreGuiError::InputError("Broken".to_string())
+            .in_context(inner)
+            .in_context(outer)
+    }
+
+    #[test]
+    fn errors_without_context_have_no_causes() {
+        let error = LoreGuiError::NoDatabase;
+        assert!(error.contexts().is_empty());
+        assert!(error.causes().is_empty());
+        assert_eq!(error.summary(), error.to_string());
+        assert_eq!(error.details(), error.to_string());
+    }
+
+    #[test]
+    fn causes_are_listed_outermost_first() {
+        let error = nested_error();
+        assert_eq!(error.summary(), "Undo failed.");
+        assert_eq!(error.causes(), vec!["Save failed.", "Input error: Broken"]);
+        assert_eq!(
+            error.to_string(),
+            "Undo failed: Save failed: Input error: Broken"
+        );
+    }
+
+    #[test]
+    fn details_name_the_involved_subjects_and_causes() {
+        let details = nested_error().details();
+        assert_eq!(
+            details,
+            "Undo failed.\nLabel: Andor\nCaused by:\n  1. Save failed.\n  2. Input error: Broken"
+        );
+    }
+
+    #[test]
+    fn source_follows_the_cause_chain() {
+        use std::error::Error;
+        let error = nested_error();
+        let source = error.source().unwrap();
+        assert_eq!(source.to_string(), "Save failed: Input error: Broken");
+        assert!(source.source().unwrap().source().is_none());
+    }
+}
@@ -1,5 +1,7 @@
 use std::path::PathBuf;
 
+use crate::localisation::tr;
+
 fn current_path() -> PathBuf {
     std::env::current_dir().unwrap_or_default()
 }
@@ -13,8 +15,8 @@ pub(super) fn new() -> Option<PathBuf> {
 
 pub(super) fn open() -> Option<PathBuf> {
     rfd::FileDialog::new()
-        .add_filter("Lore Database (.db)", &["db"])
-        .add_filter("Any", &["*"])
+        .add_filter(tr("file-filter-database"), &["db"])
+        .add_filter(tr("file-filter-any"), &["*"])
         .set_directory(current_path())
         .pick_file()
 }
@@ -0,0 +1,58 @@
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    time::{Duration, SystemTime},
+};
+
+use iced::Subscription;
+
+use crate::{app::message_handling::GuiMessage, polling};
+
+const POLL_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Size and modification time of a database file, including SQLite's write-ahead log.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct FileStamp {
+    modified: Option<SystemTime>,
+    len: u64,
+    wal_modified: Option<SystemTime>,
+    wal_len: u64,
+}
+
+impl FileStamp {
+    pub(crate) fn read(path: &Path) -> Self {
+        let mut wal_path = path.as_os_str().to_owned();
+        wal_path.push("-wal");
+        let (modified, len) = modification(path);
+        let (wal_modified, wal_len) = modification(Path::new(&wal_path));
+        FileStamp {
+            modified,
+            len,
+            wal_modified,
+            wal_len,
+        }
+    }
+}
+
+fn modification(path: &Path) -> (Option<SystemTime>, u64) {
+    match fs::metadata(path) {
+        Ok(metadata) => (metadata.modified().ok(), metadata.len()),
+        Err(_) => (None, 0),
+    }
+}
+
+/// Reports every change of the file at `path`, including the ones made by LoreGui itself.
+///
+/// The file is polled, so that changes are noticed no matter which process or connection made them.
+pub(crate) fn watch_database(path: String) -> Subscription<GuiMessage> {
+    let file = PathBuf::from(&path);
+    let mut last_stamp = FileStamp::read(&file);
+    polling::every(path.clone(), POLL_INTERVAL, move || {
+        let stamp = FileStamp::read(&file);
+        if stamp == last_stamp {
+            return None;
+        }
+        last_stamp = stamp;
+        Some(GuiMessage::DatabaseFileChanged(path.clone(), stamp))
+    })
+}
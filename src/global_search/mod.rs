@@ -0,0 +1,481 @@
+use lorecore::types::*;
+
+use crate::{
+    errors::LoreGuiError,
+    localisation::tr_args,
+    query_cache::CachedReader,
+    search_index::{IndexHit, SearchIndex},
+};
+
+pub(crate) mod widget;
+
+/// Characters shown around a match.
+const SNIPPET_CONTEXT: usize = 30;
+const MAX_HITS_PER_GROUP: usize = 50;
+
+/// What to select when a search hit is clicked.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) enum SearchTarget {
+    Entity(Label, Option<Descriptor>),
+    HistoryItem(Year, Day, Timestamp),
+    Relationship(Parent, Child, Role),
+    /// Selects the year in the history view, used by the dashboard.
+    Year(Year),
+}
+
+/// The text around a match, split such that the match can be highlighted.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct Snippet {
+    pub(crate) before: String,
+    pub(crate) matched: String,
+    pub(crate) after: String,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct SearchHit {
+    pub(crate) target: SearchTarget,
+    pub(crate) title: String,
+    pub(crate) snippet: Snippet,
+}
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub(crate) struct SearchResults {
+    pub(crate) entities: Vec<SearchHit>,
+    pub(crate) history_items: Vec<SearchHit>,
+    pub(crate) relationships: Vec<SearchHit>,
+}
+
+impl SearchResults {
+    pub(crate) fn len(&self) -> usize {
+        self.entities.len() + self.history_items.len() + self.relationships.len()
+    }
+}
+
+fn history_item_title(item: &HistoryItem) -> String {
+    tr_args(
+        "search-history-item",
+        &[
+            ("year", &item.year),
+            ("day", &item.day),
+            ("timestamp", &item.timestamp),
+        ],
+    )
+}
+
+/// Finds the first case insensitive occurrence of `search` in `text`.
+pub(crate) fn snippet(text: &str, search: &str) -> Option<Snippet> {
+    let text: Vec<char> = text
+        .chars()
+        .map(|c| if c.is_whitespace() { ' ' } else { c })
+        .collect();
+    let search: Vec<char> = search.chars().collect();
+    if search.is_empty() || search.len() > text.len() {
+        return None;
+    }
+    let start = (0..=text.len() - search.len()).find(|start| {
+        text[*start..*start + search.len()]
+            .iter()
+            .zip(search.iter())
+            .all(|(a, b)| a.to_lowercase().eq(b.to_lowercase()))
+    })?;
+    let end = start + search.len();
+
+    let before_start = start.saturating_sub(SNIPPET_CONTEXT);
+    let after_end = (end + SNIPPET_CONTEXT).min(text.len());
+    let mut before: String = text[before_start..start].iter().collect();
+    if before_start > 0 {
+        before.insert(0, '…');
+    }
+    let mut after: String = text[end..after_end].iter().collect();
+    if after_end < text.len() {
+        after.push('…');
+    }
+    Some(Snippet {
+        before,
+        matched: text[start..end].iter().collect(),
+        after,
+    })
+}
+
+/// Everything needed to search the database, detached from the search state so that it can run in the background.
+#[derive(Debug, Clone, PartialEq)]
+pub(super) struct GlobalSearchQuery {
+    search_text: String,
+    /// The database whose search index ranks descriptions and history items, if it has one.
+    index: Option<String>,
+}
+
+impl GlobalSearchQuery {
+    pub(super) fn run(&self, reader: &CachedReader) -> Result<SearchResults, LoreGuiError> {
+        let search = self.search_text.trim();
+        if search.is_empty() {
+            return Ok(SearchResults::default());
+        }
+        let (entities, history_items) = match self.index.as_ref() {
+            Some(database) => {
+                let index_hits = SearchIndex::open(database)?.search(search, MAX_HITS_PER_GROUP)?;
+                (
+                    search_entity_names(reader, search, &index_hits)?,
+                    indexed_history_items(reader, &index_hits)?,
+                )
+            }
+            None => (
+                search_entities(reader, search)?,
+                search_history_items(reader, search)?,
+            ),
+        };
+        Ok(SearchResults {
+            entities,
+            history_items,
+            relationships: search_relationships(reader, search)?,
+        })
+    }
+}
+
+/// Labels and descriptors containing `search`, followed by the descriptions found in the index.
+fn search_entity_names(
+    reader: &CachedReader,
+    search: &str,
+    index_hits: &[IndexHit],
+) -> Result<Vec<SearchHit>, LoreGuiError> {
+    let mut hits: Vec<SearchHit> = vec![];
+    for col in reader.read_entity_columns(None, None)?.iter() {
+        let label_target = SearchTarget::Entity(col.label.clone(), None);
+        if let Some(snippet) = snippet(col.label.to_str(), search) {
+            if !hits.iter().any(|hit| hit.target == label_target) {
+                hits.push(SearchHit {
+                    target: label_target,
+                    title: col.label.to_string(),
+                    snippet,
+                });
+            }
+        }
+        if let Some(snippet) = snippet(col.descriptor.to_str(), search) {
+            hits.push(SearchHit {
+                target: SearchTarget::Entity(col.label.clone(), Some(col.descriptor.clone())),
+                title: format!("{}: {}", col.label, col.descriptor),
+                snippet,
+            });
+        }
+        if hits.len() >= MAX_HITS_PER_GROUP {
+            break;
+        }
+    }
+    for index_hit in index_hits {
+        if let IndexHit::Description {
+            label,
+            descriptor,
+            snippet,
+        } = index_hit
+        {
+            let target = SearchTarget::Entity(label.clone(), Some(descriptor.clone()));
+            if !hits.iter().any(|hit| hit.target == target) {
+                hits.push(SearchHit {
+                    target,
+                    title: format!("{}: {}", label, descriptor),
+                    snippet: snippet.clone(),
+                });
+            }
+        }
+    }
+    hits.truncate(MAX_HITS_PER_GROUP);
+    Ok(hits)
+}
+
+/// The history items found in the index, in the order of relevance.
+fn indexed_history_items(
+    reader: &CachedReader,
+    index_hits: &[IndexHit],
+) -> Result<Vec<SearchHit>, LoreGuiError> {
+    let mut hits = vec![];
+    for index_hit in index_hits {
+        if let IndexHit::HistoryContent {
+            year,
+            timestamp,
+            snippet,
+        } = index_hit
+        {
+            let year = match Year::try_from(year.clone()) {
+                Ok(year) => year,
+                Err(_) => continue,
+            };
+            let items = reader.read_history_items(Some(year), None, None)?;
+            if let Some(item) = items
+                .iter()
+                .find(|item| item.timestamp.to_string() == *timestamp)
+            {
+                hits.push(SearchHit {
+                    target: SearchTarget::HistoryItem(item.year, item.day, item.timestamp),
+                    title: history_item_title(item),
+                    snippet: snippet.clone(),
+                });
+            }
+        }
+    }
+    Ok(hits)
+}
+
+fn search_entities(reader: &CachedReader, search: &str) -> Result<Vec<SearchHit>, LoreGuiError> {
+    let mut hits: Vec<SearchHit> = vec![];
+    for col in reader.read_entity_columns(None, None)?.iter() {
+        let label_target = SearchTarget::Entity(col.label.clone(), None);
+        if let Some(snippet) = snippet(col.label.to_str(), search) {
+            if !hits.iter().any(|hit| hit.target == label_target) {
+                hits.push(SearchHit {
+                    target: label_target,
+                    title: col.label.to_string(),
+                    snippet,
+                });
+            }
+        }
+        let snippet = snippet(col.descriptor.to_str(), search)
+            .or_else(|| snippet(col.description.to_str(), search));
+        if let Some(snippet) = snippet {
+            hits.push(SearchHit {
+                target: SearchTarget::Entity(col.label.clone(), Some(col.descriptor.clone())),
+                title: format!("{}: {}", col.label, col.descriptor),
+                snippet,
+            });
+        }
+        if hits.len() >= MAX_HITS_PER_GROUP {
+            break;
+        }
+    }
+    hits.truncate(MAX_HITS_PER_GROUP);
+    Ok(hits)
+}
+
+fn search_history_items(
+    reader: &CachedReader,
+    search: &str,
+) -> Result<Vec<SearchHit>, LoreGuiError> {
+    let hits = reader
+        .read_history_items(None, None, None)?
+        .iter()
+        .filter_map(|item| {
+            let snippet = snippet(item.content.to_str(), search)?;
+            Some(SearchHit {
+                target: SearchTarget::HistoryItem(item.year, item.day, item.timestamp),
+                title: history_item_title(item),
+                snippet,
+            })
+        })
+        .take(MAX_HITS_PER_GROUP)
+        .collect();
+    Ok(hits)
+}
+
+fn search_relationships(
+    reader: &CachedReader,
+    search: &str,
+) -> Result<Vec<SearchHit>, LoreGuiError> {
+    let hits = reader
+        .read_relationships(None, None)?
+        .iter()
+        .filter_map(|rel| {
+            let snippet = snippet(rel.role.to_str(), search)
+                .or_else(|| snippet(rel.parent.to_str(), search))
+                .or_else(|| snippet(rel.child.to_str(), search))?;
+            Some(SearchHit {
+                target: SearchTarget::Relationship(
+                    rel.parent.clone(),
+                    rel.child.clone(),
+                    rel.role.clone(),
+                ),
+                title: format!("{} → {}", rel.parent, rel.child),
+                snippet,
+            })
+        })
+        .take(MAX_HITS_PER_GROUP)
+        .collect();
+    Ok(hits)
+}
+
+#[derive(Debug, Clone)]
+pub(super) enum GlobalSearchMessage {
+    SearchTextChanged(String),
+    ShowResults,
+    HideResults,
+}
+
+#[derive(Debug, Default)]
+pub(super) struct GlobalSearchState {
+    search_text: String,
+    results: SearchResults,
+    shown: bool,
+    loading: bool,
+    last_query: Option<GlobalSearchQuery>,
+}
+
+impl GlobalSearchState {
+    pub(super) fn get_search_text(&self) -> &str {
+        &self.search_text
+    }
+
+    pub(super) fn set_search_text(&mut self, text: String) {
+        self.shown = !text.trim().is_empty();
+        self.search_text = text;
+    }
+
+    pub(super) fn get_results(&self) -> &SearchResults {
+        &self.results
+    }
+
+    pub(super) fn is_shown(&self) -> bool {
+        self.shown
+    }
+
+    pub(super) fn show(&mut self) {
+        self.shown = !self.search_text.trim().is_empty();
+    }
+
+    pub(super) fn hide(&mut self) {
+        self.shown = false;
+    }
+
+    pub(super) fn is_loading(&self) -> bool {
+        self.loading
+    }
+
+    pub(super) fn set_loading(&mut self, loading: bool) {
+        self.loading = loading;
+    }
+
+    /// The query to search with, unless the results are hidden or have already been searched with it.
+    pub(super) fn query_if_changed(&mut self, index: Option<String>) -> Option<GlobalSearchQuery> {
+        if !self.shown {
+            return None;
+        }
+        let query = GlobalSearchQuery {
+            search_text: self.search_text.clone(),
+            index,
+        };
+        if self.last_query.as_ref() == Some(&query) {
+            return None;
+        }
+        self.last_query = Some(query.clone());
+        Some(query)
+    }
+
+    /// Makes the next refresh search again, even if the search text did not change.
+    pub(super) fn invalidate(&mut self) {
+        self.last_query = None;
+    }
+
+    pub(super) fn apply(&mut self, results: SearchResults) {
+        self.results = results;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Mutex;
+
+    use super::*;
+    use crate::{
+        query_cache::QueryCache,
+        tests::{example_database, example_descriptors, example_labels},
+    };
+
+    fn search(search_text: &str) -> SearchResults {
+        let db = example_database();
+        let cache = Mutex::new(QueryCache::default());
+        let query = GlobalSearchQuery {
+            search_text: search_text.to_string(),
+            index: None,
+        };
+        query.run(&CachedReader::new(&db, &cache)).unwrap()
+    }
+
+    fn indexed_search(search_text: &str) -> SearchResults {
+        let db = example_database();
+        SearchIndex::build(&db).unwrap();
+        let cache = Mutex::new(QueryCache::default());
+        let query = GlobalSearchQuery {
+            search_text: search_text.to_string(),
+            index: Some(db.path_as_string()),
+        };
+        query.run(&CachedReader::new(&db, &cache)).unwrap()
+    }
+
+    #[test]
+    fn snippet_highlights_case_insensitive_match_with_context() {
+        let text = "The quick brown fox\njumps over the lazy dog, again and again and again.";
+        let snippet = snippet(text, "FOX").unwrap();
+        assert_eq!(snippet.before, "The quick brown ");
+        assert_eq!(snippet.matched, "fox");
+        assert_eq!(snippet.after, " jumps over the lazy dog, agai…");
+        assert_eq!(super::snippet(text, "cat"), None);
+    }
+
+    #[test]
+    fn descriptions_are_searched() {
+        let results = search("description 2");
+        let descriptor = example_descriptors()[1].0.clone();
+        let targets: Vec<SearchTarget> = results
+            .entities
+            .iter()
+            .map(|hit| hit.target.clone())
+            .collect();
+        let expected: Vec<SearchTarget> = example_labels()
+            .into_iter()
+            .map(|label| SearchTarget::Entity(label, Some(descriptor.clone())))
+            .collect();
+        assert_eq!(targets, expected);
+        assert!(results.history_items.is_empty());
+    }
+
+    #[test]
+    fn history_content_and_roles_are_searched() {
+        let results = search("-22-11");
+        assert_eq!(results.history_items.len(), 1);
+        assert_eq!(results.history_items[0].snippet.matched, "-22-11");
+
+        let labels = example_labels();
+        let role = format!("{}-{}", labels[0], labels[1]);
+        let results = search(&role);
+        assert_eq!(results.relationships.len(), 1);
+        assert_eq!(
+            results.relationships[0].target,
+            SearchTarget::Relationship(
+                labels[0].to_str().into(),
+                labels[1].to_str().into(),
+                role.into()
+            )
+        );
+    }
+
+    #[test]
+    fn labels_are_found_once() {
+        let label = example_labels()[2].clone();
+        let results = search(label.to_str());
+        assert_eq!(
+            results.entities,
+            vec![SearchHit {
+                target: SearchTarget::Entity(label.clone(), None),
+                title: label.to_string(),
+                snippet: Snippet {
+                    before: String::new(),
+                    matched: label.to_string(),
+                    after: String::new(),
+                },
+            }]
+        );
+    }
+
+    #[test]
+    fn index_is_used_for_descriptions_and_history_content() {
+        let results = indexed_search("\"description 2\"");
+        assert_eq!(results.entities.len(), example_labels().len());
+        assert!(results.history_items.is_empty());
+
+        let results = indexed_search("33*");
+        assert_eq!(results.history_items.len(), 3);
+        for hit in results.history_items {
+            match hit.target {
+                SearchTarget::HistoryItem(year, _, _) => assert_eq!(year, 333.into()),
+                _ => panic!("History content should lead to history items."),
+            }
+        }
+    }
+}
@@ -0,0 +1,90 @@
+use iced::{
+    widget::{button, text, text_input, Button, Column, Row, Scrollable},
+    Alignment, Element, Length,
+};
+
+use crate::{
+    app::message_handling::GuiMessage,
+    localisation::{tr, tr_args},
+    style::Sizes,
+};
+
+use super::{GlobalSearchMessage, GlobalSearchState, SearchHit};
+
+pub(crate) fn search_box(state: &GlobalSearchState, sizes: Sizes) -> Row<'_, GuiMessage> {
+    let input = text_input(&tr("search-placeholder"), state.get_search_text())
+        .size(sizes.text)
+        .on_input(|text| GuiMessage::GlobalSearchUpd(GlobalSearchMessage::SearchTextChanged(text)))
+        .on_submit(GuiMessage::GlobalSearchUpd(
+            GlobalSearchMessage::ShowResults,
+        ))
+        .width(Length::Fixed(250.0));
+    let mut row = Row::new()
+        .push(input)
+        .align_y(Alignment::Center)
+        .spacing(sizes.spacing);
+    if !state.is_shown() && !state.get_search_text().trim().is_empty() {
+        row = row.push(button(sizes.text(tr("search-results-button"))).on_press(
+            GuiMessage::GlobalSearchUpd(GlobalSearchMessage::ShowResults),
+        ));
+    }
+    row
+}
+
+pub(crate) fn results(state: &GlobalSearchState, sizes: Sizes) -> Element<'_, GuiMessage> {
+    let results = state.get_results();
+    let mut summary = tr_args("search-result-count", &[("count", &results.len())]);
+    if state.is_loading() {
+        summary = tr_args("search-in-progress", &[("summary", &summary)]);
+    }
+    let header = Row::new()
+        .push(sizes.text(summary))
+        .push(sizes.text(tr("search-syntax-hint")).style(text::secondary))
+        .push(
+            button(sizes.text(tr("button-close"))).on_press(GuiMessage::GlobalSearchUpd(
+                GlobalSearchMessage::HideResults,
+            )),
+        )
+        .align_y(Alignment::Center)
+        .spacing(10);
+
+    let groups = Column::new()
+        .push(group(tr("view-entities"), &results.entities, sizes))
+        .push(group(
+            tr("view-history-items"),
+            &results.history_items,
+            sizes,
+        ))
+        .push(group(
+            tr("view-relationships"),
+            &results.relationships,
+            sizes,
+        ))
+        .spacing(10);
+    Column::new()
+        .push(header)
+        .push(Scrollable::new(groups).height(Length::Fill))
+        .padding(sizes.spacing)
+        .spacing(sizes.spacing)
+        .into()
+}
+
+fn group(title: String, hits: &[SearchHit], sizes: Sizes) -> Column<'_, GuiMessage> {
+    let mut column = Column::new().push(sizes.text(format!("{} ({})", title, hits.len())));
+    for hit in hits {
+        column = column.push(hit_button(hit, sizes));
+    }
+    column.spacing(2)
+}
+
+fn hit_button(hit: &SearchHit, sizes: Sizes) -> Button<'_, GuiMessage> {
+    let snippet = Row::new()
+        .push(sizes.text(&hit.snippet.before))
+        .push(sizes.text(&hit.snippet.matched).style(text::primary))
+        .push(sizes.text(&hit.snippet.after));
+    let content = Column::new().push(sizes.text(&hit.title)).push(snippet);
+    Button::new(content)
+        .style(button::text)
+        .width(Length::Fill)
+        .on_press(GuiMessage::JumpTo(hit.target.clone()))
+}
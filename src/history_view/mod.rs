@@ -1,101 +1,81 @@
 use iced::widget::text_editor;
 use lorecore::{
     extractions::{extract_days, extract_years},
-    sql::{lore_database::LoreDatabase, search_params::HistoryItemSearchParams},
     types::*,
 };
 
 use crate::{
     db_col_view::{entry::DbColViewEntry, ColViewMes},
     dialog::redate_history::RedateHistoryData,
+    drafts::{Draft, DraftKey},
     editor::EditorState,
     errors::LoreGuiError,
+    query_cache::CachedReader,
 };
 
-use super::db_col_view::state::DbColViewState;
+use super::db_col_view::state::{combined_search_matches, parse_search_int, DbColViewState};
 
 pub(crate) mod widget;
 
-pub(super) struct HistoryViewState {
-    pub(super) year_view_state: DbColViewState<Year>,
-    pub(super) day_view_state: DbColViewState<Day>,
-    pub(super) timestamp_view_state: DbColViewState<Timestamp>,
-    pub(super) current_content: EditorState,
+/// The year, day and timestamp of a history item.
+type HistoryItemKey = (Year, Option<Day>, Timestamp);
+
+/// Everything needed to read the contents of the history view, detached from the view state so that it can run in the background.
+#[derive(Debug, Clone, PartialEq)]
+pub(super) struct HistoryViewQuery {
+    year_search_text: Option<String>,
+    day_search_text: Option<String>,
+    year: Option<Year>,
+    day: Option<Day>,
+    timestamp: Option<Timestamp>,
 }
 
 #[derive(Debug, Clone)]
-pub(super) enum HistoryViewMessage {
-    NewHistoryItem,
-    RedateHistoryItem(RedateHistoryData),
-    DeleteHistoryItem(Timestamp),
-    YearViewUpdate(ColViewMes<Year>),
-    DayViewUpdate(ColViewMes<Day>),
-    HistoryTimestampViewUpdate(ColViewMes<Timestamp>),
-    ContentUpdate(text_editor::Action),
-    ContentDiscard,
-    ContentSave,
+pub(super) struct HistoryViewData {
+    years: Vec<Year>,
+    days: Vec<Day>,
+    timestamps: Vec<Timestamp>,
+    content: HistoryItemContent,
+    /// The history item the content belongs to.
+    source: Option<HistoryItemKey>,
 }
 
-impl HistoryViewState {
-    pub(super) fn new() -> Self {
-        Self {
-            year_view_state: DbColViewState::default(),
-            day_view_state: DbColViewState::default(),
-            timestamp_view_state: DbColViewState::default(),
-            current_content: EditorState::default(),
-        }
+impl HistoryViewQuery {
+    pub(super) fn run(&self, reader: &CachedReader) -> Result<HistoryViewData, LoreGuiError> {
+        Ok(HistoryViewData {
+            years: self.read_years(reader)?,
+            days: self.read_days(reader)?,
+            timestamps: self.read_timestamps(reader)?,
+            content: self.read_content(reader)?,
+            source: item_key(self.year, self.day, self.timestamp),
+        })
     }
 
-    pub(super) fn get_current_years(
-        &self,
-        db: &Option<LoreDatabase>,
-    ) -> Result<Vec<Year>, LoreGuiError> {
-        let db = match db {
-            Some(db) => db,
-            None => return Ok(vec![]),
-        };
-        let year = self.year_view_state.get_search_int()?.map(|y| y.into());
-        let search_params = HistoryItemSearchParams::new(year, None, None, None);
-        let history_items = db.read_history_items(search_params)?;
+    fn read_years(&self, reader: &CachedReader) -> Result<Vec<Year>, LoreGuiError> {
+        let year = parse_search_int(self.year_search_text.as_deref())?.map(|y| y.into());
+        let history_items = reader.read_history_items(year, None, None)?;
         let years = extract_years(&history_items);
         Ok(years)
     }
 
-    pub(super) fn get_current_days(
-        &self,
-        db: &Option<LoreDatabase>,
-    ) -> Result<Vec<Day>, LoreGuiError> {
-        let db = match db {
-            Some(db) => db,
-            None => return Ok(vec![]),
-        };
-        let year = match self.get_selected_year() {
+    fn read_days(&self, reader: &CachedReader) -> Result<Vec<Day>, LoreGuiError> {
+        let year = match self.year {
             Some(year) => Some(year),
             None => return Ok(vec![]),
         };
 
-        let day = self.day_view_state.get_search_int()?.map(|d| d.into());
-        let search_params = HistoryItemSearchParams::new(year, day, None, None);
-        let history_items = db.read_history_items(search_params)?;
+        let day = parse_search_int(self.day_search_text.as_deref())?.map(|d| d.into());
+        let history_items = reader.read_history_items(year, day, None)?;
         Ok(extract_days(&history_items))
     }
 
-    pub(super) fn get_current_timestamps(
-        &self,
-        db: &Option<LoreDatabase>,
-    ) -> Result<Vec<Timestamp>, LoreGuiError> {
-        let db = match db {
-            Some(db) => db,
-            None => return Ok(vec![]),
-        };
-        let year = match self.get_selected_year() {
+    fn read_timestamps(&self, reader: &CachedReader) -> Result<Vec<Timestamp>, LoreGuiError> {
+        let year = match self.year {
             Some(year) => Some(year),
             None => return Ok(vec![]),
         };
-        let day = self.get_selected_day();
 
-        let search_params = HistoryItemSearchParams::new(year, day, None, None);
-        let history_items = db.read_history_items(search_params)?;
+        let history_items = reader.read_history_items(year, self.day, None)?;
         let timestamps = history_items
             .iter()
             .map(|item| item.timestamp)
@@ -103,21 +83,13 @@ impl HistoryViewState {
         Ok(timestamps)
     }
 
-    pub(super) fn get_current_content(
-        &self,
-        db: &Option<LoreDatabase>,
-    ) -> Result<HistoryItemContent, LoreGuiError> {
-        let db = match db {
-            Some(db) => db,
-            None => return Ok("".into()),
-        };
-        let timestamp = match self.get_selected_timestamp() {
+    fn read_content(&self, reader: &CachedReader) -> Result<HistoryItemContent, LoreGuiError> {
+        let timestamp = match self.timestamp {
             Some(timestamp) => timestamp,
             None => return Ok("".into()),
         };
 
-        let search_params = HistoryItemSearchParams::new(None, None, Some(timestamp), None);
-        let history_items = db.read_history_items(search_params)?;
+        let history_items = reader.read_history_items(None, None, Some(timestamp))?;
         if history_items.len() > 1 {
             return Err(LoreGuiError::MultipleResults);
         }
@@ -127,6 +99,123 @@ impl HistoryViewState {
         };
         Ok(content)
     }
+}
+
+pub(super) struct HistoryViewState {
+    pub(super) year_view_state: DbColViewState<Year>,
+    pub(super) day_view_state: DbColViewState<Day>,
+    pub(super) timestamp_view_state: DbColViewState<Timestamp>,
+    pub(super) current_content: EditorState,
+    /// The history item whose content the editor was loaded with.
+    content_source: Option<HistoryItemKey>,
+    last_query: Option<HistoryViewQuery>,
+    timestamp_to_restore: Option<String>,
+}
+
+#[derive(Debug, Clone)]
+pub(super) enum HistoryViewMessage {
+    NewHistoryItem,
+    RedateHistoryItem(RedateHistoryData),
+    DeleteHistoryItem(Timestamp),
+    YearViewUpdate(ColViewMes<Year>),
+    DayViewUpdate(ColViewMes<Day>),
+    HistoryTimestampViewUpdate(ColViewMes<Timestamp>),
+    ContentUpdate(text_editor::Action),
+    ContentDiscard,
+    ContentSave,
+}
+
+impl HistoryViewState {
+    pub(super) fn new() -> Self {
+        Self {
+            year_view_state: DbColViewState::default(),
+            day_view_state: DbColViewState::default(),
+            timestamp_view_state: DbColViewState::default(),
+            current_content: EditorState::default(),
+            content_source: None,
+            last_query: None,
+            timestamp_to_restore: None,
+        }
+    }
+
+    pub(super) fn query(&self) -> HistoryViewQuery {
+        HistoryViewQuery {
+            year_search_text: self.year_view_state.get_search_text().map(String::from),
+            day_search_text: self.day_view_state.get_search_text().map(String::from),
+            year: self.get_selected_year(),
+            day: self.get_selected_day(),
+            timestamp: self.get_selected_timestamp(),
+        }
+    }
+
+    /// The query to read the view with, unless the view has already been read with it.
+    pub(super) fn query_if_changed(&mut self) -> Option<HistoryViewQuery> {
+        let query = self.query();
+        if self.last_query.as_ref() == Some(&query) {
+            return None;
+        }
+        self.last_query = Some(query.clone());
+        Some(query)
+    }
+
+    /// Makes the next refresh read the view again, even if the query did not change.
+    pub(super) fn invalidate(&mut self) {
+        self.last_query = None;
+    }
+
+    pub(super) fn set_loading(&mut self, loading: bool) {
+        self.year_view_state.set_loading(loading);
+        self.day_view_state.set_loading(loading);
+        self.timestamp_view_state.set_loading(loading);
+    }
+
+    /// A read that is still running when the tab is put aside will never be applied, so it has to be repeated later.
+    pub(super) fn abandon_loading(&mut self) {
+        if self.year_view_state.is_loading() {
+            self.set_loading(false);
+            self.invalidate();
+        }
+    }
+
+    /// Unsaved changes in the content editor survive a refresh of the history item they were made for.
+    ///
+    /// A restored draft belongs to the timestamp it restores. Its content is read once that timestamp is selected.
+    pub(super) fn apply(&mut self, data: HistoryViewData) {
+        let years = data.years.into_iter().map(DbColViewEntry::from).collect();
+        self.year_view_state.set_entries(years);
+        let days = data.days.into_iter().map(DbColViewEntry::from).collect();
+        self.day_view_state.set_entries(days);
+        if let Some(text) = self.timestamp_to_restore.take() {
+            let restored = data.timestamps.iter().find(|t| t.to_string() == text);
+            self.set_selected_timestamp(restored.copied());
+            if self.current_content.is_changed() {
+                self.content_source = self.selected_item();
+            }
+        }
+        let timestamps = data
+            .timestamps
+            .into_iter()
+            .map(DbColViewEntry::from)
+            .collect();
+        self.timestamp_view_state.set_entries(timestamps);
+        if data.source != self.selected_item() {
+            return;
+        }
+        let keeps_unsaved_changes =
+            self.current_content.is_changed() && self.content_source == data.source;
+        if !keeps_unsaved_changes {
+            self.current_content = EditorState::new(data.content.to_str());
+        }
+        self.content_source = data.source;
+    }
+
+    fn selected_item(&self) -> Option<HistoryItemKey> {
+        item_key(
+            self.get_selected_year(),
+            self.get_selected_day(),
+            self.get_selected_timestamp(),
+        )
+    }
 
     pub(super) fn get_selected_year(&self) -> Option<Year> {
         self.year_view_state.get_selected().0
@@ -149,17 +238,84 @@ impl HistoryViewState {
     }
 
     pub(super) fn set_selected_timestamp(&mut self, timestamp: Option<Timestamp>) {
+        self.timestamp_to_restore = None;
         self.timestamp_view_state
             .set_selected(DbColViewEntry(timestamp));
     }
 
+    /// Timestamps can only be selected once they have been read, so a restored one is selected by the next read of the view.
+    pub(super) fn restore_timestamp(&mut self, text: Option<String>) {
+        self.set_selected_timestamp(None);
+        self.timestamp_to_restore = text;
+    }
+
+    /// The displayed selected timestamp, or the one that is still waiting to be restored.
+    pub(super) fn timestamp_text(&self) -> Option<String> {
+        match self.get_selected_timestamp() {
+            Some(timestamp) => Some(timestamp.to_string()),
+            None => self.timestamp_to_restore.clone(),
+        }
+    }
+
+    /// The entries found in the columns with a search text.
+    pub(super) fn search_matches(&self) -> Option<usize> {
+        combined_search_matches([
+            self.year_view_state.search_matches(),
+            self.day_view_state.search_matches(),
+            self.timestamp_view_state.search_matches(),
+        ])
+    }
+
     pub(super) fn get_content_text(&self) -> String {
         self.current_content.get_text()
     }
 
     pub(super) fn set_content_text(&mut self, text: &str) {
-        self.current_content = EditorState::new(text);
+        self.set_content_editor(EditorState::new(text));
     }
+
+    /// Puts `editor` into the view as the editor of the selected history item.
+    pub(super) fn set_content_editor(&mut self, editor: EditorState) {
+        self.current_content = editor;
+        self.content_source = self.selected_item();
+    }
+
+    /// The timestamp of the history item the text in the content editor belongs to, which may no longer be the selected one.
+    pub(super) fn content_timestamp(&self) -> Option<Timestamp> {
+        self.content_source.map(|(_, _, timestamp)| timestamp)
+    }
+
+    /// Text typed while the content was being saved stays unsaved.
+    pub(super) fn content_saved(&mut self, timestamp: Timestamp, content: &HistoryItemContent) {
+        if self.content_timestamp() == Some(timestamp) {
+            self.current_content.rebase_onto(content.to_str());
+        }
+    }
+
+    /// The unsaved changes of the content editor, if there are any.
+    pub(super) fn draft(&self) -> Option<Draft> {
+        if !self.current_content.is_changed() {
+            return None;
+        }
+        let (year, day, timestamp) = self.content_source?;
+        let key = DraftKey::HistoryContent {
+            year: year.to_string(),
+            day: day.map(|day| day.to_string()),
+            timestamp: timestamp.to_string(),
+        };
+        Some(Draft {
+            key,
+            text: self.get_content_text(),
+        })
+    }
+}
+
+fn item_key(
+    year: Option<Year>,
+    day: Option<Day>,
+    timestamp: Option<Timestamp>,
+) -> Option<HistoryItemKey> {
+    Some((year?, day, timestamp?))
 }
 
 impl Default for HistoryViewState {
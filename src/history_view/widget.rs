@@ -1,4 +1,3 @@
-use iced::widget::button;
 use iced::Alignment;
 use iced::{
     widget::{Column, Row},
@@ -6,24 +5,30 @@ use iced::{
 };
 
 use crate::app::message_handling::GuiMessage;
+use crate::commands::{button_row, Command};
 use crate::dialog::redate_history::RedateHistoryData;
+use crate::localisation::tr;
+use crate::references::Reading;
+use crate::style::Sizes;
 use crate::{db_col_view, editor};
 
 use super::{HistoryViewMessage, HistoryViewState};
 
-pub(crate) fn new(state: &HistoryViewState) -> Element<'_, GuiMessage> {
+pub(crate) fn new<'a>(
+    state: &'a HistoryViewState,
+    reading: Reading,
+    focused_column: usize,
+    sizes: Sizes,
+) -> Element<'a, GuiMessage> {
     Column::new()
-        .push(buttons(state))
-        .push(col_views(state))
+        .push(button_row(&commands(state), sizes))
+        .push(col_views(state, reading, focused_column, sizes))
         .into()
 }
 
-fn buttons(state: &HistoryViewState) -> Row<'_, GuiMessage> {
-    let new_item = button("New History Item").on_press(GuiMessage::HistoryViewUpd(
-        HistoryViewMessage::NewHistoryItem,
-    ));
-    let mut redate_history = button("Redate History Item");
-    let mut delete_item = button("Delete History Item");
+pub(crate) fn commands(state: &HistoryViewState) -> Vec<Command> {
+    let mut redate_history = None;
+    let mut delete_item = None;
     if let (Some(timestamp), Some(year), day) = (
         state.get_selected_timestamp(),
         state.get_selected_year(),
@@ -31,44 +36,59 @@ fn buttons(state: &HistoryViewState) -> Row<'_, GuiMessage> {
     ) {
         let day = day.clone().flatten();
         let redate_history_data = RedateHistoryData::new(timestamp, year, day);
-        redate_history = redate_history.on_press(GuiMessage::HistoryViewUpd(
+        redate_history = Some(GuiMessage::HistoryViewUpd(
             HistoryViewMessage::RedateHistoryItem(redate_history_data),
         ));
-        delete_item = delete_item.on_press(GuiMessage::HistoryViewUpd(
+        delete_item = Some(GuiMessage::HistoryViewUpd(
             HistoryViewMessage::DeleteHistoryItem(timestamp),
         ));
     }
-    Row::new()
-        .push(new_item)
-        .push(redate_history)
-        .push(delete_item)
-        .spacing(5)
-        .padding(5)
+    vec![
+        Command::enabled(
+            &tr("command-new-history-item"),
+            GuiMessage::HistoryViewUpd(HistoryViewMessage::NewHistoryItem),
+        ),
+        Command::new(&tr("command-redate-history-item"), redate_history),
+        Command::new(&tr("command-delete-history-item"), delete_item),
+    ]
 }
 
-fn col_views(state: &HistoryViewState) -> Row<'_, GuiMessage> {
+fn col_views<'a>(
+    state: &'a HistoryViewState,
+    reading: Reading,
+    focused_column: usize,
+    sizes: Sizes,
+) -> Row<'a, GuiMessage> {
     Row::new()
         .push(db_col_view::widget::new(
-            "Year",
+            tr("column-year"),
             |m| GuiMessage::HistoryViewUpd(HistoryViewMessage::YearViewUpdate(m)),
             &state.year_view_state,
+            focused_column == 0,
+            sizes,
         ))
         .push(db_col_view::widget::new(
-            "Day",
+            tr("column-day"),
             |m| GuiMessage::HistoryViewUpd(HistoryViewMessage::DayViewUpdate(m)),
             &state.day_view_state,
+            focused_column == 1,
+            sizes,
         ))
         .push(db_col_view::widget::new(
-            "Timestamp",
+            tr("column-timestamp"),
             |m| GuiMessage::HistoryViewUpd(HistoryViewMessage::HistoryTimestampViewUpdate(m)),
             &state.timestamp_view_state,
+            focused_column == 2,
+            sizes,
         ))
         .push(editor::widget::view(
-            "Content",
+            tr("column-content"),
             &state.current_content,
             |a| GuiMessage::HistoryViewUpd(HistoryViewMessage::ContentUpdate(a)),
             GuiMessage::HistoryViewUpd(HistoryViewMessage::ContentDiscard),
             GuiMessage::HistoryViewUpd(HistoryViewMessage::ContentSave),
+            reading,
+            sizes,
         ))
         .align_y(Alignment::Start)
         .width(Length::Fill)
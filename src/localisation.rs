@@ -0,0 +1,268 @@
+use std::{
+    collections::HashMap,
+    fmt::Display,
+    sync::{OnceLock, PoisonError, RwLock},
+};
+
+use fluent::{concurrent::FluentBundle, FluentArgs, FluentResource, FluentValue};
+use serde::{Deserialize, Serialize};
+use unic_langid::LanguageIdentifier;
+
+/// The languages LoreGui ships a message catalogue for.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub(crate) enum Locale {
+    #[default]
+    English,
+    German,
+    French,
+}
+
+impl Locale {
+    pub(crate) const ALL: [Locale; 3] = [Locale::English, Locale::German, Locale::French];
+
+    fn source(&self) -> &'static str {
+        match self {
+            Locale::English => include_str!("../locales/en.ftl"),
+            Locale::German => include_str!("../locales/de.ftl"),
+            Locale::French => include_str!("../locales/fr.ftl"),
+        }
+    }
+
+    fn language(&self) -> LanguageIdentifier {
+        let language = match self {
+            Locale::English => "en",
+            Locale::German => "de",
+            Locale::French => "fr",
+        };
+        language.parse().unwrap_or_default()
+    }
+
+    fn catalogue(&self) -> &'static Catalogue {
+        static CATALOGUES: OnceLock<HashMap<Locale, Catalogue>> = OnceLock::new();
+        let catalogues = CATALOGUES.get_or_init(|| {
+            Locale::ALL
+                .iter()
+                // Errors in the shipped files are found by the tests.
+                .map(|locale| {
+                    let (catalogue, _) = Catalogue::parse(locale.language(), locale.source());
+                    (*locale, catalogue)
+                })
+                .collect()
+        });
+        &catalogues[self]
+    }
+}
+
+/// Every language is named in itself, so that it can be found without understanding the current one.
+impl Display for Locale {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Locale::English => write!(f, "English"),
+            Locale::German => write!(f, "Deutsch"),
+            Locale::French => write!(f, "Français"),
+        }
+    }
+}
+
+static LOCALE: RwLock<Locale> = RwLock::new(Locale::English);
+
+/// Sets the language of all messages, once the user's preferences have been read and while they are edited.
+pub(crate) fn apply(locale: Locale) {
+    *LOCALE.write().unwrap_or_else(PoisonError::into_inner) = locale;
+}
+
+#[cfg(not(test))]
+fn current_locale() -> Locale {
+    *LOCALE.read().unwrap_or_else(PoisonError::into_inner)
+}
+
+/// Tests run in parallel and expect the English messages.
+#[cfg(test)]
+fn current_locale() -> Locale {
+    Locale::English
+}
+
+/// The message `key` in the current locale.
+pub(crate) fn tr(key: &str) -> String {
+    tr_args(key, &[])
+}
+
+/// The message `key` in the current locale, with its `{ $name }` placeables replaced by `args`.
+pub(crate) fn tr_args(key: &str, args: &[(&str, &dyn Display)]) -> String {
+    translate(current_locale(), key, args)
+}
+
+/// Messages missing in `locale` are taken from the English catalogue, and the key is shown if that lacks them too.
+fn translate(locale: Locale, key: &str, args: &[(&str, &dyn Display)]) -> String {
+    [locale, Locale::English]
+        .iter()
+        .find_map(|locale| locale.catalogue().format(key, args))
+        .unwrap_or_else(|| key.to_string())
+}
+
+/// The messages of one Fluent file.
+struct Catalogue {
+    bundle: FluentBundle<FluentResource>,
+}
+
+impl Catalogue {
+    /// Also returns the errors found while parsing, for which the affected messages are left out.
+    fn parse(language: LanguageIdentifier, source: &str) -> (Self, Vec<String>) {
+        let mut errors = vec![];
+        let resource =
+            FluentResource::try_new(source.to_string()).unwrap_or_else(|(resource, e)| {
+                errors.extend(e.iter().map(|e| e.to_string()));
+                resource
+            });
+        let mut bundle = FluentBundle::new_concurrent(vec![language]);
+        // Isolation marks around placeables would show up in the widgets.
+        bundle.set_use_isolating(false);
+        if let Err(e) = bundle.add_resource(resource) {
+            errors.extend(e.iter().map(|e| e.to_string()));
+        }
+        (Catalogue { bundle }, errors)
+    }
+
+    /// Arguments that are written like numbers are passed as such, so that they select plural variants.
+    fn format(&self, key: &str, args: &[(&str, &dyn Display)]) -> Option<String> {
+        let pattern = self.bundle.get_message(key)?.value()?;
+        let values: Vec<(&str, String)> = args
+            .iter()
+            .map(|(name, value)| (*name, value.to_string()))
+            .collect();
+        let mut fluent_args = FluentArgs::new();
+        for (name, value) in values.iter() {
+            // Labels like "007" have to stay as they are.
+            let value = match FluentValue::try_number(value) {
+                FluentValue::Number(number) if number.as_string() == *value => {
+                    FluentValue::Number(number)
+                }
+                _ => FluentValue::from(value),
+            };
+            fluent_args.set(*name, value);
+        }
+        let mut errors = vec![];
+        let message = self
+            .bundle
+            .format_pattern(pattern, Some(&fluent_args), &mut errors);
+        Some(message.into_owned())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{collections::HashSet, fs, path::Path};
+
+    use fluent_syntax::ast::Entry;
+
+    use super::*;
+
+    fn keys(locale: Locale) -> HashSet<&'static str> {
+        let resource =
+            fluent_syntax::parser::parse(locale.source()).unwrap_or_else(|(resource, _)| resource);
+        resource
+            .body
+            .into_iter()
+            .filter_map(|entry| match entry {
+                Entry::Message(message) => Some(message.id.name),
+                _ => None,
+            })
+            .collect()
+    }
+
+    fn used_keys(dir: &Path, keys: &mut Vec<String>) {
+        for entry in fs::read_dir(dir).unwrap() {
+            let path = entry.unwrap().path();
+            if path.is_dir() {
+                used_keys(&path, keys);
+                continue;
+            }
+            let source = fs::read_to_string(&path).unwrap();
+            for function in ["tr", "tr_args"] {
+                let call = format!("{}(", function);
+                for (start, _) in source.match_indices(&call) {
+                    let rest = source[start + call.len()..].trim_start();
+                    if let Some(rest) = rest.strip_prefix('"') {
+                        let end = rest.find('"').unwrap();
+                        keys.push(rest[..end].to_string());
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn shipped_locales_parse_without_errors() {
+        for locale in Locale::ALL {
+            let (_, errors) = Catalogue::parse(locale.language(), locale.source());
+            assert!(errors.is_empty(), "{}: {:?}", locale, errors);
+        }
+    }
+
+    #[test]
+    fn every_key_exists_in_every_shipped_locale() {
+        let english = keys(Locale::English);
+        for locale in Locale::ALL {
+            let translated = keys(locale);
+            let missing: Vec<_> = english.difference(&translated).collect();
+            let unknown: Vec<_> = translated.difference(&english).collect();
+            assert!(missing.is_empty(), "{} lacks {:?}", locale, missing);
+            assert!(unknown.is_empty(), "{} has unknown {:?}", locale, unknown);
+        }
+    }
+
+    #[test]
+    fn every_used_key_exists() {
+        let mut used = vec![];
+        used_keys(
+            &Path::new(env!("CARGO_MANIFEST_DIR")).join("src"),
+            &mut used,
+        );
+        let english = keys(Locale::English);
+        let missing: Vec<_> = used
+            .iter()
+            .filter(|key| !english.contains(key.as_str()))
+            .collect();
+        assert!(missing.is_empty(), "Missing messages: {:?}", missing);
+    }
+
+    #[test]
+    fn variables_are_replaced() {
+        let (catalogue, _) = Catalogue::parse(
+            Locale::English.language(),
+            "greeting = Hello { $name }, { $missing }!",
+        );
+        let message = catalogue.format("greeting", &[("name", &"Lore")]);
+        assert_eq!(message.as_deref(), Some("Hello Lore, {$missing}!"));
+        let message = catalogue.format("greeting", &[("name", &"007"), ("missing", &-1.50)]);
+        assert_eq!(message.as_deref(), Some("Hello 007, -1.5!"));
+    }
+
+    #[test]
+    fn plurals_follow_the_rules_of_the_locale() {
+        let source = "results = { $count ->\n    [0] No results\n    [one] { $count } result\n   *[other] { $count } results\n}\nitems = { $count ->\n    [one] { $count } item\n   *[other] { $count } items\n}";
+        let format = |locale: Locale, key: &str, count: i64| {
+            let (catalogue, errors) = Catalogue::parse(locale.language(), source);
+            assert!(errors.is_empty(), "{:?}", errors);
+            catalogue.format(key, &[("count", &count)]).unwrap()
+        };
+        assert_eq!(format(Locale::English, "results", 0), "No results");
+        assert_eq!(format(Locale::English, "results", 1), "1 result");
+        assert_eq!(format(Locale::English, "results", 2), "2 results");
+        assert_eq!(format(Locale::German, "results", 1), "1 result");
+        assert_eq!(format(Locale::French, "results", 1), "1 result");
+        assert_eq!(format(Locale::French, "items", 0), "0 item");
+        assert_eq!(format(Locale::German, "items", 0), "0 items");
+    }
+
+    #[test]
+    fn missing_messages_fall_back_to_english_and_then_the_key() {
+        let english = translate(Locale::English, "button-save", &[]);
+        assert_eq!(
+            translate(Locale::German, "no-such-message", &[]),
+            "no-such-message"
+        );
+        assert!(!english.is_empty());
+        assert_ne!(english, "button-save");
+    }
+}
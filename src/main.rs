@@ -3,21 +3,49 @@
 use app::SqlGui;
 
 mod app;
+mod appearance;
+mod cli;
+mod commands;
+mod dashboard;
 mod db_col_view;
 mod dialog;
+mod diff;
+mod drafts;
 mod editor;
 mod entity_view;
+mod error_log;
 mod errors;
 mod file_dialogs;
+mod file_watcher;
+mod global_search;
 mod history_view;
+mod localisation;
+mod notifications;
+mod polling;
+mod query_cache;
+mod references;
 mod relationship_view;
+mod search_index;
+mod shortcuts;
+mod status_bar;
 mod style;
+mod time_format;
 mod user_preferences;
 
 const APP_TITLE: &str = "Lore SQL GUI";
 
 fn main() -> iced::Result {
-    iced::application(APP_TITLE, SqlGui::update, SqlGui::view).run()
+    let appearance = user_preferences::load_appearance();
+    localisation::apply(appearance.locale);
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    if !args.is_empty() {
+        std::process::exit(cli::run(args));
+    }
+    iced::application(APP_TITLE, SqlGui::update, SqlGui::view)
+        .subscription(SqlGui::subscription)
+        .theme(SqlGui::theme)
+        .exit_on_close_request(false)
+        .run_with(SqlGui::init)
 }
 
 #[cfg(test)]
@@ -0,0 +1,157 @@
+use std::time::Duration;
+
+use iced::Subscription;
+
+use crate::{app::message_handling::GuiMessage, polling};
+
+pub(crate) mod widget;
+
+const TICK_INTERVAL: Duration = Duration::from_secs(1);
+const MAX_HISTORY: usize = 200;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum NotificationLevel {
+    Success,
+    Warning,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct Notification {
+    pub(crate) id: usize,
+    pub(crate) time: u64,
+    pub(crate) level: NotificationLevel,
+    pub(crate) text: String,
+}
+
+#[derive(Debug, Clone)]
+pub(crate) enum NotificationMessage {
+    Tick,
+    Dismiss(usize),
+    ShowHistory,
+    HideHistory,
+    ClearHistory,
+}
+
+/// Toasts that are shown until they expire or are dismissed, and every notification of the session.
+#[derive(Debug, Default)]
+pub(crate) struct Notifications {
+    history: Vec<Notification>,
+    active: Vec<usize>,
+    next_id: usize,
+    history_shown: bool,
+}
+
+impl Notifications {
+    pub(crate) fn push(&mut self, level: NotificationLevel, text: String, time: u64) {
+        let id = self.next_id;
+        self.next_id += 1;
+        self.history.push(Notification {
+            id,
+            time,
+            level,
+            text,
+        });
+        self.active.push(id);
+        if self.history.len() > MAX_HISTORY {
+            let oldest = self.history.remove(0);
+            self.dismiss(oldest.id);
+        }
+    }
+
+    /// The toasts to show, oldest first.
+    pub(crate) fn active(&self) -> impl Iterator<Item = &Notification> {
+        self.history
+            .iter()
+            .filter(|notification| self.active.contains(&notification.id))
+    }
+
+    pub(crate) fn has_active(&self) -> bool {
+        !self.active.is_empty()
+    }
+
+    pub(crate) fn history(&self) -> &[Notification] {
+        &self.history
+    }
+
+    pub(crate) fn is_history_shown(&self) -> bool {
+        self.history_shown
+    }
+
+    pub(crate) fn dismiss(&mut self, id: usize) {
+        self.active.retain(|active| *active != id);
+    }
+
+    /// Hides the toasts that have been shown for `duration` seconds at time `now`.
+    pub(crate) fn expire(&mut self, now: u64, duration: u64) {
+        let expired: Vec<usize> = self
+            .active()
+            .filter(|notification| notification.time + duration <= now)
+            .map(|notification| notification.id)
+            .collect();
+        self.active.retain(|id| !expired.contains(id));
+    }
+
+    pub(crate) fn clear_history(&mut self) {
+        self.history.clear();
+        self.active.clear();
+    }
+
+    pub(crate) fn set_history_shown(&mut self, shown: bool) {
+        self.history_shown = shown;
+    }
+}
+
+/// Asks for expired toasts to be hidden, as long as there are any.
+pub(crate) fn notification_ticks() -> Subscription<GuiMessage> {
+    polling::every("notifications", TICK_INTERVAL, || {
+        Some(GuiMessage::NotificationUpd(NotificationMessage::Tick))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn texts<'a>(notifications: impl Iterator<Item = &'a Notification>) -> Vec<&'a str> {
+        notifications.map(|n| n.text.as_str()).collect()
+    }
+
+    #[test]
+    fn toasts_expire_after_their_duration() {
+        let mut notifications = Notifications::default();
+        notifications.push(NotificationLevel::Success, "first".to_string(), 10);
+        notifications.push(NotificationLevel::Warning, "second".to_string(), 12);
+
+        notifications.expire(13, 4);
+        assert_eq!(texts(notifications.active()), vec!["first", "second"]);
+        notifications.expire(14, 4);
+        assert_eq!(texts(notifications.active()), vec!["second"]);
+        notifications.expire(16, 4);
+        assert!(!notifications.has_active());
+        assert_eq!(
+            texts(notifications.history().iter()),
+            vec!["first", "second"]
+        );
+    }
+
+    #[test]
+    fn dismissed_toasts_stay_in_the_history() {
+        let mut notifications = Notifications::default();
+        notifications.push(NotificationLevel::Success, "first".to_string(), 10);
+        let id = notifications.history()[0].id;
+        notifications.dismiss(id);
+        assert!(!notifications.has_active());
+        assert_eq!(notifications.history().len(), 1);
+    }
+
+    #[test]
+    fn history_keeps_the_latest_notifications() {
+        let mut notifications = Notifications::default();
+        for i in 0..=MAX_HISTORY {
+            notifications.push(NotificationLevel::Success, i.to_string(), 0);
+        }
+        assert_eq!(notifications.history().len(), MAX_HISTORY);
+        assert_eq!(notifications.history()[0].text, "1");
+        assert_eq!(notifications.active().count(), MAX_HISTORY);
+    }
+}
@@ -0,0 +1,103 @@
+use iced::{
+    widget::{button, container, text, Column, Container, Row, Scrollable, Text},
+    Alignment, Element, Length,
+};
+
+use crate::{
+    app::message_handling::GuiMessage,
+    localisation::{tr, tr_args},
+    style::Sizes,
+    time_format::format_unix_time,
+};
+
+use super::{Notification, NotificationLevel, NotificationMessage, Notifications};
+
+/// The active toasts in the bottom right corner, leaving the rest of the window usable.
+pub(crate) fn toasts(notifications: &Notifications, sizes: Sizes) -> Element<'_, GuiMessage> {
+    let toasts = notifications
+        .active()
+        .fold(Column::new(), |column, notification| {
+            column.push(toast(notification, sizes))
+        })
+        .spacing(sizes.spacing)
+        .width(Length::Fixed(350.0));
+    Container::new(toasts)
+        .align_right(Length::Fill)
+        .align_bottom(Length::Fill)
+        .padding(sizes.spacing * 4.0)
+        .into()
+}
+
+fn toast(notification: &Notification, sizes: Sizes) -> Container<'_, GuiMessage> {
+    let dismiss_button =
+        button(sizes.text("x"))
+            .style(button::text)
+            .on_press(GuiMessage::NotificationUpd(NotificationMessage::Dismiss(
+                notification.id,
+            )));
+    let row = Row::new()
+        .push(level_marker(notification.level, sizes))
+        .push(sizes.text(&notification.text).width(Length::Fill))
+        .push(dismiss_button)
+        .align_y(Alignment::Center)
+        .spacing(sizes.spacing);
+    Container::new(row)
+        .style(container::bordered_box)
+        .padding(sizes.spacing * 2.0)
+        .width(Length::Fill)
+}
+
+fn level_marker<'a>(level: NotificationLevel, sizes: Sizes) -> Text<'a> {
+    match level {
+        NotificationLevel::Success => sizes.text("✓").style(text::success),
+        NotificationLevel::Warning => sizes.text("!").style(text::danger),
+    }
+}
+
+pub(crate) fn history(notifications: &Notifications, sizes: Sizes) -> Element<'_, GuiMessage> {
+    let entries = notifications.history();
+    let header = Row::new()
+        .push(sizes.text(tr_args(
+            "notification-history-title",
+            &[("count", &entries.len())],
+        )))
+        .push(button(sizes.text(tr("button-clear"))).on_press_maybe(
+            (!entries.is_empty()).then_some(GuiMessage::NotificationUpd(
+                NotificationMessage::ClearHistory,
+            )),
+        ))
+        .push(
+            button(sizes.text(tr("button-close"))).on_press(GuiMessage::NotificationUpd(
+                NotificationMessage::HideHistory,
+            )),
+        )
+        .align_y(Alignment::Center)
+        .spacing(sizes.spacing);
+
+    let list = entries
+        .iter()
+        .rev()
+        .fold(Column::new(), |column, notification| {
+            column.push(history_row(notification, sizes))
+        })
+        .spacing(sizes.spacing);
+    Column::new()
+        .push(header)
+        .push(Scrollable::new(list).height(Length::Fill))
+        .padding(sizes.spacing)
+        .spacing(sizes.spacing)
+        .into()
+}
+
+fn history_row(notification: &Notification, sizes: Sizes) -> Row<'_, GuiMessage> {
+    Row::new()
+        .push(
+            sizes
+                .text(format_unix_time(notification.time))
+                .style(text::secondary),
+        )
+        .push(level_marker(notification.level, sizes))
+        .push(sizes.text(&notification.text))
+        .align_y(Alignment::Center)
+        .spacing(sizes.spacing)
+}
@@ -0,0 +1,100 @@
+use std::{
+    future,
+    hash::Hash,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+    thread::{self, Thread},
+    time::{Duration, Instant},
+};
+
+use iced::{
+    futures::{executor::block_on, SinkExt, Stream},
+    Subscription,
+};
+
+use crate::app::message_handling::GuiMessage;
+
+/// Calls `poll` on a background thread after every `interval` and sends the messages it returns.
+///
+/// The thread stops as soon as the subscription is no longer active.
+pub(crate) fn every<I, P>(id: I, interval: Duration, poll: P) -> Subscription<GuiMessage>
+where
+    I: Hash + 'static,
+    P: FnMut() -> Option<GuiMessage> + Send + 'static,
+{
+    Subscription::run_with_id(id, stream(interval, poll))
+}
+
+fn stream<P>(interval: Duration, mut poll: P) -> impl Stream<Item = GuiMessage>
+where
+    P: FnMut() -> Option<GuiMessage> + Send + 'static,
+{
+    iced::stream::channel(1, move |mut output| async move {
+        let stopped = Arc::new(AtomicBool::new(false));
+        let thread_stopped = stopped.clone();
+        let thread = thread::spawn(move || loop {
+            let deadline = Instant::now() + interval;
+            while let Some(remaining) = deadline.checked_duration_since(Instant::now()) {
+                if thread_stopped.load(Ordering::Relaxed) {
+                    return;
+                }
+                thread::park_timeout(remaining);
+            }
+            if thread_stopped.load(Ordering::Relaxed) {
+                return;
+            }
+            if let Some(message) = poll() {
+                if block_on(output.send(message)).is_err() {
+                    return;
+                }
+            }
+        });
+        // The stream drops this future when the subscription ends, which stops the thread.
+        let _stop = StopOnDrop {
+            stopped,
+            thread: thread.thread().clone(),
+        };
+        future::pending::<()>().await;
+    })
+}
+
+struct StopOnDrop {
+    stopped: Arc<AtomicBool>,
+    thread: Thread,
+}
+
+impl Drop for StopOnDrop {
+    fn drop(&mut self) {
+        self.stopped.store(true, Ordering::Relaxed);
+        self.thread.unpark();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::AtomicUsize;
+
+    use iced::futures::StreamExt;
+
+    use super::*;
+
+    #[test]
+    fn polling_stops_with_the_stream() {
+        let polls = Arc::new(AtomicUsize::new(0));
+        let counter = polls.clone();
+        let mut stream = Box::pin(stream(Duration::from_millis(10), move || {
+            counter.fetch_add(1, Ordering::Relaxed);
+            Some(GuiMessage::AutosaveDrafts)
+        }));
+
+        assert!(block_on(stream.next()).is_some());
+        drop(stream);
+        thread::sleep(Duration::from_millis(30));
+        let polls_after_drop = polls.load(Ordering::Relaxed);
+        thread::sleep(Duration::from_millis(100));
+
+        assert_eq!(polls.load(Ordering::Relaxed), polls_after_drop);
+    }
+}
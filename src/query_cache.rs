@@ -0,0 +1,246 @@
+use std::sync::{Arc, Mutex, MutexGuard, PoisonError};
+
+use lorecore::{
+    errors::LoreCoreError,
+    sql::{
+        lore_database::LoreDatabase,
+        search_params::{
+            EntityColumnSearchParams, HistoryItemSearchParams, RelationshipSearchParams,
+            SqlSearchText,
+        },
+    },
+    types::*,
+};
+
+use crate::errors::LoreGuiError;
+
+const MAX_CACHED_READS_PER_TABLE: usize = 16;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Table {
+    Entities,
+    History,
+    Relationships,
+}
+
+impl Table {
+    pub(crate) const ALL: [Table; 3] = [Table::Entities, Table::History, Table::Relationships];
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) enum TextFilter {
+    Exact(String),
+    Partial(String),
+}
+
+impl TextFilter {
+    fn to_search_text(&self) -> SqlSearchText {
+        match self {
+            TextFilter::Exact(text) => SqlSearchText::exact(text),
+            TextFilter::Partial(text) => SqlSearchText::partial(text),
+        }
+    }
+}
+
+type EntityColumnKey = (Option<TextFilter>, Option<TextFilter>);
+type HistoryItemKey = (Option<Year>, Option<Day>, Option<Timestamp>);
+type RelationshipKey = (Option<TextFilter>, Option<TextFilter>);
+
+/// The rows read from one table, keyed by the search parameters they were read with.
+///
+/// The version is increased whenever the table changes, so that reads started before the change are not cached afterwards.
+struct CachedReads<K, T> {
+    version: u64,
+    reads: Vec<(K, Arc<Vec<T>>)>,
+}
+
+impl<K, T> Default for CachedReads<K, T> {
+    fn default() -> Self {
+        CachedReads {
+            version: 0,
+            reads: vec![],
+        }
+    }
+}
+
+impl<K: PartialEq, T> CachedReads<K, T> {
+    fn get(&self, key: &K) -> Option<Arc<Vec<T>>> {
+        self.reads
+            .iter()
+            .find(|(k, _)| k == key)
+            .map(|(_, rows)| rows.clone())
+    }
+
+    fn insert(&mut self, version: u64, key: K, rows: Arc<Vec<T>>) {
+        if version != self.version {
+            return;
+        }
+        if self.reads.len() >= MAX_CACHED_READS_PER_TABLE {
+            self.reads.remove(0);
+        }
+        self.reads.push((key, rows));
+    }
+
+    fn invalidate(&mut self) {
+        self.version += 1;
+        self.reads.clear();
+    }
+}
+
+#[derive(Default)]
+pub(crate) struct QueryCache {
+    entity_columns: CachedReads<EntityColumnKey, EntityColumn>,
+    history_items: CachedReads<HistoryItemKey, HistoryItem>,
+    relationships: CachedReads<RelationshipKey, EntityRelationship>,
+}
+
+impl QueryCache {
+    pub(crate) fn invalidate(&mut self, table: Table) {
+        match table {
+            Table::Entities => self.entity_columns.invalidate(),
+            Table::History => self.history_items.invalidate(),
+            Table::Relationships => self.relationships.invalidate(),
+        }
+    }
+}
+
+/// A query cache that is shared between the GUI and the background tasks reading from the database.
+pub(crate) type SharedQueryCache = Arc<Mutex<QueryCache>>;
+
+/// Reads from a database, answering repeated reads with the same search parameters from the cache.
+pub(crate) struct CachedReader<'a> {
+    db: &'a LoreDatabase,
+    cache: &'a Mutex<QueryCache>,
+}
+
+impl<'a> CachedReader<'a> {
+    pub(crate) fn new(db: &'a LoreDatabase, cache: &'a Mutex<QueryCache>) -> Self {
+        CachedReader { db, cache }
+    }
+
+    fn lock(&self) -> MutexGuard<'_, QueryCache> {
+        self.cache.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    fn cached<K: PartialEq, T>(
+        &self,
+        reads: fn(&mut QueryCache) -> &mut CachedReads<K, T>,
+        key: K,
+        read: impl FnOnce(&K) -> Result<Vec<T>, LoreCoreError>,
+    ) -> Result<Arc<Vec<T>>, LoreGuiError> {
+        let version = {
+            let mut cache = self.lock();
+            let reads = reads(&mut cache);
+            if let Some(rows) = reads.get(&key) {
+                return Ok(rows);
+            }
+            reads.version
+        };
+        let rows = Arc::new(read(&key)?);
+        reads(&mut self.lock()).insert(version, key, rows.clone());
+        Ok(rows)
+    }
+
+    pub(crate) fn read_entity_columns(
+        &self,
+        label: Option<TextFilter>,
+        descriptor: Option<TextFilter>,
+    ) -> Result<Arc<Vec<EntityColumn>>, LoreGuiError> {
+        self.cached(
+            |cache| &mut cache.entity_columns,
+            (label, descriptor),
+            |(label, descriptor)| {
+                let search_params = EntityColumnSearchParams::new(
+                    label.as_ref().map(TextFilter::to_search_text),
+                    descriptor.as_ref().map(TextFilter::to_search_text),
+                );
+                self.db.read_entity_columns(search_params)
+            },
+        )
+    }
+
+    pub(crate) fn read_history_items(
+        &self,
+        year: Option<Year>,
+        day: Option<Day>,
+        timestamp: Option<Timestamp>,
+    ) -> Result<Arc<Vec<HistoryItem>>, LoreGuiError> {
+        self.cached(
+            |cache| &mut cache.history_items,
+            (year, day, timestamp),
+            |(year, day, timestamp)| {
+                let search_params = HistoryItemSearchParams::new(*year, *day, *timestamp, None);
+                self.db.read_history_items(search_params)
+            },
+        )
+    }
+
+    pub(crate) fn read_relationships(
+        &self,
+        parent: Option<TextFilter>,
+        child: Option<TextFilter>,
+    ) -> Result<Arc<Vec<EntityRelationship>>, LoreGuiError> {
+        self.cached(
+            |cache| &mut cache.relationships,
+            (parent, child),
+            |(parent, child)| {
+                let search_params = RelationshipSearchParams::new(
+                    parent.as_ref().map(TextFilter::to_search_text),
+                    child.as_ref().map(TextFilter::to_search_text),
+                );
+                self.db.read_relationships(search_params)
+            },
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::{example_database, example_labels};
+
+    #[test]
+    fn repeated_read_is_answered_from_cache() {
+        let db = example_database();
+        let cache = Mutex::default();
+        let reader = CachedReader::new(&db, &cache);
+
+        let first = reader.read_entity_columns(None, None).unwrap();
+        let second = reader.read_entity_columns(None, None).unwrap();
+        assert!(Arc::ptr_eq(&first, &second));
+
+        let filtered = reader
+            .read_entity_columns(
+                Some(TextFilter::Exact(example_labels()[0].to_str().to_string())),
+                None,
+            )
+            .unwrap();
+        assert!(!Arc::ptr_eq(&first, &filtered));
+    }
+
+    #[test]
+    fn invalidated_table_is_read_again() {
+        let db = example_database();
+        let cache = Mutex::default();
+        let reader = CachedReader::new(&db, &cache);
+        let before = reader.read_entity_columns(None, None).unwrap();
+        let relationships_before = reader.read_relationships(None, None).unwrap();
+
+        db.delete_entity(example_labels()[0].clone()).unwrap();
+        cache.lock().unwrap().invalidate(Table::Entities);
+
+        let after = reader.read_entity_columns(None, None).unwrap();
+        assert!(after.len() < before.len());
+        let relationships_after = reader.read_relationships(None, None).unwrap();
+        assert!(Arc::ptr_eq(&relationships_before, &relationships_after));
+    }
+
+    #[test]
+    fn reads_started_before_a_change_are_not_cached() {
+        let mut cached_reads = CachedReads::<u8, u8>::default();
+        let version = cached_reads.version;
+        cached_reads.invalidate();
+        cached_reads.insert(version, 0, Arc::new(vec![1]));
+        assert!(cached_reads.get(&0).is_none());
+    }
+}
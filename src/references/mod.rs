@@ -0,0 +1,117 @@
+use iced::Theme;
+use lorecore::{extractions::extract_labels, types::*};
+
+use crate::{errors::LoreGuiError, query_cache::CachedReader};
+
+pub(crate) mod widget;
+
+const REFERENCE_COMMAND: &str = "\\reference";
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) enum Segment<'a> {
+    Text(&'a str),
+    /// The label inside the braces of `\reference{label}`.
+    Reference(&'a str),
+}
+
+/// Splits a text at its `\reference {label}` commands. Commands without braces stay plain text.
+pub(crate) fn parse(text: &str) -> Vec<Segment<'_>> {
+    let mut segments = vec![];
+    let mut plain_start = 0;
+    let mut search_start = 0;
+    while let Some(found) = text[search_start..].find(REFERENCE_COMMAND) {
+        let command_start = search_start + found;
+        let command_end = command_start + REFERENCE_COMMAND.len();
+        let argument = text[command_end..].trim_start();
+        let label_start = text.len() - argument.len() + 1;
+        let label_length = argument
+            .strip_prefix('{')
+            .and_then(|argument| argument.find('}'));
+        match label_length {
+            Some(length) => {
+                if command_start > plain_start {
+                    segments.push(Segment::Text(&text[plain_start..command_start]));
+                }
+                let label = &text[label_start..label_start + length];
+                segments.push(Segment::Reference(label.trim()));
+                plain_start = label_start + length + 1;
+                search_start = plain_start;
+            }
+            None => search_start = command_end,
+        }
+    }
+    if plain_start < text.len() {
+        segments.push(Segment::Text(&text[plain_start..]));
+    }
+    segments
+}
+
+/// Whether descriptions and history content are shown with clickable references instead of an editor.
+#[derive(Debug, Default)]
+pub(super) struct ReadMode {
+    active: bool,
+    known_labels: Vec<Label>,
+}
+
+/// The read mode together with the theme the references are coloured by.
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct Reading<'a> {
+    pub(crate) read_mode: &'a ReadMode,
+    pub(crate) theme: &'a Theme,
+}
+
+impl ReadMode {
+    pub(super) fn is_active(&self) -> bool {
+        self.active
+    }
+
+    pub(super) fn toggle(&mut self) {
+        self.active = !self.active;
+    }
+
+    pub(super) fn exists(&self, label: &str) -> bool {
+        self.known_labels
+            .iter()
+            .any(|known| known.to_str() == label)
+    }
+
+    /// Reads the labels that references can point to, which is only needed while reading.
+    pub(super) fn refresh(&mut self, reader: Option<&CachedReader>) -> Result<(), LoreGuiError> {
+        self.known_labels = match reader {
+            Some(reader) if self.active => extract_labels(&reader.read_entity_columns(None, None)?),
+            _ => vec![],
+        };
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn references_are_split_from_text() {
+        let segments = parse("Born in \\reference {Town} to \\reference{Parent}.");
+        assert_eq!(
+            segments,
+            vec![
+                Segment::Text("Born in "),
+                Segment::Reference("Town"),
+                Segment::Text(" to "),
+                Segment::Reference("Parent"),
+                Segment::Text("."),
+            ]
+        );
+    }
+
+    #[test]
+    fn incomplete_references_stay_text() {
+        let text = "A \\reference without braces and \\reference{unclosed";
+        assert_eq!(parse(text), vec![Segment::Text(text)]);
+        assert_eq!(parse(""), vec![]);
+        assert_eq!(
+            parse("\\reference{ label }"),
+            vec![Segment::Reference("label")]
+        );
+    }
+}
@@ -0,0 +1,27 @@
+use iced::{
+    widget::{rich_text, span, text::Span},
+    Element, Length,
+};
+
+use crate::{app::message_handling::GuiMessage, global_search::SearchTarget, style::Sizes};
+
+use super::{parse, Reading, Segment};
+
+/// The text with its references as links to their entities, and references to missing entities highlighted.
+pub(crate) fn view<'a>(text: &str, reading: Reading, sizes: Sizes) -> Element<'a, GuiMessage> {
+    let palette = reading.theme.extended_palette();
+    let spans: Vec<Span<'a, GuiMessage>> = parse(text)
+        .into_iter()
+        .map(|segment| match segment {
+            Segment::Text(text) => span(text.to_string()),
+            Segment::Reference(label) if reading.read_mode.exists(label) => span(label.to_string())
+                .color(palette.primary.strong.color)
+                .underline(true)
+                .link(GuiMessage::JumpTo(SearchTarget::Entity(label.into(), None))),
+            Segment::Reference(label) => span(label.to_string())
+                .color(palette.danger.base.color)
+                .strikethrough(true),
+        })
+        .collect();
+    rich_text(spans).size(sizes.text).width(Length::Fill).into()
+}
@@ -1,24 +1,91 @@
 use lorecore::{
-    sql::{
-        lore_database::LoreDatabase,
-        relationship::{extract_children, extract_parents, extract_roles},
-        search_params::{RelationshipSearchParams, SqlSearchText},
-    },
+    sql::relationship::{extract_children, extract_parents, extract_roles},
     types::*,
 };
 
 use crate::{
-    db_col_view::{entry::DbColViewEntry, state::DbColViewState, ColViewMes},
+    db_col_view::{
+        entry::DbColViewEntry,
+        state::{combined_search_matches, DbColViewState},
+        ColViewMes,
+    },
     dialog::change_role::ChangeRoleData,
     errors::LoreGuiError,
+    query_cache::{CachedReader, TextFilter},
 };
 
 pub(crate) mod widget;
 
+/// Everything needed to read the contents of the relationship view, detached from the view state so that it can run in the background.
+#[derive(Debug, Clone, PartialEq)]
+pub(super) struct RelationshipViewQuery {
+    parent_search_text: Option<String>,
+    child_search_text: Option<String>,
+    parent: Option<Parent>,
+    child: Option<Child>,
+}
+
+#[derive(Debug, Clone)]
+pub(super) struct RelationshipViewData {
+    parents: Vec<Parent>,
+    children: Vec<Child>,
+    roles: Vec<Role>,
+}
+
+impl RelationshipViewQuery {
+    pub(super) fn run(&self, reader: &CachedReader) -> Result<RelationshipViewData, LoreGuiError> {
+        Ok(RelationshipViewData {
+            parents: self.read_parents(reader)?,
+            children: self.read_children(reader)?,
+            roles: self.read_roles(reader)?,
+        })
+    }
+
+    fn read_parents(&self, reader: &CachedReader) -> Result<Vec<Parent>, LoreGuiError> {
+        let child = self
+            .child
+            .as_ref()
+            .map(|c| TextFilter::Exact(c.to_str().to_string()));
+        let parent_search_text = self.parent_search_text.clone().map(TextFilter::Partial);
+        let relationships = reader.read_relationships(parent_search_text, child)?;
+        let parents = extract_parents(&relationships);
+        Ok(parents)
+    }
+
+    fn read_children(&self, reader: &CachedReader) -> Result<Vec<Child>, LoreGuiError> {
+        let parent = self
+            .parent
+            .as_ref()
+            .map(|p| TextFilter::Exact(p.to_str().to_string()));
+        let child_search_text = self.child_search_text.clone().map(TextFilter::Partial);
+        let relationships = reader.read_relationships(parent, child_search_text)?;
+        let children = extract_children(&relationships);
+        Ok(children)
+    }
+
+    fn read_roles(&self, reader: &CachedReader) -> Result<Vec<Role>, LoreGuiError> {
+        let parent = match self.parent.as_ref() {
+            Some(parent) => parent,
+            None => return Ok(vec![]),
+        };
+        let child = match self.child.as_ref() {
+            Some(child) => child,
+            None => return Ok(vec![]),
+        };
+        let relationships = reader.read_relationships(
+            Some(TextFilter::Exact(parent.to_str().to_string())),
+            Some(TextFilter::Exact(child.to_str().to_string())),
+        )?;
+        let roles = extract_roles(&relationships);
+        Ok(roles)
+    }
+}
+
 pub(super) struct RelationshipViewState {
     pub(super) parent_view_state: DbColViewState<Parent>,
     pub(super) child_view_state: DbColViewState<Child>,
     pub(super) role_view_state: DbColViewState<Role>,
+    last_query: Option<RelationshipViewQuery>,
 }
 
 #[derive(Debug, Clone)]
@@ -37,74 +104,68 @@ impl RelationshipViewState {
             parent_view_state: DbColViewState::new(vec![], true),
             child_view_state: DbColViewState::new(vec![], true),
             role_view_state: DbColViewState::default(),
+            last_query: None,
         }
     }
 
-    pub(super) fn get_current_parents(
-        &self,
-        db: &Option<LoreDatabase>,
-    ) -> Result<Vec<Parent>, LoreGuiError> {
-        let db = match db {
-            Some(db) => db,
-            None => return Ok(vec![]),
-        };
-        let child = self
-            .get_selected_child()
-            .map(|c| SqlSearchText::exact(c.to_str()));
-        let parent_search_text = self
-            .parent_view_state
-            .get_search_text()
-            .map(SqlSearchText::partial);
-        let search_params = RelationshipSearchParams::new(parent_search_text, child);
-        let relationships = db.read_relationships(search_params)?;
-        let parents = extract_parents(&relationships);
-        Ok(parents)
+    pub(super) fn query(&self) -> RelationshipViewQuery {
+        RelationshipViewQuery {
+            parent_search_text: self.parent_view_state.get_search_text().map(String::from),
+            child_search_text: self.child_view_state.get_search_text().map(String::from),
+            parent: self.get_selected_parent(),
+            child: self.get_selected_child(),
+        }
     }
 
-    pub(super) fn get_current_children(
-        &self,
-        db: &Option<LoreDatabase>,
-    ) -> Result<Vec<Child>, LoreGuiError> {
-        let db = match db {
-            Some(db) => db,
-            None => return Ok(vec![]),
-        };
-        let parent = self
-            .get_selected_parent()
-            .map(|p| SqlSearchText::exact(p.to_str()));
-        let child_search_text = self
-            .child_view_state
-            .get_search_text()
-            .map(SqlSearchText::partial);
-        let search_params = RelationshipSearchParams::new(parent, child_search_text);
-        let relationships = db.read_relationships(search_params)?;
-        let children = extract_children(&relationships);
-        Ok(children)
+    /// The query to read the view with, unless the view has already been read with it.
+    pub(super) fn query_if_changed(&mut self) -> Option<RelationshipViewQuery> {
+        let query = self.query();
+        if self.last_query.as_ref() == Some(&query) {
+            return None;
+        }
+        self.last_query = Some(query.clone());
+        Some(query)
     }
 
-    pub(super) fn get_current_roles(
-        &self,
-        db: &Option<LoreDatabase>,
-    ) -> Result<Vec<Role>, LoreGuiError> {
-        let db = match db {
-            Some(db) => db,
-            None => return Ok(vec![]),
-        };
-        let parent = match self.get_selected_parent() {
-            Some(parent) => parent,
-            None => return Ok(vec![]),
-        };
-        let child = match self.get_selected_child() {
-            Some(child) => child,
-            None => return Ok(vec![]),
-        };
-        let search_params = RelationshipSearchParams::new(
-            Some(SqlSearchText::exact(parent.to_str())),
-            Some(SqlSearchText::exact(child.to_str())),
-        );
-        let relationships = db.read_relationships(search_params)?;
-        let roles = extract_roles(&relationships);
-        Ok(roles)
+    /// Makes the next refresh read the view again, even if the query did not change.
+    pub(super) fn invalidate(&mut self) {
+        self.last_query = None;
+    }
+
+    pub(super) fn set_loading(&mut self, loading: bool) {
+        self.parent_view_state.set_loading(loading);
+        self.child_view_state.set_loading(loading);
+        self.role_view_state.set_loading(loading);
+    }
+
+    /// A read that is still running when the tab is put aside will never be applied, so it has to be repeated later.
+    pub(super) fn abandon_loading(&mut self) {
+        if self.parent_view_state.is_loading() {
+            self.set_loading(false);
+            self.invalidate();
+        }
+    }
+
+    pub(super) fn apply(&mut self, data: RelationshipViewData) {
+        let parents = data.parents.into_iter().map(DbColViewEntry::from).collect();
+        self.parent_view_state.set_entries(parents);
+        let children = data
+            .children
+            .into_iter()
+            .map(DbColViewEntry::from)
+            .collect();
+        self.child_view_state.set_entries(children);
+        let roles = data.roles.into_iter().map(DbColViewEntry::from).collect();
+        self.role_view_state.set_entries(roles);
+    }
+
+    /// The entries found in the columns with a search text.
+    pub(super) fn search_matches(&self) -> Option<usize> {
+        combined_search_matches([
+            self.parent_view_state.search_matches(),
+            self.child_view_state.search_matches(),
+            self.role_view_state.search_matches(),
+        ])
     }
 
     pub(super) fn get_selected_parent(&self) -> Option<Parent> {
@@ -1,4 +1,3 @@
-use iced::widget::button;
 use iced::Alignment;
 use iced::{
     widget::{Column, Row},
@@ -6,23 +5,31 @@ use iced::{
 };
 use lorecore::types::*;
 
-use crate::{app::message_handling::GuiMessage, db_col_view, dialog::change_role::ChangeRoleData};
+use crate::{
+    app::message_handling::GuiMessage,
+    commands::{button_row, Command},
+    db_col_view,
+    dialog::change_role::ChangeRoleData,
+    localisation::tr,
+    style::Sizes,
+};
 
 use super::{RelationshipViewMessage, RelationshipViewState};
 
-pub(crate) fn new(state: &RelationshipViewState) -> Element<'_, GuiMessage> {
+pub(crate) fn new(
+    state: &RelationshipViewState,
+    focused_column: usize,
+    sizes: Sizes,
+) -> Element<'_, GuiMessage> {
     Column::new()
-        .push(buttons(state))
-        .push(col_views(state))
+        .push(button_row(&commands(state), sizes))
+        .push(col_views(state, focused_column, sizes))
         .into()
 }
 
-fn buttons(state: &RelationshipViewState) -> Row<'_, GuiMessage> {
-    let new_relationship = button("New Relationship").on_press(GuiMessage::RelationshipViewUpd(
-        RelationshipViewMessage::NewRelationship,
-    ));
-    let mut change_role = button("Change Role");
-    let mut delete_relationship = button("Delete Relationship");
+pub(crate) fn commands(state: &RelationshipViewState) -> Vec<Command> {
+    let mut change_role = None;
+    let mut delete_relationship = None;
     if let (Some(parent), Some(child)) = (state.get_selected_parent(), state.get_selected_child()) {
         let role = state.get_selected_role().unwrap_or("".into());
         let relationship = EntityRelationship {
@@ -31,37 +38,49 @@ fn buttons(state: &RelationshipViewState) -> Row<'_, GuiMessage> {
             role,
         };
         let change_role_data = ChangeRoleData::new(relationship.clone());
-        change_role = change_role.on_press(GuiMessage::RelationshipViewUpd(
+        change_role = Some(GuiMessage::RelationshipViewUpd(
             RelationshipViewMessage::ChangeRole(change_role_data),
         ));
-        delete_relationship = delete_relationship.on_press(GuiMessage::RelationshipViewUpd(
+        delete_relationship = Some(GuiMessage::RelationshipViewUpd(
             RelationshipViewMessage::DeleteRelationship(relationship),
         ));
     }
-    Row::new()
-        .push(new_relationship)
-        .push(change_role)
-        .push(delete_relationship)
-        .spacing(5)
-        .padding(5)
+    vec![
+        Command::enabled(
+            &tr("command-new-relationship"),
+            GuiMessage::RelationshipViewUpd(RelationshipViewMessage::NewRelationship),
+        ),
+        Command::new(&tr("command-change-role"), change_role),
+        Command::new(&tr("command-delete-relationship"), delete_relationship),
+    ]
 }
 
-fn col_views(state: &RelationshipViewState) -> Row<'_, GuiMessage> {
+fn col_views(
+    state: &RelationshipViewState,
+    focused_column: usize,
+    sizes: Sizes,
+) -> Row<'_, GuiMessage> {
     Row::new()
         .push(db_col_view::widget::new(
-            "Parent",
+            tr("column-parent"),
             |m| GuiMessage::RelationshipViewUpd(RelationshipViewMessage::ParentViewUpdate(m)),
             &state.parent_view_state,
+            focused_column == 0,
+            sizes,
         ))
         .push(db_col_view::widget::new(
-            "Child",
+            tr("column-child"),
             |m| GuiMessage::RelationshipViewUpd(RelationshipViewMessage::ChildViewUpdate(m)),
             &state.child_view_state,
+            focused_column == 1,
+            sizes,
         ))
         .push(db_col_view::widget::new(
-            "Role",
+            tr("column-role"),
             |m| GuiMessage::RelationshipViewUpd(RelationshipViewMessage::RoleViewUpdate(m)),
             &state.role_view_state,
+            focused_column == 2,
+            sizes,
         ))
         .align_y(Alignment::Start)
         .width(Length::Fill)
@@ -0,0 +1,541 @@
+use lorecore::{
+    sql::{
+        lore_database::LoreDatabase,
+        search_params::{EntityColumnSearchParams, HistoryItemSearchParams, SqlSearchText},
+    },
+    types::*,
+};
+use std::{mem, path::PathBuf, time::Duration};
+
+use rusqlite::{params, Connection, Transaction};
+
+use crate::{errors::LoreGuiError, global_search::Snippet, localisation::tr_args};
+
+const MATCH_START: char = '\u{2}';
+const MATCH_END: char = '\u{3}';
+/// Number of tokens in a snippet.
+const SNIPPET_TOKENS: usize = 12;
+
+/// A full-text index of the descriptions and history item contents of a lore database.
+///
+/// It lives in a separate SQLite file next to the database, so that the lore database itself is never touched.
+pub(crate) struct SearchIndex {
+    connection: Connection,
+}
+
+/// A description or history item content matching a search, in the order of relevance.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) enum IndexHit {
+    Description {
+        label: Label,
+        descriptor: Descriptor,
+        snippet: Snippet,
+    },
+    HistoryContent {
+        year: String,
+        timestamp: String,
+        snippet: Snippet,
+    },
+}
+
+fn index_error(e: rusqlite::Error) -> LoreGuiError {
+    LoreGuiError::FileError(tr_args("error-search-index", &[("error", &e)]))
+}
+
+pub(crate) fn index_path(database_path: &str) -> String {
+    format!("{}.search-index", database_path)
+}
+
+/// Turns user input into an FTS5 query.
+///
+/// Text in double quotes is searched as a phrase, words ending in * as prefixes, and all other words must occur anywhere.
+pub(crate) fn fts_query(input: &str) -> Option<String> {
+    let mut terms = vec![];
+    for (i, part) in input.split('"').enumerate() {
+        let is_phrase = i % 2 == 1;
+        if is_phrase {
+            if !part.trim().is_empty() {
+                terms.push(format!("\"{}\"", part.trim()));
+            }
+            continue;
+        }
+        for word in part.split_whitespace() {
+            let (word, prefix) = match word.strip_suffix('*') {
+                Some(word) => (word, "*"),
+                None => (word, ""),
+            };
+            if !word.is_empty() {
+                terms.push(format!("\"{}\"{}", word, prefix));
+            }
+        }
+    }
+    if terms.is_empty() {
+        None
+    } else {
+        Some(terms.join(" "))
+    }
+}
+
+/// Splits a snippet produced by FTS5 at its first highlighted match.
+fn parse_snippet(marked: &str) -> Snippet {
+    let flat = |text: &str| -> String {
+        text.chars()
+            .filter(|c| *c != MATCH_START && *c != MATCH_END)
+            .map(|c| if c.is_whitespace() { ' ' } else { c })
+            .collect()
+    };
+    let (before, rest) = marked.split_once(MATCH_START).unwrap_or((marked, ""));
+    let (matched, after) = rest.split_once(MATCH_END).unwrap_or((rest, ""));
+    Snippet {
+        before: flat(before),
+        matched: flat(matched),
+        after: flat(after),
+    }
+}
+
+impl SearchIndex {
+    pub(crate) fn open(database_path: &str) -> Result<Self, LoreGuiError> {
+        let connection = Connection::open(index_path(database_path)).map_err(index_error)?;
+        // Searches and updates of the index run in the background and may overlap.
+        connection
+            .busy_timeout(Duration::from_secs(5))
+            .map_err(index_error)?;
+        connection
+            .execute_batch(
+                "CREATE VIRTUAL TABLE IF NOT EXISTS descriptions
+                    USING fts5(label UNINDEXED, descriptor UNINDEXED, body);
+                CREATE VIRTUAL TABLE IF NOT EXISTS history_items
+                    USING fts5(year UNINDEXED, timestamp UNINDEXED, body);",
+            )
+            .map_err(index_error)?;
+        Ok(SearchIndex { connection })
+    }
+
+    /// Opens the index of `db` and fills it from scratch.
+    pub(crate) fn build(db: &LoreDatabase) -> Result<Self, LoreGuiError> {
+        let mut index = SearchIndex::open(&db.path_as_string())?;
+        index.rebuild(db)?;
+        Ok(index)
+    }
+
+    pub(crate) fn rebuild(&mut self, db: &LoreDatabase) -> Result<(), LoreGuiError> {
+        let columns = db.read_entity_columns(EntityColumnSearchParams::new(None, None))?;
+        let items = db.read_history_items(HistoryItemSearchParams::new(None, None, None, None))?;
+        let transaction = self.connection.transaction().map_err(index_error)?;
+        transaction
+            .execute_batch("DELETE FROM descriptions; DELETE FROM history_items;")
+            .map_err(index_error)?;
+        insert_descriptions(&transaction, &columns)?;
+        insert_history_items(&transaction, &items)?;
+        transaction.commit().map_err(index_error)
+    }
+
+    /// Indexes the descriptions of `labels` again.
+    pub(crate) fn update_entities(
+        &mut self,
+        db: &LoreDatabase,
+        labels: &[Label],
+    ) -> Result<(), LoreGuiError> {
+        let transaction = self.connection.transaction().map_err(index_error)?;
+        for label in labels {
+            transaction
+                .execute(
+                    "DELETE FROM descriptions WHERE label = ?1",
+                    params![label.to_str()],
+                )
+                .map_err(index_error)?;
+            let search_params =
+                EntityColumnSearchParams::new(Some(SqlSearchText::exact(label.to_str())), None);
+            insert_descriptions(&transaction, &db.read_entity_columns(search_params)?)?;
+        }
+        transaction.commit().map_err(index_error)
+    }
+
+    /// Indexes the contents of the history items at `timestamps` again.
+    pub(crate) fn update_history_items(
+        &mut self,
+        db: &LoreDatabase,
+        timestamps: &[Timestamp],
+    ) -> Result<(), LoreGuiError> {
+        let transaction = self.connection.transaction().map_err(index_error)?;
+        for timestamp in timestamps {
+            transaction
+                .execute(
+                    "DELETE FROM history_items WHERE timestamp = ?1",
+                    params![timestamp.to_string()],
+                )
+                .map_err(index_error)?;
+            let search_params = HistoryItemSearchParams::new(None, None, Some(*timestamp), None);
+            insert_history_items(&transaction, &db.read_history_items(search_params)?)?;
+        }
+        transaction.commit().map_err(index_error)
+    }
+
+    /// The best matching descriptions and history item contents, most relevant first.
+    pub(crate) fn search(&self, input: &str, limit: usize) -> Result<Vec<IndexHit>, LoreGuiError> {
+        let query = match fts_query(input) {
+            Some(query) => query,
+            None => return Ok(vec![]),
+        };
+        let snippet = |table: &str| {
+            format!(
+                "snippet({}, 2, char(2), char(3), '…', {})",
+                table, SNIPPET_TOKENS
+            )
+        };
+        // Both tables are ranked with bm25, so their hits can be merged into one ranking.
+        let sql = format!(
+            "SELECT 0, label, descriptor, {}, rank AS relevance FROM descriptions WHERE descriptions MATCH ?1
+            UNION ALL
+            SELECT 1, year, timestamp, {}, rank AS relevance FROM history_items WHERE history_items MATCH ?1
+            ORDER BY relevance LIMIT ?2",
+            snippet("descriptions"),
+            snippet("history_items")
+        );
+        let mut statement = self.connection.prepare(&sql).map_err(index_error)?;
+        let rows = statement
+            .query_map(params![query, limit as i64], |row| {
+                let snippet = parse_snippet(&row.get::<_, String>(3)?);
+                Ok(match row.get::<_, i64>(0)? {
+                    0 => IndexHit::Description {
+                        label: row.get::<_, String>(1)?.into(),
+                        descriptor: row.get::<_, String>(2)?.into(),
+                        snippet,
+                    },
+                    _ => IndexHit::HistoryContent {
+                        year: row.get(1)?,
+                        timestamp: row.get(2)?,
+                        snippet,
+                    },
+                })
+            })
+            .map_err(index_error)?;
+        let mut hits = vec![];
+        for row in rows {
+            hits.push(row.map_err(index_error)?);
+        }
+        Ok(hits)
+    }
+}
+
+/// Brings the index of a database up to date. Runs in the background.
+#[derive(Debug, Clone)]
+pub(crate) enum IndexUpdate {
+    Rebuild,
+    Entries {
+        labels: Vec<Label>,
+        timestamps: Vec<Timestamp>,
+    },
+}
+
+impl IndexUpdate {
+    pub(crate) fn run(&self, database_path: &str) -> Result<(), LoreGuiError> {
+        let db = LoreDatabase::open(PathBuf::from(database_path))?;
+        match self {
+            IndexUpdate::Rebuild => SearchIndex::build(&db).map(|_| ()),
+            IndexUpdate::Entries { labels, timestamps } => {
+                let mut index = SearchIndex::open(database_path)?;
+                index.update_entities(&db, labels)?;
+                index.update_history_items(&db, timestamps)
+            }
+        }
+    }
+}
+
+/// The state of the search index of a database together with the writes it has not yet been updated for.
+#[derive(Default)]
+pub(crate) struct SearchIndexState {
+    built: bool,
+    updating: bool,
+    labels: Vec<Label>,
+    timestamps: Vec<Timestamp>,
+    everything: bool,
+}
+
+impl SearchIndexState {
+    /// The index is built by the first update.
+    pub(crate) fn unbuilt() -> Self {
+        SearchIndexState {
+            everything: true,
+            ..Default::default()
+        }
+    }
+
+    #[cfg(test)]
+    pub(crate) fn build(db: &LoreDatabase) -> Self {
+        let mut state = SearchIndexState::unbuilt();
+        state.update_now(db);
+        state
+    }
+
+    /// The database path to open the index with, if the index is up to date.
+    ///
+    /// Otherwise searches fall back to reading the whole database.
+    pub(crate) fn database_path(&self, db: &LoreDatabase) -> Option<String> {
+        let pending = self.everything || !self.labels.is_empty() || !self.timestamps.is_empty();
+        (self.built && !self.updating && !pending).then(|| db.path_as_string())
+    }
+
+    pub(crate) fn record(&mut self, labels: &[Label], timestamps: &[Timestamp]) {
+        for label in labels {
+            if !self.labels.contains(label) {
+                self.labels.push(label.clone());
+            }
+        }
+        for timestamp in timestamps {
+            if !self.timestamps.contains(timestamp) {
+                self.timestamps.push(*timestamp);
+            }
+        }
+    }
+
+    /// Used for writes whose extent is unknown, such as changes by other programs.
+    pub(crate) fn record_everything(&mut self) {
+        self.everything = true;
+    }
+
+    /// The update for the recorded writes, unless one is still running.
+    ///
+    /// An index that failed to build or update is only used again once it has been rebuilt.
+    pub(crate) fn next_update(&mut self) -> Option<IndexUpdate> {
+        if self.updating {
+            return None;
+        }
+        let labels = mem::take(&mut self.labels);
+        let timestamps = mem::take(&mut self.timestamps);
+        let update = if mem::take(&mut self.everything) {
+            IndexUpdate::Rebuild
+        } else if self.built && !(labels.is_empty() && timestamps.is_empty()) {
+            IndexUpdate::Entries { labels, timestamps }
+        } else {
+            return None;
+        };
+        self.updating = true;
+        Some(update)
+    }
+
+    pub(crate) fn update_finished(&mut self, succeeded: bool) {
+        self.updating = false;
+        self.built = succeeded;
+    }
+
+    #[cfg(test)]
+    pub(crate) fn update_now(&mut self, db: &LoreDatabase) {
+        if let Some(update) = self.next_update() {
+            let succeeded = update.run(&db.path_as_string()).is_ok();
+            self.update_finished(succeeded);
+        }
+    }
+}
+
+fn insert_descriptions(
+    transaction: &Transaction,
+    columns: &[EntityColumn],
+) -> Result<(), LoreGuiError> {
+    let mut statement = transaction
+        .prepare("INSERT INTO descriptions (label, descriptor, body) VALUES (?1, ?2, ?3)")
+        .map_err(index_error)?;
+    for col in columns {
+        statement
+            .execute(params![
+                col.label.to_str(),
+                col.descriptor.to_str(),
+                col.description.to_str()
+            ])
+            .map_err(index_error)?;
+    }
+    Ok(())
+}
+
+fn insert_history_items(
+    transaction: &Transaction,
+    items: &[HistoryItem],
+) -> Result<(), LoreGuiError> {
+    let mut statement = transaction
+        .prepare("INSERT INTO history_items (year, timestamp, body) VALUES (?1, ?2, ?3)")
+        .map_err(index_error)?;
+    for item in items {
+        statement
+            .execute(params![
+                item.year.to_string(),
+                item.timestamp.to_string(),
+                item.content.to_str()
+            ])
+            .map_err(index_error)?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::{example_database, example_descriptors, example_labels};
+
+    fn described(hits: &[IndexHit]) -> Vec<(Label, Descriptor)> {
+        hits.iter()
+            .filter_map(|hit| match hit {
+                IndexHit::Description {
+                    label, descriptor, ..
+                } => Some((label.clone(), descriptor.clone())),
+                _ => None,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn user_input_becomes_fts_query() {
+        assert_eq!(fts_query("  "), None);
+        assert_eq!(fts_query("dragon"), Some("\"dragon\"".to_string()));
+        assert_eq!(
+            fts_query("drag* \"red dragon\" -22-11"),
+            Some("\"drag\"* \"red dragon\" \"-22-11\"".to_string())
+        );
+    }
+
+    #[test]
+    fn phrase_search_finds_matching_descriptions() {
+        let db = example_database();
+        let index = SearchIndex::build(&db).unwrap();
+
+        let hits = index.search("\"Description 2\"", 100).unwrap();
+
+        let descriptor = example_descriptors()[1].0.clone();
+        let mut expected: Vec<(Label, Descriptor)> = example_labels()
+            .into_iter()
+            .map(|label| (label, descriptor.clone()))
+            .collect();
+        let mut found = described(&hits);
+        found.sort_by(|a, b| a.0.to_str().cmp(b.0.to_str()));
+        expected.sort_by(|a, b| a.0.to_str().cmp(b.0.to_str()));
+        assert_eq!(found, expected);
+        match &hits[0] {
+            IndexHit::Description { snippet, .. } => assert_eq!(snippet.matched, "Description 2"),
+            _ => panic!("Only descriptions should match."),
+        }
+    }
+
+    #[test]
+    fn prefix_search_finds_history_content() {
+        let db = example_database();
+        let index = SearchIndex::build(&db).unwrap();
+
+        let hits = index.search("33*", 100).unwrap();
+
+        let years: Vec<&str> = hits
+            .iter()
+            .filter_map(|hit| match hit {
+                IndexHit::HistoryContent { year, .. } => Some(year.as_str()),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(years, vec!["333"; 3]);
+    }
+
+    #[test]
+    fn more_relevant_descriptions_come_first() {
+        let db = example_database();
+        let labels = example_labels();
+        let descriptor = example_descriptors()[0].0.clone();
+        db.change_entity_description((&labels[2], &descriptor), &"Dragon dragon dragon".into())
+            .unwrap();
+        db.change_entity_description(
+            (&labels[1], &descriptor),
+            &"A dragon among many other words in a rather long description".into(),
+        )
+        .unwrap();
+        let index = SearchIndex::build(&db).unwrap();
+
+        let hits = index.search("dragon", 100).unwrap();
+
+        assert_eq!(
+            described(&hits),
+            vec![
+                (labels[2].clone(), descriptor.clone()),
+                (labels[1].clone(), descriptor)
+            ]
+        );
+    }
+
+    #[test]
+    fn descriptions_and_history_items_share_ranking_and_limit() {
+        let db = example_database();
+        let labels = example_labels();
+        let descriptor = example_descriptors()[0].0.clone();
+        db.change_entity_description(
+            (&labels[1], &descriptor),
+            &"A dragon among many other words in a rather long description".into(),
+        )
+        .unwrap();
+        let item = db
+            .read_history_items(HistoryItemSearchParams::new(None, None, None, None))
+            .unwrap()[0]
+            .clone();
+        db.change_history_item_content(item.timestamp, &"Dragon dragon dragon".into())
+            .unwrap();
+        let index = SearchIndex::build(&db).unwrap();
+
+        let hits = index.search("dragon", 100).unwrap();
+        assert_eq!(hits.len(), 2);
+        assert!(matches!(hits[0], IndexHit::HistoryContent { .. }));
+        assert!(matches!(hits[1], IndexHit::Description { .. }));
+
+        let hits = index.search("dragon", 1).unwrap();
+        assert_eq!(hits.len(), 1);
+        assert!(matches!(hits[0], IndexHit::HistoryContent { .. }));
+    }
+
+    #[test]
+    fn updates_replace_outdated_entries() {
+        let db = example_database();
+        let mut index = SearchIndex::build(&db).unwrap();
+        let labels = example_labels();
+        let descriptor = example_descriptors()[0].0.clone();
+        db.change_entity_description((&labels[0], &descriptor), &"Griffin".into())
+            .unwrap();
+        assert!(index.search("griffin", 100).unwrap().is_empty());
+
+        index.update_entities(&db, &[labels[0].clone()]).unwrap();
+
+        assert_eq!(
+            described(&index.search("griffin", 100).unwrap()),
+            vec![(labels[0].clone(), descriptor)]
+        );
+        assert_eq!(
+            described(&index.search("\"Description 1\"", 100).unwrap()).len(),
+            2
+        );
+    }
+
+    #[test]
+    fn index_is_only_used_while_up_to_date() {
+        let db = example_database();
+        let mut state = SearchIndexState::unbuilt();
+        assert_eq!(state.database_path(&db), None);
+
+        let update = state.next_update().unwrap();
+        assert!(state.next_update().is_none());
+        state.record(&[example_labels()[0].clone()], &[]);
+        update.run(&db.path_as_string()).unwrap();
+        state.update_finished(true);
+        assert_eq!(state.database_path(&db), None);
+
+        state.update_now(&db);
+        assert_eq!(state.database_path(&db), Some(db.path_as_string()));
+    }
+
+    #[test]
+    fn failed_updates_disable_index_until_rebuilt() {
+        let db = example_database();
+        let mut state = SearchIndexState::build(&db);
+        state.record(&[example_labels()[0].clone()], &[]);
+        state.next_update().unwrap();
+        state.update_finished(false);
+        state.record(&[example_labels()[1].clone()], &[]);
+        assert!(state.next_update().is_none());
+        assert_eq!(state.database_path(&db), None);
+
+        state.record_everything();
+        state.update_now(&db);
+        assert_eq!(state.database_path(&db), Some(db.path_as_string()));
+    }
+}
@@ -0,0 +1,361 @@
+use std::fmt::Display;
+
+use iced::keyboard::{key::Named, Key, Modifiers};
+use serde::{Deserialize, Serialize};
+
+use crate::localisation::tr;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub(crate) enum ShortcutAction {
+    Save,
+    New,
+    Delete,
+    SubmitDialog,
+    CloseDialog,
+    Undo,
+    Redo,
+    EntityView,
+    HistoryView,
+    RelationshipView,
+    DashboardView,
+    SelectPrevious,
+    SelectNext,
+    PreviousColumn,
+    NextColumn,
+    CommandPalette,
+    ToggleReadMode,
+}
+
+impl ShortcutAction {
+    pub(crate) const ALL: [ShortcutAction; 17] = [
+        ShortcutAction::Save,
+        ShortcutAction::New,
+        ShortcutAction::Delete,
+        ShortcutAction::SubmitDialog,
+        ShortcutAction::CloseDialog,
+        ShortcutAction::Undo,
+        ShortcutAction::Redo,
+        ShortcutAction::EntityView,
+        ShortcutAction::HistoryView,
+        ShortcutAction::RelationshipView,
+        ShortcutAction::DashboardView,
+        ShortcutAction::SelectPrevious,
+        ShortcutAction::SelectNext,
+        ShortcutAction::PreviousColumn,
+        ShortcutAction::NextColumn,
+        ShortcutAction::CommandPalette,
+        ShortcutAction::ToggleReadMode,
+    ];
+
+    pub(crate) fn description(&self) -> String {
+        match self {
+            ShortcutAction::Save => tr("shortcut-save"),
+            ShortcutAction::New => tr("shortcut-new"),
+            ShortcutAction::Delete => tr("shortcut-delete"),
+            ShortcutAction::SubmitDialog => tr("shortcut-submit-dialog"),
+            ShortcutAction::CloseDialog => tr("shortcut-close-dialog"),
+            ShortcutAction::Undo => tr("shortcut-undo"),
+            ShortcutAction::Redo => tr("shortcut-redo"),
+            ShortcutAction::EntityView => tr("shortcut-entity-view"),
+            ShortcutAction::HistoryView => tr("shortcut-history-view"),
+            ShortcutAction::RelationshipView => tr("shortcut-relationship-view"),
+            ShortcutAction::DashboardView => tr("shortcut-dashboard-view"),
+            ShortcutAction::SelectPrevious => tr("shortcut-select-previous"),
+            ShortcutAction::SelectNext => tr("shortcut-select-next"),
+            ShortcutAction::PreviousColumn => tr("shortcut-previous-column"),
+            ShortcutAction::NextColumn => tr("shortcut-next-column"),
+            ShortcutAction::CommandPalette => tr("shortcut-command-palette"),
+            ShortcutAction::ToggleReadMode => tr("shortcut-toggle-read-mode"),
+        }
+    }
+
+    fn default_binding(&self) -> KeyBinding {
+        match self {
+            ShortcutAction::Save => KeyBinding::command("s"),
+            ShortcutAction::New => KeyBinding::command("n"),
+            ShortcutAction::Delete => KeyBinding::plain("Delete"),
+            ShortcutAction::SubmitDialog => KeyBinding::plain("Enter"),
+            ShortcutAction::CloseDialog => KeyBinding::plain("Escape"),
+            ShortcutAction::Undo => KeyBinding::command("z"),
+            ShortcutAction::Redo => KeyBinding {
+                shift: true,
+                ..KeyBinding::command("z")
+            },
+            ShortcutAction::EntityView => KeyBinding::command("1"),
+            ShortcutAction::HistoryView => KeyBinding::command("2"),
+            ShortcutAction::RelationshipView => KeyBinding::command("3"),
+            ShortcutAction::DashboardView => KeyBinding::command("4"),
+            ShortcutAction::SelectPrevious => KeyBinding::plain("ArrowUp"),
+            ShortcutAction::SelectNext => KeyBinding::plain("ArrowDown"),
+            ShortcutAction::PreviousColumn => KeyBinding::plain("ArrowLeft"),
+            ShortcutAction::NextColumn => KeyBinding::plain("ArrowRight"),
+            ShortcutAction::CommandPalette => KeyBinding::command("p"),
+            ShortcutAction::ToggleReadMode => KeyBinding::command("r"),
+        }
+    }
+}
+
+/// The names under which named keys are stored, so that stored bindings do not depend on iced's debug output.
+const NAMED_KEYS: [(Named, &str); 32] = [
+    (Named::Control, "Control"),
+    (Named::Shift, "Shift"),
+    (Named::Alt, "Alt"),
+    (Named::Super, "Super"),
+    (Named::Meta, "Meta"),
+    (Named::Enter, "Enter"),
+    (Named::Tab, "Tab"),
+    (Named::Space, "Space"),
+    (Named::Backspace, "Backspace"),
+    (Named::Delete, "Delete"),
+    (Named::Escape, "Escape"),
+    (Named::Insert, "Insert"),
+    (Named::Home, "Home"),
+    (Named::End, "End"),
+    (Named::PageUp, "PageUp"),
+    (Named::PageDown, "PageDown"),
+    (Named::ArrowUp, "ArrowUp"),
+    (Named::ArrowDown, "ArrowDown"),
+    (Named::ArrowLeft, "ArrowLeft"),
+    (Named::ArrowRight, "ArrowRight"),
+    (Named::F1, "F1"),
+    (Named::F2, "F2"),
+    (Named::F3, "F3"),
+    (Named::F4, "F4"),
+    (Named::F5, "F5"),
+    (Named::F6, "F6"),
+    (Named::F7, "F7"),
+    (Named::F8, "F8"),
+    (Named::F9, "F9"),
+    (Named::F10, "F10"),
+    (Named::F11, "F11"),
+    (Named::F12, "F12"),
+];
+
+fn named_key_name(named: Named) -> Option<&'static str> {
+    NAMED_KEYS
+        .iter()
+        .find(|(key, _)| *key == named)
+        .map(|(_, name)| *name)
+}
+
+fn named_key(name: &str) -> Option<Named> {
+    NAMED_KEYS
+        .iter()
+        .find(|(_, n)| *n == name)
+        .map(|(key, _)| *key)
+}
+
+/// A key together with its modifiers.
+///
+/// Characters are stored in lower case, named keys by their name in [`NAMED_KEYS`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub(crate) struct KeyBinding {
+    key: String,
+    command: bool,
+    shift: bool,
+    alt: bool,
+}
+
+impl KeyBinding {
+    fn plain(key: &str) -> Self {
+        KeyBinding {
+            key: key.to_string(),
+            command: false,
+            shift: false,
+            alt: false,
+        }
+    }
+
+    fn command(key: &str) -> Self {
+        KeyBinding {
+            command: true,
+            ..Self::plain(key)
+        }
+    }
+
+    pub(crate) fn from_key(key: &Key, modifiers: Modifiers) -> Option<Self> {
+        let key = match key {
+            Key::Character(c) => c.to_lowercase(),
+            Key::Named(named) => named_key_name(*named)?.to_string(),
+            Key::Unidentified => return None,
+        };
+        Some(KeyBinding {
+            key,
+            command: modifiers.command(),
+            shift: modifiers.shift(),
+            alt: modifiers.alt(),
+        })
+    }
+
+    /// Stored bindings are either a single character or a known named key.
+    fn is_valid(&self) -> bool {
+        self.key.chars().count() == 1 || named_key(&self.key).is_some()
+    }
+
+    /// Pure modifier presses cannot be bound on their own.
+    pub(crate) fn is_modifier(&self) -> bool {
+        matches!(
+            self.key.as_str(),
+            "Control" | "Shift" | "Alt" | "Super" | "Meta"
+        )
+    }
+}
+
+impl Display for KeyBinding {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.command {
+            write!(f, "Ctrl+")?;
+        }
+        if self.shift {
+            write!(f, "Shift+")?;
+        }
+        if self.alt {
+            write!(f, "Alt+")?;
+        }
+        if self.key.chars().count() == 1 {
+            write!(f, "{}", self.key.to_uppercase())
+        } else {
+            write!(f, "{}", self.key)
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub(crate) struct KeyBindings(Vec<(ShortcutAction, KeyBinding)>);
+
+impl Default for KeyBindings {
+    fn default() -> Self {
+        KeyBindings(
+            ShortcutAction::ALL
+                .iter()
+                .map(|action| (*action, action.default_binding()))
+                .collect(),
+        )
+    }
+}
+
+impl KeyBindings {
+    /// Bindings for actions that were added after the preferences were stored fall back to the defaults,
+    /// as do bindings to keys that cannot be pressed.
+    pub(crate) fn with_defaults_for_missing(mut self) -> Self {
+        self.0.retain(|(_, binding)| binding.is_valid());
+        for action in ShortcutAction::ALL {
+            if self.get(action).is_none() {
+                self.0.push((action, action.default_binding()));
+            }
+        }
+        self
+    }
+
+    pub(crate) fn get(&self, action: ShortcutAction) -> Option<&KeyBinding> {
+        self.0
+            .iter()
+            .find(|(a, _)| *a == action)
+            .map(|(_, binding)| binding)
+    }
+
+    pub(crate) fn set(&mut self, action: ShortcutAction, binding: KeyBinding) {
+        self.0.retain(|(a, _)| *a != action);
+        self.0.push((action, binding));
+    }
+
+    pub(crate) fn action_for(&self, binding: &KeyBinding) -> Option<ShortcutAction> {
+        self.0
+            .iter()
+            .find(|(_, b)| b == binding)
+            .map(|(action, _)| *action)
+    }
+
+    /// Actions that share a key binding with another action.
+    pub(crate) fn conflicts(&self) -> Vec<ShortcutAction> {
+        self.0
+            .iter()
+            .filter(|(action, binding)| self.0.iter().any(|(a, b)| a != action && b == binding))
+            .map(|(action, _)| *action)
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn default_bindings_cover_all_actions_without_conflicts() {
+        let bindings = KeyBindings::default();
+        for action in ShortcutAction::ALL {
+            assert!(bindings.get(action).is_some());
+        }
+        assert!(bindings.conflicts().is_empty());
+    }
+
+    #[test]
+    fn pressed_keys_are_resolved_to_actions() {
+        let bindings = KeyBindings::default();
+
+        let ctrl_s = KeyBinding::from_key(&Key::Character("S".into()), Modifiers::COMMAND);
+        assert_eq!(
+            bindings.action_for(&ctrl_s.unwrap()),
+            Some(ShortcutAction::Save)
+        );
+
+        let escape = KeyBinding::from_key(&Key::Named(Named::Escape), Modifiers::empty());
+        assert_eq!(
+            bindings.action_for(&escape.unwrap()),
+            Some(ShortcutAction::CloseDialog)
+        );
+
+        let plain_s = KeyBinding::from_key(&Key::Character("s".into()), Modifiers::empty());
+        assert_eq!(bindings.action_for(&plain_s.unwrap()), None);
+    }
+
+    #[test]
+    fn rebinding_an_action_replaces_its_old_binding() {
+        let mut bindings = KeyBindings::default();
+        let f5 = KeyBinding::plain("F5");
+        bindings.set(ShortcutAction::Save, f5.clone());
+        assert_eq!(bindings.action_for(&f5), Some(ShortcutAction::Save));
+        assert_eq!(bindings.action_for(&KeyBinding::command("s")), None);
+    }
+
+    #[test]
+    fn missing_actions_get_default_bindings() {
+        let bindings = KeyBindings(vec![(ShortcutAction::Save, KeyBinding::plain("F5"))])
+            .with_defaults_for_missing();
+        assert_eq!(
+            bindings.get(ShortcutAction::Save),
+            Some(&KeyBinding::plain("F5"))
+        );
+        assert_eq!(
+            bindings.get(ShortcutAction::New),
+            Some(&KeyBinding::command("n"))
+        );
+    }
+
+    #[test]
+    fn named_keys_are_stored_by_their_name() {
+        for (named, name) in NAMED_KEYS {
+            let binding = KeyBinding::from_key(&Key::Named(named), Modifiers::empty()).unwrap();
+            assert_eq!(binding, KeyBinding::plain(name));
+            assert_eq!(named_key(name), Some(named));
+        }
+        let unknown = KeyBinding::from_key(&Key::Named(Named::MediaPlay), Modifiers::empty());
+        assert_eq!(unknown, None);
+    }
+
+    #[test]
+    fn unknown_stored_keys_fall_back_to_defaults() {
+        let bindings = KeyBindings(vec![(ShortcutAction::Save, KeyBinding::plain("Kana"))])
+            .with_defaults_for_missing();
+        assert_eq!(
+            bindings.get(ShortcutAction::Save),
+            Some(&KeyBinding::command("s"))
+        );
+    }
+
+    #[test]
+    fn bindings_are_displayed_readably() {
+        let redo = ShortcutAction::Redo.default_binding();
+        assert_eq!(redo.to_string(), "Ctrl+Shift+Z");
+    }
+}
@@ -0,0 +1,100 @@
+use std::{fs, path::Path, time::UNIX_EPOCH};
+
+use lorecore::extractions::extract_labels;
+
+use crate::{errors::LoreGuiError, query_cache::CachedReader};
+
+pub(crate) mod widget;
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub(crate) struct DatabaseStatistics {
+    pub(crate) entities: usize,
+    pub(crate) descriptors: usize,
+    pub(crate) history_items: usize,
+    pub(crate) relationships: usize,
+}
+
+impl DatabaseStatistics {
+    pub(crate) fn read(reader: &CachedReader) -> Result<Self, LoreGuiError> {
+        let entity_columns = reader.read_entity_columns(None, None)?;
+        Ok(DatabaseStatistics {
+            entities: extract_labels(&entity_columns).len(),
+            descriptors: entity_columns.len(),
+            history_items: reader.read_history_items(None, None, None)?.len(),
+            relationships: reader.read_relationships(None, None)?.len(),
+        })
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct FileInfo {
+    pub(crate) size: u64,
+    /// Seconds since the unix epoch.
+    pub(crate) modified: Option<u64>,
+}
+
+impl FileInfo {
+    pub(crate) fn read(path: &Path) -> Option<Self> {
+        let metadata = fs::metadata(path).ok()?;
+        let modified = metadata
+            .modified()
+            .ok()
+            .and_then(|time| time.duration_since(UNIX_EPOCH).ok())
+            .map(|duration| duration.as_secs());
+        Some(FileInfo {
+            size: metadata.len(),
+            modified,
+        })
+    }
+}
+
+/// What the status bar at the bottom of the window shows.
+///
+/// The statistics and file information are only read again once the database changed, the rest is updated after every message.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub(crate) struct StatusBar {
+    pub(crate) statistics: Option<DatabaseStatistics>,
+    pub(crate) file: Option<FileInfo>,
+    /// The path of the database the statistics were read from.
+    pub(crate) database: Option<String>,
+    /// Whether tables changed since the statistics were read.
+    pub(crate) outdated: bool,
+    /// Entries matching the search texts of the active view, if any are entered.
+    pub(crate) search_matches: Option<usize>,
+    /// Whether the editor of the active view has unsaved changes, if the view has an editor.
+    pub(crate) unsaved_changes: Option<bool>,
+}
+
+/// Formats a number of bytes with binary prefixes, like "1.5 KiB".
+pub(crate) fn format_file_size(bytes: u64) -> String {
+    const UNITS: [&str; 4] = ["KiB", "MiB", "GiB", "TiB"];
+    if bytes < 1024 {
+        return format!("{} B", bytes);
+    }
+    let mut size = bytes as f64 / 1024.0;
+    let mut unit = 0;
+    while size >= 1024.0 && unit < UNITS.len() - 1 {
+        size /= 1024.0;
+        unit += 1;
+    }
+    format!("{:.1} {}", size, UNITS[unit])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn small_files_are_shown_in_bytes() {
+        assert_eq!(format_file_size(0), "0 B");
+        assert_eq!(format_file_size(1023), "1023 B");
+    }
+
+    #[test]
+    fn larger_files_use_binary_prefixes() {
+        assert_eq!(format_file_size(1024), "1.0 KiB");
+        assert_eq!(format_file_size(1536), "1.5 KiB");
+        assert_eq!(format_file_size(5 * 1024 * 1024), "5.0 MiB");
+        assert_eq!(format_file_size(3 * 1024 * 1024 * 1024), "3.0 GiB");
+    }
+}
@@ -0,0 +1,66 @@
+use iced::{
+    widget::{text, Row},
+    Alignment, Element, Length,
+};
+
+use crate::{
+    app::message_handling::GuiMessage,
+    localisation::{tr, tr_args},
+    style::Sizes,
+    time_format::format_unix_time,
+};
+
+use super::{format_file_size, StatusBar};
+
+pub(crate) fn new(status: &StatusBar, sizes: Sizes) -> Element<'_, GuiMessage> {
+    let mut parts = vec![];
+    if let Some(statistics) = status.statistics {
+        parts.push(tr_args(
+            "status-entities",
+            &[("count", &statistics.entities)],
+        ));
+        parts.push(tr_args(
+            "status-descriptors",
+            &[("count", &statistics.descriptors)],
+        ));
+        parts.push(tr_args(
+            "status-history-items",
+            &[("count", &statistics.history_items)],
+        ));
+        parts.push(tr_args(
+            "status-relationships",
+            &[("count", &statistics.relationships)],
+        ));
+    }
+    if let Some(matches) = status.search_matches {
+        parts.push(tr_args("status-search-matches", &[("count", &matches)]));
+    }
+    let mut row = Row::new()
+        .push(sizes.text(parts.join(" · ")).width(Length::Fill))
+        .align_y(Alignment::Center)
+        .spacing(sizes.spacing * 4.0)
+        .padding(sizes.spacing);
+    match status.unsaved_changes {
+        Some(true) => row = row.push(sizes.text(tr("status-unsaved-changes")).style(text::danger)),
+        Some(false) => row = row.push(sizes.text(tr("status-saved")).style(text::secondary)),
+        None => {}
+    }
+    if let Some(file) = status.file {
+        let modified = file
+            .modified
+            .map(format_unix_time)
+            .unwrap_or_else(|| tr("status-unknown"));
+        row = row.push(
+            sizes
+                .text(tr_args(
+                    "status-file",
+                    &[
+                        ("size", &format_file_size(file.size)),
+                        ("modified", &modified),
+                    ],
+                ))
+                .style(text::secondary),
+        );
+    }
+    row.into()
+}
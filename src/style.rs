@@ -1,5 +1,37 @@
 use iced::widget::{text, Text};
 
-pub(super) fn header(content: &str) -> Text {
-    text(content).size(25)
+/// Sizes of an appearance, handed to the widgets while they are built.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) struct Sizes {
+    pub(crate) text: f32,
+    pub(crate) header: f32,
+    pub(crate) row_height: f32,
+    /// Used for both the spacing between and the padding around widgets.
+    pub(crate) spacing: f32,
+}
+
+impl Default for Sizes {
+    fn default() -> Self {
+        Sizes {
+            text: 16.0,
+            header: 25.0,
+            row_height: 20.0,
+            spacing: 5.0,
+        }
+    }
+}
+
+impl Sizes {
+    pub(crate) fn text<'a>(&self, content: impl text::IntoFragment<'a>) -> Text<'a> {
+        text(content).size(self.text)
+    }
+
+    /// Padding that makes the rows of a list as high as the row height.
+    pub(crate) fn list_padding(&self) -> f32 {
+        ((self.row_height - self.text) / 2.0).max(0.0)
+    }
+
+    pub(crate) fn header<'a>(&self, content: impl text::IntoFragment<'a>) -> Text<'a> {
+        text(content).size(self.header)
+    }
 }
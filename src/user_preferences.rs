@@ -1,6 +1,14 @@
-use crate::errors::LoreGuiError;
+use crate::{
+    app::ViewType, appearance::Appearance, errors::LoreGuiError, localisation::tr,
+    shortcuts::KeyBindings,
+};
 use preferences::{AppInfo, Preferences, PreferencesMap};
-use std::path::PathBuf;
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::BTreeMap,
+    path::{Path, PathBuf},
+    time::{SystemTime, UNIX_EPOCH},
+};
 
 use crate::APP_TITLE;
 
@@ -10,23 +18,303 @@ const APP_INFO: AppInfo = AppInfo {
 };
 
 const DATABASE_PATH_KEY: &str = "database_path";
+const RECENT_DATABASES_KEY: &str = "recent_databases";
+const KEY_BINDINGS_KEY: &str = "key_bindings";
+const DATABASE_SESSIONS_KEY: &str = "database_sessions";
+const APPEARANCE_KEY: &str = "appearance";
+const MAX_UNPINNED_RECENT_DATABASES: usize = 10;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub(crate) struct RecentDatabase {
+    pub(crate) path: PathBuf,
+    /// Seconds since the unix epoch.
+    pub(crate) last_opened: u64,
+    pub(crate) pinned: bool,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub(crate) struct RecentDatabases(Vec<RecentDatabase>);
+
+impl RecentDatabase {
+    pub(crate) fn exists(&self) -> bool {
+        self.path.exists()
+    }
+}
+
+impl RecentDatabases {
+    /// Pinned databases first, then the most recently opened ones.
+    pub(crate) fn entries(&self) -> &[RecentDatabase] {
+        &self.0
+    }
 
-pub(super) fn store_database_path(path: PathBuf) -> Result<(), LoreGuiError> {
-    let mut path_pref: PreferencesMap<PathBuf> = PreferencesMap::new();
-    path_pref.insert(DATABASE_PATH_KEY.to_string(), path.clone());
-    path_pref.save(&APP_INFO, DATABASE_PATH_KEY).map_err(|_| {
-        LoreGuiError::FileError(
-            "The following database path could not be stored as user preference:\n".to_string()
-                + &path.to_string_lossy(),
-        )
-    })?;
+    pub(crate) fn most_recent(&self) -> Option<&RecentDatabase> {
+        self.0.iter().max_by_key(|db| db.last_opened)
+    }
+
+    pub(crate) fn touch(&mut self, path: PathBuf, now: u64) {
+        let pinned = self.0.iter().any(|db| db.path == path && db.pinned);
+        self.remove(&path);
+        self.0.push(RecentDatabase {
+            path,
+            last_opened: now,
+            pinned,
+        });
+        self.sort();
+        self.truncate();
+    }
+
+    pub(crate) fn set_pinned(&mut self, path: &Path, pinned: bool) {
+        for db in self.0.iter_mut().filter(|db| db.path == path) {
+            db.pinned = pinned;
+        }
+        self.sort();
+        self.truncate();
+    }
+
+    pub(crate) fn remove(&mut self, path: &Path) {
+        self.0.retain(|db| db.path != path);
+    }
+
+    /// Forgets databases that no longer exist, unless they are pinned.
+    pub(crate) fn prune_missing(&mut self) {
+        self.0.retain(|db| db.pinned || db.exists());
+    }
+
+    fn sort(&mut self) {
+        self.0
+            .sort_by_key(|db| std::cmp::Reverse((db.pinned, db.last_opened)));
+    }
+
+    fn truncate(&mut self) {
+        let mut unpinned = 0;
+        self.0.retain(|db| {
+            if db.pinned {
+                return true;
+            }
+            unpinned += 1;
+            unpinned <= MAX_UNPINNED_RECENT_DATABASES
+        });
+    }
+}
+
+/// Where the user was in a database when they last worked with it.
+///
+/// Selections are stored as they are displayed, so that they can be matched against the entries read from the database.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub(crate) struct DatabaseSession {
+    pub(crate) view: ViewType,
+    pub(crate) label: Option<String>,
+    pub(crate) descriptor: Option<String>,
+    pub(crate) year: Option<String>,
+    pub(crate) day: Option<String>,
+    pub(crate) timestamp: Option<String>,
+    pub(crate) parent: Option<String>,
+    pub(crate) child: Option<String>,
+    pub(crate) role: Option<String>,
+    pub(crate) label_search: String,
+    pub(crate) descriptor_search: String,
+    pub(crate) year_search: String,
+    pub(crate) day_search: String,
+    pub(crate) timestamp_search: String,
+    pub(crate) parent_search: String,
+    pub(crate) child_search: String,
+    pub(crate) role_search: String,
+}
+
+/// The last session of every database, keyed by its path.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub(crate) struct DatabaseSessions(BTreeMap<PathBuf, DatabaseSession>);
+
+impl DatabaseSessions {
+    pub(crate) fn get(&self, path: &Path) -> Option<&DatabaseSession> {
+        self.0.get(path)
+    }
+
+    /// Returns whether the stored session changed.
+    pub(crate) fn update(&mut self, path: PathBuf, session: DatabaseSession) -> bool {
+        if self.0.get(&path) == Some(&session) {
+            return false;
+        }
+        self.0.insert(path, session);
+        true
+    }
+
+    pub(crate) fn remove(&mut self, path: &Path) {
+        self.0.remove(path);
+    }
+}
+
+pub(crate) fn now_in_seconds() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or_default()
+}
+
+pub(super) fn store_recent_databases(recent: &RecentDatabases) -> Result<(), LoreGuiError> {
+    recent
+        .save(&APP_INFO, RECENT_DATABASES_KEY)
+        .map_err(|_| LoreGuiError::FileError(tr("error-store-recent-databases")))?;
     Ok(())
 }
 
-pub(super) fn load_database_path() -> Option<PathBuf> {
+pub(super) fn load_recent_databases() -> RecentDatabases {
+    if let Ok(recent) = RecentDatabases::load(&APP_INFO, RECENT_DATABASES_KEY) {
+        return recent;
+    }
+    let mut recent = RecentDatabases::default();
+    if let Some(path) = load_legacy_database_path() {
+        recent.touch(path, 0);
+    }
+    recent
+}
+
+pub(super) fn store_key_bindings(key_bindings: &KeyBindings) -> Result<(), LoreGuiError> {
+    key_bindings
+        .save(&APP_INFO, KEY_BINDINGS_KEY)
+        .map_err(|_| LoreGuiError::FileError(tr("error-store-key-bindings")))?;
+    Ok(())
+}
+
+pub(super) fn load_key_bindings() -> KeyBindings {
+    match KeyBindings::load(&APP_INFO, KEY_BINDINGS_KEY) {
+        Ok(key_bindings) => key_bindings.with_defaults_for_missing(),
+        Err(_) => KeyBindings::default(),
+    }
+}
+
+pub(super) fn store_database_sessions(sessions: &DatabaseSessions) -> Result<(), LoreGuiError> {
+    sessions
+        .save(&APP_INFO, DATABASE_SESSIONS_KEY)
+        .map_err(|_| LoreGuiError::FileError(tr("error-store-database-sessions")))?;
+    Ok(())
+}
+
+pub(super) fn load_database_sessions() -> DatabaseSessions {
+    DatabaseSessions::load(&APP_INFO, DATABASE_SESSIONS_KEY).unwrap_or_default()
+}
+
+pub(super) fn store_appearance(appearance: &Appearance) -> Result<(), LoreGuiError> {
+    appearance
+        .save(&APP_INFO, APPEARANCE_KEY)
+        .map_err(|_| LoreGuiError::FileError(tr("error-store-appearance")))?;
+    Ok(())
+}
+
+pub(super) fn load_appearance() -> Appearance {
+    Appearance::load(&APP_INFO, APPEARANCE_KEY).unwrap_or_default()
+}
+
+/// Older versions only remembered the last opened database.
+fn load_legacy_database_path() -> Option<PathBuf> {
     let path_pref = match PreferencesMap::<PathBuf>::load(&APP_INFO, DATABASE_PATH_KEY) {
         Ok(pref) => pref,
         Err(_) => return None,
     };
     path_pref.get(DATABASE_PATH_KEY).map(|path| path.into())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn recent_with(paths: &[&str]) -> RecentDatabases {
+        let mut recent = RecentDatabases::default();
+        for (i, path) in paths.iter().enumerate() {
+            recent.touch(PathBuf::from(path), i as u64);
+        }
+        recent
+    }
+
+    fn paths(recent: &RecentDatabases) -> Vec<&str> {
+        recent
+            .entries()
+            .iter()
+            .map(|db| db.path.to_str().unwrap())
+            .collect()
+    }
+
+    #[test]
+    fn touching_moves_database_to_front() {
+        let mut recent = recent_with(&["a.db", "b.db", "c.db"]);
+        recent.touch(PathBuf::from("a.db"), 10);
+        assert_eq!(paths(&recent), vec!["a.db", "c.db", "b.db"]);
+        assert_eq!(recent.most_recent().unwrap().path, PathBuf::from("a.db"));
+    }
+
+    #[test]
+    fn pinned_databases_come_first_and_stay_pinned() {
+        let mut recent = recent_with(&["a.db", "b.db", "c.db"]);
+        recent.set_pinned(Path::new("a.db"), true);
+        assert_eq!(paths(&recent), vec!["a.db", "c.db", "b.db"]);
+
+        recent.touch(PathBuf::from("a.db"), 10);
+        assert!(recent.entries()[0].pinned);
+    }
+
+    #[test]
+    fn only_unpinned_databases_are_truncated() {
+        let names: Vec<String> = (0..=MAX_UNPINNED_RECENT_DATABASES)
+            .map(|i| format!("{}.db", i))
+            .collect();
+        let mut recent = RecentDatabases::default();
+        recent.touch(PathBuf::from("pinned.db"), 0);
+        recent.set_pinned(Path::new("pinned.db"), true);
+        for (i, name) in names.iter().enumerate() {
+            recent.touch(PathBuf::from(name), i as u64 + 1);
+        }
+
+        assert_eq!(recent.entries().len(), MAX_UNPINNED_RECENT_DATABASES + 1);
+        assert!(paths(&recent).contains(&"pinned.db"));
+        assert!(!paths(&recent).contains(&"0.db"));
+    }
+
+    #[test]
+    fn pruning_keeps_existing_and_pinned_databases() {
+        let existing = tempfile::NamedTempFile::new().unwrap();
+        let existing = existing.path().to_str().unwrap();
+        let mut recent = recent_with(&[existing, "/missing/a.db", "/missing/b.db"]);
+        recent.set_pinned(Path::new("/missing/a.db"), true);
+
+        recent.prune_missing();
+
+        assert_eq!(paths(&recent), vec!["/missing/a.db", existing]);
+    }
+
+    #[test]
+    fn sessions_are_only_updated_when_changed() {
+        let mut sessions = DatabaseSessions::default();
+        let session = DatabaseSession {
+            label: Some("label".to_string()),
+            ..Default::default()
+        };
+        assert!(sessions.update(PathBuf::from("a.db"), session.clone()));
+        assert!(!sessions.update(PathBuf::from("a.db"), session.clone()));
+        assert_eq!(sessions.get(Path::new("a.db")), Some(&session));
+        assert_eq!(sessions.get(Path::new("b.db")), None);
+    }
+
+    #[test]
+    fn sessions_survive_serialisation() {
+        let mut sessions = DatabaseSessions::default();
+        let session = DatabaseSession {
+            view: ViewType::History,
+            year: Some("-22".to_string()),
+            day: Some("".to_string()),
+            year_search: "2".to_string(),
+            ..Default::default()
+        };
+        sessions.update(PathBuf::from("a.db"), session);
+        let json = serde_json::to_string(&sessions).unwrap();
+        let restored: DatabaseSessions = serde_json::from_str(&json).unwrap();
+        assert_eq!(restored, sessions);
+    }
+
+    #[test]
+    fn removing_forgets_database() {
+        let mut recent = recent_with(&["a.db", "b.db"]);
+        recent.remove(Path::new("a.db"));
+        assert_eq!(paths(&recent), vec!["b.db"]);
+    }
+}